@@ -0,0 +1,129 @@
+//! Benchmarks for updating and drawing a process table with many rows.
+//!
+//! These use few samples so they finish quickly, e.g. in CI. Run with `cargo bench`.
+
+use std::time::Duration;
+
+use bottom::{
+    app::{data_harvester::processes::ProcessHarvest, App},
+    args,
+    canvas::{canvas_styling::CanvasStyling, Painter},
+    options::{build_app, get_widget_layout, Config},
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use tui::{backend::TestBackend, Terminal};
+
+const NUM_PROCESSES: usize = 10_000;
+const WIDTH: u16 = 200;
+const HEIGHT: u16 = 50;
+
+/// A query that matches about half of the synthetic processes.
+const FILTER: &str = "worker";
+
+/// Builds an app showing just the process table, with [`NUM_PROCESSES`] made up processes and
+/// [`FILTER`] as the search.
+fn setup() -> (App, Painter, Terminal<TestBackend>) {
+    let matches =
+        args::build_app().get_matches_from(["btm", "--default_widget_type", "proc", "--expanded"]);
+    let config = Config::default();
+    let (layout, default_widget_id, default_widget_type) =
+        get_widget_layout(&matches, &config).unwrap();
+    let mut app = build_app(
+        matches,
+        config,
+        &layout,
+        default_widget_id,
+        &default_widget_type,
+        &CanvasStyling::default(),
+    )
+    .unwrap();
+    let painter = Painter::init(layout, CanvasStyling::default()).unwrap();
+    let terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+
+    app.data_collection.process_data.process_harvest = (1..=NUM_PROCESSES)
+        .map(|pid| {
+            let name = if pid % 2 == 0 {
+                format!("worker-{}", pid % 97)
+            } else {
+                format!("service-{}", pid % 89)
+            };
+            let process = ProcessHarvest {
+                pid: pid as _,
+                parent_pid: Some((pid / 4) as _),
+                cpu_usage_percent: (pid * 7 % 1000) as f32 / 10.0,
+                mem_usage_percent: (pid * 13 % 1000) as f32 / 10.0,
+                mem_usage_bytes: (pid * 104_729) as u64,
+                command: format!("/usr/bin/{name} --id {pid} --config /etc/{name}.toml"),
+                name,
+                read_bytes_per_sec: (pid * 31) as u64,
+                write_bytes_per_sec: (pid * 17) as u64,
+                ..Default::default()
+            };
+            (process.pid, process)
+        })
+        .collect();
+
+    for state in app.states.proc_state.widget_states.values_mut() {
+        state.proc_search.search_state.current_search_query = FILTER.to_string();
+        state.update_query();
+        state.ingest_data(&app.data_collection);
+        assert_eq!(state.processes().len(), NUM_PROCESSES / 2);
+    }
+
+    (app, painter, terminal)
+}
+
+fn update_data(c: &mut Criterion) {
+    let (mut app, _painter, _terminal) = setup();
+
+    c.bench_function("update_data with sort and filter", |b| {
+        b.iter(|| {
+            for state in app.states.proc_state.widget_states.values_mut() {
+                state.force_data_update();
+            }
+            bottom::update_data(&mut app);
+        })
+    });
+}
+
+fn draw(c: &mut Criterion) {
+    let (mut app, mut painter, mut terminal) = setup();
+
+    c.bench_function("draw", |b| {
+        b.iter(|| painter.draw_data(&mut terminal, &mut app).unwrap())
+    });
+}
+
+fn scroll_and_draw(c: &mut Criterion) {
+    let (mut app, mut painter, mut terminal) = setup();
+
+    // Start at the bottom of the first page, so every step down scrolls the rows.
+    for _ in 0..HEIGHT {
+        app.on_down_key();
+    }
+    painter.draw_data(&mut terminal, &mut app).unwrap();
+
+    let mut steps = 0;
+    c.bench_function("scroll by one and draw", |b| {
+        b.iter(|| {
+            // Turn around every so often, so this doesn't stop at the end of the table.
+            if (steps / 1000) % 2 == 0 {
+                app.on_down_key();
+            } else {
+                app.on_up_key();
+            }
+            steps += 1;
+            painter.draw_data(&mut terminal, &mut app).unwrap();
+        })
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = update_data, draw, scroll_and_draw
+}
+criterion_main!(benches);
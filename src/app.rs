@@ -1,38 +1,73 @@
 use std::{
+    borrow::Cow,
     cmp::{max, min},
-    time::Instant,
+    collections::VecDeque,
+    time::{Duration, Instant},
 };
 
 use concat_string::concat_string;
 use data_farmer::*;
 use data_harvester::temperature;
 use filter::*;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use layout_manager::*;
 pub use states::*;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 use crate::{
+    canvas::{
+        canvas_styling::{CanvasStyling, Symbols},
+        ColourScheme,
+    },
+    components::{time_graph::step_time_cursor, tui_widget::time_chart::Point},
     constants,
-    data_conversion::ConvertedData,
+    data_conversion::{ConvertedData, CpuWidgetData},
+    options::{
+        config_watcher::ReloadedConfig,
+        config_writer::{SaveOutcome, SavedProcessSettings, SavedSettings, SavedWidget},
+        ConfigColours,
+    },
     utils::error::{BottomError, Result},
     Pid,
 };
 use crate::{
-    utils::data_units::DataUnit,
-    widgets::{ProcWidgetColumn, ProcWidgetMode},
+    utils::{
+        clock::{Clock, RealClock},
+        data_units::DataUnit,
+        units::{self, ByteStandard, NumberFormat},
+    },
+    widgets::{ProcWidgetColumn, ProcWidgetMode, SnapshotComparison},
 };
 
+pub mod data_budget;
 pub mod data_farmer;
 pub mod data_harvester;
 pub mod filter;
 pub mod frozen_state;
 pub mod layout_manager;
+pub mod pending_keys;
 mod process_killer;
+pub mod process_snapshots;
 pub mod query;
 pub mod states;
+pub mod timings;
+pub mod update_rates;
 
+use data_budget::{DataBudget, DataStore};
 use frozen_state::FrozenState;
+use pending_keys::PendingKeys;
+use process_snapshots::ProcessSnapshots;
+use timings::Timings;
+use update_rates::{CollectionRates, WidgetUpdateSchedule};
+
+/// A change of which widget is focused, see [`App::take_focus_events`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FocusEvent {
+    /// The widget with this ID lost focus.
+    Lost(u64),
+    /// The widget with this ID gained focus.
+    Gained(u64),
+}
 
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub enum AxisScaling {
@@ -54,21 +89,52 @@ pub struct AppConfigFields {
     pub unnormalized_cpu: bool,
     pub use_basic_mode: bool,
     pub default_time_value: u64,
-    pub time_interval: u64,
+    /// The smallest time window graphs can be zoomed in to.
+    pub min_time_value: u64,
+    /// How much zooming changes a graph's time window by, or [`None`] to halve and double it.
+    pub time_interval: Option<u64>,
+    /// Whether zooming one graph zooms every graph.
+    pub zoom_all_graphs: bool,
+    /// Whether saving the config file only logs what would change.
+    pub config_save_dry_run: bool,
     pub hide_time: bool,
     pub autohide_time: bool,
     pub use_old_network_legend: bool,
     pub table_gap: u16,
     pub disable_click: bool,
+    pub debug_timings: bool,
     pub enable_gpu_memory: bool,
     pub enable_cache_memory: bool,
     pub show_table_scroll_position: bool,
+    pub scroll_step: u64,
+    /// The smallest terminal, as (width, height), that widgets are drawn in.
+    pub min_terminal_size: (u16, u16),
+    pub wrap_navigation: bool,
+    /// Whether closing the process search with Esc keeps its filter applied.
+    pub keep_filter_on_close: bool,
+    /// Whether clearing the process search scrolls back to where the table was before searching.
+    pub restore_scroll_after_search: bool,
+    /// Whether non-ASCII glyphs may be drawn.
+    pub unicode_symbols: bool,
+    /// Whether to draw table column headers in uppercase.
+    pub uppercase_headers: bool,
+    /// Whether to dim tables that aren't focused.
+    pub dim_unfocused_tables: bool,
+    /// Whether to draw without any colours.
+    pub no_color: bool,
+    /// Whether to always show the status bar, rather than only for status messages.
+    pub status_bar: bool,
     pub is_advanced_kill: bool,
     // TODO: Remove these, move network details state-side.
     pub network_unit_type: DataUnit,
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
+    pub byte_standard: ByteStandard,
+    /// How to write numbers, e.g. with thousands separators.
+    pub number_format: NumberFormat,
     pub retention_ms: u64,
+    /// The most bytes the data kept for widgets may take up, or [`None`] if there is no limit.
+    pub max_data_memory: Option<u64>,
 }
 
 /// For filtering out information
@@ -102,12 +168,10 @@ cfg_if::cfg_if! {
 }
 
 pub struct App {
-    awaiting_second_char: bool,
-    second_char: Option<char>,
+    pending_keys: PendingKeys,
     pub dd_err: Option<String>, // FIXME: The way we do deletes is really gross.
-    to_delete_process_list: Option<(String, Vec<Pid>)>,
+    to_delete_process_list: Option<ProcessesToKill>,
     pub frozen_state: FrozenState,
-    last_key_press: Instant,
     pub converted_data: ConvertedData,
     pub data_collection: DataCollection,
     pub delete_dialog_state: AppDeleteDialogState,
@@ -122,8 +186,38 @@ pub struct App {
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
     pub current_widget: BottomWidget,
+    focus_history: VecDeque<u64>,
+    focus_events: Vec<FocusEvent>,
     pub used_widgets: UsedWidgets,
+    pub collection_rates: CollectionRates,
+    widget_update_schedule: WidgetUpdateSchedule,
     pub filters: DataFilters,
+    pub timings: Option<Timings>,
+    colour_scheme: ColourScheme,
+    custom_colours: Option<ConfigColours>,
+    pending_colours: Option<CanvasStyling>,
+    status_message: Option<(String, Instant)>,
+    /// When a table row was last clicked, while its flash may still be drawn.
+    click_flash_at: Option<Instant>,
+    config_reload_requested: bool,
+    pub is_showing_save_config: bool,
+    /// The name and description of a column being shown in a dialog.
+    pub column_description: Option<(Cow<'static, str>, Cow<'static, str>)>,
+    config_save_requested: bool,
+    pub layout_profiles: Vec<String>,
+    pub active_layout_profile: String,
+    pub layout_switcher_state: Option<usize>,
+    layout_switch_request: Option<String>,
+    /// Snapshots of the processes, to compare the current processes against.
+    pub process_snapshots: ProcessSnapshots,
+    /// The name being typed for a new snapshot of the processes, while asking for one.
+    pub snapshot_name: Option<String>,
+    /// The comparison between a snapshot and the current processes, if it's being shown.
+    pub snapshot_comparison: Option<SnapshotComparison>,
+    update_rate_change: Option<u64>,
+    /// How much memory the data kept for widgets takes up, as of the last update.
+    pub data_budget: DataBudget,
+    clock: Box<dyn Clock>,
 }
 
 impl App {
@@ -132,13 +226,17 @@ impl App {
         widget_map: HashMap<u64, BottomWidget>, current_widget: BottomWidget,
         used_widgets: UsedWidgets, filters: DataFilters, is_expanded: bool,
     ) -> Self {
-        Self {
-            awaiting_second_char: false,
-            second_char: None,
+        let collection_rates =
+            CollectionRates::new(widget_map.values(), app_config_fields.update_rate);
+
+        let mut app = Self {
+            timings: app_config_fields.debug_timings.then(Timings::default),
+            data_budget: DataBudget::new(app_config_fields.max_data_memory),
+            clock: Box::new(RealClock),
+            pending_keys: PendingKeys::default(),
             dd_err: None,
             to_delete_process_list: None,
             frozen_state: FrozenState::default(),
-            last_key_press: Instant::now(),
             converted_data: ConvertedData::default(),
             data_collection: DataCollection::default(),
             delete_dialog_state: AppDeleteDialogState::default(),
@@ -153,8 +251,134 @@ impl App {
             app_config_fields,
             widget_map,
             current_widget,
+            focus_history: VecDeque::new(),
+            focus_events: Vec::new(),
             used_widgets,
+            collection_rates,
+            widget_update_schedule: WidgetUpdateSchedule::default(),
             filters,
+            colour_scheme: ColourScheme::Default,
+            custom_colours: None,
+            pending_colours: None,
+            status_message: None,
+            click_flash_at: None,
+            config_reload_requested: false,
+            is_showing_save_config: false,
+            column_description: None,
+            config_save_requested: false,
+            layout_profiles: Vec::new(),
+            active_layout_profile: constants::DEFAULT_LAYOUT_PROFILE.to_string(),
+            layout_switcher_state: None,
+            layout_switch_request: None,
+            process_snapshots: ProcessSnapshots::default(),
+            snapshot_name: None,
+            snapshot_comparison: None,
+            update_rate_change: None,
+        };
+
+        if is_expanded {
+            app.set_expanded(true);
+        }
+        app.data_collection.set_retention(
+            app.app_config_fields.retention_ms,
+            app.app_config_fields.update_rate,
+        );
+
+        app
+    }
+
+    /// Sets the colour scheme the app started with, and the custom colours from the config that
+    /// should be applied on top of any scheme switched to later.
+    pub fn with_colour_scheme(
+        mut self, colour_scheme: ColourScheme, custom_colours: Option<ConfigColours>,
+    ) -> Self {
+        self.colour_scheme = colour_scheme;
+        self.custom_colours = custom_colours;
+        self
+    }
+
+    /// Sets the names of the layout profiles that can be switched to, and the one in use.
+    pub fn with_layout_profiles(mut self, layout_profiles: Vec<String>, active: &str) -> Self {
+        self.layout_profiles = layout_profiles;
+        self.active_layout_profile = active.to_string();
+        self
+    }
+
+    /// Shows `warnings` from loading the config in the status line, if there are any.
+    pub fn with_config_warnings(mut self, warnings: Vec<String>) -> Self {
+        if !warnings.is_empty() {
+            self.set_status_message(warnings.join("; "));
+        }
+        self
+    }
+
+    /// Returns the IDs of the widgets that should be given the data collected at `collected_at`,
+    /// which excludes any widget that set its own rate and was given data too recently.
+    pub fn take_widgets_due_for_update(&mut self, collected_at: Instant) -> HashSet<u64> {
+        self.widget_update_schedule.take_due(
+            self.widget_map.values(),
+            self.app_config_fields.update_rate,
+            collected_at,
+        )
+    }
+
+    /// Sets the [`Clock`] used for time-based behaviour like multi-key shortcuts.
+    pub fn set_clock<T: Clock + 'static>(&mut self, clock: T) {
+        self.clock = Box::new(clock);
+    }
+
+    /// Returns when to start measuring something from, if timings are enabled.
+    pub fn timing_start(&self) -> Option<Instant> {
+        self.timings.is_some().then(|| self.clock.now())
+    }
+
+    /// Records a measurement for `kind` that started at `start`, as returned by [`App::timing_start`].
+    pub fn record_timing(&mut self, kind: timings::TimingKind, start: Option<Instant>) {
+        if let (Some(timings), Some(start)) = (&mut self.timings, start) {
+            timings.record(kind, self.clock.now().saturating_duration_since(start));
+        }
+    }
+
+    /// Finishes timing a frame that started at `start`, as returned by [`App::timing_start`].
+    pub fn finish_frame_timing(&mut self, start: Option<Instant>) {
+        if let (Some(timings), Some(start)) = (&mut self.timings, start) {
+            timings.finish_frame(self.clock.now().saturating_duration_since(start));
+        }
+    }
+
+    /// Returns how long the last frame took to draw, if timings are enabled and a frame was drawn.
+    pub fn last_render_time(&self) -> Option<Duration> {
+        self.timings.as_ref()?.last_frame_time()
+    }
+
+    /// Measures how much memory each [`DataStore`] takes up.
+    fn measure_data_usage(&mut self) {
+        let budget = &mut self.data_budget;
+        budget.set_usage(DataStore::History, self.data_collection.history_bytes());
+        budget.set_usage(
+            DataStore::Processes,
+            self.data_collection.process_data.approx_bytes(),
+        );
+        budget.set_usage(DataStore::Converted, self.converted_data.approx_bytes());
+    }
+
+    /// Measures the data kept for widgets and, if it takes up more than `max_data_memory`, frees
+    /// up memory until it doesn't. The converted data goes first, as it can be rebuilt, followed
+    /// by the oldest history. The latest processes and the latest entry of the history are always
+    /// kept, so the cap may still be exceeded if they alone don't fit.
+    pub fn enforce_data_budget(&mut self) {
+        self.measure_data_usage();
+        if self.data_budget.excess() == 0 {
+            return;
+        }
+
+        self.converted_data.shrink_to_fit();
+        self.measure_data_usage();
+
+        let excess = self.data_budget.excess();
+        if excess > 0 {
+            self.data_collection.shrink_history(excess);
+            self.measure_data_usage();
         }
     }
 
@@ -163,7 +387,7 @@ impl App {
         self.reset_multi_tap_keys();
 
         // Reset dialog state
-        self.help_dialog_state.is_showing_help = false;
+        self.close_help();
         self.delete_dialog_state.is_showing_dd = false;
 
         // Close all searches and reset it
@@ -181,11 +405,10 @@ impl App {
 
         // Unfreeze.
         self.frozen_state.thaw();
+        self.states.clear_time_cursors();
 
         // Reset zoom
-        self.reset_cpu_zoom();
-        self.reset_mem_zoom();
-        self.reset_net_zoom();
+        self.reset_zoom();
 
         // Reset data
         self.data_collection.reset();
@@ -207,8 +430,23 @@ impl App {
         self.reset_multi_tap_keys();
         if self.is_in_dialog() {
             if self.help_dialog_state.is_showing_help {
-                self.help_dialog_state.is_showing_help = false;
-                self.help_dialog_state.scroll_state.current_scroll_index = 0;
+                // Clear any search first, so it takes another Esc to close the help menu.
+                if self.help_dialog_state.is_searching || !self.help_dialog_state.query.is_empty() {
+                    self.help_dialog_state.is_searching = false;
+                    self.set_help_query(String::new());
+                } else {
+                    self.close_help();
+                }
+            } else if self.is_showing_save_config {
+                self.is_showing_save_config = false;
+            } else if self.column_description.is_some() {
+                self.column_description = None;
+            } else if self.layout_switcher_state.is_some() {
+                self.layout_switcher_state = None;
+            } else if self.snapshot_name.is_some() {
+                self.snapshot_name = None;
+            } else if self.snapshot_comparison.is_some() {
+                self.snapshot_comparison = None;
             } else {
                 self.close_dd();
             }
@@ -222,9 +460,8 @@ impl App {
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        if pws.is_search_enabled() || pws.is_sort_open {
-                            pws.proc_search.search_state.is_enabled = false;
-                            pws.is_sort_open = false;
+                        let keep_filter = self.app_config_fields.keep_filter_on_close;
+                        if pws.close_top_overlay(keep_filter) {
                             self.is_force_redraw = true;
                             return;
                         }
@@ -236,8 +473,8 @@ impl App {
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id - 1)
                     {
-                        if pws.is_search_enabled() {
-                            pws.proc_search.search_state.is_enabled = false;
+                        let keep_filter = self.app_config_fields.keep_filter_on_close;
+                        if pws.close_top_overlay(keep_filter) {
                             self.move_widget_selection(&WidgetDirection::Up);
                             self.is_force_redraw = true;
                             return;
@@ -258,16 +495,90 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Cpu
+                | BottomWidgetType::CpuLegend
+                | BottomWidgetType::Mem
+                | BottomWidgetType::Net => {
+                    if let Some(time_cursor) = self.time_cursor_mut() {
+                        if time_cursor.take().is_some() {
+                            self.is_force_redraw = true;
+                            return;
+                        }
+                    }
+                }
                 _ => {}
             }
 
             if self.is_expanded {
-                self.is_expanded = false;
+                self.set_expanded(false);
                 self.is_force_redraw = true;
             }
         }
     }
 
+    /// Returns the time cursor of the selected graph, if it's a graph that can be scrubbed through.
+    fn time_cursor_mut(&mut self) -> Option<&mut Option<u64>> {
+        let widget_id = self.current_widget.widget_id;
+        let states = &mut self.states;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => states
+                .cpu_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.time_cursor),
+            BottomWidgetType::CpuLegend => states
+                .cpu_state
+                .widget_states
+                .get_mut(&(widget_id - 1))
+                .map(|state| &mut state.time_cursor),
+            BottomWidgetType::Mem => states
+                .mem_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.time_cursor),
+            BottomWidgetType::Net => states
+                .net_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.time_cursor),
+            _ => None,
+        }
+    }
+
+    /// Returns the points that the selected graph's time cursor moves between.
+    fn time_cursor_points(&self) -> &[Point] {
+        match self.current_widget.widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => self
+                .converted_data
+                .cpu_data
+                .iter()
+                .find_map(|cpu| match cpu {
+                    CpuWidgetData::Entry { data, .. } => Some(data.as_slice()),
+                    CpuWidgetData::All => None,
+                })
+                .unwrap_or_default(),
+            BottomWidgetType::Mem => &self.converted_data.mem_data,
+            BottomWidgetType::Net => &self.converted_data.network_data_rx,
+            _ => &[],
+        }
+    }
+
+    /// Moves the selected graph's time cursor to the next older or newer point while frozen,
+    /// starting from the latest point.
+    fn move_time_cursor(&mut self, older: bool) {
+        if !self.frozen_state.is_frozen() {
+            return;
+        }
+
+        let Some(cursor) = self.time_cursor_mut().map(|cursor| cursor.unwrap_or(0)) else {
+            return;
+        };
+        let cursor = step_time_cursor(self.time_cursor_points(), cursor, older).unwrap_or(cursor);
+        if let Some(time_cursor) = self.time_cursor_mut() {
+            *time_cursor = Some(cursor);
+        }
+    }
+
     pub fn is_in_search_widget(&self) -> bool {
         matches!(
             self.current_widget.widget_type,
@@ -276,12 +587,22 @@ impl App {
     }
 
     fn reset_multi_tap_keys(&mut self) {
-        self.awaiting_second_char = false;
-        self.second_char = None;
+        self.pending_keys.clear();
+    }
+
+    /// Returns the keys typed so far towards a multi-key binding like `gg`.
+    pub fn pending_keys(&self) -> &str {
+        self.pending_keys.pending(self.clock.now())
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.is_showing_save_config
+            || self.column_description.is_some()
+            || self.layout_switcher_state.is_some()
+            || self.snapshot_name.is_some()
+            || self.snapshot_comparison.is_some()
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -326,6 +647,22 @@ impl App {
         }
     }
 
+    /// Clears the filter kept on the process widget after its search widget was closed.
+    pub fn clear_process_filter(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(pws) = self
+                .states
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                if pws.committed_filter().is_some() {
+                    pws.clear_search();
+                    self.is_force_redraw = true;
+                }
+            }
+        }
+    }
+
     pub fn toggle_sort_menu(&mut self) {
         let widget_id = self.current_widget.widget_id
             - match &self.current_widget.widget_type {
@@ -364,6 +701,26 @@ impl App {
                     pws.force_data_update();
                 }
             }
+            BottomWidgetType::Disk => {
+                if let Some(disk) = self
+                    .states
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    disk.table.toggle_order();
+                    disk.force_data_update();
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp) = self
+                    .states
+                    .temp_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    temp.table.toggle_order();
+                    temp.force_data_update();
+                }
+            }
             _ => {}
         }
     }
@@ -383,11 +740,70 @@ impl App {
                     proc_widget_state.toggle_mem_percentage();
                 }
             }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self
+                    .states
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    disk_widget_state.toggle_free_percentage();
+                }
+            }
 
             _ => {}
         }
     }
 
+    /// Toggles matching case while in the process search, and otherwise opens the help menu.
+    pub fn on_f1(&mut self) {
+        if self.is_in_search_widget() {
+            self.toggle_ignore_case();
+        } else if !self.is_in_dialog() {
+            self.open_help();
+        }
+    }
+
+    /// Shows the description of the column selected in the process sort widget, or otherwise
+    /// opens the help menu.
+    fn on_question_mark(&mut self) {
+        if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
+            let description = self
+                .states
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id - 2)
+                .and_then(|proc_widget_state| proc_widget_state.sort_table_column_description());
+
+            if description.is_some() {
+                self.column_description = description;
+                self.is_force_redraw = true;
+                return;
+            }
+        }
+
+        self.open_help();
+    }
+
+    fn open_help(&mut self) {
+        self.help_dialog_state.is_showing_help = true;
+        self.is_force_redraw = true;
+    }
+
+    /// Closes the help menu, which also clears any search in it.
+    fn close_help(&mut self) {
+        self.help_dialog_state.is_showing_help = false;
+        self.help_dialog_state.is_searching = false;
+        self.help_dialog_state.query.clear();
+        self.help_dialog_state.scroll_state.current_scroll_index = 0;
+        self.is_force_redraw = true;
+    }
+
+    /// Changes what the help menu is searched for, going back to the top of the results.
+    fn set_help_query(&mut self, query: String) {
+        self.help_dialog_state.query = query;
+        self.help_dialog_state.scroll_state.current_scroll_index = 0;
+        self.is_force_redraw = true;
+    }
+
     pub fn toggle_ignore_case(&mut self) {
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
@@ -418,6 +834,21 @@ impl App {
         }
     }
 
+    pub fn toggle_search_word_boundary(&mut self) {
+        let is_in_search_widget = self.is_in_search_widget();
+        if let Some(proc_widget_state) = self
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            if is_in_search_widget && proc_widget_state.is_search_enabled() {
+                proc_widget_state.proc_search.search_toggle_word_boundary();
+                proc_widget_state.update_query();
+            }
+        }
+    }
+
     pub fn toggle_search_regex(&mut self) {
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
@@ -458,7 +889,20 @@ impl App {
 
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.help_dialog_state.is_searching {
+            // Stop typing, but keep the results.
+            self.help_dialog_state.is_searching = false;
+            self.is_force_redraw = true;
+        } else if self.is_showing_save_config {
+            self.confirm_save_config();
+        } else if self.column_description.is_some() {
+            self.on_esc();
+        } else if self.layout_switcher_state.is_some() {
+            self.confirm_layout_switch();
+        } else if let Some(name) = self.snapshot_name.take() {
+            self.take_process_snapshot(&name);
+            self.is_force_redraw = true;
+        } else if self.delete_dialog_state.is_showing_dd {
             if self.dd_err.is_some() {
                 self.close_dd();
             } else if self.delete_dialog_state.selected_signal != KillSignal::Cancel {
@@ -470,10 +914,16 @@ impl App {
                     self.delete_dialog_state.selected_signal = KillSignal::default();
 
                     // Check if there was an issue... if so, inform the user.
-                    if let Err(dd_err) = dd_result {
-                        self.dd_err = Some(dd_err.to_string());
-                    } else {
-                        self.delete_dialog_state.is_showing_dd = false;
+                    match dd_result {
+                        Ok(result) => {
+                            self.set_status_message(result.summary());
+                            if result.failed.is_empty() {
+                                self.delete_dialog_state.is_showing_dd = false;
+                            } else {
+                                self.dd_err = Some(result.failure_details());
+                            }
+                        }
+                        Err(dd_err) => self.dd_err = Some(dd_err.to_string()),
                     }
                 }
             } else {
@@ -483,7 +933,19 @@ impl App {
             }
             self.is_force_redraw = true;
         } else if !self.is_in_dialog() {
-            if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
+            if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+                // Close the search widget, but keep the filter regardless of keep_filter_on_close.
+                if let Some(proc_widget_state) = self
+                    .states
+                    .proc_state
+                    .widget_states
+                    .get_mut(&(self.current_widget.widget_id - 1))
+                {
+                    proc_widget_state.proc_search.search_state.is_enabled = false;
+                    self.move_widget_selection(&WidgetDirection::Up);
+                    self.is_force_redraw = true;
+                }
+            } else if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
                 if let Some(proc_widget_state) = self
                     .states
                     .proc_state
@@ -546,7 +1008,14 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.help_dialog_state.is_searching {
+            let mut query = std::mem::take(&mut self.help_dialog_state.query);
+            query.pop();
+            self.set_help_query(query);
+        } else if let Some(name) = &mut self.snapshot_name {
+            name.pop();
+            self.is_force_redraw = true;
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
                 .states
@@ -599,10 +1068,11 @@ impl App {
     #[cfg(target_family = "unix")]
     pub fn on_number(&mut self, number_char: char) {
         if self.delete_dialog_state.is_showing_dd {
+            let now = self.clock.now();
             if self
                 .delete_dialog_state
                 .last_number_press
-                .map_or(100, |ins| ins.elapsed().as_millis())
+                .map_or(100, |ins| now.saturating_duration_since(ins).as_millis())
                 >= 400
             {
                 self.delete_dialog_state.keyboard_signal_select = 0;
@@ -626,15 +1096,21 @@ impl App {
             } else {
                 self.delete_dialog_state.keyboard_signal_select = 0;
             }
-            self.delete_dialog_state.last_number_press = Some(Instant::now());
+            self.delete_dialog_state.last_number_press = Some(now);
         }
     }
 
     pub fn on_up_key(&mut self) {
         if !self.is_in_dialog() {
-            self.decrement_position_count();
+            if self.decrement_position_count().is_none() && self.app_config_fields.wrap_navigation {
+                self.skip_to_last();
+            }
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if let Some(selected) = &mut self.layout_switcher_state {
+            *selected = selected.saturating_sub(1);
+        } else if let Some(comparison) = &mut self.snapshot_comparison {
+            comparison.table.increment_position(-1);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_right_key();
@@ -653,9 +1129,15 @@ impl App {
 
     pub fn on_down_key(&mut self) {
         if !self.is_in_dialog() {
-            self.increment_position_count();
+            if self.increment_position_count().is_none() && self.app_config_fields.wrap_navigation {
+                self.skip_to_first();
+            }
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if let Some(selected) = &mut self.layout_switcher_state {
+            *selected = min(*selected + 1, self.layout_profiles.len().saturating_sub(1));
+        } else if let Some(comparison) = &mut self.snapshot_comparison {
+            comparison.table.increment_position(1);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_left_key();
@@ -705,8 +1187,14 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Cpu
+                | BottomWidgetType::CpuLegend
+                | BottomWidgetType::Mem
+                | BottomWidgetType::Net => self.move_time_cursor(true),
                 _ => {}
             }
+        } else if self.snapshot_comparison.is_some() {
+            self.switch_compared_snapshot(false);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -769,8 +1257,14 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Cpu
+                | BottomWidgetType::CpuLegend
+                | BottomWidgetType::Mem
+                | BottomWidgetType::Net => self.move_time_cursor(false),
                 _ => {}
             }
+        } else if self.snapshot_comparison.is_some() {
+            self.switch_compared_snapshot(true);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -814,7 +1308,10 @@ impl App {
             let current = &mut self.help_dialog_state.scroll_state.current_scroll_index;
             let amount = self.help_dialog_state.height;
             *current = current.saturating_sub(amount);
-        } else if self.current_widget.widget_type.is_widget_table() {
+        } else if self.current_widget.widget_type.is_widget_table()
+            && !self.move_current_table_pages(-1)
+        {
+            // Paged tables move by their own pages; others by however many rows fit.
             if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
                 &self.current_widget.top_left_corner,
                 &self.current_widget.bottom_right_corner,
@@ -842,7 +1339,10 @@ impl App {
             let amount = self.help_dialog_state.height;
 
             self.help_scroll_to_or_max(current + amount);
-        } else if self.current_widget.widget_type.is_widget_table() {
+        } else if self.current_widget.widget_type.is_widget_table()
+            && !self.move_current_table_pages(1)
+        {
+            // Paged tables move by their own pages; others by however many rows fit.
             if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
                 &self.current_widget.top_left_corner,
                 &self.current_widget.bottom_right_corner,
@@ -1021,6 +1521,7 @@ impl App {
         }
     }
 
+    /// Opens the kill dialog for the marked processes, or the selected one if none are marked.
     pub fn start_killing_process(&mut self) {
         self.reset_multi_tap_keys();
 
@@ -1030,20 +1531,40 @@ impl App {
             .widget_states
             .get(&self.current_widget.widget_id)
         {
-            if let Some(current) = pws.table.current_item() {
-                let id = current.id.to_string();
-                if let Some(pids) = pws
+            let marked: Vec<(Pid, String)> = pws
+                .table
+                .marked_items()
+                .flat_map(|process| {
+                    let name = process.id.to_string();
+                    let pids = pws
+                        .id_pid_map
+                        .get(&name)
+                        .cloned()
+                        .unwrap_or_else(|| vec![process.pid]);
+
+                    pids.into_iter().map(move |pid| (pid, name.clone()))
+                })
+                .collect();
+
+            if !marked.is_empty() {
+                self.to_delete_process_list = Some(ProcessesToKill::Marked(marked));
+                self.delete_dialog_state.is_showing_dd = true;
+                self.is_determining_widget_boundary = true;
+            } else if let Some(current) = pws.selected_process() {
+                let name = current.id.to_string();
+                let pids = pws
                     .id_pid_map
-                    .get(&id)
+                    .get(&name)
                     .cloned()
-                    .or_else(|| Some(vec![current.pid]))
-                {
-                    let current_process = (id, pids);
-
-                    self.to_delete_process_list = Some(current_process);
-                    self.delete_dialog_state.is_showing_dd = true;
-                    self.is_determining_widget_boundary = true;
-                }
+                    .unwrap_or_else(|| vec![current.pid]);
+
+                self.to_delete_process_list = Some(ProcessesToKill::Selected {
+                    name,
+                    pids,
+                    is_grouped: matches!(pws.mode, ProcWidgetMode::Grouped),
+                });
+                self.delete_dialog_state.is_showing_dd = true;
+                self.is_determining_widget_boundary = true;
             }
         }
         // FIXME: This should handle errors.
@@ -1057,16 +1578,6 @@ impl App {
 
         // Forbid any char key presses when showing a dialog box...
         if !self.ignore_normal_keybinds() {
-            let current_key_press_inst = Instant::now();
-            if current_key_press_inst
-                .duration_since(self.last_key_press)
-                .as_millis()
-                > constants::MAX_KEY_TIMEOUT_IN_MILLISECONDS.into()
-            {
-                self.reset_multi_tap_keys();
-            }
-            self.last_key_press = current_key_press_inst;
-
             if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
                 let is_in_search_widget = self.is_in_search_widget();
                 if let Some(proc_widget_state) = self
@@ -1103,8 +1614,20 @@ impl App {
                 }
             }
             self.handle_char(caught_char);
+        } else if self.help_dialog_state.is_searching {
+            let mut query = std::mem::take(&mut self.help_dialog_state.query);
+            query.push(caught_char);
+            self.set_help_query(query);
+        } else if let Some(name) = &mut self.snapshot_name {
+            name.push(caught_char);
+            self.is_force_redraw = true;
         } else if self.help_dialog_state.is_showing_help {
             match caught_char {
+                '/' => {
+                    self.help_dialog_state.is_searching = true;
+                    self.is_force_redraw = true;
+                }
+                '?' | 'q' => self.close_help(),
                 '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
                     let potential_index = caught_char.to_digit(10);
                     if let Some(potential_index) = potential_index {
@@ -1119,7 +1642,40 @@ impl App {
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
                 _ => {}
             }
+        } else if self.is_showing_save_config {
+            match caught_char {
+                'y' => self.confirm_save_config(),
+                'n' => self.on_esc(),
+                _ => {}
+            }
+        } else if self.column_description.is_some() {
+            if caught_char == '?' {
+                self.on_esc();
+            }
+        } else if self.layout_switcher_state.is_some() {
+            match caught_char {
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
+                _ => {}
+            }
+        } else if let Some(comparison) = &mut self.snapshot_comparison {
+            match caught_char {
+                's' => comparison.cycle_sort(),
+                'I' => comparison.invert_sort(),
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
+                'h' => self.on_left_key(),
+                'l' => self.on_right_key(),
+                'x' => self.drop_compared_snapshot(),
+                'q' => self.on_esc(),
+                _ => {}
+            }
         } else if self.delete_dialog_state.is_showing_dd {
+            if self.pending_keys.press(caught_char, self.clock.now()) == Some("gg") {
+                self.skip_to_first();
+                return;
+            }
+
             match caught_char {
                 'h' => self.on_left_key(),
                 'j' => self.on_down_key(),
@@ -1129,22 +1685,6 @@ impl App {
                 '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
                     self.on_number(caught_char)
                 }
-                'g' => {
-                    let mut is_first_g = true;
-                    if let Some(second_char) = self.second_char {
-                        if self.awaiting_second_char && second_char == 'g' {
-                            is_first_g = false;
-                            self.awaiting_second_char = false;
-                            self.second_char = None;
-                            self.skip_to_first();
-                        }
-                    }
-
-                    if is_first_g {
-                        self.awaiting_second_char = true;
-                        self.second_char = Some('g');
-                    }
-                }
                 'G' => self.skip_to_last(),
                 _ => {}
             }
@@ -1153,56 +1693,41 @@ impl App {
 
     // FIXME: Refactor this system...
     fn handle_char(&mut self, caught_char: char) {
+        match self.pending_keys.press(caught_char, self.clock.now()) {
+            Some("gg") => {
+                self.skip_to_first();
+                return;
+            }
+            Some("dd") if matches!(self.current_widget.widget_type, BottomWidgetType::Proc) => {
+                self.start_killing_process();
+                return;
+            }
+            _ => {}
+        }
+
         match caught_char {
             '/' => {
                 self.on_slash();
             }
             'd' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    let mut is_first_d = true;
-                    if let Some(second_char) = self.second_char {
-                        if self.awaiting_second_char && second_char == 'd' {
-                            is_first_d = false;
-                            self.awaiting_second_char = false;
-                            self.second_char = None;
-
-                            self.start_killing_process();
-                        }
-                    }
-
-                    if is_first_d {
-                        self.awaiting_second_char = true;
-                        self.second_char = Some('d');
-                    }
-                } else if let Some(disk) = self
+                if let Some(disk) = self
                     .states
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(0);
-                }
-            }
-            'g' => {
-                let mut is_first_g = true;
-                if let Some(second_char) = self.second_char {
-                    if self.awaiting_second_char && second_char == 'g' {
-                        is_first_g = false;
-                        self.awaiting_second_char = false;
-                        self.second_char = None;
-                        self.skip_to_first();
-                    }
-                }
-
-                if is_first_g {
-                    self.awaiting_second_char = true;
-                    self.second_char = Some('g');
+                    disk.sort_by_column("Disk");
                 }
             }
             'G' => self.skip_to_last(),
             'k' => self.on_up_key(),
             'j' => self.on_down_key(),
             'f' => {
-                self.frozen_state.toggle(&self.data_collection); // TODO: Thawing should force a full data refresh and redraw immediately.
+                // TODO: Thawing should force a full data refresh and redraw immediately.
+                let is_frozen = self.frozen_state.toggle(&self.data_collection);
+                if !is_frozen {
+                    // Data kept being collected while frozen, so this snaps graphs back to now.
+                    self.states.clear_time_cursors();
+                }
             }
             'c' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
@@ -1229,7 +1754,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(1);
+                    disk.sort_by_column("Mount");
                 }
             }
             'p' => {
@@ -1246,7 +1771,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(5);
+                    disk.sort_by_column("Used%");
                 }
             }
             'P' => {
@@ -1274,13 +1799,10 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(3);
+                    disk.sort_by_column("Free");
                 }
             }
-            '?' => {
-                self.help_dialog_state.is_showing_help = true;
-                self.is_force_redraw = true;
-            }
+            '?' => self.on_question_mark(),
             'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
             'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
@@ -1293,20 +1815,20 @@ impl App {
                     .temp_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    temp.table.set_sort_index(1);
-                    temp.force_data_update();
+                    temp.sort_by_column("Temp");
                 } else if let Some(disk) = self
                     .states
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(4);
+                    disk.sort_by_column("Total");
                 }
             }
             '+' => self.on_plus(),
             '-' => self.on_minus(),
             '=' => self.reset_zoom(),
             'e' => self.toggle_expand_widget(),
+            'O' => self.open_layout_switcher(),
             's' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     self.toggle_sort_menu()
@@ -1315,18 +1837,19 @@ impl App {
                     .temp_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    temp.table.set_sort_index(0);
-                    temp.force_data_update();
+                    temp.sort_by_column("Sensor");
                     self.is_force_redraw = true;
                 }
             }
             'u' => {
-                if let Some(disk) = self
+                if let BottomWidgetType::Temp = self.current_widget.widget_type {
+                    self.set_temperature_type(self.app_config_fields.temperature_type.next());
+                } else if let Some(disk) = self
                     .states
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(2);
+                    disk.sort_by_column("Used");
                 }
             }
             'r' => {
@@ -1335,7 +1858,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(6);
+                    disk.sort_by_column("R/s");
                 }
             }
             'w' => {
@@ -1344,42 +1867,67 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(7);
+                    disk.sort_by_column("W/s");
+                }
+            }
+            ' ' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if let Some(proc_widget_state) = self
+                        .states
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_widget_state.table.toggle_marked();
+                    }
                 }
             }
             'I' => self.invert_sort(),
+            'x' => self.clear_process_filter(),
+            'v' => self.start_process_snapshot(),
+            'V' => self.open_snapshot_comparison(),
             '%' => self.toggle_percentages(),
+            'T' => self.cycle_colour_scheme(),
+            'b' => self.set_byte_standard(self.app_config_fields.byte_standard.toggle()),
+            'R' => self.config_reload_requested = true,
+            '<' => self.change_update_rate(false),
+            '>' => self.change_update_rate(true),
+            '}' => self.skip_to_sort_group(true),
+            '{' => self.skip_to_sort_group(false),
             _ => {}
         }
-
-        if let Some(second_char) = self.second_char {
-            if self.awaiting_second_char && caught_char != second_char {
-                self.awaiting_second_char = false;
-            }
-        }
     }
 
-    pub fn kill_highlighted_process(&mut self) -> Result<()> {
+    /// Kills each of the processes picked to be killed, returning which ones were and weren't.
+    pub fn kill_highlighted_process(&mut self) -> Result<process_killer::KillResult> {
         if let BottomWidgetType::Proc = self.current_widget.widget_type {
-            if let Some((_, pids)) = &self.to_delete_process_list {
+            let mut result = process_killer::KillResult::default();
+            if let Some(to_kill) = &self.to_delete_process_list {
                 #[cfg(target_family = "unix")]
                 let signal = match self.delete_dialog_state.selected_signal {
                     KillSignal::Kill(sig) => sig,
                     KillSignal::Cancel => 15, // should never happen, so just TERM
                 };
-                for pid in pids {
+
+                result = process_killer::kill_each(&to_kill.pids(), |pid| {
                     #[cfg(target_family = "unix")]
                     {
-                        process_killer::kill_process_given_pid(*pid, signal)?;
+                        process_killer::kill_process_given_pid(pid, signal)
                     }
                     #[cfg(target_os = "windows")]
                     {
-                        process_killer::kill_process_given_pid(*pid)?;
+                        process_killer::kill_process_given_pid(pid)
                     }
-                }
+                });
             }
             self.to_delete_process_list = None;
-            Ok(())
+            if let Some(pws) = self
+                .states
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                pws.table.clear_marked();
+            }
+            Ok(result)
         } else {
             Err(BottomError::GenericError(
                 "Cannot kill processes if the current widget is not the Process widget!"
@@ -1388,13 +1936,13 @@ impl App {
         }
     }
 
-    pub fn get_to_delete_processes(&self) -> Option<(String, Vec<Pid>)> {
+    pub fn get_to_delete_processes(&self) -> Option<ProcessesToKill> {
         self.to_delete_process_list.clone()
     }
 
     fn toggle_expand_widget(&mut self) {
         if self.is_expanded {
-            self.is_expanded = false;
+            self.set_expanded(false);
             self.is_force_redraw = true;
         } else {
             self.expand_widget();
@@ -1409,38 +1957,892 @@ impl App {
             match self.current_widget.widget_type {
                 BottomWidgetType::ProcSearch => {}
                 _ => {
-                    self.is_expanded = true;
+                    self.set_expanded(true);
                     self.is_force_redraw = true;
                 }
             }
         }
     }
 
-    pub fn move_widget_selection(&mut self, direction: &WidgetDirection) {
-        // Since we only want to call reset once, we do it like this to avoid
-        // redundant calls on recursion.
-        self.move_widget_selection_logic(direction);
-        self.reset_multi_tap_keys();
-    }
-
-    fn move_widget_selection_logic(&mut self, direction: &WidgetDirection) {
-        /*
-            The actual logic for widget movement.
-
-            We follow these following steps:
-            1. Send a movement signal in `direction`.
-            2. Check if this new widget we've landed on is hidden.  If not, halt.
-            3. If it hidden, loop and either send:
-               - A signal equal to the current direction, if it is opposite of the reflection.
-               - Reflection direction.
-        */
+    /// Sets whether the current widget is expanded, and lets the widget's tables know.
+    fn set_expanded(&mut self, is_expanded: bool) {
+        self.is_expanded = is_expanded;
 
-        if !self.ignore_normal_keybinds() && !self.is_expanded {
-            if let Some(new_widget_id) = &(match direction {
-                WidgetDirection::Left => self.current_widget.left_neighbour,
-                WidgetDirection::Right => self.current_widget.right_neighbour,
-                WidgetDirection::Up => self.current_widget.up_neighbour,
-                WidgetDirection::Down => self.current_widget.down_neighbour,
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc | BottomWidgetType::ProcSearch | BottomWidgetType::ProcSort => {
+                let offset = match self.current_widget.widget_type {
+                    BottomWidgetType::ProcSearch => 1,
+                    BottomWidgetType::ProcSort => 2,
+                    _ => 0,
+                };
+                if let Some(proc_widget_state) = self
+                    .states
+                    .proc_state
+                    .get_mut_widget_state(widget_id - offset)
+                {
+                    proc_widget_state.table.set_expanded(is_expanded);
+                    proc_widget_state.sort_table.set_expanded(is_expanded);
+                }
+            }
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => {
+                let offset =
+                    u64::from(self.current_widget.widget_type == BottomWidgetType::CpuLegend);
+                if let Some(cpu_widget_state) = self
+                    .states
+                    .cpu_state
+                    .get_mut_widget_state(widget_id - offset)
+                {
+                    cpu_widget_state.table.set_expanded(is_expanded);
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp_widget_state) =
+                    self.states.temp_state.get_mut_widget_state(widget_id)
+                {
+                    temp_widget_state.table.set_expanded(is_expanded);
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) =
+                    self.states.disk_state.get_mut_widget_state(widget_id)
+                {
+                    disk_widget_state.table.set_expanded(is_expanded);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves focus to the widget with the given ID, returning whether it could be focused, see
+    /// [`App::is_focusable`]. The previously focused widget is remembered, so focus can be moved
+    /// back with [`App::focus_previous_widget`], and the change is recorded as [`FocusEvent`]s.
+    ///
+    /// All focus changes should go through this rather than setting `current_widget` directly.
+    pub fn focus_widget(&mut self, widget_id: u64) -> bool {
+        if !self.is_focusable(widget_id) {
+            return false;
+        }
+        let Some(widget) = self.widget_map.get(&widget_id) else {
+            return false;
+        };
+
+        let previous_id = self.current_widget.widget_id;
+        self.current_widget = widget.clone();
+        if widget_id != previous_id {
+            if self.focus_history.len() == constants::MAX_FOCUS_HISTORY {
+                self.focus_history.pop_front();
+            }
+            self.focus_history.push_back(previous_id);
+            self.focus_events.push(FocusEvent::Lost(previous_id));
+            self.focus_events.push(FocusEvent::Gained(widget_id));
+        }
+
+        true
+    }
+
+    /// Moves focus to the next widget in `order` after the focused one that can be focused,
+    /// wrapping around to the start. Returns whether focus moved.
+    pub fn focus_next(&mut self, order: &[u64]) -> bool {
+        self.focus_in_order(order.iter().copied())
+    }
+
+    /// Moves focus to the previous widget in `order` before the focused one that can be focused,
+    /// wrapping around to the end. Returns whether focus moved.
+    pub fn focus_prev(&mut self, order: &[u64]) -> bool {
+        self.focus_in_order(order.iter().rev().copied())
+    }
+
+    fn focus_in_order(&mut self, order: impl Iterator<Item = u64> + Clone) -> bool {
+        let current_id = self.current_widget.widget_id;
+        let after_current = order.clone().skip_while(|&id| id != current_id).skip(1);
+        let candidates: Vec<u64> = after_current.chain(order).collect();
+
+        match candidates
+            .into_iter()
+            .find(|&id| id != current_id && self.is_focusable(id))
+        {
+            Some(id) => self.focus_widget(id),
+            None => false,
+        }
+    }
+
+    /// Takes the focus changes since this was last called, oldest first. Each change is a
+    /// [`FocusEvent::Lost`] for the previously focused widget followed by a [`FocusEvent::Gained`].
+    pub fn take_focus_events(&mut self) -> Vec<FocusEvent> {
+        std::mem::take(&mut self.focus_events)
+    }
+
+    /// Returns whether the widget with the given ID exists and can currently be focused. Some widgets,
+    /// like the process search or sort widgets, can only be focused while they are shown.
+    pub fn is_focusable(&self, widget_id: u64) -> bool {
+        let widget_type = match self.widget_map.get(&widget_id) {
+            Some(widget) => &widget.widget_type,
+            None => return false,
+        };
+
+        match widget_type {
+            BottomWidgetType::Empty => false,
+            BottomWidgetType::CpuLegend => self
+                .states
+                .cpu_state
+                .get_widget_state(widget_id - 1)
+                .map(|cpu_widget_state| !cpu_widget_state.is_legend_hidden)
+                .unwrap_or(false),
+            BottomWidgetType::ProcSearch => self
+                .states
+                .proc_state
+                .get_widget_state(widget_id - 1)
+                .map(|proc_widget_state| proc_widget_state.is_search_enabled())
+                .unwrap_or(false),
+            BottomWidgetType::ProcSort => self
+                .states
+                .proc_state
+                .get_widget_state(widget_id - 2)
+                .map(|proc_widget_state| proc_widget_state.is_sort_open)
+                .unwrap_or(false),
+            _ => true,
+        }
+    }
+
+    /// Moves focus back to the most recently focused widget that can still be focused. Doing this
+    /// twice in a row returns to the original widget.
+    pub fn focus_previous_widget(&mut self) {
+        if self.ignore_normal_keybinds() || self.is_expanded {
+            return;
+        }
+
+        while let Some(widget_id) = self.focus_history.pop_back() {
+            if widget_id != self.current_widget.widget_id && self.is_focusable(widget_id) {
+                self.focus_widget(widget_id);
+
+                if let Some(basic_table_widget_state) = &mut self.states.basic_table_widget_state {
+                    if self.current_widget.widget_type.is_widget_table()
+                        || matches!(self.current_widget.widget_type, BottomWidgetType::Battery)
+                    {
+                        basic_table_widget_state.currently_displayed_widget_id =
+                            self.current_widget.widget_id;
+                        basic_table_widget_state.currently_displayed_widget_type =
+                            self.current_widget.widget_type.clone();
+                    }
+                }
+
+                self.is_determining_widget_boundary = true;
+                break;
+            }
+        }
+
+        self.reset_multi_tap_keys();
+    }
+
+    /// Switches to the next built-in colour scheme, re-applying any custom colours on top of it.
+    pub fn cycle_colour_scheme(&mut self) {
+        if self.app_config_fields.no_color {
+            self.set_status_message("Colours are turned off".to_string());
+            return;
+        }
+
+        let colour_scheme = self.colour_scheme.next();
+
+        match self.styling_for(colour_scheme) {
+            Ok(styling) => {
+                self.states.set_colours(&styling);
+                self.colour_scheme = colour_scheme;
+                self.pending_colours = Some(styling);
+                self.is_force_redraw = true;
+                self.set_status_message(format!("Colour scheme: {}", colour_scheme.name()));
+            }
+            Err(err) => {
+                self.set_status_message(format!(
+                    "Unable to switch to colour scheme {}: {err}",
+                    colour_scheme.name()
+                ));
+            }
+        }
+    }
+
+    /// Takes the styling of a colour scheme that was switched to but not yet drawn with, if any.
+    pub fn take_pending_colours(&mut self) -> Option<CanvasStyling> {
+        self.pending_colours.take()
+    }
+
+    /// Halves or doubles how long to wait between data updates, within
+    /// [`constants::MIN_REFRESH_RATE_IN_MILLISECONDS`] and
+    /// [`constants::MAX_REFRESH_RATE_IN_MILLISECONDS`].
+    pub fn change_update_rate(&mut self, slower: bool) {
+        let update_rate = self.app_config_fields.update_rate;
+        let new_update_rate = if slower {
+            update_rate
+                .saturating_mul(2)
+                .min(constants::MAX_REFRESH_RATE_IN_MILLISECONDS.max(update_rate))
+        } else {
+            (update_rate / 2).max(constants::MIN_REFRESH_RATE_IN_MILLISECONDS.min(update_rate))
+        };
+
+        if new_update_rate != update_rate {
+            self.app_config_fields.update_rate = new_update_rate;
+            self.update_rate_change = Some(new_update_rate);
+            self.data_collection
+                .set_retention(self.app_config_fields.retention_ms, new_update_rate);
+        }
+        self.set_status_message(format!("Update rate: {new_update_rate}ms"));
+    }
+
+    /// Takes the update rate that was changed to since this was last called, if any, which the
+    /// collection thread should be told about.
+    pub fn take_update_rate_change(&mut self) -> Option<u64> {
+        self.update_rate_change.take()
+    }
+
+    /// Shows temperatures in all temperature widgets in `unit`.
+    pub fn set_temperature_type(&mut self, unit: temperature::TemperatureType) {
+        self.app_config_fields.temperature_type = unit;
+        for temp in self.states.temp_state.widget_states.values_mut() {
+            temp.set_temperature_type(unit);
+        }
+        self.set_status_message(format!(
+            "Temperature unit: {}",
+            units::temperature_suffix(unit)
+        ));
+    }
+
+    /// Sets whether sizes in the process and disk widgets use binary or decimal prefixes.
+    pub fn set_byte_standard(&mut self, byte_standard: ByteStandard) {
+        self.app_config_fields.byte_standard = byte_standard;
+        self.apply_byte_standard();
+        self.set_status_message(format!(
+            "Byte units: {}",
+            match byte_standard {
+                ByteStandard::Binary => "binary",
+                ByteStandard::Decimal => "decimal",
+            }
+        ));
+    }
+
+    fn apply_byte_standard(&mut self) {
+        let byte_standard = self.app_config_fields.byte_standard;
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.set_byte_standard(byte_standard);
+        }
+        for disk in self.states.disk_state.widget_states.values_mut() {
+            disk.set_byte_standard(byte_standard);
+        }
+    }
+
+    fn apply_number_format(&mut self) {
+        let number_format = self.app_config_fields.number_format;
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.set_number_format(number_format);
+        }
+        for disk in self.states.disk_state.widget_states.values_mut() {
+            disk.set_number_format(number_format);
+        }
+    }
+
+    fn apply_table_settings(&mut self) {
+        self.states.set_table_settings(&self.app_config_fields);
+        if let Some(comparison) = &mut self.snapshot_comparison {
+            comparison.set_table_settings(&self.app_config_fields);
+        }
+    }
+
+    fn apply_restore_scroll_after_search(&mut self) {
+        let restore_scroll_after_search = self.app_config_fields.restore_scroll_after_search;
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.set_restore_scroll_after_search(restore_scroll_after_search);
+        }
+    }
+
+    /// Returns whether a reload of the config file was asked for since this was last called.
+    pub fn take_config_reload_request(&mut self) -> bool {
+        std::mem::take(&mut self.config_reload_requested)
+    }
+
+    /// Asks whether to save the current settings to the config file.
+    pub fn open_save_config(&mut self) {
+        if !self.is_in_dialog() {
+            self.is_showing_save_config = true;
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn confirm_save_config(&mut self) {
+        self.is_showing_save_config = false;
+        self.config_save_requested = true;
+        self.is_force_redraw = true;
+    }
+
+    /// Returns whether saving to the config file was confirmed since this was last called.
+    pub fn take_config_save_request(&mut self) -> bool {
+        std::mem::take(&mut self.config_save_requested)
+    }
+
+    /// Returns the settings that can be changed while running, to save to the config file.
+    pub fn saved_settings(&self) -> SavedSettings {
+        let process = self
+            .states
+            .proc_state
+            .widget_states
+            .iter()
+            .min_by_key(|(widget_id, _)| **widget_id)
+            .map(|(_, proc)| SavedProcessSettings {
+                group: matches!(proc.mode, ProcWidgetMode::Grouped),
+                tree: matches!(proc.mode, ProcWidgetMode::Tree { .. }),
+                process_command: proc.is_using_command(),
+                mem_as_value: !proc.is_mem_percent(),
+            });
+
+        SavedSettings {
+            color: self.colour_scheme.name(),
+            rate: self.app_config_fields.update_rate,
+            temperature_type: self.app_config_fields.temperature_type.name(),
+            decimal_bytes: self.app_config_fields.byte_standard == ByteStandard::Decimal,
+            process,
+            layout_profile: self.active_layout_profile.clone(),
+            widgets: self.saved_widgets(),
+        }
+    }
+
+    /// Returns the settings of each widget in the layout, in the order they are listed in the
+    /// config. Widgets that are only ever made alongside another one, like the CPU legend, are
+    /// left out, as they don't have their own layout entry.
+    fn saved_widgets(&self) -> Vec<SavedWidget> {
+        use BottomWidgetType::*;
+
+        // The basic layout isn't made from the config's layout.
+        if self.app_config_fields.use_basic_mode {
+            return Vec::new();
+        }
+
+        let mut widgets: Vec<&BottomWidget> = self
+            .widget_map
+            .values()
+            .filter(|widget| {
+                !matches!(
+                    widget.widget_type,
+                    CpuLegend
+                        | ProcSearch
+                        | ProcSort
+                        | BasicCpu
+                        | BasicMem
+                        | BasicNet
+                        | BasicTables
+                )
+            })
+            .collect();
+        widgets.sort_by_key(|widget| widget.widget_id);
+
+        widgets
+            .into_iter()
+            .map(|widget| {
+                let id = widget.widget_id;
+                let hidden_columns = match widget.widget_type {
+                    Proc => self
+                        .states
+                        .proc_state
+                        .widget_states
+                        .get(&id)
+                        .map(|proc| proc.hidden_column_names()),
+                    Disk => self
+                        .states
+                        .disk_state
+                        .widget_states
+                        .get(&id)
+                        .map(|disk| disk.table.hidden_column_names()),
+                    Temp => self
+                        .states
+                        .temp_state
+                        .widget_states
+                        .get(&id)
+                        .map(|temp| temp.table.hidden_column_names()),
+                    _ => None,
+                };
+
+                SavedWidget {
+                    widget_type: widget.widget_type.clone(),
+                    hidden_columns,
+                }
+            })
+            .collect()
+    }
+
+    /// Reports how saving to the config file went.
+    pub fn on_config_saved(&mut self, result: anyhow::Result<SaveOutcome>) {
+        self.set_status_message(match result {
+            Ok(SaveOutcome::Saved) => "Saved the current settings to the config file".to_string(),
+            Ok(SaveOutcome::Unchanged) => "The config file already has these settings".to_string(),
+            Ok(SaveOutcome::DryRun) => {
+                "Dry run: logged the changes instead of saving the config file".to_string()
+            }
+            Err(err) => format!("Unable to save the config file: {err:#}"),
+        });
+    }
+
+    /// Opens the list of layout profiles to switch between, with the one in use selected. There is
+    /// nothing to switch in basic mode, as it has a fixed layout.
+    pub fn open_layout_switcher(&mut self) {
+        if !self.is_in_dialog() && !self.app_config_fields.use_basic_mode {
+            let selected = self
+                .layout_profiles
+                .iter()
+                .position(|name| *name == self.active_layout_profile)
+                .unwrap_or(0);
+            self.layout_switcher_state = Some(selected);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Replaces the names of the layout profiles that can be switched between. If the layout
+    /// switcher is open, it keeps the same profile selected by name, wherever it moved to, or
+    /// selects the one in use if it is gone.
+    fn set_layout_profiles(&mut self, layout_profiles: Vec<String>) {
+        let selected = self
+            .layout_switcher_state
+            .and_then(|selected| self.layout_profiles.get(selected))
+            .cloned();
+        self.layout_profiles = layout_profiles;
+
+        if let Some(selected) = selected {
+            let position = |name: &str| self.layout_profiles.iter().position(|n| n == name);
+            self.layout_switcher_state = Some(
+                position(&selected)
+                    .or_else(|| position(&self.active_layout_profile))
+                    .unwrap_or(0),
+            );
+        }
+    }
+
+    fn confirm_layout_switch(&mut self) {
+        if let Some(selected) = self.layout_switcher_state.take() {
+            if let Some(name) = self.layout_profiles.get(selected) {
+                if *name != self.active_layout_profile {
+                    self.layout_switch_request = Some(name.clone());
+                }
+            }
+        }
+        self.is_force_redraw = true;
+    }
+
+    /// Returns the layout profile that was picked to switch to since this was last called, if any.
+    /// This is the one already in use if a reloaded config changed its layout.
+    pub fn take_layout_switch_request(&mut self) -> Option<String> {
+        self.layout_switch_request.take()
+    }
+
+    /// Returns the styling currently drawn with, built from the colour scheme and custom colours.
+    pub fn current_styling(&self) -> anyhow::Result<CanvasStyling> {
+        self.styling_for(self.colour_scheme)
+    }
+
+    /// Returns the styling for `colour_scheme`, with any custom colours applied on top of it.
+    /// Colours are left out entirely if they are turned off.
+    fn styling_for(&self, colour_scheme: ColourScheme) -> anyhow::Result<CanvasStyling> {
+        let styling = if self.app_config_fields.no_color {
+            CanvasStyling::no_colour()
+        } else {
+            CanvasStyling::with_custom_colours(colour_scheme, self.custom_colours.as_ref())?
+        };
+
+        Ok(styling.with_symbols(Symbols::new(self.app_config_fields.unicode_symbols)))
+    }
+
+    /// Switches to the layout profile called `name`, taking the widgets of `new_app`, which was
+    /// built for it. Widgets don't have names, so the state of each widget in the old layout,
+    /// like its sort order, search, or zoom, is carried over to the widget of the same kind in
+    /// the same position in the new one. Collected data and runtime settings are kept.
+    ///
+    /// Switching to the layout profile already in use rebuilds it, like after the config file
+    /// changed it.
+    pub fn switch_layout(&mut self, name: &str, new_app: App) {
+        let App {
+            states: mut new_states,
+            widget_map,
+            current_widget,
+            used_widgets,
+            collection_rates,
+            layout_profiles,
+            ..
+        } = new_app;
+
+        carry_over_states(
+            &mut self.states.cpu_state.widget_states,
+            &mut new_states.cpu_state.widget_states,
+        );
+        carry_over_states(
+            &mut self.states.mem_state.widget_states,
+            &mut new_states.mem_state.widget_states,
+        );
+        carry_over_states(
+            &mut self.states.net_state.widget_states,
+            &mut new_states.net_state.widget_states,
+        );
+        carry_over_states(
+            &mut self.states.proc_state.widget_states,
+            &mut new_states.proc_state.widget_states,
+        );
+        carry_over_states(
+            &mut self.states.temp_state.widget_states,
+            &mut new_states.temp_state.widget_states,
+        );
+        carry_over_states(
+            &mut self.states.disk_state.widget_states,
+            &mut new_states.disk_state.widget_states,
+        );
+        carry_over_states(
+            &mut self.states.battery_state.widget_states,
+            &mut new_states.battery_state.widget_states,
+        );
+
+        self.states = new_states;
+        self.widget_map = widget_map;
+        self.current_widget = current_widget;
+        self.used_widgets = used_widgets;
+        self.collection_rates = collection_rates;
+        self.set_layout_profiles(layout_profiles);
+        self.widget_update_schedule = WidgetUpdateSchedule::default();
+        self.focus_history.clear();
+        self.is_expanded = false;
+
+        // Widgets new to this layout were built from the config, so bring them in line with
+        // anything changed while running.
+        for temp in self.states.temp_state.widget_states.values_mut() {
+            temp.set_temperature_type(self.app_config_fields.temperature_type);
+        }
+        self.apply_byte_standard();
+        self.apply_number_format();
+        self.apply_restore_scroll_after_search();
+
+        if self.active_layout_profile == name {
+            self.set_status_message("Reloaded the config file and its layout".to_string());
+        } else {
+            self.active_layout_profile = name.to_string();
+            self.set_status_message(format!("Switched to layout {name}"));
+        }
+        self.is_force_redraw = true;
+    }
+
+    /// Reports that switching to the layout profile called `name` failed. The current layout is
+    /// kept.
+    pub fn on_layout_switch_error(&mut self, name: &str, err: &anyhow::Error) {
+        self.set_status_message(format!("Unable to switch to layout {name}: {err:#}"));
+    }
+
+    /// Applies the settings and colours from a reloaded config file. Only the ones that changed in
+    /// the file are applied, so anything changed while running, like the byte units, is kept
+    /// unless the file changes it too. If the layout changed, the layout profile in use is rebuilt
+    /// like when switching layouts, see [`App::take_layout_switch_request`]. Settings that the
+    /// collection thread was started with are kept as-is, as changing them requires a restart.
+    pub fn apply_reloaded_config(&mut self, reloaded: ReloadedConfig) {
+        let ReloadedConfig {
+            app_config_fields: loaded,
+            previous_app_config_fields: previous,
+            colour_scheme,
+            custom_colours,
+            styling,
+            colours_changed,
+            layout_changed,
+            layout_profiles,
+        } = reloaded;
+
+        // Every field is listed so that a new one has to be either applied or left for a restart.
+        let AppConfigFields {
+            update_rate,
+            temperature_type,
+            use_dot,
+            left_legend,
+            show_average_cpu: _,
+            use_current_cpu_total: _,
+            unnormalized_cpu: _,
+            use_basic_mode: _,
+            default_time_value,
+            min_time_value,
+            time_interval,
+            zoom_all_graphs,
+            config_save_dry_run,
+            hide_time,
+            autohide_time,
+            use_old_network_legend,
+            table_gap,
+            disable_click: _,
+            debug_timings: _,
+            enable_gpu_memory,
+            enable_cache_memory,
+            show_table_scroll_position,
+            scroll_step,
+            min_terminal_size,
+            wrap_navigation,
+            keep_filter_on_close,
+            restore_scroll_after_search,
+            unicode_symbols,
+            uppercase_headers,
+            dim_unfocused_tables,
+            no_color,
+            status_bar,
+            is_advanced_kill,
+            network_unit_type,
+            network_scale_type,
+            network_use_binary_prefix,
+            byte_standard,
+            number_format,
+            retention_ms,
+            max_data_memory,
+        } = loaded;
+
+        let current = &mut self.app_config_fields;
+        macro_rules! apply_changed {
+            ($($field:ident),* $(,)?) => {{
+                let mut changed = false;
+                $(
+                    if $field != previous.$field {
+                        current.$field = $field;
+                        changed = true;
+                    }
+                )*
+                changed
+            }};
+        }
+
+        let rate_changed = apply_changed!(update_rate);
+        let history_changed = apply_changed!(retention_ms, max_data_memory);
+        let temperature_changed = apply_changed!(temperature_type);
+        let byte_standard_changed = apply_changed!(byte_standard);
+        let number_format_changed = apply_changed!(number_format);
+        let restore_scroll_changed = apply_changed!(restore_scroll_after_search);
+        let table_settings_changed = apply_changed!(
+            table_gap,
+            show_table_scroll_position,
+            uppercase_headers,
+            dim_unfocused_tables,
+        );
+        apply_changed!(
+            use_dot,
+            left_legend,
+            default_time_value,
+            min_time_value,
+            time_interval,
+            zoom_all_graphs,
+            config_save_dry_run,
+            hide_time,
+            autohide_time,
+            use_old_network_legend,
+            enable_gpu_memory,
+            enable_cache_memory,
+            scroll_step,
+            min_terminal_size,
+            wrap_navigation,
+            keep_filter_on_close,
+            unicode_symbols,
+            no_color,
+            status_bar,
+            is_advanced_kill,
+            network_unit_type,
+            network_scale_type,
+            network_use_binary_prefix,
+        );
+
+        if rate_changed {
+            self.update_rate_change = Some(self.app_config_fields.update_rate);
+        }
+        if rate_changed || history_changed {
+            self.data_collection.set_retention(
+                self.app_config_fields.retention_ms,
+                self.app_config_fields.update_rate,
+            );
+            self.data_budget
+                .set_max_bytes(self.app_config_fields.max_data_memory);
+            self.enforce_data_budget();
+            self.clamp_graph_times();
+        }
+
+        if temperature_changed {
+            for temp in self.states.temp_state.widget_states.values_mut() {
+                temp.set_temperature_type(self.app_config_fields.temperature_type);
+            }
+        }
+        if byte_standard_changed {
+            self.apply_byte_standard();
+        }
+        if number_format_changed {
+            self.apply_number_format();
+        }
+        if restore_scroll_changed {
+            self.apply_restore_scroll_after_search();
+        }
+        if table_settings_changed {
+            self.apply_table_settings();
+        }
+
+        if colours_changed {
+            self.states.set_colours(&styling);
+            self.colour_scheme = colour_scheme;
+            self.custom_colours = custom_colours;
+            self.pending_colours = Some(styling);
+        }
+        self.is_force_redraw = true;
+
+        self.set_layout_profiles(layout_profiles);
+        self.set_status_message("Reloaded the config file".to_string());
+        if layout_changed {
+            self.layout_switch_request = Some(self.active_layout_profile.clone());
+        }
+    }
+
+    /// Reports that reloading the config file failed. The previously loaded settings are kept.
+    pub fn on_config_reload_error(&mut self, err: &anyhow::Error) {
+        self.set_status_message(format!("Unable to reload the config file: {err:#}"));
+    }
+
+    /// Asks for a name for a new snapshot of the processes shown, if a process widget is selected.
+    fn start_process_snapshot(&mut self) {
+        if !matches!(self.current_widget.widget_type, BottomWidgetType::Proc) {
+            return;
+        }
+
+        self.snapshot_name = Some(String::new());
+        self.is_force_redraw = true;
+    }
+
+    /// Takes a snapshot of the processes shown called `name`.
+    fn take_process_snapshot(&mut self, name: &str) {
+        let data = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+        let snapshot =
+            self.process_snapshots
+                .take(name, &data.process_data.process_harvest, self.clock.now());
+        let message = format!("Took {}, press V to compare against it", snapshot.name);
+        self.set_status_message(message);
+    }
+
+    /// Opens a comparison between the latest snapshot and the current processes.
+    fn open_snapshot_comparison(&mut self) {
+        if !matches!(self.current_widget.widget_type, BottomWidgetType::Proc) {
+            return;
+        }
+
+        if self.process_snapshots.is_empty() {
+            self.set_status_message("No snapshots to compare against, press v to take one".into());
+            return;
+        }
+
+        let latest = self.process_snapshots.len() - 1;
+        self.snapshot_comparison = Some(SnapshotComparison::new(&self.app_config_fields, latest));
+        self.refresh_snapshot_comparison();
+        self.is_force_redraw = true;
+    }
+
+    /// Compares the processes shown against the open comparison's snapshot again.
+    pub fn refresh_snapshot_comparison(&mut self) {
+        let Some(comparison) = &mut self.snapshot_comparison else {
+            return;
+        };
+
+        let data = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+        match self.process_snapshots.get(comparison.snapshot_index) {
+            Some(snapshot) => comparison.update(
+                snapshot,
+                &data.process_data.process_harvest,
+                self.clock.now(),
+            ),
+            None => self.snapshot_comparison = None,
+        }
+    }
+
+    /// Compares against the next newer snapshot, or older if `newer` is false, if there is one.
+    fn switch_compared_snapshot(&mut self, newer: bool) {
+        let num_snapshots = self.process_snapshots.len();
+        if let Some(comparison) = &mut self.snapshot_comparison {
+            let index = if newer {
+                min(
+                    comparison.snapshot_index + 1,
+                    num_snapshots.saturating_sub(1),
+                )
+            } else {
+                comparison.snapshot_index.saturating_sub(1)
+            };
+
+            if index != comparison.snapshot_index {
+                comparison.snapshot_index = index;
+                comparison.table.set_first();
+                self.refresh_snapshot_comparison();
+            }
+        }
+    }
+
+    /// Drops the snapshot being compared against, closing the comparison if it was the last one.
+    fn drop_compared_snapshot(&mut self) {
+        let Some(comparison) = &mut self.snapshot_comparison else {
+            return;
+        };
+
+        if let Some(snapshot) = self.process_snapshots.remove(comparison.snapshot_index) {
+            if self.process_snapshots.is_empty() {
+                self.snapshot_comparison = None;
+            } else {
+                comparison.snapshot_index =
+                    min(comparison.snapshot_index, self.process_snapshots.len() - 1);
+                self.refresh_snapshot_comparison();
+            }
+            self.set_status_message(format!("Dropped {}", snapshot.name));
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Shows `message` in the status line for a short while.
+    fn set_status_message(&mut self, message: String) {
+        self.status_message = Some((message, self.clock.now()));
+    }
+
+    /// Clears the status message if it has been shown long enough, returning whether it was
+    /// cleared. This is meant to be checked on every tick, as otherwise an expired message stays
+    /// drawn until something else triggers a redraw, like while frozen.
+    pub fn take_expired_status_message(&mut self) -> bool {
+        let is_expired = self.status_message.is_some() && self.status_message().is_none();
+        if is_expired {
+            self.status_message = None;
+        }
+
+        is_expired
+    }
+
+    /// Returns the status message to show, if one was set recently enough.
+    pub fn status_message(&self) -> Option<&str> {
+        self.status_message.as_ref().and_then(|(message, set_at)| {
+            let elapsed = self.clock.now().duration_since(*set_at).as_millis();
+            (elapsed < u128::from(constants::STATUS_MESSAGE_DURATION_IN_MILLISECONDS))
+                .then_some(message.as_str())
+        })
+    }
+
+    pub fn move_widget_selection(&mut self, direction: &WidgetDirection) {
+        // Since we only want to call reset once, we do it like this to avoid
+        // redundant calls on recursion.
+        self.move_widget_selection_logic(direction);
+        self.reset_multi_tap_keys();
+    }
+
+    fn move_widget_selection_logic(&mut self, direction: &WidgetDirection) {
+        /*
+            The actual logic for widget movement.
+
+            We follow these following steps:
+            1. Send a movement signal in `direction`.
+            2. Check if this new widget we've landed on is hidden.  If not, halt.
+            3. If it hidden, loop and either send:
+               - A signal equal to the current direction, if it is opposite of the reflection.
+               - Reflection direction.
+        */
+
+        if !self.ignore_normal_keybinds() && !self.is_expanded {
+            if let Some(new_widget_id) = &(match direction {
+                WidgetDirection::Left => self.current_widget.left_neighbour,
+                WidgetDirection::Right => self.current_widget.right_neighbour,
+                WidgetDirection::Up => self.current_widget.up_neighbour,
+                WidgetDirection::Down => self.current_widget.down_neighbour,
             }) {
                 if let Some(new_widget) = self.widget_map.get(new_widget_id) {
                     match &new_widget.widget_type {
@@ -1462,7 +2864,7 @@ impl App {
                                     .get(&(new_widget_id - 2))
                                 {
                                     if proc_widget_state.is_sort_open {
-                                        self.current_widget = new_widget.clone();
+                                        self.focus_widget(new_widget.widget_id);
                                     } else if let Some(next_new_widget_id) = match direction {
                                         WidgetDirection::Left => new_widget.left_neighbour,
                                         _ => new_widget.right_neighbour,
@@ -1470,12 +2872,12 @@ impl App {
                                         if let Some(next_new_widget) =
                                             self.widget_map.get(&next_new_widget_id)
                                         {
-                                            self.current_widget = next_new_widget.clone();
+                                            self.focus_widget(next_new_widget.widget_id);
                                         }
                                     }
                                 }
                             } else {
-                                self.current_widget = new_widget.clone();
+                                self.focus_widget(new_widget.widget_id);
                             }
 
                             if let Some(basic_table_widget_state) =
@@ -1500,7 +2902,7 @@ impl App {
                                         if let Some(next_new_widget) =
                                             self.widget_map.get(&next_new_widget_id)
                                         {
-                                            self.current_widget = next_new_widget.clone();
+                                            self.focus_widget(next_new_widget.widget_id);
                                         }
                                     }
                                 }
@@ -1524,11 +2926,13 @@ impl App {
                                         if let Some(next_new_widget) = self.widget_map.get(
                                             &basic_table_widget_state.currently_displayed_widget_id,
                                         ) {
-                                            self.current_widget = next_new_widget.clone();
+                                            self.focus_widget(next_new_widget.widget_id);
                                         }
                                     }
                                 }
-                                _ => self.current_widget = new_widget.clone(),
+                                _ => {
+                                    self.focus_widget(new_widget.widget_id);
+                                }
                             }
                         }
                         _ if new_widget.parent_reflector.is_some() => {
@@ -1558,12 +2962,13 @@ impl App {
                                                         if let Some(next_neighbour_widget) =
                                                             self.widget_map.get(&next_neighbour_id)
                                                         {
-                                                            self.current_widget =
-                                                                next_neighbour_widget.clone();
+                                                            self.focus_widget(
+                                                                next_neighbour_widget.widget_id,
+                                                            );
                                                         }
                                                     }
                                                 } else {
-                                                    self.current_widget = new_widget.clone();
+                                                    self.focus_widget(new_widget.widget_id);
                                                 }
                                             }
                                         }
@@ -1585,14 +2990,14 @@ impl App {
                                                                     self.widget_map
                                                                         .get(&next_neighbour_id)
                                                                 {
-                                                                    self.current_widget =
+                                                                    self.focus_widget(
                                                                         next_neighbour_widget
-                                                                            .clone();
+                                                                            .widget_id,
+                                                                    );
                                                                 }
                                                             }
                                                         } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
+                                                            self.focus_widget(new_widget.widget_id);
                                                         }
                                                     }
                                                     BottomWidgetType::ProcSort => {
@@ -1604,24 +3009,24 @@ impl App {
                                                                     self.widget_map
                                                                         .get(&next_neighbour_id)
                                                                 {
-                                                                    self.current_widget =
+                                                                    self.focus_widget(
                                                                         next_neighbour_widget
-                                                                            .clone();
+                                                                            .widget_id,
+                                                                    );
                                                                 }
                                                             }
                                                         } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
+                                                            self.focus_widget(new_widget.widget_id);
                                                         }
                                                     }
                                                     _ => {
-                                                        self.current_widget = new_widget.clone();
+                                                        self.focus_widget(new_widget.widget_id);
                                                     }
                                                 }
                                             }
                                         }
                                         _ => {
-                                            self.current_widget = new_widget.clone();
+                                            self.focus_widget(new_widget.widget_id);
                                         }
                                     }
                                 } else {
@@ -1639,11 +3044,12 @@ impl App {
                                                         .widget_map
                                                         .get(&(new_widget_id - *offset))
                                                     {
-                                                        self.current_widget =
-                                                            parent_cpu_widget.clone();
+                                                        self.focus_widget(
+                                                            parent_cpu_widget.widget_id,
+                                                        );
                                                     }
                                                 } else {
-                                                    self.current_widget = new_widget.clone();
+                                                    self.focus_widget(new_widget.widget_id);
                                                 }
                                             }
                                         }
@@ -1662,12 +3068,12 @@ impl App {
                                                                 .widget_map
                                                                 .get(&(new_widget_id - *offset))
                                                             {
-                                                                self.current_widget =
-                                                                    parent_proc_widget.clone();
+                                                                self.focus_widget(
+                                                                    parent_proc_widget.widget_id,
+                                                                );
                                                             }
                                                         } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
+                                                            self.focus_widget(new_widget.widget_id);
                                                         }
                                                     }
                                                     BottomWidgetType::ProcSort => {
@@ -1676,22 +3082,22 @@ impl App {
                                                                 .widget_map
                                                                 .get(&(new_widget_id - *offset))
                                                             {
-                                                                self.current_widget =
-                                                                    parent_proc_widget.clone();
+                                                                self.focus_widget(
+                                                                    parent_proc_widget.widget_id,
+                                                                );
                                                             }
                                                         } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
+                                                            self.focus_widget(new_widget.widget_id);
                                                         }
                                                     }
                                                     _ => {
-                                                        self.current_widget = new_widget.clone();
+                                                        self.focus_widget(new_widget.widget_id);
                                                     }
                                                 }
                                             }
                                         }
                                         _ => {
-                                            self.current_widget = new_widget.clone();
+                                            self.focus_widget(new_widget.widget_id);
                                         }
                                     }
                                 }
@@ -1699,7 +3105,7 @@ impl App {
                         }
                         _ => {
                             // Cannot be hidden, does not special treatment.
-                            self.current_widget = new_widget.clone();
+                            self.focus_widget(new_widget.widget_id);
                         }
                     }
 
@@ -1761,7 +3167,7 @@ impl App {
                         {
                             if let Some(new_widget_id) = current_widget.up_neighbour {
                                 if let Some(new_widget) = self.widget_map.get(&new_widget_id) {
-                                    self.current_widget = new_widget.clone();
+                                    self.focus_widget(new_widget.widget_id);
                                 }
                             }
                         }
@@ -1781,7 +3187,7 @@ impl App {
                                         self.states.proc_state.get_widget_state(widget_id)
                                     {
                                         if proc_widget_state.is_search_enabled() {
-                                            self.current_widget = new_widget.clone();
+                                            self.focus_widget(new_widget.widget_id);
                                         }
                                     }
                                 }
@@ -1805,7 +3211,7 @@ impl App {
                 {
                     if proc_widget_state.is_sort_open {
                         if let Some(proc_sort_widget) = self.widget_map.get(&new_widget_id) {
-                            self.current_widget = proc_sort_widget.clone(); // TODO: Could I remove this clone w/ static references?
+                            self.focus_widget(proc_sort_widget.widget_id);
                         }
                     }
                 }
@@ -1822,7 +3228,7 @@ impl App {
                         if !cpu_widget_state.is_legend_hidden {
                             if let Some(new_widget_id) = current_widget.left_neighbour {
                                 if let Some(new_widget) = self.widget_map.get(&new_widget_id) {
-                                    self.current_widget = new_widget.clone();
+                                    self.focus_widget(new_widget.widget_id);
                                 }
                             }
                         }
@@ -1833,7 +3239,7 @@ impl App {
             if let Some(current_widget) = self.widget_map.get(&self.current_widget.widget_id) {
                 if let Some(new_widget_id) = current_widget.left_neighbour {
                     if let Some(new_widget) = self.widget_map.get(&new_widget_id) {
-                        self.current_widget = new_widget.clone();
+                        self.focus_widget(new_widget.widget_id);
                     }
                 }
             }
@@ -1844,7 +3250,7 @@ impl App {
         if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
             if let Some(new_widget_id) = self.current_widget.right_neighbour {
                 if let Some(proc_sort_widget) = self.widget_map.get(&new_widget_id) {
-                    self.current_widget = proc_sort_widget.clone();
+                    self.focus_widget(proc_sort_widget.widget_id);
                 }
             }
         } else if self.app_config_fields.left_legend {
@@ -1852,7 +3258,7 @@ impl App {
                 if let Some(current_widget) = self.widget_map.get(&self.current_widget.widget_id) {
                     if let Some(new_widget_id) = current_widget.right_neighbour {
                         if let Some(new_widget) = self.widget_map.get(&new_widget_id) {
-                            self.current_widget = new_widget.clone();
+                            self.focus_widget(new_widget.widget_id);
                         }
                     }
                 }
@@ -1868,7 +3274,7 @@ impl App {
                     if !cpu_widget_state.is_legend_hidden {
                         if let Some(new_widget_id) = current_widget.right_neighbour {
                             if let Some(new_widget) = self.widget_map.get(&new_widget_id) {
-                                self.current_widget = new_widget.clone();
+                                self.focus_widget(new_widget.widget_id);
                             }
                         }
                     }
@@ -1997,30 +3403,81 @@ impl App {
         }
     }
 
-    pub fn decrement_position_count(&mut self) {
-        self.change_position_count(-1);
+    /// Moves the selection of the current table to the next (or previous) point where the value in
+    /// the sort column changes.
+    fn skip_to_sort_group(&mut self, forward: bool) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                if let Some(proc_widget_state) =
+                    self.states.proc_state.get_mut_widget_state(widget_id)
+                {
+                    if forward {
+                        proc_widget_state.table.skip_to_next_group();
+                    } else {
+                        proc_widget_state.table.skip_to_previous_group();
+                    }
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp_widget_state) =
+                    self.states.temp_state.get_mut_widget_state(widget_id)
+                {
+                    if forward {
+                        temp_widget_state.table.skip_to_next_group();
+                    } else {
+                        temp_widget_state.table.skip_to_previous_group();
+                    }
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) =
+                    self.states.disk_state.get_mut_widget_state(widget_id)
+                {
+                    if forward {
+                        disk_widget_state.table.skip_to_next_group();
+                    } else {
+                        disk_widget_state.table.skip_to_previous_group();
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.reset_multi_tap_keys();
+    }
+
+    /// Moves the selection of the current table up by one. Returns the new position if it moved.
+    pub fn decrement_position_count(&mut self) -> Option<usize> {
+        self.change_position_count(-1)
     }
 
-    pub fn increment_position_count(&mut self) {
-        self.change_position_count(1);
+    /// Moves the selection of the current table down by one. Returns the new position if it moved.
+    pub fn increment_position_count(&mut self) -> Option<usize> {
+        self.change_position_count(1)
     }
 
-    fn change_position_count(&mut self, amount: i64) {
+    /// Returns the new position.
+    fn change_position_count(&mut self, amount: i64) -> Option<usize> {
         if !self.ignore_normal_keybinds() {
             match self.current_widget.widget_type {
-                BottomWidgetType::Proc => {
-                    self.change_process_position(amount);
-                }
+                BottomWidgetType::Proc => self.change_process_position(amount),
                 BottomWidgetType::ProcSort => self.change_process_sort_position(amount),
                 BottomWidgetType::Temp => self.change_temp_position(amount),
                 BottomWidgetType::Disk => self.change_disk_position(amount),
                 BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
-                _ => {}
+                _ => None,
             }
+        } else {
+            None
         }
     }
 
-    fn change_process_sort_position(&mut self, num_to_change_by: i64) {
+    /// Returns the new position.
+    fn change_process_sort_position(&mut self, num_to_change_by: i64) -> Option<usize> {
         if let Some(proc_widget_state) = self
             .states
             .proc_state
@@ -2028,18 +3485,23 @@ impl App {
         {
             proc_widget_state
                 .sort_table
-                .increment_position(num_to_change_by);
+                .increment_position(num_to_change_by)
+        } else {
+            None
         }
     }
 
-    fn change_cpu_legend_position(&mut self, num_to_change_by: i64) {
+    /// Returns the new position.
+    fn change_cpu_legend_position(&mut self, num_to_change_by: i64) -> Option<usize> {
         if let Some(cpu_widget_state) = self
             .states
             .cpu_state
             .widget_states
             .get_mut(&(self.current_widget.widget_id - 1))
         {
-            cpu_widget_state.table.increment_position(num_to_change_by);
+            cpu_widget_state.table.increment_position(num_to_change_by)
+        } else {
+            None
         }
     }
 
@@ -2056,25 +3518,31 @@ impl App {
         }
     }
 
-    fn change_temp_position(&mut self, num_to_change_by: i64) {
+    /// Returns the new position.
+    fn change_temp_position(&mut self, num_to_change_by: i64) -> Option<usize> {
         if let Some(temp_widget_state) = self
             .states
             .temp_state
             .widget_states
             .get_mut(&self.current_widget.widget_id)
         {
-            temp_widget_state.table.increment_position(num_to_change_by);
+            temp_widget_state.table.increment_position(num_to_change_by)
+        } else {
+            None
         }
     }
 
-    fn change_disk_position(&mut self, num_to_change_by: i64) {
+    /// Returns the new position.
+    fn change_disk_position(&mut self, num_to_change_by: i64) -> Option<usize> {
         if let Some(disk_widget_state) = self
             .states
             .disk_state
             .widget_states
             .get_mut(&self.current_widget.widget_id)
         {
-            disk_widget_state.table.increment_position(num_to_change_by);
+            disk_widget_state.table.increment_position(num_to_change_by)
+        } else {
+            None
         }
     }
 
@@ -2101,6 +3569,15 @@ impl App {
         }
     }
 
+    /// How many entries to move a table's selection by per mouse scroll.
+    fn scroll_step(&self) -> i64 {
+        self.app_config_fields
+            .scroll_step
+            .try_into()
+            .unwrap_or(i64::MAX)
+            .max(1)
+    }
+
     pub fn handle_scroll_up(&mut self) {
         if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
@@ -2111,10 +3588,12 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
-        } else if self.current_widget.widget_type.is_widget_graph() {
-            self.zoom_in();
-        } else if self.current_widget.widget_type.is_widget_table() {
-            self.decrement_position_count();
+        } else if self.current_widget.widget_type.accepts_scroll() {
+            if self.current_widget.widget_type.is_widget_graph() {
+                self.zoom_in();
+            } else {
+                self.change_position_count(-self.scroll_step());
+            }
         }
     }
 
@@ -2128,10 +3607,12 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
-        } else if self.current_widget.widget_type.is_widget_graph() {
-            self.zoom_out();
-        } else if self.current_widget.widget_type.is_widget_table() {
-            self.increment_position_count();
+        } else if self.current_widget.widget_type.accepts_scroll() {
+            if self.current_widget.widget_type.is_widget_graph() {
+                self.zoom_out();
+            } else {
+                self.change_position_count(self.scroll_step());
+            }
         }
     }
 
@@ -2165,236 +3646,134 @@ impl App {
     }
 
     fn zoom_out(&mut self) {
-        match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => {
-                if let Some(cpu_widget_state) = self
-                    .states
-                    .cpu_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = cpu_widget_state
-                        .current_display_time
-                        .saturating_add(self.app_config_fields.time_interval);
-
-                    if new_time <= self.app_config_fields.retention_ms {
-                        cpu_widget_state.current_display_time = new_time;
-                        self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if cpu_widget_state.current_display_time
-                        != self.app_config_fields.retention_ms
-                    {
-                        cpu_widget_state.current_display_time = self.app_config_fields.retention_ms;
-                        self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+        let time_interval = self.app_config_fields.time_interval;
+        self.zoom_graphs(|current| match time_interval {
+            Some(time_interval) => current.saturating_add(time_interval),
+            None => current.saturating_mul(2),
+        });
+    }
+
+    fn zoom_in(&mut self) {
+        let time_interval = self.app_config_fields.time_interval;
+        self.zoom_graphs(|current| match time_interval {
+            Some(time_interval) => current.saturating_sub(time_interval),
+            None => current / 2,
+        });
+    }
+
+    fn reset_zoom(&mut self) {
+        let default_time_value = self.app_config_fields.default_time_value;
+        self.zoom_graphs(|_| default_time_value);
+    }
+
+    /// Sets the time window of the current graph, or of every graph if `zoom_all_graphs` is set,
+    /// to whatever `new_time` returns for its current window, kept between `min_time_value` and
+    /// the retention period.
+    fn zoom_graphs(&mut self, new_time: impl Fn(u64) -> u64) {
+        let AppConfigFields {
+            min_time_value,
+            retention_ms,
+            autohide_time,
+            zoom_all_graphs,
+            ..
+        } = self.app_config_fields;
+        let current_widget = &self.current_widget;
+        let should_zoom = |widget_type: BottomWidgetType, widget_id: u64| {
+            if zoom_all_graphs {
+                current_widget.widget_type.is_widget_graph()
+            } else {
+                current_widget.widget_type == widget_type && current_widget.widget_id == widget_id
             }
-            BottomWidgetType::Mem => {
-                if let Some(mem_widget_state) = self
-                    .states
-                    .mem_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = mem_widget_state
-                        .current_display_time
-                        .saturating_add(self.app_config_fields.time_interval);
-
-                    if new_time <= self.app_config_fields.retention_ms {
-                        mem_widget_state.current_display_time = new_time;
-                        self.states.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if mem_widget_state.current_display_time
-                        != self.app_config_fields.retention_ms
-                    {
-                        mem_widget_state.current_display_time = self.app_config_fields.retention_ms;
-                        self.states.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+        };
+
+        // Returns whether the window changed, so the graph knows to update.
+        let zoom = |current_display_time: &mut u64, autohide_timer: &mut Option<Instant>| {
+            let new_time = new_time(*current_display_time).clamp(min_time_value, retention_ms);
+            if new_time == *current_display_time {
+                return false;
             }
-            BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .states
-                    .net_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = net_widget_state
-                        .current_display_time
-                        .saturating_add(self.app_config_fields.time_interval);
-
-                    if new_time <= self.app_config_fields.retention_ms {
-                        net_widget_state.current_display_time = new_time;
-                        self.states.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if net_widget_state.current_display_time
-                        != self.app_config_fields.retention_ms
-                    {
-                        net_widget_state.current_display_time = self.app_config_fields.retention_ms;
-                        self.states.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+
+            *current_display_time = new_time;
+            if autohide_time {
+                *autohide_timer = Some(Instant::now());
             }
-            _ => {}
-        }
-    }
+            true
+        };
 
-    fn zoom_in(&mut self) {
-        match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => {
-                if let Some(cpu_widget_state) = self
-                    .states
-                    .cpu_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = cpu_widget_state
-                        .current_display_time
-                        .saturating_sub(self.app_config_fields.time_interval);
-
-                    if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        cpu_widget_state.current_display_time = new_time;
-                        self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if cpu_widget_state.current_display_time
-                        != constants::STALE_MIN_MILLISECONDS
-                    {
-                        cpu_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+        let cpu_state = &mut self.states.cpu_state;
+        for (&widget_id, state) in &mut cpu_state.widget_states {
+            if should_zoom(BottomWidgetType::Cpu, widget_id)
+                && zoom(&mut state.current_display_time, &mut state.autohide_timer)
+            {
+                cpu_state.force_update = Some(widget_id);
             }
-            BottomWidgetType::Mem => {
-                if let Some(mem_widget_state) = self
-                    .states
-                    .mem_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = mem_widget_state
-                        .current_display_time
-                        .saturating_sub(self.app_config_fields.time_interval);
-
-                    if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        mem_widget_state.current_display_time = new_time;
-                        self.states.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if mem_widget_state.current_display_time
-                        != constants::STALE_MIN_MILLISECONDS
-                    {
-                        mem_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.states.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+        }
+
+        let mem_state = &mut self.states.mem_state;
+        for (&widget_id, state) in &mut mem_state.widget_states {
+            if should_zoom(BottomWidgetType::Mem, widget_id)
+                && zoom(&mut state.current_display_time, &mut state.autohide_timer)
+            {
+                mem_state.force_update = Some(widget_id);
             }
-            BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .states
-                    .net_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = net_widget_state
-                        .current_display_time
-                        .saturating_sub(self.app_config_fields.time_interval);
-
-                    if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        net_widget_state.current_display_time = new_time;
-                        self.states.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if net_widget_state.current_display_time
-                        != constants::STALE_MIN_MILLISECONDS
-                    {
-                        net_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.states.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+        }
+
+        let net_state = &mut self.states.net_state;
+        for (&widget_id, state) in &mut net_state.widget_states {
+            if should_zoom(BottomWidgetType::Net, widget_id)
+                && zoom(&mut state.current_display_time, &mut state.autohide_timer)
+            {
+                net_state.force_update = Some(widget_id);
             }
-            _ => {}
         }
     }
 
-    fn reset_cpu_zoom(&mut self) {
-        if let Some(cpu_widget_state) = self
-            .states
-            .cpu_state
-            .widget_states
-            .get_mut(&self.current_widget.widget_id)
-        {
-            cpu_widget_state.current_display_time = self.app_config_fields.default_time_value;
-            self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
-            if self.app_config_fields.autohide_time {
-                cpu_widget_state.autohide_timer = Some(Instant::now());
+    /// Zooms graphs in so that none of them show more time than is kept.
+    fn clamp_graph_times(&mut self) {
+        /// Clamps each widget's time to `retention_ms`, returning the id of the first one that
+        /// changed, if any did.
+        fn clamp<'a>(
+            times: impl Iterator<Item = (&'a u64, &'a mut u64)>, retention_ms: u64,
+        ) -> Option<u64> {
+            let mut clamped = None;
+            for (&widget_id, time) in times {
+                if *time > retention_ms {
+                    *time = retention_ms;
+                    clamped = clamped.or(Some(widget_id));
+                }
             }
+            clamped
         }
-    }
 
-    fn reset_mem_zoom(&mut self) {
-        if let Some(mem_widget_state) = self
-            .states
-            .mem_state
+        let retention_ms = self.app_config_fields.retention_ms;
+
+        // Graph data is converted once for every widget of a type, so forcing an update for any
+        // one of them also updates every other one that was clamped.
+        let cpu_state = &mut self.states.cpu_state;
+        let times = cpu_state
             .widget_states
-            .get_mut(&self.current_widget.widget_id)
-        {
-            mem_widget_state.current_display_time = self.app_config_fields.default_time_value;
-            self.states.mem_state.force_update = Some(self.current_widget.widget_id);
-            if self.app_config_fields.autohide_time {
-                mem_widget_state.autohide_timer = Some(Instant::now());
-            }
+            .iter_mut()
+            .map(|(widget_id, state)| (widget_id, &mut state.current_display_time));
+        if let Some(widget_id) = clamp(times, retention_ms) {
+            cpu_state.force_update = Some(widget_id);
         }
-    }
 
-    fn reset_net_zoom(&mut self) {
-        if let Some(net_widget_state) = self
-            .states
-            .net_state
+        let mem_state = &mut self.states.mem_state;
+        let times = mem_state
             .widget_states
-            .get_mut(&self.current_widget.widget_id)
-        {
-            net_widget_state.current_display_time = self.app_config_fields.default_time_value;
-            self.states.net_state.force_update = Some(self.current_widget.widget_id);
-            if self.app_config_fields.autohide_time {
-                net_widget_state.autohide_timer = Some(Instant::now());
-            }
+            .iter_mut()
+            .map(|(widget_id, state)| (widget_id, &mut state.current_display_time));
+        if let Some(widget_id) = clamp(times, retention_ms) {
+            mem_state.force_update = Some(widget_id);
         }
-    }
 
-    fn reset_zoom(&mut self) {
-        match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => self.reset_cpu_zoom(),
-            BottomWidgetType::Mem => self.reset_mem_zoom(),
-            BottomWidgetType::Net => self.reset_net_zoom(),
-            _ => {}
+        let net_state = &mut self.states.net_state;
+        let times = net_state
+            .widget_states
+            .iter_mut()
+            .map(|(widget_id, state)| (widget_id, &mut state.current_display_time));
+        if let Some(widget_id) = clamp(times, retention_ms) {
+            net_state.force_update = Some(widget_id);
         }
     }
 
@@ -2423,17 +3802,14 @@ impl App {
             {
                 if (x >= left_tlc_x && y >= left_tlc_y) && (x < left_brc_x && y < left_brc_y) {
                     // Case for the left "button" in the simple arrow.
-                    if let Some(new_widget) =
-                        self.widget_map.get(&(bt.currently_displayed_widget_id))
-                    {
-                        // We have to move to the current table widget first...
-                        self.current_widget = new_widget.clone();
-
-                        if let BottomWidgetType::Proc = &new_widget.widget_type {
+                    // We have to move to the current table widget first...
+                    let currently_displayed_widget_id = bt.currently_displayed_widget_id;
+                    if self.focus_widget(currently_displayed_widget_id) {
+                        if let BottomWidgetType::Proc = &self.current_widget.widget_type {
                             if let Some(proc_widget_state) = self
                                 .states
                                 .proc_state
-                                .get_widget_state(new_widget.widget_id)
+                                .get_widget_state(self.current_widget.widget_id)
                             {
                                 if proc_widget_state.is_sort_open {
                                     self.move_widget_selection(&WidgetDirection::Left);
@@ -2447,17 +3823,14 @@ impl App {
                     && (x < right_brc_x && y < right_brc_y)
                 {
                     // Case for the right "button" in the simple arrow.
-                    if let Some(new_widget) =
-                        self.widget_map.get(&(bt.currently_displayed_widget_id))
-                    {
-                        // We have to move to the current table widget first...
-                        self.current_widget = new_widget.clone();
-
-                        if let BottomWidgetType::ProcSort = &new_widget.widget_type {
+                    // We have to move to the current table widget first...
+                    let currently_displayed_widget_id = bt.currently_displayed_widget_id;
+                    if self.focus_widget(currently_displayed_widget_id) {
+                        if let BottomWidgetType::ProcSort = &self.current_widget.widget_type {
                             if let Some(proc_widget_state) = self
                                 .states
                                 .proc_state
-                                .get_widget_state(new_widget.widget_id - 2)
+                                .get_widget_state(self.current_widget.widget_id - 2)
                             {
                                 if proc_widget_state.is_sort_open {
                                     self.move_widget_selection(&WidgetDirection::Right);
@@ -2498,41 +3871,39 @@ impl App {
             return;
         }
 
-        let mut failed_to_get = true;
-        for (new_widget_id, widget) in &self.widget_map {
+        let clicked_widget_id = self.widget_map.iter().find_map(|(widget_id, widget)| {
             if let (Some((tlc_x, tlc_y)), Some((brc_x, brc_y))) =
                 (widget.top_left_corner, widget.bottom_right_corner)
             {
                 if (x >= tlc_x && y >= tlc_y) && (x < brc_x && y < brc_y) {
-                    if let Some(new_widget) = self.widget_map.get(new_widget_id) {
-                        self.current_widget = new_widget.clone();
-                        match &self.current_widget.widget_type {
-                            BottomWidgetType::Temp
-                            | BottomWidgetType::Proc
-                            | BottomWidgetType::ProcSort
-                            | BottomWidgetType::Disk
-                            | BottomWidgetType::Battery => {
-                                if let Some(basic_table_widget_state) =
-                                    &mut self.states.basic_table_widget_state
-                                {
-                                    basic_table_widget_state.currently_displayed_widget_id =
-                                        self.current_widget.widget_id;
-                                    basic_table_widget_state.currently_displayed_widget_type =
-                                        self.current_widget.widget_type.clone();
-                                }
-                            }
-                            _ => {}
-                        }
+                    return Some(*widget_id);
+                }
+            }
+
+            None
+        });
 
-                        failed_to_get = false;
-                        break;
+        match clicked_widget_id {
+            Some(widget_id) if self.focus_widget(widget_id) => {
+                match &self.current_widget.widget_type {
+                    BottomWidgetType::Temp
+                    | BottomWidgetType::Proc
+                    | BottomWidgetType::ProcSort
+                    | BottomWidgetType::Disk
+                    | BottomWidgetType::Battery => {
+                        if let Some(basic_table_widget_state) =
+                            &mut self.states.basic_table_widget_state
+                        {
+                            basic_table_widget_state.currently_displayed_widget_id =
+                                self.current_widget.widget_id;
+                            basic_table_widget_state.currently_displayed_widget_type =
+                                self.current_widget.widget_type.clone();
+                        }
                     }
+                    _ => {}
                 }
             }
-        }
-
-        if failed_to_get {
-            return;
+            _ => return,
         }
 
         // Now handle click propagation down to widget.
@@ -2585,7 +3956,6 @@ impl App {
                                     }
                                 }
                                 BottomWidgetType::ProcSort => {
-                                    // TODO: [Feature] This could sort if you double click!
                                     if let Some(proc_widget_state) = self
                                         .states
                                         .proc_state
@@ -2636,17 +4006,35 @@ impl App {
                                         .disk_state
                                         .get_widget_state(self.current_widget.widget_id)
                                     {
-                                        if let Some(visual_index) =
-                                            disk_widget_state.table.tui_selected()
-                                        {
-                                            self.change_disk_position(
-                                                offset_clicked_entry as i64 - visual_index as i64,
-                                            );
+                                        let table = &disk_widget_state.table;
+                                        if let (Some(visual_index), Some(clicked_row)) = (
+                                            table.tui_selected(),
+                                            table.row_at_line(offset_clicked_entry.into()),
+                                        ) {
+                                            let change = clicked_row as i64 - visual_index as i64;
+                                            self.change_disk_position(change);
+
+                                            // Clicking the selected row again shows or hides its
+                                            // details beneath it.
+                                            if change == 0 {
+                                                self.toggle_disk_row_details();
+                                            }
                                         }
                                     }
                                 }
                                 _ => {}
                             }
+
+                            let is_double_click = self.click_current_row();
+                            if is_double_click
+                                && matches!(
+                                    self.current_widget.widget_type,
+                                    BottomWidgetType::ProcSort
+                                )
+                            {
+                                // Double clicking a sort entry selects it, like pressing enter.
+                                self.on_enter();
+                            }
                         } else {
                             // We might have clicked on a header!  Check if we only exceeded the table + border offset, and
                             // it's implied we exceeded the gap offset.
@@ -2715,6 +4103,133 @@ impl App {
         }
     }
 
+    /// Moves the current table `pages` pages forward (or back, if negative), returning whether it
+    /// is paged. Tables that aren't paged are left alone.
+    fn move_current_table_pages(&mut self, pages: i64) -> bool {
+        if self.ignore_normal_keybinds() {
+            return false;
+        }
+
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                match self.states.proc_state.get_mut_widget_state(widget_id) {
+                    Some(state) => state.table.move_pages(pages),
+                    None => false,
+                }
+            }
+            BottomWidgetType::Temp => {
+                match self.states.temp_state.get_mut_widget_state(widget_id) {
+                    Some(state) => state.table.move_pages(pages),
+                    None => false,
+                }
+            }
+            BottomWidgetType::Disk => {
+                match self.states.disk_state.get_mut_widget_state(widget_id) {
+                    Some(state) => state.table.move_pages(pages),
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Switches the current table between scrolling and being split into pages.
+    pub fn toggle_paging(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                if let Some(state) = self.states.proc_state.get_mut_widget_state(widget_id) {
+                    state.table.set_paged(!state.table.is_paged());
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(state) = self.states.temp_state.get_mut_widget_state(widget_id) {
+                    state.table.set_paged(!state.table.is_paged());
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(state) = self.states.disk_state.get_mut_widget_state(widget_id) {
+                    state.table.set_paged(!state.table.is_paged());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Expands or collapses the details of the selected row in the current disk widget.
+    fn toggle_disk_row_details(&mut self) {
+        if let Some(disk_widget_state) = self
+            .states
+            .disk_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            let table = &mut disk_widget_state.table;
+            table.toggle_row_details(table.current_index());
+        }
+    }
+
+    /// Registers a click on the selected row of the current widget's table, if it has one. Returns
+    /// whether the click was a double click.
+    fn click_current_row(&mut self) -> bool {
+        let widget_id = self.current_widget.widget_id;
+
+        let is_double_click = match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self
+                .states
+                .proc_state
+                .get_mut_widget_state(widget_id)
+                .map(|state| state.table.click_row(state.table.current_index())),
+            BottomWidgetType::ProcSort => self
+                .states
+                .proc_state
+                .get_mut_widget_state(widget_id - 2)
+                .map(|state| state.sort_table.click_row(state.sort_table.current_index())),
+            BottomWidgetType::CpuLegend => self
+                .states
+                .cpu_state
+                .get_mut_widget_state(widget_id - 1)
+                .map(|state| state.table.click_row(state.table.current_index())),
+            BottomWidgetType::Temp => self
+                .states
+                .temp_state
+                .get_mut_widget_state(widget_id)
+                .map(|state| state.table.click_row(state.table.current_index())),
+            BottomWidgetType::Disk => self
+                .states
+                .disk_state
+                .get_mut_widget_state(widget_id)
+                .map(|state| state.table.click_row(state.table.current_index())),
+            _ => None,
+        };
+
+        if is_double_click.is_some() {
+            self.click_flash_at = Some(self.clock.now());
+        }
+
+        is_double_click.unwrap_or(false)
+    }
+
+    /// Clears the last click if its flash has been shown long enough, returning whether it was
+    /// cleared. Like [`App::take_expired_status_message`], this is meant to be checked on every
+    /// tick, so the flash is drawn over even if nothing else triggers a redraw.
+    pub fn take_expired_click_flash(&mut self) -> bool {
+        let duration = Duration::from_millis(constants::CLICK_FLASH_DURATION_IN_MILLISECONDS);
+        let is_expired = matches!(
+            self.click_flash_at,
+            Some(clicked) if self.clock.now().saturating_duration_since(clicked) >= duration
+        );
+        if is_expired {
+            self.click_flash_at = None;
+        }
+
+        is_expired
+    }
+
     fn is_drawing_border(&self) -> bool {
         self.is_expanded || !self.app_config_fields.use_basic_mode
     }
@@ -2736,9 +4251,18 @@ impl App {
     }
 
     /// A quick and dirty way to handle paste events.
+    ///
+    /// Inserts pasted text into the search query as a single edit, so the query is only parsed and
+    /// the processes only filtered once, rather than once per pasted character. Line breaks are
+    /// joined with spaces, as the query is a single line.
     pub fn handle_paste(&mut self, paste: String) {
-        // Partially copy-pasted from the single-char variant; should probably clean up this process in the future.
-        // In particular, encapsulate this entire logic and add some tests to make it less potentially error-prone.
+        let paste = paste.lines().collect::<Vec<_>>().join(" ");
+        if let Some(name) = &mut self.snapshot_name {
+            name.push_str(&paste);
+            self.is_force_redraw = true;
+            return;
+        }
+
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
             .states
@@ -2772,3 +4296,1153 @@ impl App {
         }
     }
 }
+
+/// Moves the widget states in `old` over to the widgets in `new`, matching them up by the order of
+/// their IDs, which follows their position in the layout. Widgets in `new` without a match keep
+/// their fresh state.
+fn carry_over_states<S>(old: &mut HashMap<u64, S>, new: &mut HashMap<u64, S>) {
+    let mut old_ids: Vec<u64> = old.keys().copied().collect();
+    let mut new_ids: Vec<u64> = new.keys().copied().collect();
+    old_ids.sort_unstable();
+    new_ids.sort_unstable();
+
+    for (old_id, new_id) in old_ids.into_iter().zip(new_ids) {
+        if let Some(state) = old.remove(&old_id) {
+            new.insert(new_id, state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        args,
+        canvas::canvas_styling::CanvasStyling,
+        components::data_table::{ColumnHeader, DataTableColumn},
+        options::{apply_layout_profile, get_app_config_fields, Config},
+        utils::test_utils::build_test_app,
+    };
+
+    fn test_app() -> App {
+        test_app_with_config(Config::default())
+    }
+
+    fn test_app_with_config(config: Config) -> App {
+        build_test_app(&["btm"], config, &CanvasStyling::default()).0
+    }
+
+    fn widget_id(app: &App, widget_type: BottomWidgetType) -> u64 {
+        app.widget_map
+            .values()
+            .find(|widget| widget.widget_type == widget_type)
+            .unwrap()
+            .widget_id
+    }
+
+    #[test]
+    fn test_focus_widget() {
+        let mut app = test_app();
+        let temp = widget_id(&app, BottomWidgetType::Temp);
+
+        assert!(app.focus_widget(temp));
+        assert_eq!(app.current_widget.widget_id, temp);
+
+        assert!(!app.focus_widget(u64::MAX));
+        assert_eq!(app.current_widget.widget_id, temp);
+
+        // Widgets that aren't shown can't be focused.
+        let proc_sort = widget_id(&app, BottomWidgetType::ProcSort);
+        assert!(!app.focus_widget(proc_sort));
+        assert_eq!(app.current_widget.widget_id, temp);
+    }
+
+    #[test]
+    fn test_focus_previous_widget() {
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        let proc_sort = widget_id(&app, BottomWidgetType::ProcSort);
+        let temp = widget_id(&app, BottomWidgetType::Temp);
+        let disk = widget_id(&app, BottomWidgetType::Disk);
+
+        app.focus_widget(proc);
+        app.focus_widget(temp);
+        app.focus_widget(disk);
+
+        // Going back twice in a row should swap between the last two widgets.
+        app.focus_previous_widget();
+        assert_eq!(app.current_widget.widget_id, temp);
+        app.focus_previous_widget();
+        assert_eq!(app.current_widget.widget_id, disk);
+
+        // The sort widget can't be focused while it's closed, so it should be skipped.
+        assert!(!app.is_focusable(proc_sort));
+        app.focus_widget(proc);
+        app.toggle_sort_menu();
+        assert_eq!(app.current_widget.widget_id, proc_sort);
+        app.focus_widget(temp);
+        app.states
+            .proc_state
+            .get_mut_widget_state(proc)
+            .unwrap()
+            .is_sort_open = false;
+        app.focus_previous_widget();
+        assert_eq!(app.current_widget.widget_id, proc);
+    }
+
+    #[test]
+    fn test_focus_next_and_prev() {
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        let proc_sort = widget_id(&app, BottomWidgetType::ProcSort);
+        let temp = widget_id(&app, BottomWidgetType::Temp);
+        let disk = widget_id(&app, BottomWidgetType::Disk);
+        let order = [proc, proc_sort, temp, disk];
+
+        app.focus_widget(proc);
+        app.take_focus_events();
+
+        // The closed sort widget is skipped, and focus wraps around at either end.
+        assert!(app.focus_next(&order));
+        assert_eq!(app.current_widget.widget_id, temp);
+        assert!(app.focus_next(&order));
+        assert_eq!(app.current_widget.widget_id, disk);
+        assert!(app.focus_next(&order));
+        assert_eq!(app.current_widget.widget_id, proc);
+        assert!(app.focus_prev(&order));
+        assert_eq!(app.current_widget.widget_id, disk);
+        assert_eq!(
+            app.take_focus_events(),
+            [
+                FocusEvent::Lost(proc),
+                FocusEvent::Gained(temp),
+                FocusEvent::Lost(temp),
+                FocusEvent::Gained(disk),
+                FocusEvent::Lost(disk),
+                FocusEvent::Gained(proc),
+                FocusEvent::Lost(proc),
+                FocusEvent::Gained(disk),
+            ]
+        );
+
+        // Nothing else in the order can be focused.
+        assert!(!app.focus_next(&[disk, proc_sort]));
+        assert_eq!(app.current_widget.widget_id, disk);
+        assert!(app.take_focus_events().is_empty());
+    }
+
+    #[test]
+    fn test_cycle_colour_scheme() {
+        use std::time::Duration;
+
+        use tui::style::Color;
+
+        use crate::utils::clock::MockClock;
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [colors]
+            table_header_color = "red"
+            "#,
+        )
+        .unwrap();
+        let mut app = test_app_with_config(config);
+        let clock = MockClock::default();
+        app.set_clock(clock.clone());
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+
+        app.cycle_colour_scheme();
+        assert_eq!(app.colour_scheme, ColourScheme::DefaultLight);
+        assert_eq!(app.status_message(), Some("Colour scheme: default-light"));
+
+        // The custom colours should still win over the new scheme, both for the painter and for
+        // the widgets that keep their own copy.
+        let styling = app.take_pending_colours().unwrap();
+        assert_eq!(styling.table_header_style.fg, Some(Color::Red));
+        assert_eq!(
+            app.states.proc_state.widget_states[&proc]
+                .table
+                .styling
+                .header_style
+                .fg,
+            Some(Color::Red)
+        );
+        assert!(app.take_pending_colours().is_none());
+
+        clock.advance(Duration::from_millis(
+            constants::STATUS_MESSAGE_DURATION_IN_MILLISECONDS,
+        ));
+        assert_eq!(app.status_message(), None);
+
+        for _ in 1..ColourScheme::BUILT_IN_NAMES.len() {
+            app.cycle_colour_scheme();
+        }
+        assert_eq!(app.colour_scheme, ColourScheme::Default);
+    }
+
+    #[test]
+    fn test_clamp_graph_times() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [flags]
+            retention = "30s"
+            default_time_value = "30s"
+            [[row]]
+              [[row.child]]
+                type = "cpu"
+              [[row.child]]
+                type = "cpu"
+              [[row.child]]
+                type = "mem"
+              [[row.child]]
+                type = "net"
+            "#,
+        )
+        .unwrap();
+        let mut app = test_app_with_config(config);
+        for state in app.states.cpu_state.widget_states.values_mut() {
+            state.current_display_time = 60000;
+        }
+        app.states
+            .mem_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.current_display_time = 60000);
+
+        app.clamp_graph_times();
+
+        // Every widget is clamped, and each type that changed is updated.
+        assert_eq!(app.states.cpu_state.widget_states.len(), 2);
+        assert!(app
+            .states
+            .cpu_state
+            .widget_states
+            .values()
+            .all(|state| state.current_display_time == 30000));
+        assert!(app
+            .states
+            .mem_state
+            .widget_states
+            .values()
+            .all(|state| state.current_display_time == 30000));
+        assert!(app.states.cpu_state.force_update.is_some());
+        assert!(app.states.mem_state.force_update.is_some());
+        assert!(app.states.net_state.force_update.is_none());
+    }
+
+    #[test]
+    fn test_apply_reloaded_config() {
+        use tui::style::Color;
+
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [flags]
+            rate = 5000
+            retention = "30s"
+            default_time_value = "30s"
+            dot_marker = true
+            [colors]
+            table_header_color = "red"
+            "#,
+        )
+        .unwrap();
+        let matches = args::build_app().get_matches_from(["btm"]);
+        let reloaded = ReloadedConfig {
+            app_config_fields: get_app_config_fields(&matches, &config).unwrap(),
+            previous_app_config_fields: get_app_config_fields(&matches, &Config::default())
+                .unwrap(),
+            colour_scheme: ColourScheme::Nord,
+            styling: CanvasStyling::with_custom_colours(ColourScheme::Nord, config.colors.as_ref())
+                .unwrap(),
+            custom_colours: config.colors.clone(),
+            colours_changed: true,
+            layout_changed: false,
+            layout_profiles: app.layout_profiles.clone(),
+        };
+
+        // Settings that were changed while running, but not in the file, are kept.
+        app.app_config_fields.byte_standard = ByteStandard::Decimal;
+
+        let cpu = widget_id(&app, BottomWidgetType::Cpu);
+        app.states
+            .cpu_state
+            .widget_states
+            .get_mut(&cpu)
+            .unwrap()
+            .current_display_time = 60000;
+        app.apply_reloaded_config(reloaded);
+
+        // The update rate is passed on to the collection thread, and graphs can't show more than
+        // the new retention.
+        assert_eq!(app.app_config_fields.update_rate, 5000);
+        assert_eq!(app.take_update_rate_change(), Some(5000));
+        assert_eq!(app.app_config_fields.retention_ms, 30000);
+        assert_eq!(
+            app.states.cpu_state.widget_states[&cpu].current_display_time,
+            30000
+        );
+        assert!(app.app_config_fields.use_dot);
+        assert_eq!(app.app_config_fields.byte_standard, ByteStandard::Decimal);
+        assert_eq!(app.colour_scheme, ColourScheme::Nord);
+        assert_eq!(app.status_message(), Some("Reloaded the config file"));
+        assert_eq!(
+            app.take_pending_colours().unwrap().table_header_style.fg,
+            Some(Color::Red)
+        );
+        assert_eq!(
+            app.states.proc_state.widget_states[&proc]
+                .table
+                .styling
+                .header_style
+                .fg,
+            Some(Color::Red)
+        );
+
+        // Switching schemes afterwards should keep the reloaded custom colours.
+        app.cycle_colour_scheme();
+        assert_eq!(
+            app.take_pending_colours().unwrap().table_header_style.fg,
+            Some(Color::Red)
+        );
+
+        // Reloading again with only the rate changed keeps the scheme that was switched to.
+        let switched_scheme = app.colour_scheme;
+        let previous = get_app_config_fields(&matches, &config).unwrap();
+        let mut loaded = get_app_config_fields(&matches, &config).unwrap();
+        loaded.update_rate = 2000;
+        app.apply_reloaded_config(ReloadedConfig {
+            app_config_fields: loaded,
+            previous_app_config_fields: previous,
+            colour_scheme: ColourScheme::Nord,
+            styling: CanvasStyling::default(),
+            custom_colours: None,
+            colours_changed: false,
+            layout_changed: false,
+            layout_profiles: app.layout_profiles.clone(),
+        });
+        assert_eq!(app.app_config_fields.update_rate, 2000);
+        assert_eq!(app.colour_scheme, switched_scheme);
+        assert!(app.take_pending_colours().is_none());
+        assert_eq!(app.app_config_fields.retention_ms, 30000);
+
+        app.on_config_reload_error(&anyhow::anyhow!("bad config"));
+        assert_eq!(
+            app.status_message(),
+            Some("Unable to reload the config file: bad config")
+        );
+        assert!(app.app_config_fields.use_dot);
+    }
+
+    #[test]
+    fn test_apply_reloaded_table_settings() {
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        let temp = widget_id(&app, BottomWidgetType::Temp);
+        let disk = widget_id(&app, BottomWidgetType::Disk);
+        let cpu = widget_id(&app, BottomWidgetType::Cpu);
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [flags]
+            hide_table_gap = true
+            uppercase_headers = true
+            "#,
+        )
+        .unwrap();
+        let matches = args::build_app().get_matches_from(["btm"]);
+        app.apply_reloaded_config(ReloadedConfig {
+            app_config_fields: get_app_config_fields(&matches, &config).unwrap(),
+            previous_app_config_fields: get_app_config_fields(&matches, &Config::default())
+                .unwrap(),
+            colour_scheme: ColourScheme::Default,
+            styling: CanvasStyling::default(),
+            custom_colours: None,
+            colours_changed: false,
+            layout_changed: false,
+            layout_profiles: app.layout_profiles.clone(),
+        });
+
+        let proc_state = &app.states.proc_state.widget_states[&proc];
+        let tables = [
+            &proc_state.table.props,
+            &proc_state.sort_table.props,
+            &app.states.temp_state.widget_states[&temp].table.props,
+            &app.states.disk_state.widget_states[&disk].table.props,
+            &app.states.cpu_state.widget_states[&cpu].table.props,
+        ];
+        for props in tables {
+            assert_eq!(props.table_gap, 0);
+            assert!(props.uppercase_headers);
+        }
+    }
+
+    #[test]
+    fn test_help_search() {
+        let mut app = test_app();
+        app.on_f1();
+        assert!(app.help_dialog_state.is_showing_help);
+
+        // While searching, every key is part of the query, even ones that would close the menu.
+        app.on_char_key('/');
+        for c in "kiq".chars() {
+            app.on_char_key(c);
+        }
+        app.on_backspace();
+        app.on_char_key('l');
+        assert_eq!(app.help_dialog_state.query, "kil");
+        assert!(app.help_dialog_state.is_showing_help);
+
+        // Enter keeps the results, and Esc clears them before closing the menu.
+        app.on_enter();
+        assert!(!app.help_dialog_state.is_searching);
+        assert_eq!(app.help_dialog_state.query, "kil");
+        app.on_esc();
+        assert!(app.help_dialog_state.is_showing_help);
+        assert!(app.help_dialog_state.query.is_empty());
+        app.on_esc();
+        assert!(!app.help_dialog_state.is_showing_help);
+
+        // Closing the menu also clears the search.
+        app.on_char_key('?');
+        app.on_char_key('/');
+        app.on_char_key('x');
+        app.on_enter();
+        app.on_char_key('q');
+        assert!(!app.help_dialog_state.is_showing_help);
+        assert!(app.help_dialog_state.query.is_empty());
+    }
+
+    #[test]
+    fn test_proc_overlay_open() {
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.focus_widget(proc);
+        let is_overlay_open =
+            |app: &App| app.states.proc_state.widget_states[&proc].is_overlay_open();
+        assert!(!is_overlay_open(&app));
+
+        app.on_char_key('/');
+        assert!(is_overlay_open(&app));
+        app.on_esc();
+        assert!(!is_overlay_open(&app));
+
+        app.toggle_sort_menu();
+        assert!(is_overlay_open(&app));
+        app.on_esc();
+        assert!(!is_overlay_open(&app));
+    }
+
+    #[test]
+    fn test_take_expired_status_message() {
+        use std::time::Duration;
+
+        use crate::utils::clock::MockClock;
+
+        let mut app = test_app();
+        let clock = MockClock::default();
+        app.set_clock(clock.clone());
+        assert!(!app.take_expired_status_message());
+
+        app.on_char_key('<');
+        assert!(!app.take_expired_status_message());
+        assert_eq!(app.status_message(), Some("Update rate: 500ms"));
+
+        clock.advance(Duration::from_millis(
+            constants::STATUS_MESSAGE_DURATION_IN_MILLISECONDS,
+        ));
+        assert!(app.take_expired_status_message());
+        assert!(!app.take_expired_status_message());
+        assert_eq!(app.status_message(), None);
+    }
+
+    #[test]
+    fn test_take_expired_click_flash() {
+        use std::time::Duration;
+
+        use crate::utils::clock::MockClock;
+
+        let mut app = test_app();
+        let clock = MockClock::default();
+        app.set_clock(clock.clone());
+        app.focus_widget(widget_id(&app, BottomWidgetType::Proc));
+        assert!(!app.take_expired_click_flash());
+
+        app.click_current_row();
+        assert!(!app.take_expired_click_flash());
+
+        clock.advance(Duration::from_millis(
+            constants::CLICK_FLASH_DURATION_IN_MILLISECONDS,
+        ));
+        assert!(app.take_expired_click_flash());
+        assert!(!app.take_expired_click_flash());
+    }
+
+    #[test]
+    fn test_change_update_rate() {
+        let mut app = test_app();
+        assert_eq!(app.app_config_fields.update_rate, 1000);
+
+        app.on_char_key('<');
+        assert_eq!(app.app_config_fields.update_rate, 500);
+        assert_eq!(app.take_update_rate_change(), Some(500));
+        assert_eq!(app.status_message(), Some("Update rate: 500ms"));
+
+        for _ in 0..4 {
+            app.on_char_key('<');
+        }
+        assert_eq!(
+            app.app_config_fields.update_rate,
+            constants::MIN_REFRESH_RATE_IN_MILLISECONDS
+        );
+        assert_eq!(
+            app.take_update_rate_change(),
+            Some(constants::MIN_REFRESH_RATE_IN_MILLISECONDS)
+        );
+
+        // Nothing should be sent to the collection thread if the rate is already at a limit.
+        app.on_char_key('<');
+        assert_eq!(app.take_update_rate_change(), None);
+
+        for _ in 0..20 {
+            app.on_char_key('>');
+        }
+        assert_eq!(
+            app.app_config_fields.update_rate,
+            constants::MAX_REFRESH_RATE_IN_MILLISECONDS
+        );
+        assert_eq!(
+            app.take_update_rate_change(),
+            Some(constants::MAX_REFRESH_RATE_IN_MILLISECONDS)
+        );
+    }
+
+    #[test]
+    fn test_paste_into_search() {
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.focus_widget(proc);
+        app.on_slash();
+        assert!(app.is_in_search_widget());
+
+        app.on_char_key(')');
+        app.on_left_key();
+
+        // Each event is followed by a data update, like in the event loop. Timings record one
+        // sample for each time the processes are filtered.
+        app.timings = Some(Timings::default());
+        let filter_passes = |app: &App| {
+            app.timings
+                .as_ref()
+                .and_then(|timings| timings.get(timings::TimingKind::Update(proc)))
+                .map(|stats| stats.count())
+                .unwrap_or(0)
+        };
+        app.handle_paste("(cpu > 5 and\r\nmem > 1\n".to_string());
+        assert!(app.states.proc_state.widget_states[&proc].force_update_data);
+        crate::update_data(&mut app);
+        assert_eq!(filter_passes(&app), 1);
+
+        let search_state = &app.states.proc_state.widget_states[&proc]
+            .proc_search
+            .search_state;
+        assert_eq!(search_state.current_search_query, "(cpu > 5 and mem > 1)");
+        assert_eq!(search_state.grapheme_cursor.cur_cursor(), 20);
+        assert!(!search_state.is_invalid_search);
+        assert!(search_state.query.is_some());
+
+        // Typing the same text instead filters once per character.
+        for c in " or pid = 1".chars() {
+            app.on_char_key(c);
+            crate::update_data(&mut app);
+        }
+        assert_eq!(filter_passes(&app), 12);
+    }
+
+    #[test]
+    fn test_disk_percentage_key() {
+        let mut app = test_app();
+        let disk = widget_id(&app, BottomWidgetType::Disk);
+        let free_column = |app: &App| {
+            app.states.disk_state.widget_states[&disk]
+                .table
+                .columns
+                .iter()
+                .map(|column| column.inner().text())
+                .find(|text| text.starts_with("Free"))
+                .unwrap()
+        };
+
+        assert!(app.focus_widget(disk));
+        app.handle_char('%');
+        assert_eq!(free_column(&app), "Free%(n)");
+        assert!(app.states.disk_state.widget_states[&disk].force_update_data);
+
+        app.handle_char('%');
+        assert_eq!(free_column(&app), "Free(n)");
+    }
+
+    #[test]
+    fn test_byte_standard_toggle() {
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        let disk = widget_id(&app, BottomWidgetType::Disk);
+        assert_eq!(app.app_config_fields.byte_standard, ByteStandard::Binary);
+
+        app.on_char_key('b');
+        assert_eq!(app.app_config_fields.byte_standard, ByteStandard::Decimal);
+        assert_eq!(app.status_message(), Some("Byte units: decimal"));
+        assert!(app.states.proc_state.widget_states[&proc].force_update_data);
+        assert!(app.states.disk_state.widget_states[&disk].force_update_data);
+
+        app.on_char_key('b');
+        assert_eq!(app.app_config_fields.byte_standard, ByteStandard::Binary);
+        assert_eq!(app.status_message(), Some("Byte units: binary"));
+    }
+
+    #[test]
+    fn test_switch_layout() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[layouts]]
+              name = "procs"
+              [[layouts.row]]
+                [[layouts.row.child]]
+                  type = "temp"
+                [[layouts.row.child]]
+                  type = "proc"
+                [[layouts.row.child]]
+                  type = "proc"
+            "#,
+        )
+        .unwrap();
+        let mut app = test_app_with_config(config.clone());
+        assert_eq!(app.layout_profiles, ["default", "procs"]);
+
+        app.open_layout_switcher();
+        assert_eq!(app.layout_switcher_state, Some(0));
+        app.on_char_key('j');
+        app.on_char_key('j');
+        assert_eq!(app.layout_switcher_state, Some(1));
+        app.on_enter();
+        assert!(app.layout_switcher_state.is_none());
+        assert_eq!(app.take_layout_switch_request().as_deref(), Some("procs"));
+        assert!(app.take_layout_switch_request().is_none());
+
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.states
+            .proc_state
+            .get_mut_widget_state(proc)
+            .unwrap()
+            .proc_search
+            .search_state
+            .current_search_query = "bottom".to_string();
+
+        let new_app = test_app_with_config(apply_layout_profile(config, Some("procs")).unwrap());
+        app.switch_layout("procs", new_app);
+
+        assert_eq!(app.active_layout_profile, "procs");
+        assert_eq!(app.status_message(), Some("Switched to layout procs"));
+        assert!(!app.used_widgets.use_cpu);
+        let mut procs: Vec<_> = app.states.proc_state.widget_states.iter().collect();
+        procs.sort_unstable_by_key(|(widget_id, _)| **widget_id);
+        let queries: Vec<&str> = procs
+            .iter()
+            .map(|(_, proc)| proc.proc_search.search_state.current_search_query.as_str())
+            .collect();
+        assert_eq!(queries, ["bottom", ""]);
+
+        app.open_layout_switcher();
+        assert_eq!(app.layout_switcher_state, Some(1));
+        app.on_esc();
+        assert!(app.layout_switcher_state.is_none());
+    }
+
+    #[test]
+    fn test_save_config_dialog() {
+        let mut app = test_app();
+
+        app.open_save_config();
+        assert!(app.is_showing_save_config);
+        app.on_char_key('n');
+        assert!(!app.is_showing_save_config);
+        assert!(!app.take_config_save_request());
+
+        app.open_save_config();
+        app.on_char_key('y');
+        assert!(!app.is_showing_save_config);
+        assert!(app.take_config_save_request());
+        assert!(!app.take_config_save_request());
+
+        app.cycle_colour_scheme();
+        app.set_temperature_type(temperature::TemperatureType::Kelvin);
+        let settings = app.saved_settings();
+        assert_eq!(settings.color, "default-light");
+        assert_eq!(settings.temperature_type, "kelvin");
+        assert_eq!(settings.rate, app.app_config_fields.update_rate);
+        assert!(!settings.process.unwrap().tree);
+
+        app.on_config_saved(Ok(SaveOutcome::Saved));
+        assert_eq!(
+            app.status_message(),
+            Some("Saved the current settings to the config file")
+        );
+    }
+
+    #[test]
+    fn test_zoom() {
+        let mut app = test_app();
+        let cpu = widget_id(&app, BottomWidgetType::Cpu);
+        let mem = widget_id(&app, BottomWidgetType::Mem);
+        let net = widget_id(&app, BottomWidgetType::Net);
+        let display_times = |app: &App| {
+            (
+                app.states.cpu_state.widget_states[&cpu].current_display_time,
+                app.states.mem_state.widget_states[&mem].current_display_time,
+                app.states.net_state.widget_states[&net].current_display_time,
+            )
+        };
+
+        // Zooming only changes the focused graph, and stops at the minimum and the retention.
+        app.focus_widget(mem);
+        app.on_char_key('+');
+        assert_eq!(display_times(&app), (60_000, 30_000, 60_000));
+        app.on_char_key('+');
+        assert_eq!(display_times(&app), (60_000, 30_000, 60_000));
+        for _ in 0..10 {
+            app.on_char_key('-');
+        }
+        assert_eq!(display_times(&app), (60_000, 600_000, 60_000));
+        app.on_char_key('=');
+        assert_eq!(display_times(&app), (60_000, 60_000, 60_000));
+
+        app.app_config_fields.zoom_all_graphs = true;
+        app.on_char_key('-');
+        assert_eq!(display_times(&app), (120_000, 120_000, 120_000));
+        app.on_char_key('=');
+        assert_eq!(display_times(&app), (60_000, 60_000, 60_000));
+
+        // A fixed time delta steps by that amount instead.
+        app.app_config_fields.time_interval = Some(15_000);
+        app.on_char_key('-');
+        assert_eq!(display_times(&app), (75_000, 75_000, 75_000));
+    }
+
+    #[test]
+    fn test_temperature_unit_toggle() {
+        use crate::{
+            components::data_table::{DataTableColumn, DataToCell},
+            widgets::TempWidgetData,
+        };
+
+        let mut app = test_app();
+        let temp = widget_id(&app, BottomWidgetType::Temp);
+        app.focus_widget(temp);
+
+        let data = [TempWidgetData {
+            sensor: "cpu".into(),
+            temperature: 45.0,
+        }];
+        let shown_temperature = |app: &App| {
+            let table = &app.states.temp_state.widget_states[&temp].table;
+            let cell = table.data()[0].to_cell(table.columns[1].inner(), u16::MAX);
+            (
+                table.columns[1].header(),
+                cell.unwrap().lines[0].spans[0].content.to_string(),
+            )
+        };
+
+        app.states
+            .temp_state
+            .get_mut_widget_state(temp)
+            .unwrap()
+            .ingest_data(&data);
+        assert_eq!(
+            shown_temperature(&app),
+            ("Temp °C(t)".into(), "45.0".into())
+        );
+
+        // Switching units should re-render the same data, rather than needing it collected again.
+        app.on_char_key('u');
+        assert_eq!(
+            app.app_config_fields.temperature_type,
+            temperature::TemperatureType::Fahrenheit
+        );
+        assert_eq!(app.status_message(), Some("Temperature unit: °F"));
+        assert!(app.states.temp_state.widget_states[&temp].force_update_data);
+        assert_eq!(shown_temperature(&app), ("Temp °F(t)".into(), "113".into()));
+
+        app.on_char_key('u');
+        assert_eq!(shown_temperature(&app), ("Temp K(t)".into(), "318".into()));
+
+        app.on_char_key('u');
+        assert_eq!(
+            app.app_config_fields.temperature_type,
+            temperature::TemperatureType::Celsius
+        );
+    }
+
+    #[test]
+    fn test_kill_marked_processes() {
+        use crate::app::data_harvester::processes::ProcessHarvest;
+
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.focus_widget(proc);
+
+        app.data_collection.process_data.process_harvest = [1, 2, 3]
+            .into_iter()
+            .map(|pid| {
+                let process = ProcessHarvest {
+                    pid,
+                    name: format!("process{pid}"),
+                    ..Default::default()
+                };
+                (pid, process)
+            })
+            .collect();
+        let state = app.states.proc_state.get_mut_widget_state(proc).unwrap();
+        state.ingest_data(&app.data_collection);
+        let pids: Vec<Pid> = state.table.data().iter().map(|row| row.pid).collect();
+
+        // Without any marks, only the selected process is killed.
+        app.on_char_key('d');
+        app.on_char_key('d');
+        assert_eq!(app.get_to_delete_processes().unwrap().pids(), [pids[0]]);
+        app.on_esc();
+
+        app.on_char_key(' ');
+        app.on_char_key('j');
+        app.on_char_key('j');
+        app.on_char_key(' ');
+        app.on_char_key('d');
+        app.on_char_key('d');
+        assert_eq!(
+            app.get_to_delete_processes().unwrap().pids(),
+            [pids[0], pids[2]]
+        );
+    }
+
+    #[test]
+    fn test_paged_table() {
+        use crate::widgets::TempWidgetData;
+
+        let mut app = test_app();
+        let temp = widget_id(&app, BottomWidgetType::Temp);
+        app.focus_widget(temp);
+
+        let data = ["a", "b", "c", "d", "e", "f", "g"].map(|sensor| TempWidgetData {
+            sensor: sensor.into(),
+            temperature: 45.0,
+        });
+        let state = app.states.temp_state.get_mut_widget_state(temp).unwrap();
+        state.ingest_data(&data);
+
+        app.toggle_paging();
+        let table = &mut app
+            .states
+            .temp_state
+            .get_mut_widget_state(temp)
+            .unwrap()
+            .table;
+        assert!(table.is_paged());
+
+        // Pretend the table was drawn with room for 3 rows a page.
+        table.state.page_size = 3;
+        let page = |app: &App| app.states.temp_state.widget_states[&temp].table.page();
+        assert_eq!(page(&app), Some((1, 3)));
+
+        app.on_page_down();
+        assert_eq!(page(&app), Some((2, 3)));
+        app.on_char_key('G');
+        assert_eq!(page(&app), Some((3, 3)));
+        app.on_page_up();
+        assert_eq!(page(&app), Some((2, 3)));
+        app.on_char_key('g');
+        app.on_char_key('g');
+        assert_eq!(page(&app), Some((1, 3)));
+
+        app.toggle_paging();
+        assert_eq!(page(&app), None);
+    }
+
+    #[test]
+    fn test_invert_sort() {
+        use crate::{components::data_table::SortOrder, widgets::TempWidgetData};
+
+        let mut app = test_app();
+        let temp = widget_id(&app, BottomWidgetType::Temp);
+        app.focus_widget(temp);
+
+        let data = ["b", "c", "a"].map(|sensor| TempWidgetData {
+            sensor: sensor.into(),
+            temperature: 45.0,
+        });
+        let sensors = |app: &mut App| {
+            let state = app.states.temp_state.get_mut_widget_state(temp).unwrap();
+            state.ingest_data(&data);
+            state
+                .table
+                .data()
+                .iter()
+                .map(|row| row.sensor.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let table = &mut app
+            .states
+            .temp_state
+            .get_mut_widget_state(temp)
+            .unwrap()
+            .table;
+        table.set_sort_index(0);
+        table.set_order(SortOrder::Ascending);
+        assert_eq!(sensors(&mut app), ["a", "b", "c"]);
+
+        // Inverting keeps the sort column, and flips the order.
+        app.on_char_key('I');
+        assert!(app.states.temp_state.widget_states[&temp].force_update_data);
+        assert_eq!(sensors(&mut app), ["c", "b", "a"]);
+        assert_eq!(
+            app.states.temp_state.widget_states[&temp]
+                .table
+                .sort_index(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_config_reload_key() {
+        let mut app = test_app();
+        assert!(!app.take_config_reload_request());
+
+        app.on_char_key('R');
+        assert!(app.take_config_reload_request());
+        assert!(!app.take_config_reload_request());
+    }
+
+    #[test]
+    fn test_committed_filter() {
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.focus_widget(proc);
+
+        let filter = |app: &App| {
+            app.states.proc_state.widget_states[&proc]
+                .committed_filter()
+                .map(str::to_string)
+        };
+
+        app.on_slash();
+        for c in "chrome".chars() {
+            app.on_char_key(c);
+        }
+        assert_eq!(filter(&app), None);
+
+        // Enter closes the search widget but keeps filtering.
+        app.on_enter();
+        assert_eq!(app.current_widget.widget_id, proc);
+        assert_eq!(filter(&app).as_deref(), Some("chrome"));
+
+        // Reopening shows the kept query.
+        app.on_slash();
+        assert_eq!(
+            app.states.proc_state.widget_states[&proc].current_search_query(),
+            "chrome"
+        );
+        app.on_esc();
+        assert_eq!(filter(&app).as_deref(), Some("chrome"));
+
+        app.on_char_key('x');
+        assert_eq!(filter(&app), None);
+        assert!(app.states.proc_state.widget_states[&proc]
+            .current_search_query()
+            .is_empty());
+
+        // Without keep_filter_on_close, Esc drops the filter.
+        app.app_config_fields.keep_filter_on_close = false;
+        app.on_slash();
+        app.on_char_key('a');
+        app.on_esc();
+        assert_eq!(filter(&app), None);
+    }
+
+    #[test]
+    fn test_column_description() {
+        use crate::components::data_table::{ColumnHeader, DataTableColumn};
+
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.focus_widget(proc);
+        app.toggle_sort_menu();
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::ProcSort);
+
+        app.on_down_key();
+        let index = app.states.proc_state.widget_states[&proc]
+            .sort_table
+            .current_index();
+        let column = *app.states.proc_state.widget_states[&proc]
+            .table
+            .columns
+            .iter()
+            .filter(|column| !column.is_hidden)
+            .nth(index)
+            .unwrap()
+            .inner();
+
+        // In the sort widget, '?' shows the description of the selected column instead of help.
+        app.on_char_key('?');
+        assert!(!app.help_dialog_state.is_showing_help);
+        assert_eq!(
+            app.column_description,
+            Some((
+                column.text(),
+                column.description().unwrap().to_string().into()
+            ))
+        );
+
+        app.on_char_key('?');
+        assert_eq!(app.column_description, None);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::ProcSort);
+
+        // Anywhere else, it still opens the help menu.
+        app.focus_widget(proc);
+        app.on_char_key('?');
+        assert_eq!(app.column_description, None);
+        assert!(app.help_dialog_state.is_showing_help);
+    }
+
+    #[test]
+    fn test_wrap_navigation() {
+        let mut app = test_app();
+        let sort = widget_id(&app, BottomWidgetType::ProcSort);
+        let proc = sort - 2;
+        app.states
+            .proc_state
+            .widget_states
+            .get_mut(&proc)
+            .unwrap()
+            .is_sort_open = true;
+        app.focus_widget(sort);
+
+        let sort_index = |app: &App| {
+            app.states.proc_state.widget_states[&proc]
+                .sort_table
+                .current_index()
+        };
+        let last = app.states.proc_state.widget_states[&proc]
+            .column_text()
+            .len()
+            - 1;
+
+        // Without wrapping, moving past either end does nothing.
+        app.on_up_key();
+        assert_eq!(sort_index(&app), 0);
+
+        app.app_config_fields.wrap_navigation = true;
+        app.on_up_key();
+        assert_eq!(sort_index(&app), last);
+        app.on_down_key();
+        assert_eq!(sort_index(&app), 0);
+    }
+
+    #[test]
+    fn test_process_snapshots() {
+        use crate::{app::data_harvester::processes::ProcessHarvest, widgets::SnapshotStatus};
+
+        let mut app = test_app();
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.focus_widget(proc);
+
+        // There's nothing to compare against yet.
+        app.on_char_key('V');
+        assert!(app.snapshot_comparison.is_none());
+
+        let process = |pid: Pid, name: &str| ProcessHarvest {
+            pid,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let processes = &mut app.data_collection.process_data.process_harvest;
+        processes.insert(1, process(1, "init"));
+        app.on_char_key('v');
+        assert!(app.process_snapshots.is_empty());
+        for c in "quiet".chars() {
+            app.on_char_key(c);
+        }
+        app.on_backspace();
+        app.on_enter();
+        assert!(app.snapshot_name.is_none());
+
+        // Cancelling doesn't take a snapshot, and one left unnamed gets a default name.
+        app.on_char_key('v');
+        app.on_esc();
+        let processes = &mut app.data_collection.process_data.process_harvest;
+        processes.insert(2, process(2, "btm"));
+        app.on_char_key('v');
+        app.on_enter();
+        assert_eq!(app.process_snapshots.len(), 2);
+        assert_eq!(app.process_snapshots.get(0).unwrap().name, "quie");
+        assert_eq!(app.process_snapshots.get(1).unwrap().name, "Snapshot 2");
+
+        // The comparison starts with the latest snapshot, in which nothing has changed since.
+        app.on_char_key('V');
+        let comparison = app.snapshot_comparison.as_ref().unwrap();
+        assert_eq!(comparison.snapshot_index, 1);
+        assert_eq!(comparison.table.data().len(), 2);
+
+        app.on_left_key();
+        let comparison = app.snapshot_comparison.as_ref().unwrap();
+        assert_eq!(comparison.snapshot_index, 0);
+        let new = comparison
+            .table
+            .data()
+            .iter()
+            .filter(|row| row.status == SnapshotStatus::New)
+            .map(|row| row.pid)
+            .collect::<Vec<_>>();
+        assert_eq!(new, vec![2]);
+
+        // Dropping a snapshot moves on to the next one, and dropping the last closes the dialog.
+        app.on_char_key('x');
+        assert_eq!(app.process_snapshots.len(), 1);
+        assert_eq!(app.snapshot_comparison.as_ref().unwrap().snapshot_index, 0);
+        app.on_char_key('x');
+        assert!(app.process_snapshots.is_empty());
+        assert!(app.snapshot_comparison.is_none());
+    }
+
+    #[test]
+    fn test_time_cursor() {
+        let mut app = test_app();
+        let mem = widget_id(&app, BottomWidgetType::Mem);
+        app.focus_widget(mem);
+        app.converted_data.mem_data = vec![(-9000.0, 10.0), (-3500.0, 20.0), (0.0, 30.0)];
+        let time_cursor = |app: &App| app.states.mem_state.widget_states[&mem].time_cursor;
+
+        // Scrubbing only works while frozen.
+        app.on_left_key();
+        assert_eq!(time_cursor(&app), None);
+
+        app.on_char_key('f');
+        app.on_left_key();
+        assert_eq!(time_cursor(&app), Some(3500));
+        app.on_left_key();
+        app.on_left_key();
+        assert_eq!(time_cursor(&app), Some(9000));
+        app.on_right_key();
+        assert_eq!(time_cursor(&app), Some(3500));
+
+        // Esc goes back to the latest data before anything else.
+        app.set_expanded(true);
+        app.on_esc();
+        assert_eq!(time_cursor(&app), None);
+        assert!(app.is_expanded);
+
+        app.on_left_key();
+        app.on_char_key('f');
+        assert_eq!(time_cursor(&app), None);
+    }
+}
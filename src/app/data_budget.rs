@@ -0,0 +1,177 @@
+//! Rough accounting of how much memory the data kept for widgets takes up, so it can be held
+//! under `max_data_memory`.
+
+use crate::utils::gen_util::get_binary_bytes;
+
+/// Where widget data is kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataStore {
+    /// The collected history that graphs are drawn from.
+    History,
+
+    /// The latest processes.
+    Processes,
+
+    /// Data converted for drawing, like graph points, labels, and table rows. This is rebuilt
+    /// from the other stores on each update.
+    Converted,
+}
+
+impl DataStore {
+    pub const ALL: [DataStore; 3] = [
+        DataStore::History,
+        DataStore::Processes,
+        DataStore::Converted,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            DataStore::History => "History",
+            DataStore::Processes => "Processes",
+            DataStore::Converted => "Converted",
+        }
+    }
+}
+
+/// Approximately how many bytes each [`DataStore`] takes up, and the most they may take up in
+/// total, if there is a limit.
+#[derive(Debug, Default, Clone)]
+pub struct DataBudget {
+    max_bytes: Option<u64>,
+    usage: [u64; DataStore::ALL.len()],
+}
+
+impl DataBudget {
+    pub fn new(max_bytes: Option<u64>) -> Self {
+        Self {
+            max_bytes,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the most bytes the stores may take up in total, or [`None`] if there is no limit.
+    pub fn max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+
+    pub fn set_max_bytes(&mut self, max_bytes: Option<u64>) {
+        self.max_bytes = max_bytes;
+    }
+
+    /// Returns how many bytes `store` took up when it was last measured.
+    pub fn usage(&self, store: DataStore) -> u64 {
+        self.usage[store as usize]
+    }
+
+    pub fn set_usage(&mut self, store: DataStore, bytes: u64) {
+        self.usage[store as usize] = bytes;
+    }
+
+    /// Returns how many bytes all stores took up when they were last measured.
+    pub fn total_usage(&self) -> u64 {
+        self.usage.iter().sum()
+    }
+
+    /// Returns how many bytes over the limit the stores are, which is 0 if they are within it or
+    /// there is no limit.
+    pub fn excess(&self) -> u64 {
+        match self.max_bytes {
+            Some(max_bytes) => self.total_usage().saturating_sub(max_bytes),
+            None => 0,
+        }
+    }
+
+    /// Generates a readable report of the usage of each store and the limit.
+    pub fn report(&self) -> String {
+        let format = |bytes: u64| {
+            let (value, unit) = get_binary_bytes(bytes);
+            format!("{value:.1}{unit}")
+        };
+
+        let mut report = format!("{:<32} {:>12}\n", "Data memory", "Size");
+        for store in DataStore::ALL {
+            report.push_str(&format!(
+                "{:<32} {:>12}\n",
+                store.name(),
+                format(self.usage(store))
+            ));
+        }
+        report.push_str(&format!(
+            "{:<32} {:>12}\n",
+            "Total",
+            format(self.total_usage())
+        ));
+
+        let max = match self.max_bytes {
+            Some(max_bytes) => format(max_bytes),
+            None => "None".to_string(),
+        };
+        report.push_str(&format!("{:<32} {:>12}\n", "Limit", max));
+
+        report
+    }
+}
+
+/// Parses a size in bytes like "64MiB", "1.5 GB", or "65536". Units are matched ignoring case,
+/// with binary units (e.g. KiB) counting in powers of 1024, and decimal units (e.g. KB) in powers
+/// of 1000.
+pub fn parse_bytes(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value.parse().ok()?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" | "k" => 1_000,
+        "mb" | "m" => 1_000_000,
+        "gb" | "g" => 1_000_000_000,
+        "tb" | "t" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return None,
+    };
+
+    let bytes = value * multiplier as f64;
+    (bytes.is_finite() && bytes < u64::MAX as f64).then_some(bytes as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(parse_bytes("65536"), Some(65536));
+        assert_eq!(parse_bytes("64MiB"), Some(64 * 1024 * 1024));
+        assert_eq!(parse_bytes("64 mib"), Some(64 * 1024 * 1024));
+        assert_eq!(parse_bytes("1.5GB"), Some(1_500_000_000));
+        assert_eq!(parse_bytes("512 KiB"), Some(512 * 1024));
+        assert_eq!(parse_bytes("10b"), Some(10));
+
+        assert_eq!(parse_bytes(""), None);
+        assert_eq!(parse_bytes("MiB"), None);
+        assert_eq!(parse_bytes("64 lots"), None);
+        assert_eq!(parse_bytes("-5MiB"), None);
+    }
+
+    #[test]
+    fn test_excess() {
+        let mut budget = DataBudget::new(None);
+        budget.set_usage(DataStore::History, 600);
+        budget.set_usage(DataStore::Processes, 300);
+        assert_eq!(budget.total_usage(), 900);
+        assert_eq!(budget.excess(), 0);
+
+        budget.set_max_bytes(Some(800));
+        assert_eq!(budget.excess(), 100);
+
+        budget.set_usage(DataStore::History, 400);
+        assert_eq!(budget.excess(), 0);
+        assert_eq!(budget.usage(DataStore::History), 400);
+    }
+}
@@ -13,25 +13,37 @@
 //! memory usage and higher CPU usage - you will be trying to process more and
 //! more points as this is used!
 
-use std::{collections::BTreeMap, time::Instant, vec::Vec};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    mem::size_of,
+    time::Instant,
+    vec::Vec,
+};
 
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 #[cfg(feature = "battery")]
 use crate::data_harvester::batteries;
 use crate::{
-    data_harvester::{cpu, disks, memory, network, processes::ProcessHarvest, temperature, Data},
-    utils::gen_util::{get_decimal_bytes, GIGA_LIMIT},
+    constants::MAX_PREALLOCATED_HISTORY_ENTRIES,
+    data_harvester::{
+        cpu, disks, memory, network,
+        processes::{ProcessHarvest, ProcessUpdate},
+        temperature, Data,
+    },
     Pid,
 };
 
+/// When shrinking the history, this fraction of it is freed on top of what was asked for.
+const HISTORY_HEADROOM_DIVISOR: u64 = 16;
+
 pub type TimeOffset = f64;
 pub type Value = f64;
 
 #[derive(Debug, Default, Clone)]
 pub struct TimedData {
-    pub rx_data: Value,
-    pub tx_data: Value,
+    pub rx_data: Option<Value>,
+    pub tx_data: Option<Value>,
     pub cpu_data: Vec<Value>,
     pub load_avg_data: [f32; 3],
     pub mem_data: Option<Value>,
@@ -44,6 +56,20 @@ pub struct TimedData {
     pub gpu_data: Vec<Option<Value>>,
 }
 
+impl TimedData {
+    /// Roughly how many bytes this takes up outside of itself, i.e. on the heap.
+    fn heap_bytes(&self) -> usize {
+        #[allow(unused_mut)]
+        let mut bytes = self.cpu_data.capacity() * size_of::<Value>();
+        #[cfg(feature = "gpu")]
+        {
+            bytes += self.gpu_data.capacity() * size_of::<Option<Value>>();
+        }
+
+        bytes
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ProcessData {
     /// A PID to process data map.
@@ -57,29 +83,64 @@ pub struct ProcessData {
 }
 
 impl ProcessData {
-    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
+    /// Roughly how many bytes the processes take up.
+    pub fn approx_bytes(&self) -> u64 {
+        let processes: usize = self
+            .process_harvest
+            .values()
+            .map(|process| {
+                let user = match &process.user {
+                    std::borrow::Cow::Owned(user) => user.capacity(),
+                    std::borrow::Cow::Borrowed(_) => 0,
+                };
+
+                size_of::<(Pid, ProcessHarvest)>()
+                    + process.name.capacity()
+                    + process.command.capacity()
+                    + process.process_state.0.capacity()
+                    + user
+            })
+            .sum();
+        let mappings: usize = self
+            .process_parent_mapping
+            .values()
+            .map(|children| size_of::<(Pid, Vec<Pid>)>() + children.capacity() * size_of::<Pid>())
+            .sum();
+        let orphans = self.orphan_pids.capacity() * size_of::<Pid>();
+
+        (processes + mappings + orphans) as u64
+    }
+
+    fn ingest(&mut self, update: ProcessUpdate) {
+        match update {
+            ProcessUpdate::Full(list_of_processes) => {
+                self.process_harvest = list_of_processes
+                    .into_iter()
+                    .map(|process| (process.pid, process))
+                    .collect();
+            }
+            ProcessUpdate::Diff(diff) => diff.apply(&mut self.process_harvest),
+        }
+
         self.process_parent_mapping.clear();
 
         // Reverse as otherwise the pid mappings are in the wrong order.
-        list_of_processes.iter().rev().for_each(|process_harvest| {
-            if let Some(parent_pid) = process_harvest.parent_pid {
-                if let Some(entry) = self.process_parent_mapping.get_mut(&parent_pid) {
-                    entry.push(process_harvest.pid);
-                } else {
-                    self.process_parent_mapping
-                        .insert(parent_pid, vec![process_harvest.pid]);
+        self.process_harvest
+            .values()
+            .rev()
+            .for_each(|process_harvest| {
+                if let Some(parent_pid) = process_harvest.parent_pid {
+                    if let Some(entry) = self.process_parent_mapping.get_mut(&parent_pid) {
+                        entry.push(process_harvest.pid);
+                    } else {
+                        self.process_parent_mapping
+                            .insert(parent_pid, vec![process_harvest.pid]);
+                    }
                 }
-            }
-        });
+            });
 
         self.process_parent_mapping.shrink_to_fit();
 
-        let process_pid_map = list_of_processes
-            .into_iter()
-            .map(|process| (process.pid, process))
-            .collect();
-        self.process_harvest = process_pid_map;
-
         // We collect all processes that either:
         // - Do not have a parent PID (that is, they are orphan processes)
         // - Have a parent PID but we don't have the parent (we promote them as orphans)
@@ -91,6 +152,32 @@ impl ProcessData {
                 _ => Some(*pid),
             })
             .collect();
+
+        // Parents that loop back around (e.g. a parent exited and its PID was reused by a
+        // descendant) can't be reached from any orphan, so promote them to orphans as well.
+        let mut reachable_pids = HashSet::new();
+        let mut stack = self.orphan_pids.clone();
+        let mut pids = self.process_harvest.keys();
+        loop {
+            while let Some(pid) = stack.pop() {
+                if reachable_pids.insert(pid) {
+                    if let Some(children) = self.process_parent_mapping.get(&pid) {
+                        stack.extend(children);
+                    }
+                }
+            }
+
+            let Some(pid) = pids.find(|pid| !reachable_pids.contains(*pid)) else {
+                break;
+            };
+            if let Some(parent_pid) = self.process_harvest[pid].parent_pid {
+                if let Some(siblings) = self.process_parent_mapping.get_mut(&parent_pid) {
+                    siblings.retain(|sibling| sibling != pid);
+                }
+            }
+            self.orphan_pids.push(*pid);
+            stack.push(*pid);
+        }
     }
 }
 
@@ -107,7 +194,19 @@ impl ProcessData {
 #[derive(Debug, Clone)]
 pub struct DataCollection {
     pub current_instant: Instant,
-    pub timed_data_vec: Vec<(Instant, TimedData)>,
+    /// The data collected over time, oldest first. Entries older than the retention are dropped as
+    /// new ones come in.
+    pub timed_data_vec: VecDeque<(Instant, TimedData)>,
+    /// How long to keep entries in `timed_data_vec` for, in milliseconds, or [`None`] to keep
+    /// them until cleaned.
+    retention_ms: Option<u64>,
+    /// The most entries `timed_data_vec` may hold, or [`None`] if it may grow. This is set once
+    /// the history has been shrunk to fit in `max_data_memory`, so that new entries replace the
+    /// oldest ones rather than growing it past the cap again.
+    max_history_len: Option<usize>,
+    /// How many times this has been reset, which matches the collector's generation once it has
+    /// handled each reset, see [`Data::generation`].
+    generation: u64,
     pub network_harvest: network::NetworkHarvest,
     pub memory_harvest: memory::MemHarvest,
     #[cfg(not(target_os = "windows"))]
@@ -118,8 +217,11 @@ pub struct DataCollection {
     pub process_data: ProcessData,
     pub disk_harvest: Vec<disks::DiskHarvest>,
     pub io_harvest: disks::IoHarvest,
+    /// When disk data was last eaten, which is used to work out IO rates.
+    pub io_instant: Instant,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
-    pub io_labels: Vec<(String, String)>,
+    /// The read and write rates of each disk in bytes per second, or [`None`] if they are unknown.
+    pub io_rates: Vec<Option<(u64, u64)>>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryHarvest>,
@@ -133,7 +235,10 @@ impl Default for DataCollection {
     fn default() -> Self {
         DataCollection {
             current_instant: Instant::now(),
-            timed_data_vec: Vec::default(),
+            timed_data_vec: VecDeque::default(),
+            retention_ms: None,
+            max_history_len: None,
+            generation: 0,
             network_harvest: network::NetworkHarvest::default(),
             memory_harvest: memory::MemHarvest::default(),
             #[cfg(not(target_os = "windows"))]
@@ -144,8 +249,9 @@ impl Default for DataCollection {
             process_data: Default::default(),
             disk_harvest: Vec::default(),
             io_harvest: disks::IoHarvest::default(),
+            io_instant: Instant::now(),
             io_labels_and_prev: Vec::default(),
-            io_labels: Vec::default(),
+            io_rates: Vec::default(),
             temp_harvest: Vec::default(),
             #[cfg(feature = "battery")]
             battery_harvest: Vec::default(),
@@ -159,7 +265,8 @@ impl Default for DataCollection {
 
 impl DataCollection {
     pub fn reset(&mut self) {
-        self.timed_data_vec = Vec::default();
+        self.generation += 1;
+        self.timed_data_vec.clear();
         self.network_harvest = network::NetworkHarvest::default();
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
@@ -200,7 +307,84 @@ impl DataCollection {
         };
 
         self.timed_data_vec.drain(0..remove_index);
+    }
+
+    /// Keeps data for `retention_ms`, dropping anything already older than that. Room is made up
+    /// front for all the entries collected over that time at an update rate of `update_rate_ms`,
+    /// so that new data doesn't need to allocate once the retention is reached. This is capped at
+    /// [`MAX_PREALLOCATED_HISTORY_ENTRIES`], and skipped if the memory can't be had.
+    pub fn set_retention(&mut self, retention_ms: u64, update_rate_ms: u64) {
+        self.retention_ms = Some(retention_ms);
+        self.max_history_len = None;
+        self.drop_expired_data(self.current_instant);
+
+        // +2 for the entries at either end of the window, as collection times can drift a bit.
+        let capacity = usize::try_from(retention_ms / update_rate_ms.max(1))
+            .unwrap_or(usize::MAX)
+            .saturating_add(2)
+            .min(MAX_PREALLOCATED_HISTORY_ENTRIES);
+        if capacity > self.timed_data_vec.capacity() {
+            let additional = capacity.saturating_sub(self.timed_data_vec.len());
+            let _ = self.timed_data_vec.try_reserve_exact(additional);
+        } else {
+            self.timed_data_vec.shrink_to(capacity);
+        }
+    }
+
+    /// Roughly how many bytes the history in `timed_data_vec` takes up, including room reserved
+    /// for entries to come.
+    pub fn history_bytes(&self) -> u64 {
+        let entries = self.timed_data_vec.capacity() * size_of::<(Instant, TimedData)>();
+        let heap: usize = self
+            .timed_data_vec
+            .iter()
+            .map(|(_, data)| data.heap_bytes())
+            .sum();
+
+        (entries + heap) as u64
+    }
+
+    /// Frees up at least `bytes` from the history if it can, returning how many bytes were freed,
+    /// as measured by [`DataCollection::history_bytes`]. Room reserved for new entries goes first,
+    /// then the oldest entries. The latest entry is always kept, so graphs still have their
+    /// current values.
+    ///
+    /// A bit more than `bytes` is freed, so that new entries taking up more memory than the ones
+    /// they replace don't need the history to be shrunk again on every update. Afterwards, the
+    /// history doesn't grow any more, and each new entry replaces the oldest one instead.
+    pub fn shrink_history(&mut self, bytes: u64) -> u64 {
+        let before = self.history_bytes();
+        let goal = bytes.saturating_add(before / HISTORY_HEADROOM_DIVISOR);
+
+        let entry_size = size_of::<(Instant, TimedData)>() as u64;
+        let len = self.timed_data_vec.len();
+        let mut freed = (self.timed_data_vec.capacity() - len) as u64 * entry_size;
+        let mut num_dropped = 0;
+        for (_, data) in self.timed_data_vec.iter().take(len.saturating_sub(1)) {
+            if freed >= goal {
+                break;
+            }
+            freed += entry_size + data.heap_bytes() as u64;
+            num_dropped += 1;
+        }
+        self.timed_data_vec.drain(..num_dropped);
         self.timed_data_vec.shrink_to_fit();
+        self.max_history_len = Some(self.timed_data_vec.capacity());
+
+        before - self.history_bytes()
+    }
+
+    /// Drops entries that are older than the retention as of `now`.
+    fn drop_expired_data(&mut self, now: Instant) {
+        if let Some(retention_ms) = self.retention_ms {
+            while let Some((instant, _)) = self.timed_data_vec.front() {
+                if now.saturating_duration_since(*instant).as_millis() > retention_ms.into() {
+                    self.timed_data_vec.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
     }
 
     pub fn eat_data(&mut self, harvested_data: Box<Data>) {
@@ -256,8 +440,12 @@ impl DataCollection {
         }
 
         // Processes
-        if let Some(list_of_processes) = harvested_data.list_of_processes {
-            self.eat_proc(list_of_processes);
+        if let Some(processes) = harvested_data.processes {
+            // Process changes from before a reset were made against processes that have since
+            // been cleared, so they are dropped until the whole list is sent again.
+            if harvested_data.generation >= self.generation {
+                self.eat_proc(processes);
+            }
         }
 
         #[cfg(feature = "battery")]
@@ -270,7 +458,14 @@ impl DataCollection {
 
         // And we're done eating.  Update time and push the new entry!
         self.current_instant = harvested_time;
-        self.timed_data_vec.push((harvested_time, new_entry));
+        self.drop_expired_data(harvested_time);
+        if let Some(max_history_len) = self.max_history_len {
+            // Make room first, so the history isn't grown past what it was shrunk to.
+            while self.timed_data_vec.len() >= max_history_len.max(1) {
+                self.timed_data_vec.pop_front();
+            }
+        }
+        self.timed_data_vec.push_back((harvested_time, new_entry));
     }
 
     fn eat_memory_and_swap(
@@ -297,15 +492,8 @@ impl DataCollection {
     }
 
     fn eat_network(&mut self, network: network::NetworkHarvest, new_entry: &mut TimedData) {
-        // RX
-        if network.rx > 0 {
-            new_entry.rx_data = network.rx as f64;
-        }
-
-        // TX
-        if network.tx > 0 {
-            new_entry.tx_data = network.tx as f64;
-        }
+        new_entry.rx_data = Some(network.rx as f64);
+        new_entry.tx_data = Some(network.tx as f64);
 
         // In addition copy over latest data for easy reference
         self.network_harvest = network;
@@ -334,9 +522,8 @@ impl DataCollection {
     fn eat_disks(
         &mut self, disks: Vec<disks::DiskHarvest>, io: disks::IoHarvest, harvested_time: Instant,
     ) {
-        let time_since_last_harvest = harvested_time
-            .duration_since(self.current_instant)
-            .as_secs_f64();
+        let time_since_last_harvest = harvested_time.duration_since(self.io_instant).as_secs_f64();
+        self.io_instant = harvested_time;
 
         for (itx, device) in disks.iter().enumerate() {
             let checked_name = {
@@ -387,8 +574,8 @@ impl DataCollection {
                         (0, 0)
                     };
 
-                    if self.io_labels.len() <= itx {
-                        self.io_labels.push((String::default(), String::default()));
+                    if self.io_rates.len() <= itx {
+                        self.io_rates.push(None);
                     }
 
                     if self.io_labels_and_prev.len() <= itx {
@@ -406,30 +593,17 @@ impl DataCollection {
                         *io_curr = (r_rate, w_rate);
                         *io_prev = (io_r_pt, io_w_pt);
 
-                        if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                            let converted_read = get_decimal_bytes(r_rate);
-                            let converted_write = get_decimal_bytes(w_rate);
-                            *io_labels = (
-                                if r_rate >= GIGA_LIMIT {
-                                    format!("{:.*}{}/s", 1, converted_read.0, converted_read.1)
-                                } else {
-                                    format!("{:.*}{}/s", 0, converted_read.0, converted_read.1)
-                                },
-                                if w_rate >= GIGA_LIMIT {
-                                    format!("{:.*}{}/s", 1, converted_write.0, converted_write.1)
-                                } else {
-                                    format!("{:.*}{}/s", 0, converted_write.0, converted_write.1)
-                                },
-                            );
+                        if let Some(io_rates) = self.io_rates.get_mut(itx) {
+                            *io_rates = Some((r_rate, w_rate));
                         }
                     }
                 } else {
-                    if self.io_labels.len() <= itx {
-                        self.io_labels.push((String::default(), String::default()));
+                    if self.io_rates.len() <= itx {
+                        self.io_rates.push(None);
                     }
 
-                    if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                        *io_labels = ("N/A".to_string(), "N/A".to_string());
+                    if let Some(io_rates) = self.io_rates.get_mut(itx) {
+                        *io_rates = None;
                     }
                 }
             }
@@ -439,8 +613,8 @@ impl DataCollection {
         self.io_harvest = io;
     }
 
-    fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>) {
-        self.process_data.ingest(list_of_processes);
+    fn eat_proc(&mut self, processes: ProcessUpdate) {
+        self.process_data.ingest(processes);
     }
 
     #[cfg(feature = "battery")]
@@ -465,3 +639,253 @@ impl DataCollection {
         self.gpu_harvest = gpu;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::utils::test_utils::random_generator;
+
+    #[test]
+    fn test_process_parent_cycles() {
+        // 2 and 3 are each other's parents, and 4 is its own parent.
+        let processes = [
+            (1, None),
+            (2, Some(3)),
+            (3, Some(2)),
+            (4, Some(4)),
+            (5, Some(3)),
+        ]
+        .into_iter()
+        .map(|(pid, parent_pid)| ProcessHarvest {
+            pid,
+            parent_pid,
+            ..Default::default()
+        })
+        .collect();
+
+        let mut process_data = ProcessData::default();
+        process_data.ingest(ProcessUpdate::Full(processes));
+
+        let mut orphan_pids = process_data.orphan_pids.clone();
+        orphan_pids.sort_unstable();
+        assert_eq!(orphan_pids, [1, 2, 4]);
+        assert_eq!(process_data.process_parent_mapping[&2], [3]);
+        assert_eq!(process_data.process_parent_mapping[&3], [5]);
+        assert!(process_data.process_parent_mapping[&4].is_empty());
+    }
+
+    #[test]
+    fn test_process_diffs() {
+        use crate::data_harvester::processes::ProcessDiffer;
+
+        let mut random = random_generator(0x2545_f491);
+
+        let mut processes: BTreeMap<Pid, ProcessHarvest> = BTreeMap::new();
+        let mut differ = ProcessDiffer::default();
+        let mut process_data = ProcessData::default();
+        let mut pending: Option<ProcessUpdate> = None;
+        let mut num_diffs = 0;
+
+        for _ in 0..500 {
+            // Add, remove, and change processes, with PIDs often being reused.
+            for _ in 0..random(8) {
+                let pid = random(64) as Pid + 1;
+                let parent_pid = Some(random(64) as Pid + 1).filter(|&parent| parent != pid);
+                match processes.get_mut(&pid) {
+                    Some(_) if random(3) == 0 => {
+                        processes.remove(&pid);
+                    }
+                    Some(process) => {
+                        process.cpu_usage_percent = random(100) as f32;
+                        process.parent_pid = parent_pid;
+                    }
+                    None => {
+                        let process = ProcessHarvest {
+                            pid,
+                            parent_pid,
+                            name: format!("process {}", random(1000)),
+                            ..Default::default()
+                        };
+                        processes.insert(pid, process);
+                    }
+                }
+            }
+
+            // Like the collection thread, updates may be replaced before they are applied.
+            let update = differ.update(processes.values().cloned().collect());
+            num_diffs += usize::from(matches!(update, ProcessUpdate::Diff(_)));
+            pending = Some(match pending.take() {
+                Some(pending) => pending.then(update),
+                None => update,
+            });
+            if random(3) != 0 {
+                process_data.ingest(pending.take().unwrap());
+
+                let mut reference = ProcessData::default();
+                reference.ingest(ProcessUpdate::Full(processes.values().cloned().collect()));
+                assert_eq!(process_data.process_harvest, reference.process_harvest);
+                assert_eq!(
+                    process_data.process_parent_mapping,
+                    reference.process_parent_mapping
+                );
+                assert_eq!(process_data.orphan_pids, reference.orphan_pids);
+            }
+
+            // Resetting starts over with the whole list.
+            if random(50) == 0 {
+                differ.reset();
+                process_data = ProcessData::default();
+                pending = None;
+            }
+        }
+
+        assert!(num_diffs > 400);
+    }
+
+    #[test]
+    fn test_process_updates_after_reset() {
+        use crate::data_harvester::processes::ProcessDiffer;
+
+        let process = |pid| ProcessHarvest {
+            pid,
+            ..Default::default()
+        };
+        let data = |generation, processes| {
+            Box::new(Data {
+                generation,
+                processes: Some(processes),
+                ..Default::default()
+            })
+        };
+
+        let mut differ = ProcessDiffer::default();
+        let mut data_collection = DataCollection::default();
+        data_collection.eat_data(data(0, differ.update(vec![process(1), process(2)])));
+        data_collection.reset();
+
+        // The collector hasn't handled the reset yet, so its changes are from before it.
+        data_collection.eat_data(data(0, differ.update(vec![process(1), process(3)])));
+        assert!(data_collection.process_data.process_harvest.is_empty());
+
+        // Once it has, it starts over with the whole list.
+        differ.reset();
+        data_collection.eat_data(data(1, differ.update(vec![process(1), process(3)])));
+        assert_eq!(
+            data_collection
+                .process_data
+                .process_harvest
+                .keys()
+                .collect::<Vec<_>>(),
+            [&1, &3]
+        );
+        data_collection.eat_data(data(1, differ.update(vec![process(3)])));
+        assert_eq!(
+            data_collection
+                .process_data
+                .process_harvest
+                .keys()
+                .collect::<Vec<_>>(),
+            [&3]
+        );
+    }
+
+    #[test]
+    fn test_retention() {
+        let mut data_collection = DataCollection::default();
+        data_collection.set_retention(10000, 1000);
+        assert!(data_collection.timed_data_vec.capacity() >= 12);
+
+        let start = Instant::now();
+        for secs in 0..=20 {
+            data_collection.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(secs),
+                ..Default::default()
+            }));
+        }
+
+        // Only the last 10 seconds are kept.
+        assert_eq!(data_collection.timed_data_vec.len(), 11);
+        assert_eq!(
+            data_collection.timed_data_vec.front().unwrap().0,
+            start + Duration::from_secs(10)
+        );
+
+        // Shrinking the retention drops data right away, and growing it only makes more room.
+        data_collection.set_retention(5000, 1000);
+        assert_eq!(data_collection.timed_data_vec.len(), 6);
+        data_collection.set_retention(60000, 1000);
+        assert_eq!(data_collection.timed_data_vec.len(), 6);
+        assert!(data_collection.timed_data_vec.capacity() >= 62);
+
+        // Very long retentions are valid, but only make so much room up front.
+        for (retention_ms, update_rate_ms) in [(30 * 24 * 60 * 60 * 1000, 250), (u64::MAX, 0)] {
+            data_collection.set_retention(retention_ms, update_rate_ms);
+            assert_eq!(data_collection.timed_data_vec.len(), 6);
+            assert!(data_collection.timed_data_vec.capacity() >= MAX_PREALLOCATED_HISTORY_ENTRIES);
+            assert!(
+                data_collection.timed_data_vec.capacity() < 2 * MAX_PREALLOCATED_HISTORY_ENTRIES
+            );
+        }
+    }
+
+    #[test]
+    fn test_shrink_history() {
+        let mut data_collection = DataCollection::default();
+        data_collection.set_retention(60000, 1000);
+
+        let start = Instant::now();
+        for secs in 0..10 {
+            data_collection.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(secs),
+                ..Default::default()
+            }));
+        }
+
+        // The reserved room goes before any entries.
+        let entry_size = size_of::<(Instant, TimedData)>() as u64;
+        let spare = data_collection.timed_data_vec.capacity() as u64 - 10;
+        let before = data_collection.history_bytes();
+        assert_eq!(data_collection.shrink_history(1), spare * entry_size);
+        assert_eq!(data_collection.timed_data_vec.len(), 10);
+        assert_eq!(data_collection.history_bytes(), before - spare * entry_size);
+
+        // Then the oldest entries go, along with their room, plus a bit more so this isn't needed
+        // again right away.
+        let before = data_collection.history_bytes();
+        let freed = data_collection.shrink_history(entry_size * 3);
+        assert!(freed >= entry_size * 3 + before / HISTORY_HEADROOM_DIVISOR);
+        assert_eq!(data_collection.history_bytes(), before - freed);
+        assert_eq!(data_collection.timed_data_vec.len(), 6);
+        assert_eq!(
+            data_collection.timed_data_vec.front().unwrap().0,
+            start + Duration::from_secs(4)
+        );
+
+        // New entries replace the oldest ones, rather than growing the history past the cap.
+        let capacity = data_collection.timed_data_vec.capacity();
+        for secs in 10..30 {
+            data_collection.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(secs),
+                ..Default::default()
+            }));
+        }
+        assert_eq!(data_collection.timed_data_vec.capacity(), capacity);
+        assert_eq!(data_collection.timed_data_vec.len(), capacity);
+        assert_eq!(
+            data_collection.timed_data_vec.back().unwrap().0,
+            start + Duration::from_secs(29)
+        );
+
+        // The latest entry is always kept.
+        let before = data_collection.history_bytes();
+        let freed = data_collection.shrink_history(u64::MAX);
+        assert_eq!(data_collection.history_bytes(), before - freed);
+        assert_eq!(data_collection.timed_data_vec.len(), 1);
+        assert_eq!(
+            data_collection.timed_data_vec.back().unwrap().0,
+            start + Duration::from_secs(29)
+        );
+    }
+}
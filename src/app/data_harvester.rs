@@ -2,14 +2,15 @@
 
 use std::time::{Duration, Instant};
 
-#[cfg(target_os = "linux")]
 use hashbrown::HashMap;
 #[cfg(feature = "battery")]
 use starship_battery::{Battery, Manager};
 use sysinfo::{System, SystemExt};
 
-use self::temperature::TemperatureType;
-use super::DataFilters;
+use super::{
+    update_rates::{CollectionRates, DataSource},
+    DataFilters,
+};
 use crate::app::layout_manager::UsedWidgets;
 
 #[cfg(feature = "nvidia")]
@@ -28,6 +29,10 @@ pub mod temperature;
 #[derive(Clone, Debug)]
 pub struct Data {
     pub collection_time: Instant,
+    /// How many resets the collector had handled when this was collected. Process changes are
+    /// made against what was sent in the same generation, so older ones can't be applied after a
+    /// reset.
+    pub generation: u64,
     pub cpu: Option<cpu::CpuHarvest>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
     pub memory: Option<memory::MemHarvest>,
@@ -36,7 +41,7 @@ pub struct Data {
     pub swap: Option<memory::MemHarvest>,
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
     pub network: Option<network::NetworkHarvest>,
-    pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
+    pub processes: Option<processes::ProcessUpdate>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IoHarvest>,
     #[cfg(feature = "battery")]
@@ -51,6 +56,7 @@ impl Default for Data {
     fn default() -> Self {
         Data {
             collection_time: Instant::now(),
+            generation: 0,
             cpu: None,
             load_avg: None,
             memory: None,
@@ -58,7 +64,7 @@ impl Default for Data {
             cache: None,
             swap: None,
             temperature_sensors: None,
-            list_of_processes: None,
+            processes: None,
             disks: None,
             io: None,
             network: None,
@@ -73,10 +79,22 @@ impl Default for Data {
 }
 
 impl Data {
+    /// Keeps the process changes from `older` data that this replaces before it was applied, as
+    /// they may only be sent once. Everything else is sent in full each time, so only the newer
+    /// values are kept.
+    pub fn carry_over(&mut self, older: Data) {
+        if let Some(older_processes) = older.processes {
+            self.processes = Some(match self.processes.take() {
+                Some(processes) => older_processes.then(processes),
+                None => older_processes,
+            });
+        }
+    }
+
     pub fn cleanup(&mut self) {
         self.io = None;
         self.temperature_sensors = None;
-        self.list_of_processes = None;
+        self.processes = None;
         self.disks = None;
         self.memory = None;
         self.swap = None;
@@ -101,7 +119,6 @@ impl Data {
 pub struct DataCollector {
     pub data: Data,
     sys: System,
-    temperature_type: TemperatureType,
     use_current_cpu_total: bool,
     unnormalized_cpu: bool,
     last_collection_time: Instant,
@@ -109,7 +126,14 @@ pub struct DataCollector {
     total_tx: u64,
     show_average_cpu: bool,
     widgets_to_harvest: UsedWidgets,
+    collection_rates: CollectionRates,
+    /// The widgets whose data is collected in the current update, based on `collection_rates`.
+    due_widgets: UsedWidgets,
+    last_collected: HashMap<DataSource, Instant>,
     filters: DataFilters,
+    process_differ: processes::ProcessDiffer,
+    /// How many resets the main thread has asked for, see [`Data::generation`].
+    generation: u64,
 
     #[cfg(target_os = "linux")]
     pid_mapping: HashMap<crate::Pid, processes::PrevProcDetails>,
@@ -138,7 +162,6 @@ impl DataCollector {
             prev_idle: 0_f64,
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
-            temperature_type: TemperatureType::Celsius,
             use_current_cpu_total: false,
             unnormalized_cpu: false,
             last_collection_time: Instant::now(),
@@ -146,17 +169,52 @@ impl DataCollector {
             total_tx: 0,
             show_average_cpu: false,
             widgets_to_harvest: UsedWidgets::default(),
+            collection_rates: CollectionRates::default(),
+            due_widgets: UsedWidgets::default(),
+            last_collected: Default::default(),
             #[cfg(feature = "battery")]
             battery_manager: None,
             #[cfg(feature = "battery")]
             battery_list: None,
             filters,
+            process_differ: Default::default(),
+            generation: 0,
             #[cfg(target_family = "unix")]
             user_table: Default::default(),
         }
     }
 
     pub fn init(&mut self) {
+        self.init_sources();
+        self.update_data();
+
+        // Sleep a few seconds to avoid potentially weird data.
+        const SLEEP: Duration = get_sleep_duration();
+
+        std::thread::sleep(SLEEP);
+        self.reset();
+    }
+
+    /// Clears the collected data, and sends the whole process list again next time as nothing
+    /// that was sent before is kept.
+    pub fn reset(&mut self) {
+        self.data.cleanup();
+        self.process_differ.reset();
+    }
+
+    /// Resets for a reset asked for by the main thread. Data collected from now on is in a new
+    /// [generation](Data::generation), starting with the whole process list.
+    pub fn reset_generation(&mut self) {
+        self.reset();
+        self.generation += 1;
+    }
+
+    /// Sets up the batteries and lists needed by the widgets being harvested for. This is done
+    /// again if the widgets change, as something may be used that wasn't before.
+    pub fn init_sources(&mut self) {
+        // Processes may not have been collected for a while, so start over with the whole list.
+        self.process_differ.reset();
+
         #[cfg(feature = "battery")]
         {
             if self.widgets_to_harvest.use_battery {
@@ -191,22 +249,14 @@ impl DataCollector {
                 self.sys.refresh_disks_list();
             }
         }
-
-        self.update_data();
-
-        // Sleep a few seconds to avoid potentially weird data.
-        const SLEEP: Duration = get_sleep_duration();
-
-        std::thread::sleep(SLEEP);
-        self.data.cleanup();
     }
 
     pub fn set_data_collection(&mut self, used_widgets: UsedWidgets) {
         self.widgets_to_harvest = used_widgets;
     }
 
-    pub fn set_temperature_type(&mut self, temperature_type: TemperatureType) {
-        self.temperature_type = temperature_type;
+    pub fn set_collection_rates(&mut self, collection_rates: CollectionRates) {
+        self.collection_rates = collection_rates;
     }
 
     pub fn set_use_current_cpu_total(&mut self, use_current_cpu_total: bool) {
@@ -233,15 +283,15 @@ impl DataCollector {
         const LIST_REFRESH_TIME: Duration = Duration::from_secs(60);
         let refresh_start = Instant::now();
 
-        if self.widgets_to_harvest.use_cpu || self.widgets_to_harvest.use_proc {
+        if self.due_widgets.use_cpu || self.due_widgets.use_proc {
             self.sys.refresh_cpu();
         }
 
-        if self.widgets_to_harvest.use_mem || self.widgets_to_harvest.use_proc {
+        if self.due_widgets.use_mem || self.due_widgets.use_proc {
             self.sys.refresh_memory();
         }
 
-        if self.widgets_to_harvest.use_net {
+        if self.due_widgets.use_net {
             if refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME {
                 self.sys.refresh_networks_list();
             }
@@ -254,7 +304,7 @@ impl DataCollector {
         // - Temperatures and temperature components list.
         #[cfg(not(target_os = "linux"))]
         {
-            if self.widgets_to_harvest.use_proc {
+            if self.due_widgets.use_proc {
                 // For Windows, sysinfo also handles the users list.
                 #[cfg(target_os = "windows")]
                 if refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME {
@@ -264,7 +314,7 @@ impl DataCollector {
                 self.sys.refresh_processes();
             }
 
-            if self.widgets_to_harvest.use_temp {
+            if self.due_widgets.use_temp {
                 if refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME {
                     self.sys.refresh_components_list();
                 }
@@ -273,7 +323,7 @@ impl DataCollector {
         }
 
         #[cfg(target_os = "windows")]
-        if self.widgets_to_harvest.use_disk {
+        if self.due_widgets.use_disk {
             if refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME {
                 self.sys.refresh_disks_list();
             }
@@ -282,9 +332,11 @@ impl DataCollector {
     }
 
     pub fn update_data(&mut self) {
+        self.due_widgets = self.get_due_widgets(Instant::now());
         self.refresh_sysinfo_data();
 
         self.data.collection_time = Instant::now();
+        self.data.generation = self.generation;
 
         self.update_cpu_usage();
         self.update_memory_usage();
@@ -298,11 +350,58 @@ impl DataCollector {
 
         // Update times for future reference.
         self.last_collection_time = self.data.collection_time;
+
+        let due = &self.due_widgets;
+        for (source, is_collected) in [
+            (DataSource::Cpu, due.use_cpu),
+            (DataSource::Mem, due.use_mem),
+            (DataSource::Net, due.use_net),
+            (DataSource::Proc, due.use_proc),
+            (DataSource::Disk, due.use_disk),
+            (DataSource::Temp, due.use_temp),
+            (DataSource::Battery, due.use_battery),
+        ] {
+            if is_collected {
+                self.last_collected
+                    .insert(source, self.data.collection_time);
+            }
+        }
+    }
+
+    /// Returns the widgets whose data should be collected at `now`, which are those in use whose
+    /// source is due according to the collection rates.
+    fn get_due_widgets(&self, now: Instant) -> UsedWidgets {
+        let is_due = |source| {
+            self.collection_rates
+                .is_due(source, self.last_collected.get(&source).copied(), now)
+        };
+        let used = &self.widgets_to_harvest;
+
+        UsedWidgets {
+            use_cpu: used.use_cpu && is_due(DataSource::Cpu),
+            use_mem: used.use_mem && is_due(DataSource::Mem),
+            use_cache: used.use_cache && is_due(DataSource::Mem),
+            use_gpu: used.use_gpu && is_due(DataSource::Mem),
+            use_net: used.use_net && is_due(DataSource::Net),
+            use_proc: used.use_proc && is_due(DataSource::Proc),
+            use_disk: used.use_disk && is_due(DataSource::Disk),
+            use_temp: used.use_temp && is_due(DataSource::Temp),
+            use_battery: used.use_battery && is_due(DataSource::Battery),
+        }
+    }
+
+    /// Returns when `source` was last collected, for working out rates. Until it has been
+    /// collected once, this is when anything was last collected.
+    pub(crate) fn last_collected(&self, source: DataSource) -> Instant {
+        self.last_collected
+            .get(&source)
+            .copied()
+            .unwrap_or(self.last_collection_time)
     }
 
     #[inline]
     fn update_cpu_usage(&mut self) {
-        if self.widgets_to_harvest.use_cpu {
+        if self.due_widgets.use_cpu {
             self.data.cpu = cpu::get_cpu_data_list(&self.sys, self.show_average_cpu).ok();
 
             #[cfg(target_family = "unix")]
@@ -314,33 +413,29 @@ impl DataCollector {
 
     #[inline]
     fn update_processes(&mut self) {
-        if self.widgets_to_harvest.use_proc {
+        if self.due_widgets.use_proc {
             if let Ok(mut process_list) = self.get_processes() {
                 // NB: To avoid duplicate sorts on rerenders/events, we sort the processes by PID here.
                 // We also want to avoid re-sorting *again* later on if we're sorting by PID, since we already
                 // did it here!
                 process_list.sort_unstable_by_key(|p| p.pid);
-                self.data.list_of_processes = Some(process_list);
+                self.data.processes = Some(self.process_differ.update(process_list));
             }
         }
     }
 
     #[inline]
     fn update_temps(&mut self) {
-        if self.widgets_to_harvest.use_temp {
+        if self.due_widgets.use_temp {
             #[cfg(not(target_os = "linux"))]
-            if let Ok(data) = temperature::get_temperature_data(
-                &self.sys,
-                &self.temperature_type,
-                &self.filters.temp_filter,
-            ) {
+            if let Ok(data) =
+                temperature::get_temperature_data(&self.sys, &self.filters.temp_filter)
+            {
                 self.data.temperature_sensors = data;
             }
 
             #[cfg(target_os = "linux")]
-            if let Ok(data) =
-                temperature::get_temperature_data(&self.temperature_type, &self.filters.temp_filter)
-            {
+            if let Ok(data) = temperature::get_temperature_data(&self.filters.temp_filter) {
                 self.data.temperature_sensors = data;
             }
         }
@@ -348,11 +443,11 @@ impl DataCollector {
 
     #[inline]
     fn update_memory_usage(&mut self) {
-        if self.widgets_to_harvest.use_mem {
+        if self.due_widgets.use_mem {
             self.data.memory = memory::get_ram_usage(&self.sys);
 
             #[cfg(not(target_os = "windows"))]
-            if self.widgets_to_harvest.use_cache {
+            if self.due_widgets.use_cache {
                 self.data.cache = memory::get_cache_usage(&self.sys);
             }
 
@@ -367,7 +462,7 @@ impl DataCollector {
             }
 
             #[cfg(feature = "gpu")]
-            if self.widgets_to_harvest.use_gpu {
+            if self.due_widgets.use_gpu {
                 self.data.gpu = memory::gpu::get_gpu_mem_usage();
             }
         }
@@ -377,10 +472,10 @@ impl DataCollector {
     fn update_network_usage(&mut self) {
         let current_instant = self.data.collection_time;
 
-        if self.widgets_to_harvest.use_net {
+        if self.due_widgets.use_net {
             let net_data = network::get_network_data(
                 &self.sys,
-                self.last_collection_time,
+                self.last_collected(DataSource::Net),
                 &mut self.total_rx,
                 &mut self.total_tx,
                 current_instant,
@@ -396,6 +491,10 @@ impl DataCollector {
     #[inline]
     #[cfg(feature = "battery")]
     fn update_batteries(&mut self) {
+        if !self.due_widgets.use_battery {
+            return;
+        }
+
         if let Some(battery_manager) = &self.battery_manager {
             if let Some(battery_list) = &mut self.battery_list {
                 self.data.list_of_batteries =
@@ -406,7 +505,7 @@ impl DataCollector {
 
     #[inline]
     fn update_disks(&mut self) {
-        if self.widgets_to_harvest.use_disk {
+        if self.due_widgets.use_disk {
             self.data.disks = disks::get_disk_usage(self).ok();
             self.data.io = disks::get_io_usage().ok();
         }
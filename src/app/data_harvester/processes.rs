@@ -4,7 +4,7 @@
 //! For Windows, macOS, FreeBSD, Android, and Linux, this is handled by sysinfo.
 
 use cfg_if::cfg_if;
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, collections::BTreeMap, time::Duration};
 
 use super::DataCollector;
 
@@ -36,7 +36,7 @@ cfg_if! {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ProcessHarvest {
     /// The pid of the process.
     pub pid: Pid,
@@ -101,12 +101,112 @@ impl ProcessHarvest {
     }
 }
 
+/// The processes collected in an update, sent either as the whole list or as the changes since
+/// the previously sent list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessUpdate {
+    /// Every process, sorted by PID.
+    Full(Vec<ProcessHarvest>),
+
+    /// The changes since the previous update.
+    Diff(ProcessDiff),
+}
+
+/// The changes to the process list between two updates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessDiff {
+    /// Processes that are new, or whose values changed, keyed by PID.
+    pub changed: BTreeMap<Pid, ProcessHarvest>,
+
+    /// PIDs of processes that are gone. A PID can also be in `changed` if it was reused by a new
+    /// process, as removals are applied first.
+    pub removed: Vec<Pid>,
+}
+
+impl ProcessDiff {
+    /// Applies the changes to `processes`.
+    pub fn apply(self, processes: &mut BTreeMap<Pid, ProcessHarvest>) {
+        for pid in &self.removed {
+            processes.remove(pid);
+        }
+        processes.extend(self.changed);
+    }
+}
+
+impl ProcessUpdate {
+    /// Combines this update with the `next` one, as if both had been applied in turn. This is
+    /// used when an update is replaced before it was applied.
+    pub fn then(self, next: ProcessUpdate) -> ProcessUpdate {
+        match (self, next) {
+            (_, ProcessUpdate::Full(processes)) => ProcessUpdate::Full(processes),
+            (ProcessUpdate::Full(processes), ProcessUpdate::Diff(diff)) => {
+                let mut processes = processes
+                    .into_iter()
+                    .map(|process| (process.pid, process))
+                    .collect();
+                diff.apply(&mut processes);
+                ProcessUpdate::Full(processes.into_values().collect())
+            }
+            (ProcessUpdate::Diff(mut first), ProcessUpdate::Diff(second)) => {
+                for pid in &second.removed {
+                    first.changed.remove(pid);
+                }
+                first.removed.extend(second.removed);
+                first.changed.extend(second.changed);
+                ProcessUpdate::Diff(first)
+            }
+        }
+    }
+}
+
+/// Turns each collected process list into a [`ProcessUpdate`] against the previously sent one, so
+/// only what changed has to be sent. Starts with, and goes back to, sending the whole list after
+/// being [reset](ProcessDiffer::reset).
+#[derive(Debug, Default)]
+pub struct ProcessDiffer {
+    previous: Option<BTreeMap<Pid, ProcessHarvest>>,
+}
+
+impl ProcessDiffer {
+    /// Returns the update from the previous list to `processes`.
+    pub fn update(&mut self, processes: Vec<ProcessHarvest>) -> ProcessUpdate {
+        let current: BTreeMap<Pid, ProcessHarvest> = processes
+            .into_iter()
+            .map(|process| (process.pid, process))
+            .collect();
+
+        let update = match &self.previous {
+            None => ProcessUpdate::Full(current.values().cloned().collect()),
+            Some(previous) => ProcessUpdate::Diff(ProcessDiff {
+                changed: current
+                    .iter()
+                    .filter(|(pid, process)| previous.get(pid) != Some(process))
+                    .map(|(pid, process)| (*pid, process.clone()))
+                    .collect(),
+                removed: previous
+                    .keys()
+                    .filter(|pid| !current.contains_key(pid))
+                    .copied()
+                    .collect(),
+            }),
+        };
+        self.previous = Some(current);
+
+        update
+    }
+
+    /// Forgets the previous list, so the next update sends the whole list again.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
 impl DataCollector {
     pub(crate) fn get_processes(&mut self) -> error::Result<Vec<ProcessHarvest>> {
         cfg_if! {
             if #[cfg(target_os = "linux")] {
                 let time_diff = self.data.collection_time
-                    .duration_since(self.last_collection_time)
+                    .duration_since(self.last_collected(super::DataSource::Proc))
                     .as_secs();
 
                 linux_process_data(
@@ -21,6 +21,7 @@ use crate::app::Filter;
 #[derive(Default, Debug, Clone)]
 pub struct TempHarvest {
     pub name: String,
+    /// The temperature in degrees Celsius, which is converted to the displayed unit when drawn.
     pub temperature: f32,
 }
 
@@ -32,12 +33,24 @@ pub enum TemperatureType {
     Fahrenheit,
 }
 
-fn convert_celsius_to_kelvin(celsius: f32) -> f32 {
-    celsius + 273.15
-}
+impl TemperatureType {
+    /// Returns the name of this unit, as it is written in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            TemperatureType::Celsius => "celsius",
+            TemperatureType::Kelvin => "kelvin",
+            TemperatureType::Fahrenheit => "fahrenheit",
+        }
+    }
 
-fn convert_celsius_to_fahrenheit(celsius: f32) -> f32 {
-    (celsius * (9.0 / 5.0)) + 32.0
+    /// Returns the unit to switch to after this one.
+    pub fn next(self) -> Self {
+        match self {
+            TemperatureType::Celsius => TemperatureType::Fahrenheit,
+            TemperatureType::Fahrenheit => TemperatureType::Kelvin,
+            TemperatureType::Kelvin => TemperatureType::Celsius,
+        }
+    }
 }
 
 fn is_temp_filtered(filter: &Option<Filter>, text: &str) -> bool {
@@ -8,11 +8,8 @@ use std::{
 use anyhow::Result;
 use hashbrown::{HashMap, HashSet};
 
-use super::{is_temp_filtered, TempHarvest, TemperatureType};
-use crate::app::{
-    data_harvester::temperature::{convert_celsius_to_fahrenheit, convert_celsius_to_kelvin},
-    Filter,
-};
+use super::{is_temp_filtered, TempHarvest};
+use crate::app::Filter;
 
 const EMPTY_NAME: &str = "Unknown";
 
@@ -31,14 +28,6 @@ fn read_temp(path: &Path) -> Result<f32> {
         / 1_000.0)
 }
 
-fn convert_temp_unit(temp: f32, temp_type: &TemperatureType) -> f32 {
-    match temp_type {
-        TemperatureType::Celsius => temp,
-        TemperatureType::Kelvin => convert_celsius_to_kelvin(temp),
-        TemperatureType::Fahrenheit => convert_celsius_to_fahrenheit(temp),
-    }
-}
-
 /// Get all candidates from hwmon and coretemp. It will also return the number of entries from hwmon.
 fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
     let mut dirs = HashSet::default();
@@ -169,7 +158,7 @@ fn finalize_name(
 /// the device is already in ACPI D0. This has the notable issue that
 /// once this happens, the device will be *kept* on through the sensor
 /// reading, and not be able to re-enter ACPI D3cold.
-fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> HwmonResults {
+fn hwmon_temperatures(filter: &Option<Filter>) -> HwmonResults {
     let mut temperatures: Vec<TempHarvest> = vec![];
     let mut seen_names: HashMap<String, u32> = HashMap::new();
 
@@ -295,7 +284,7 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
 
                     temperatures.push(TempHarvest {
                         name,
-                        temperature: convert_temp_unit(temp, temp_type),
+                        temperature: temp,
                     });
                 }
             }
@@ -313,12 +302,10 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
 ///
 /// See [the Linux kernel documentation](https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-thermal)
 /// for more details.
-fn add_thermal_zone_temperatures(
-    temperatures: &mut Vec<TempHarvest>, temp_type: &TemperatureType, filter: &Option<Filter>,
-) {
+fn add_thermal_zone_temperatures(temperatures: &mut Vec<TempHarvest>, filter: &Option<Filter>) {
     let path = Path::new("/sys/class/thermal");
     let Ok(read_dir) = path.read_dir() else {
-        return
+        return;
     };
 
     let mut seen_names: HashMap<String, u32> = HashMap::new();
@@ -340,7 +327,7 @@ fn add_thermal_zone_temperatures(
 
                         temperatures.push(TempHarvest {
                             name,
-                            temperature: convert_temp_unit(temp, temp_type),
+                            temperature: temp,
                         });
                     }
                 }
@@ -349,19 +336,17 @@ fn add_thermal_zone_temperatures(
     }
 }
 
-/// Gets temperature sensors and data.
-pub fn get_temperature_data(
-    temp_type: &TemperatureType, filter: &Option<Filter>,
-) -> Result<Option<Vec<TempHarvest>>> {
-    let mut results = hwmon_temperatures(temp_type, filter);
+/// Gets temperature sensors and data, in degrees Celsius.
+pub fn get_temperature_data(filter: &Option<Filter>) -> Result<Option<Vec<TempHarvest>>> {
+    let mut results = hwmon_temperatures(filter);
 
     if results.num_hwmon == 0 {
-        add_thermal_zone_temperatures(&mut results.temperatures, temp_type, filter);
+        add_thermal_zone_temperatures(&mut results.temperatures, filter);
     }
 
     #[cfg(feature = "nvidia")]
     {
-        super::nvidia::add_nvidia_data(&mut results.temperatures, temp_type, filter)?;
+        super::nvidia::add_nvidia_data(&mut results.temperatures, filter)?;
     }
 
     Ok(Some(results.temperatures))
@@ -1,15 +1,12 @@
 use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
 
-use super::{
-    convert_celsius_to_fahrenheit, convert_celsius_to_kelvin, is_temp_filtered, TempHarvest,
-    TemperatureType,
-};
+use super::{is_temp_filtered, TempHarvest};
 use crate::app::Filter;
 use crate::data_harvester::nvidia::NVML_DATA;
 use crate::utils::error;
 
 pub fn add_nvidia_data(
-    temperature_vec: &mut Vec<TempHarvest>, temp_type: &TemperatureType, filter: &Option<Filter>,
+    temperature_vec: &mut Vec<TempHarvest>, filter: &Option<Filter>,
 ) -> error::Result<()> {
     if let Ok(nvml) = &*NVML_DATA {
         if let Ok(gpu_num) = nvml.device_count() {
@@ -20,13 +17,6 @@ pub fn add_nvidia_data(
                     {
                         if is_temp_filtered(filter, &name) {
                             let temperature = temperature as f32;
-                            let temperature = match temp_type {
-                                TemperatureType::Celsius => temperature,
-                                TemperatureType::Kelvin => convert_celsius_to_kelvin(temperature),
-                                TemperatureType::Fahrenheit => {
-                                    convert_celsius_to_fahrenheit(temperature)
-                                }
-                            };
 
                             temperature_vec.push(TempHarvest { name, temperature });
                         }
@@ -2,14 +2,12 @@
 
 use anyhow::Result;
 
-use super::{
-    convert_celsius_to_fahrenheit, convert_celsius_to_kelvin, is_temp_filtered, TempHarvest,
-    TemperatureType,
-};
+use super::{is_temp_filtered, TempHarvest};
 use crate::app::Filter;
 
+/// Gets temperature sensors and data, in degrees Celsius.
 pub fn get_temperature_data(
-    sys: &sysinfo::System, temp_type: &TemperatureType, filter: &Option<Filter>,
+    sys: &sysinfo::System, filter: &Option<Filter>,
 ) -> Result<Option<Vec<TempHarvest>>> {
     use sysinfo::{ComponentExt, SystemExt};
 
@@ -22,20 +20,14 @@ pub fn get_temperature_data(
         if is_temp_filtered(filter, &name) {
             temperature_vec.push(TempHarvest {
                 name,
-                temperature: match temp_type {
-                    TemperatureType::Celsius => component.temperature(),
-                    TemperatureType::Kelvin => convert_celsius_to_kelvin(component.temperature()),
-                    TemperatureType::Fahrenheit => {
-                        convert_celsius_to_fahrenheit(component.temperature())
-                    }
-                },
+                temperature: component.temperature(),
             });
         }
     }
 
     #[cfg(feature = "nvidia")]
     {
-        super::nvidia::add_nvidia_data(&mut temperature_vec, temp_type, filter)?;
+        super::nvidia::add_nvidia_data(&mut temperature_vec, filter)?;
     }
 
     // For RockPro64 boards on FreeBSD, they apparently use "hw.temperature" for sensors.
@@ -50,11 +42,7 @@ pub fn get_temperature_data(
                     if let Some(temp) = temp.as_temperature() {
                         temperature_vec.push(TempHarvest {
                             name,
-                            temperature: match temp_type {
-                                TemperatureType::Celsius => temp.celsius(),
-                                TemperatureType::Kelvin => temp.kelvin(),
-                                TemperatureType::Fahrenheit => temp.fahrenheit(),
-                            },
+                            temperature: temp.celsius(),
                         });
                     }
                 }
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use crate::constants::DEFAULT_WIDGET_ID;
+use crate::constants::*;
 use crate::error::{BottomError, Result};
 
 /// Represents a more usable representation of the layout, derived from the
@@ -890,6 +890,26 @@ pub struct BottomWidget {
 
     /// Bottom right corner when drawn, for mouse click detection. (x, y)
     pub bottom_right_corner: Option<(u16, u16)>,
+
+    /// The names of columns to hide by default, if this widget is a table.
+    pub hidden_columns: Vec<String>,
+
+    /// The names of the only columns to show, in order, if this widget is a table. Empty if the
+    /// table's own columns are used.
+    pub columns: Vec<String>,
+
+    /// Whether to split this widget's rows into pages rather than scrolling, if it is a table.
+    pub paged: bool,
+
+    /// How often this widget wants its data updated in milliseconds, if it should be less often
+    /// than the global update rate.
+    pub update_rate: Option<u64>,
+
+    /// The width to height ratio to draw this widget at within its space, if it should keep one.
+    pub aspect_ratio: Option<(u16, u16)>,
+
+    /// Whether this widget should start expanded.
+    pub expanded_on_startup: bool,
 }
 
 impl BottomWidget {
@@ -907,6 +927,12 @@ impl BottomWidget {
             parent_reflector: None,
             top_left_corner: None,
             bottom_right_corner: None,
+            hidden_columns: Vec::new(),
+            columns: Vec::new(),
+            paged: false,
+            update_rate: None,
+            aspect_ratio: None,
+            expanded_on_startup: false,
         }
     }
 
@@ -951,6 +977,36 @@ impl BottomWidget {
         self.parent_reflector = parent_reflector;
         self
     }
+
+    pub(crate) fn hidden_columns(mut self, hidden_columns: Vec<String>) -> Self {
+        self.hidden_columns = hidden_columns;
+        self
+    }
+
+    pub(crate) fn columns(mut self, columns: Vec<String>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub(crate) fn paged(mut self, paged: bool) -> Self {
+        self.paged = paged;
+        self
+    }
+
+    pub(crate) fn update_rate(mut self, update_rate: Option<u64>) -> Self {
+        self.update_rate = update_rate;
+        self
+    }
+
+    pub(crate) fn aspect_ratio(mut self, aspect_ratio: Option<(u16, u16)>) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    pub(crate) fn expanded_on_startup(mut self, expanded_on_startup: bool) -> Self {
+        self.expanded_on_startup = expanded_on_startup;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
@@ -984,6 +1040,29 @@ impl BottomWidgetType {
         matches!(self, Cpu | Net | Mem)
     }
 
+    /// Whether this widget does anything with scroll events. Scrolling over a widget that returns
+    /// false here is ignored, rather than each scroll handler having to check for itself.
+    pub fn accepts_scroll(&self) -> bool {
+        self.is_widget_table() || self.is_widget_graph()
+    }
+
+    /// Returns the key hints to show in the status bar while this widget is selected, not
+    /// including the general ones.
+    pub fn key_hints(&self) -> &'static [(&'static str, &'static str)] {
+        use BottomWidgetType::*;
+        match self {
+            Cpu | Mem | Net => &GRAPH_KEY_HINTS,
+            Proc => &PROCESS_KEY_HINTS,
+            ProcSearch => &SEARCH_KEY_HINTS,
+            ProcSort => &SORT_KEY_HINTS,
+            Temp => &TEMP_KEY_HINTS,
+            Disk => &DISK_KEY_HINTS,
+            Battery => &BATTERY_KEY_HINTS,
+            BasicMem => &BASIC_MEM_KEY_HINTS,
+            _ => &[],
+        }
+    }
+
     pub fn get_pretty_name(&self) -> &str {
         use BottomWidgetType::*;
         match self {
@@ -1080,3 +1159,35 @@ pub struct UsedWidgets {
     pub use_temp: bool,
     pub use_battery: bool,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_accepts_scroll() {
+        use BottomWidgetType::*;
+
+        for widget_type in [Cpu, Mem, Net, Proc, ProcSort, Temp, Disk, CpuLegend] {
+            assert!(
+                widget_type.accepts_scroll(),
+                "{widget_type:?} should scroll"
+            );
+        }
+
+        for widget_type in [
+            Empty,
+            ProcSearch,
+            BasicCpu,
+            BasicMem,
+            BasicNet,
+            BasicTables,
+            Battery,
+        ] {
+            assert!(
+                !widget_type.accepts_scroll(),
+                "{widget_type:?} should not scroll"
+            );
+        }
+    }
+}
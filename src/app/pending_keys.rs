@@ -0,0 +1,123 @@
+//! Buffering for multi-key bindings like `gg` and `dd`.
+
+use std::time::{Duration, Instant};
+
+use crate::constants::MAX_KEY_TIMEOUT_IN_MILLISECONDS;
+
+/// The multi-key sequences that [`PendingKeys`] can resolve.
+pub const KEY_SEQUENCES: [&str; 2] = ["gg", "dd"];
+
+/// Accumulates key presses that may be the start of a multi-key sequence, resolving them once a
+/// full sequence has been typed. A pending prefix is dropped if the next key takes too long.
+#[derive(Debug)]
+pub struct PendingKeys {
+    keys: String,
+    last_press: Instant,
+    timeout: Duration,
+}
+
+impl Default for PendingKeys {
+    fn default() -> Self {
+        Self {
+            keys: String::new(),
+            last_press: Instant::now(),
+            timeout: Duration::from_millis(MAX_KEY_TIMEOUT_IN_MILLISECONDS),
+        }
+    }
+}
+
+impl PendingKeys {
+    /// Records a key press at `now`, returning the sequence it completes, if any.
+    pub fn press(&mut self, key: char, now: Instant) -> Option<&'static str> {
+        if self.has_timed_out(now) {
+            self.keys.clear();
+        }
+        self.last_press = now;
+        self.keys.push(key);
+
+        if let Some(sequence) = KEY_SEQUENCES.iter().find(|s| **s == self.keys) {
+            self.keys.clear();
+            return Some(sequence);
+        }
+
+        if !Self::is_prefix(&self.keys) {
+            // Not going anywhere, but the key on its own might still start a new sequence.
+            self.keys.clear();
+            if Self::is_prefix(key.encode_utf8(&mut [0; 4])) {
+                self.keys.push(key);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the keys typed so far towards a sequence, or nothing if they've timed out.
+    pub fn pending(&self, now: Instant) -> &str {
+        if self.has_timed_out(now) {
+            ""
+        } else {
+            &self.keys
+        }
+    }
+
+    /// Drops any pending keys.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+
+    fn has_timed_out(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.last_press) > self.timeout
+    }
+
+    fn is_prefix(keys: &str) -> bool {
+        KEY_SEQUENCES
+            .iter()
+            .any(|sequence| sequence.len() > keys.len() && sequence.starts_with(keys))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gg() {
+        let mut keys = PendingKeys::default();
+        let now = Instant::now();
+
+        assert_eq!(keys.press('g', now), None);
+        assert_eq!(keys.pending(now), "g");
+        assert_eq!(keys.press('g', now), Some("gg"));
+        assert_eq!(keys.pending(now), "");
+
+        // Other keys in between break the sequence.
+        assert_eq!(keys.press('g', now), None);
+        assert_eq!(keys.press('j', now), None);
+        assert_eq!(keys.pending(now), "");
+        assert_eq!(keys.press('g', now), None);
+    }
+
+    #[test]
+    fn test_dd() {
+        let mut keys = PendingKeys::default();
+        let now = Instant::now();
+
+        // A `g` followed by a `d` starts a `dd` instead.
+        assert_eq!(keys.press('g', now), None);
+        assert_eq!(keys.press('d', now), None);
+        assert_eq!(keys.pending(now), "d");
+        assert_eq!(keys.press('d', now), Some("dd"));
+    }
+
+    #[test]
+    fn test_timed_out_sequence() {
+        let mut keys = PendingKeys::default();
+        let now = Instant::now();
+        let later = now + Duration::from_millis(MAX_KEY_TIMEOUT_IN_MILLISECONDS + 1);
+
+        assert_eq!(keys.press('g', now), None);
+        assert_eq!(keys.pending(later), "");
+        assert_eq!(keys.press('g', later), None);
+        assert_eq!(keys.pending(later), "g");
+    }
+}
@@ -12,6 +12,72 @@ use windows::Win32::{
 use crate::utils::error::BottomError;
 use crate::Pid;
 
+/// What happened when trying to kill a list of processes.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct KillResult {
+    /// The processes that were killed.
+    pub killed: Vec<Pid>,
+    /// The processes that couldn't be killed, and why.
+    pub failed: Vec<(Pid, String)>,
+}
+
+impl KillResult {
+    /// Returns a short summary, meant for the status bar.
+    pub fn summary(&self) -> String {
+        match (self.killed.as_slice(), self.failed.as_slice()) {
+            ([pid], []) => format!("Killed PID {pid}"),
+            (killed, []) => format!("Killed {} processes", killed.len()),
+            ([], [(pid, err)]) => format!("Failed to kill PID {pid}: {err}"),
+            (killed, failed) => format!(
+                "Killed {} of {} processes, failed on {}",
+                killed.len(),
+                killed.len() + failed.len(),
+                self.failed_pids()
+            ),
+        }
+    }
+
+    /// Returns why each process that couldn't be killed failed.
+    pub fn failure_details(&self) -> String {
+        self.failed
+            .iter()
+            .map(|(pid, err)| format!("PID {pid}: {err}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    fn failed_pids(&self) -> String {
+        let pids = self
+            .failed
+            .iter()
+            .map(|(pid, _)| pid.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if self.failed.len() == 1 {
+            format!("PID {pids}")
+        } else {
+            format!("PIDs {pids}")
+        }
+    }
+}
+
+/// Tries to kill each of `pids` with `kill`, carrying on past any that fail.
+pub fn kill_each<F>(pids: &[Pid], mut kill: F) -> KillResult
+where
+    F: FnMut(Pid) -> crate::utils::error::Result<()>,
+{
+    let mut result = KillResult::default();
+    for &pid in pids {
+        match kill(pid) {
+            Ok(()) => result.killed.push(pid),
+            Err(err) => result.failed.push((pid, err.to_string())),
+        }
+    }
+
+    result
+}
+
 /// Based from [this SO answer](https://stackoverflow.com/a/55231715).
 #[cfg(target_os = "windows")]
 struct Process(HANDLE);
@@ -88,3 +154,51 @@ pub fn kill_process_given_pid(pid: Pid, signal: usize) -> crate::utils::error::R
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::error::BottomError;
+
+    fn kill_odd_pids(pid: Pid) -> crate::utils::error::Result<()> {
+        if pid % 2 == 1 {
+            Ok(())
+        } else {
+            Err(BottomError::GenericError("permission denied".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_kill_each() {
+        let result = kill_each(&[1, 2, 3, 4], kill_odd_pids);
+
+        // Failures don't stop the remaining processes from being killed.
+        assert_eq!(result.killed, vec![1, 3]);
+        assert_eq!(result.failed.len(), 2);
+        assert_eq!(
+            result.summary(),
+            "Killed 2 of 4 processes, failed on PIDs 2, 4"
+        );
+        assert_eq!(
+            result.failure_details(),
+            "PID 2: Error, permission denied; PID 4: Error, permission denied"
+        );
+    }
+
+    #[test]
+    fn test_kill_summary() {
+        assert_eq!(kill_each(&[1], kill_odd_pids).summary(), "Killed PID 1");
+        assert_eq!(
+            kill_each(&[1, 3], kill_odd_pids).summary(),
+            "Killed 2 processes"
+        );
+        assert_eq!(
+            kill_each(&[2], kill_odd_pids).summary(),
+            "Failed to kill PID 2: Error, permission denied"
+        );
+        assert_eq!(
+            kill_each(&[1, 2], kill_odd_pids).summary(),
+            "Killed 1 of 2 processes, failed on PID 2"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Copies of the process list taken on demand, to compare the current processes against later.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Instant,
+};
+
+use crate::{
+    app::data_harvester::processes::ProcessHarvest, constants::MAX_PROCESS_SNAPSHOTS, Pid,
+};
+
+/// The processes running at some point in time.
+#[derive(Debug, Clone)]
+pub struct ProcessSnapshot {
+    /// The name given to the snapshot, or one like "Snapshot 3" if it wasn't given one.
+    pub name: String,
+    pub taken_at: Instant,
+    pub processes: BTreeMap<Pid, ProcessHarvest>,
+}
+
+/// The snapshots taken so far, oldest first. As each one holds a copy of the whole process list,
+/// only the latest [`MAX_PROCESS_SNAPSHOTS`] are kept.
+#[derive(Debug, Default)]
+pub struct ProcessSnapshots {
+    snapshots: VecDeque<ProcessSnapshot>,
+    num_taken: usize,
+}
+
+impl ProcessSnapshots {
+    /// Takes a snapshot of `processes` called `name`, dropping the oldest snapshot if there are too
+    /// many. A blank `name` falls back to [`ProcessSnapshots::default_name`].
+    pub fn take(
+        &mut self, name: &str, processes: &BTreeMap<Pid, ProcessHarvest>, taken_at: Instant,
+    ) -> &ProcessSnapshot {
+        if self.snapshots.len() >= MAX_PROCESS_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+
+        let name = match name.trim() {
+            "" => self.default_name(),
+            name => name.to_string(),
+        };
+        self.num_taken += 1;
+        self.snapshots.push_back(ProcessSnapshot {
+            name,
+            taken_at,
+            processes: processes.clone(),
+        });
+
+        &self.snapshots[self.snapshots.len() - 1]
+    }
+
+    /// The name the next snapshot gets if it isn't given one.
+    pub fn default_name(&self) -> String {
+        format!("Snapshot {}", self.num_taken + 1)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ProcessSnapshot> {
+        self.snapshots.get(index)
+    }
+
+    /// Drops the snapshot at `index`, returning it if it existed.
+    pub fn remove(&mut self, index: usize) -> Option<ProcessSnapshot> {
+        self.snapshots.remove(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_limit() {
+        let mut snapshots = ProcessSnapshots::default();
+        let now = Instant::now();
+
+        for _ in 0..MAX_PROCESS_SNAPSHOTS + 2 {
+            snapshots.take("", &BTreeMap::new(), now);
+        }
+
+        assert_eq!(snapshots.len(), MAX_PROCESS_SNAPSHOTS);
+        assert_eq!(snapshots.get(0).unwrap().name, "Snapshot 3");
+
+        // Names aren't reused after dropping a snapshot.
+        assert!(snapshots.remove(MAX_PROCESS_SNAPSHOTS - 1).is_some());
+        let name = &snapshots.take(" ", &BTreeMap::new(), now).name;
+        assert_eq!(name, &format!("Snapshot {}", MAX_PROCESS_SNAPSHOTS + 3));
+
+        // Given names are kept, and still count towards the default names.
+        let name = &snapshots
+            .take(" before the build ", &BTreeMap::new(), now)
+            .name;
+        assert_eq!(name, "before the build");
+        assert_eq!(
+            snapshots.default_name(),
+            format!("Snapshot {}", MAX_PROCESS_SNAPSHOTS + 5)
+        );
+    }
+}
@@ -35,8 +35,8 @@ const AND_LIST: [&str; 2] = ["and", "&&"];
 /// or quoted elements after splitting to treat as process names.
 /// Furthermore, we want to support boolean joiners like AND and OR, and brackets.
 pub fn parse_query(
-    search_query: &str, is_searching_whole_word: bool, is_ignoring_case: bool,
-    is_searching_with_regex: bool,
+    search_query: &str, is_searching_whole_word: bool, is_matching_word_boundary: bool,
+    is_ignoring_case: bool, is_searching_with_regex: bool,
 ) -> Result<Query> {
     fn process_string_to_filter(query: &mut VecDeque<String>) -> Result<Query> {
         let lhs = process_or(query)?;
@@ -484,6 +484,7 @@ pub fn parse_query(
     let mut process_filter = process_string_to_filter(&mut split_query)?;
     process_filter.process_regexes(
         is_searching_whole_word,
+        is_matching_word_boundary,
         is_ignoring_case,
         is_searching_with_regex,
     )?;
@@ -498,12 +499,13 @@ pub struct Query {
 
 impl Query {
     pub fn process_regexes(
-        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        &mut self, is_searching_whole_word: bool, is_matching_word_boundary: bool,
+        is_ignoring_case: bool, is_searching_with_regex: bool,
     ) -> Result<()> {
         for or in &mut self.query {
             or.process_regexes(
                 is_searching_whole_word,
+                is_matching_word_boundary,
                 is_ignoring_case,
                 is_searching_with_regex,
             )?;
@@ -517,6 +519,17 @@ impl Query {
             .iter()
             .all(|ok| ok.check(process, is_using_command))
     }
+
+    /// Checks a group of processes, where `group` has the combined values of `members`. Values
+    /// that are combined, like the CPU usage, are checked against the group, and the rest, like
+    /// the PID, match if they match any member.
+    pub fn check_group(
+        &self, group: &ProcessHarvest, members: &[&ProcessHarvest], is_using_command: bool,
+    ) -> bool {
+        self.query
+            .iter()
+            .all(|ok| ok.check_group(group, members, is_using_command))
+    }
 }
 
 impl Debug for Query {
@@ -533,17 +546,19 @@ pub struct Or {
 
 impl Or {
     pub fn process_regexes(
-        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        &mut self, is_searching_whole_word: bool, is_matching_word_boundary: bool,
+        is_ignoring_case: bool, is_searching_with_regex: bool,
     ) -> Result<()> {
         self.lhs.process_regexes(
             is_searching_whole_word,
+            is_matching_word_boundary,
             is_ignoring_case,
             is_searching_with_regex,
         )?;
         if let Some(rhs) = &mut self.rhs {
             rhs.process_regexes(
                 is_searching_whole_word,
+                is_matching_word_boundary,
                 is_ignoring_case,
                 is_searching_with_regex,
             )?;
@@ -559,6 +574,17 @@ impl Or {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    pub fn check_group(
+        &self, group: &ProcessHarvest, members: &[&ProcessHarvest], is_using_command: bool,
+    ) -> bool {
+        if let Some(rhs) = &self.rhs {
+            self.lhs.check_group(group, members, is_using_command)
+                || rhs.check_group(group, members, is_using_command)
+        } else {
+            self.lhs.check_group(group, members, is_using_command)
+        }
+    }
 }
 
 impl Debug for Or {
@@ -578,17 +604,19 @@ pub struct And {
 
 impl And {
     pub fn process_regexes(
-        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        &mut self, is_searching_whole_word: bool, is_matching_word_boundary: bool,
+        is_ignoring_case: bool, is_searching_with_regex: bool,
     ) -> Result<()> {
         self.lhs.process_regexes(
             is_searching_whole_word,
+            is_matching_word_boundary,
             is_ignoring_case,
             is_searching_with_regex,
         )?;
         if let Some(rhs) = &mut self.rhs {
             rhs.process_regexes(
                 is_searching_whole_word,
+                is_matching_word_boundary,
                 is_ignoring_case,
                 is_searching_with_regex,
             )?;
@@ -604,6 +632,17 @@ impl And {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    pub fn check_group(
+        &self, group: &ProcessHarvest, members: &[&ProcessHarvest], is_using_command: bool,
+    ) -> bool {
+        if let Some(rhs) = &self.rhs {
+            self.lhs.check_group(group, members, is_using_command)
+                && rhs.check_group(group, members, is_using_command)
+        } else {
+            self.lhs.check_group(group, members, is_using_command)
+        }
+    }
 }
 
 impl Debug for And {
@@ -668,12 +707,13 @@ pub struct Prefix {
 
 impl Prefix {
     pub fn process_regexes(
-        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        &mut self, is_searching_whole_word: bool, is_matching_word_boundary: bool,
+        is_ignoring_case: bool, is_searching_with_regex: bool,
     ) -> Result<()> {
         if let Some(or) = &mut self.or {
             return or.process_regexes(
                 is_searching_whole_word,
+                is_matching_word_boundary,
                 is_ignoring_case,
                 is_searching_with_regex,
             );
@@ -681,10 +721,20 @@ impl Prefix {
         {
             match prefix_type {
                 PrefixType::Pid | PrefixType::Name | PrefixType::State | PrefixType::User => {
+                    // Matching at word boundaries only needs the characters around a match to
+                    // not be part of a word, so "cat" matches "cat-food" but not "concatenate".
+                    let (start, end) = if is_searching_whole_word {
+                        ("^", "$")
+                    } else if is_matching_word_boundary {
+                        (r"(?:^|\W)(?:", r")(?:\W|$)")
+                    } else {
+                        ("", "")
+                    };
+
                     let escaped_regex: String;
                     let final_regex_string = &format!(
                         "{}{}{}{}",
-                        if is_searching_whole_word { "^" } else { "" },
+                        start,
                         if is_ignoring_case { "(?i)" } else { "" },
                         if !is_searching_with_regex {
                             escaped_regex = regex::escape(regex_string);
@@ -692,7 +742,7 @@ impl Prefix {
                         } else {
                             regex_string
                         },
-                        if is_searching_whole_word { "$" } else { "" },
+                        end,
                     );
 
                     let taken_pwc = self.regex_prefix.take();
@@ -806,6 +856,21 @@ impl Prefix {
             true
         }
     }
+
+    pub fn check_group(
+        &self, group: &ProcessHarvest, members: &[&ProcessHarvest], is_using_command: bool,
+    ) -> bool {
+        if let Some(or) = &self.or {
+            or.check_group(group, members, is_using_command)
+        } else if self.regex_prefix.is_some() {
+            // Names, PIDs, states, and users aren't combined, so they are checked per member.
+            members
+                .iter()
+                .any(|process| self.check(process, is_using_command))
+        } else {
+            self.check(group, is_using_command)
+        }
+    }
 }
 
 impl Debug for Prefix {
@@ -854,3 +919,32 @@ pub struct TimeQuery {
     pub condition: QueryComparison,
     pub duration: Duration,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matches(query: &str, is_matching_word_boundary: bool, name: &str) -> bool {
+        let process = ProcessHarvest {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        parse_query(query, false, is_matching_word_boundary, true, false)
+            .unwrap()
+            .check(&process, false)
+    }
+
+    #[test]
+    fn test_word_boundary() {
+        assert!(matches("cat", false, "concatenate"));
+        assert!(!matches("cat", true, "concatenate"));
+        assert!(!matches("cat", true, "cats"));
+
+        assert!(matches("cat", true, "cat"));
+        assert!(matches("cat", true, "cat-food"));
+        assert!(matches("cat", true, "/usr/bin/cat"));
+        assert!(matches("CAT", true, "my cat"));
+        assert!(matches("my cat", true, "my cat --help"));
+    }
+}
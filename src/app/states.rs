@@ -5,13 +5,15 @@ use indexmap::IndexMap;
 use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete, UnicodeSegmentation};
 
 use crate::{
-    app::{layout_manager::BottomWidgetType, query::*},
+    app::{layout_manager::BottomWidgetType, query::*, AppConfigFields},
+    canvas::canvas_styling::CanvasStyling,
     constants,
     utils::gen_util::str_width,
     widgets::{
         BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState, NetWidgetState,
         ProcWidgetState, TempWidgetState,
     },
+    Pid,
 };
 
 pub struct AppWidgetStates {
@@ -25,6 +27,66 @@ pub struct AppWidgetStates {
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
 }
 
+impl AppWidgetStates {
+    /// Restyles every widget that keeps its own copy of the colours, e.g. after switching colour
+    /// schemes.
+    pub fn set_colours(&mut self, colours: &CanvasStyling) {
+        self.cpu_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_colours(colours));
+        self.proc_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_colours(colours));
+        self.temp_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_colours(colours));
+        self.disk_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_colours(colours));
+    }
+
+    /// Brings every table's settings, like the gap under the header, in line with `config`, e.g.
+    /// after reloading the config file.
+    pub fn set_table_settings(&mut self, config: &AppConfigFields) {
+        self.cpu_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_table_settings(config));
+        self.proc_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_table_settings(config));
+        self.temp_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_table_settings(config));
+        self.disk_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.set_table_settings(config));
+    }
+
+    /// Returns every graph to showing the latest data, e.g. after unfreezing.
+    pub fn clear_time_cursors(&mut self) {
+        self.cpu_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.time_cursor = None);
+        self.mem_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.time_cursor = None);
+        self.net_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| state.time_cursor = None);
+    }
+}
+
 #[derive(Debug)]
 pub enum CursorDirection {
     Left,
@@ -48,6 +110,30 @@ impl Default for KillSignal {
     }
 }
 
+/// The processes the kill dialog was opened for. This is captured when the dialog opens, so the
+/// dialog keeps describing what will be killed even if the table changes while it is open.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProcessesToKill {
+    /// The selected row. If processes are grouped, this is every process with its name.
+    Selected {
+        name: String,
+        pids: Vec<Pid>,
+        is_grouped: bool,
+    },
+    /// The marked rows, as the PID and name of each process.
+    Marked(Vec<(Pid, String)>),
+}
+
+impl ProcessesToKill {
+    /// The PIDs to kill.
+    pub fn pids(&self) -> Vec<Pid> {
+        match self {
+            ProcessesToKill::Selected { pids, .. } => pids.clone(),
+            ProcessesToKill::Marked(processes) => processes.iter().map(|(pid, _)| *pid).collect(),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct AppDeleteDialogState {
     pub is_showing_dd: bool,
@@ -64,6 +150,10 @@ pub struct AppHelpDialogState {
     pub height: u16,
     pub scroll_state: ParagraphScrollState,
     pub index_shortcuts: Vec<u16>,
+    /// Whether a search query is being typed in.
+    pub is_searching: bool,
+    /// Only lines containing this are shown, ignoring case. Empty shows everything.
+    pub query: String,
 }
 
 impl Default for AppHelpDialogState {
@@ -73,6 +163,8 @@ impl Default for AppHelpDialogState {
             height: 0,
             scroll_state: ParagraphScrollState::default(),
             index_shortcuts: vec![0; constants::HELP_TEXT.len()],
+            is_searching: false,
+            query: String::new(),
         }
     }
 }
@@ -0,0 +1,214 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use hashbrown::HashMap;
+
+use super::layout_manager::BottomWidget;
+
+/// How many of the most recent measurements are used for the rolling average.
+const ROLLING_WINDOW: usize = 100;
+
+/// What a measurement in [`Timings`] is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TimingKind {
+    /// Laying out a frame, which is the time spent drawing a frame that isn't spent drawing widgets.
+    Layout,
+    /// A full call to update the data of all widgets.
+    UpdateData,
+    /// Updating the data of a widget with the given ID.
+    Update(u64),
+    /// Drawing the widget with the given ID.
+    Draw(u64),
+}
+
+/// Statistics for one [`TimingKind`].
+#[derive(Debug, Default)]
+pub struct TimingStats {
+    count: u64,
+    max: Duration,
+    recent: VecDeque<Duration>,
+}
+
+impl TimingStats {
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.max = self.max.max(elapsed);
+
+        if self.recent.len() == ROLLING_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(elapsed);
+    }
+
+    /// The number of measurements taken.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The longest measurement taken.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// The average of the most recent measurements.
+    pub fn rolling_average(&self) -> Duration {
+        if self.recent.is_empty() {
+            Duration::ZERO
+        } else {
+            self.recent.iter().sum::<Duration>() / self.recent.len() as u32
+        }
+    }
+}
+
+/// Wall time measurements of drawing and updating data, used to track down slow widgets. This is only
+/// created if timings were enabled, so callers should check for that first before measuring anything.
+#[derive(Debug, Default)]
+pub struct Timings {
+    stats: HashMap<TimingKind, TimingStats>,
+    frame_draw_time: Duration,
+    last_frame_time: Option<Duration>,
+}
+
+impl Timings {
+    /// Records a measurement for `kind` that started at `start`.
+    pub fn record_since(&mut self, kind: TimingKind, start: Instant) {
+        self.record(kind, start.elapsed());
+    }
+
+    /// Records a measurement of `elapsed` for `kind`.
+    pub fn record(&mut self, kind: TimingKind, elapsed: Duration) {
+        if let TimingKind::Draw(_) = kind {
+            self.frame_draw_time += elapsed;
+        }
+
+        self.stats.entry(kind).or_default().record(elapsed);
+    }
+
+    /// Finishes a frame that took `elapsed` in total. The time not spent drawing widgets is recorded
+    /// as the layout time.
+    pub fn finish_frame(&mut self, elapsed: Duration) {
+        let layout_time = elapsed.saturating_sub(self.frame_draw_time);
+        self.frame_draw_time = Duration::ZERO;
+        self.last_frame_time = Some(elapsed);
+
+        self.record(TimingKind::Layout, layout_time);
+    }
+
+    /// Returns how long the last frame took in total, if a frame was finished.
+    pub fn last_frame_time(&self) -> Option<Duration> {
+        self.last_frame_time
+    }
+
+    /// Returns the statistics for a [`TimingKind`], if it was ever measured.
+    pub fn get(&self, kind: TimingKind) -> Option<&TimingStats> {
+        self.stats.get(&kind)
+    }
+
+    /// Generates a readable report of all measurements, using `widget_map` to name widgets.
+    pub fn report(&self, widget_map: &HashMap<u64, BottomWidget>) -> String {
+        let widget_name = |widget_id: &u64| {
+            let name = match widget_map.get(widget_id) {
+                Some(widget) => match widget.widget_type.get_pretty_name() {
+                    "" => format!("{:?}", widget.widget_type),
+                    name => name.to_string(),
+                },
+                None => "Unknown".to_string(),
+            };
+
+            format!("{name} ({widget_id})")
+        };
+
+        let mut kinds = self.stats.keys().collect::<Vec<_>>();
+        kinds.sort();
+
+        let mut report = format!(
+            "{:<32} {:>8} {:>12} {:>12}\n",
+            "Timing", "Count", "Average", "Max"
+        );
+        for kind in kinds {
+            let stats = &self.stats[kind];
+            let label = match kind {
+                TimingKind::Layout => "Layout".to_string(),
+                TimingKind::UpdateData => "Update data".to_string(),
+                TimingKind::Update(widget_id) => format!("Update {}", widget_name(widget_id)),
+                TimingKind::Draw(widget_id) => format!("Draw {}", widget_name(widget_id)),
+            };
+
+            report.push_str(&format!(
+                "{:<32} {:>8} {:>12} {:>12}\n",
+                label,
+                stats.count(),
+                format!("{:.2?}", stats.rolling_average()),
+                format!("{:.2?}", stats.max()),
+            ));
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::layout_manager::BottomWidgetType;
+
+    #[test]
+    fn test_timing_stats() {
+        let mut timings = Timings::default();
+        assert_eq!(timings.last_frame_time(), None);
+
+        timings.record(TimingKind::Draw(1), Duration::from_millis(2));
+        timings.record(TimingKind::Draw(1), Duration::from_millis(4));
+        timings.finish_frame(Duration::from_millis(10));
+        assert_eq!(timings.last_frame_time(), Some(Duration::from_millis(10)));
+
+        let draw = timings.get(TimingKind::Draw(1)).unwrap();
+        assert_eq!(draw.count(), 2);
+        assert_eq!(draw.max(), Duration::from_millis(4));
+        assert_eq!(draw.rolling_average(), Duration::from_millis(3));
+
+        let layout = timings.get(TimingKind::Layout).unwrap();
+        assert_eq!(layout.count(), 1);
+        assert_eq!(layout.max(), Duration::from_millis(4));
+
+        assert!(timings.get(TimingKind::UpdateData).is_none());
+    }
+
+    #[test]
+    fn test_rolling_average_window() {
+        let mut timings = Timings::default();
+
+        timings.record(TimingKind::UpdateData, Duration::from_secs(1));
+        for _ in 0..ROLLING_WINDOW {
+            timings.record(TimingKind::UpdateData, Duration::from_millis(1));
+        }
+
+        let stats = timings.get(TimingKind::UpdateData).unwrap();
+        assert_eq!(stats.count(), ROLLING_WINDOW as u64 + 1);
+        assert_eq!(stats.max(), Duration::from_secs(1));
+        assert_eq!(stats.rolling_average(), Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_report_names_widgets() {
+        let mut timings = Timings::default();
+        timings.record(TimingKind::Draw(3), Duration::from_millis(1));
+        timings.record(TimingKind::Draw(4), Duration::from_millis(1));
+
+        let mut widget_map = HashMap::new();
+        widget_map.insert(
+            3,
+            BottomWidget {
+                widget_type: BottomWidgetType::Proc,
+                widget_id: 3,
+                ..Default::default()
+            },
+        );
+
+        let report = timings.report(&widget_map);
+        assert!(report.contains("Draw Processes (3)"));
+        assert!(report.contains("Draw Unknown (4)"));
+    }
+}
@@ -0,0 +1,208 @@
+//! Per-widget update rates, for widgets in the layout that set their own `rate`.
+//!
+//! Every source of data is still collected on the global update rate's tick, but a source is only
+//! collected on a tick if one of the widgets using it is due, and each widget is only given new
+//! data once its own rate has passed.
+
+use std::time::{Duration, Instant};
+
+use hashbrown::{HashMap, HashSet};
+
+use super::layout_manager::{BottomWidget, BottomWidgetType};
+
+/// A source of data that can be collected separately from the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataSource {
+    Cpu,
+    Mem,
+    Net,
+    Proc,
+    Disk,
+    Temp,
+    Battery,
+}
+
+impl DataSource {
+    /// Returns the source of the data that a widget of `widget_type` shows, if it shows any.
+    pub fn for_widget(widget_type: &BottomWidgetType) -> Option<Self> {
+        use BottomWidgetType::*;
+
+        match widget_type {
+            Cpu | BasicCpu => Some(DataSource::Cpu),
+            Mem | BasicMem => Some(DataSource::Mem),
+            Net | BasicNet => Some(DataSource::Net),
+            Proc => Some(DataSource::Proc),
+            Disk => Some(DataSource::Disk),
+            Temp => Some(DataSource::Temp),
+            Battery => Some(DataSource::Battery),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the rate `widget` wants its data updated at in milliseconds, if it set one. This is
+/// never faster than `update_rate`, as nothing is collected more often than that.
+fn widget_rate(widget: &BottomWidget, update_rate: u64) -> Option<u64> {
+    widget.update_rate.map(|rate| rate.max(update_rate))
+}
+
+/// How often each [`DataSource`] should be collected, in milliseconds.
+///
+/// A source only gets a rate if every widget using it set one, in which case it is the fastest of
+/// those rates. Any other source is collected on every update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectionRates {
+    rates: HashMap<DataSource, u64>,
+}
+
+impl CollectionRates {
+    /// Works out the rate of each source from the widgets using it, where `update_rate` is the
+    /// global update rate.
+    pub fn new<'a>(widgets: impl IntoIterator<Item = &'a BottomWidget>, update_rate: u64) -> Self {
+        let mut source_rates: HashMap<DataSource, Option<u64>> = HashMap::new();
+
+        for widget in widgets {
+            if let Some(source) = DataSource::for_widget(&widget.widget_type) {
+                let rate = widget_rate(widget, update_rate);
+                let source_rate = source_rates.entry(source).or_insert(rate);
+                *source_rate = match (*source_rate, rate) {
+                    (Some(source_rate), Some(rate)) => Some(source_rate.min(rate)),
+                    _ => None,
+                };
+            }
+        }
+
+        Self {
+            rates: source_rates
+                .into_iter()
+                .filter_map(|(source, rate)| Some((source, rate?)))
+                .collect(),
+        }
+    }
+
+    /// Returns how often `source` should be collected in milliseconds, or [`None`] if it should be
+    /// collected on every update.
+    pub fn get(&self, source: DataSource) -> Option<u64> {
+        self.rates.get(&source).copied()
+    }
+
+    /// Returns whether `source`, which was last collected at `last_collected`, is due to be
+    /// collected again at `now`.
+    pub fn is_due(
+        &self, source: DataSource, last_collected: Option<Instant>, now: Instant,
+    ) -> bool {
+        match (self.get(source), last_collected) {
+            (Some(rate), Some(last_collected)) => {
+                now.saturating_duration_since(last_collected) >= Duration::from_millis(rate)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Keeps track of when each widget that set its own rate was last given new data.
+#[derive(Debug, Default)]
+pub struct WidgetUpdateSchedule {
+    last_updated: HashMap<u64, Instant>,
+}
+
+impl WidgetUpdateSchedule {
+    /// Returns the IDs of the widgets in `widgets` that should be given the data collected at
+    /// `now`, and counts them as updated. Widgets that didn't set a rate are always included.
+    pub fn take_due<'a>(
+        &mut self, widgets: impl IntoIterator<Item = &'a BottomWidget>, update_rate: u64,
+        now: Instant,
+    ) -> HashSet<u64> {
+        widgets
+            .into_iter()
+            .filter(|widget| match widget_rate(widget, update_rate) {
+                Some(rate) => {
+                    let is_due = match self.last_updated.get(&widget.widget_id) {
+                        Some(last) => {
+                            now.saturating_duration_since(*last) >= Duration::from_millis(rate)
+                        }
+                        None => true,
+                    };
+                    if is_due {
+                        self.last_updated.insert(widget.widget_id, now);
+                    }
+                    is_due
+                }
+                None => true,
+            })
+            .map(|widget| widget.widget_id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn widget(widget_type: BottomWidgetType, widget_id: u64, rate: Option<u64>) -> BottomWidget {
+        BottomWidget::new(widget_type, widget_id).update_rate(rate)
+    }
+
+    #[test]
+    fn test_collection_rates() {
+        let widgets = [
+            widget(BottomWidgetType::Cpu, 1, None),
+            widget(BottomWidgetType::CpuLegend, 2, None),
+            widget(BottomWidgetType::Temp, 3, Some(5000)),
+            widget(BottomWidgetType::Disk, 4, Some(5000)),
+            widget(BottomWidgetType::Disk, 5, Some(2000)),
+            widget(BottomWidgetType::Proc, 6, Some(3000)),
+            widget(BottomWidgetType::Proc, 7, None),
+            widget(BottomWidgetType::Net, 8, Some(100)),
+        ];
+        let rates = CollectionRates::new(&widgets, 1000);
+
+        assert_eq!(rates.get(DataSource::Cpu), None);
+        assert_eq!(rates.get(DataSource::Temp), Some(5000));
+        assert_eq!(rates.get(DataSource::Disk), Some(2000));
+        assert_eq!(rates.get(DataSource::Proc), None);
+        assert_eq!(rates.get(DataSource::Net), Some(1000));
+        assert_eq!(rates.get(DataSource::Mem), None);
+
+        let start = Instant::now();
+        assert!(rates.is_due(DataSource::Temp, None, start));
+        assert!(!rates.is_due(
+            DataSource::Temp,
+            Some(start),
+            start + Duration::from_millis(4999)
+        ));
+        assert!(rates.is_due(
+            DataSource::Temp,
+            Some(start),
+            start + Duration::from_millis(5000)
+        ));
+        assert!(rates.is_due(DataSource::Cpu, Some(start), start));
+    }
+
+    #[test]
+    fn test_widget_update_schedule() {
+        let widgets = [
+            widget(BottomWidgetType::Cpu, 1, None),
+            widget(BottomWidgetType::Temp, 2, Some(5000)),
+            widget(BottomWidgetType::Proc, 3, Some(100)),
+        ];
+        let mut schedule = WidgetUpdateSchedule::default();
+        let start = Instant::now();
+        let after = |millis| start + Duration::from_millis(millis);
+
+        let due = |schedule: &mut WidgetUpdateSchedule, now| {
+            let mut due = schedule
+                .take_due(&widgets, 1000, now)
+                .into_iter()
+                .collect::<Vec<_>>();
+            due.sort_unstable();
+            due
+        };
+
+        assert_eq!(due(&mut schedule, start), [1, 2, 3]);
+        assert_eq!(due(&mut schedule, after(1000)), [1, 3]);
+        assert_eq!(due(&mut schedule, after(2000)), [1, 3]);
+        assert_eq!(due(&mut schedule, after(5000)), [1, 2, 3]);
+        assert_eq!(due(&mut schedule, after(6000)), [1, 3]);
+    }
+}
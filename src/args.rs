@@ -157,8 +157,18 @@ pub fn build_app() -> Command {
     let disable_click = Arg::new("disable_click")
         .long("disable_click")
         .action(ArgAction::SetTrue)
-        .help("Disables mouse clicks.")
-        .long_help("Disables mouse clicks from interacting with the program.");
+        .help("Disables the mouse.")
+        .long_help("Disables mouse clicks and scrolling from interacting with the program. The mouse is not captured at all, which can help in terminals where mouse reporting misbehaves.");
+
+    let debug_timings = Arg::new("debug_timings")
+        .long("debug_timings")
+        .action(ArgAction::SetTrue)
+        .help("Records draw and update timings, and prints them on exit.")
+        .long_help(
+            "Records how long drawing and updating the data of each widget takes, and prints \
+            a summary once the program exits, along with how much memory the stored data takes \
+            up. Useful for tracking down slow widgets.",
+        );
 
     let dot_marker = Arg::new("dot_marker")
         .short('m')
@@ -225,6 +235,35 @@ pub fn build_app() -> Command {
         .help("Shows the scroll position tracker in table widgets.")
         .long_help("Shows the list scroll position tracker in the widget title for table widgets.");
 
+    let scroll_step = Arg::new("scroll_step")
+        .long("scroll_step")
+        .action(ArgAction::Set)
+        .value_name("INT")
+        .help("The number of entries to scroll tables by per mouse scroll.")
+        .long_help(
+            "The number of entries to move the selection in table widgets by per mouse scroll. \
+            Must be at least 1, and defaults to 1. Steps above 1000 are capped at 1000.",
+        );
+
+    let min_terminal_size = Arg::new("min_terminal_size")
+        .long("min_terminal_size")
+        .action(ArgAction::Set)
+        .value_name("WxH")
+        .help("The smallest terminal size to draw widgets in.")
+        .long_help(
+            "The smallest terminal size to draw widgets in, as WIDTHxHEIGHT (e.g. 40x10). A smaller \
+            terminal shows a warning instead of the widgets. Defaults to 0x0, which turns this off.",
+        );
+
+    let wrap_navigation = Arg::new("wrap_navigation")
+        .long("wrap_navigation")
+        .action(ArgAction::SetTrue)
+        .help("Wraps table selection around at the first and last entries.")
+        .long_help(
+            "Moving the selection up past the first entry of a table widget goes to the last \
+            entry, and moving it down past the last entry goes to the first.",
+        );
+
     let use_old_network_legend = Arg::new("use_old_network_legend")
         .long("use_old_network_legend")
         .action(ArgAction::SetTrue)
@@ -304,7 +343,16 @@ Defaults to \"default\".
         .value_name("TIME")
         .help("Default time value for graphs.")
         .long_help(
-            "Default time value for graphs. Takes a number in milliseconds or a human duration (e.g. 60s). The minimum time is 30s, and the default is 60s.",
+            "Default time value for graphs. Takes a number in milliseconds or a human duration (e.g. 60s). It must be at least the minimum time value, and the default is 60s.",
+        );
+
+    let min_time_value = Arg::new("min_time_value")
+        .long("min_time_value")
+        .action(ArgAction::Set)
+        .value_name("TIME")
+        .help("The smallest time value graphs can be zoomed in to.")
+        .long_help(
+            "The smallest time value graphs can be zoomed in to. Takes a number in milliseconds or a human duration (e.g. 30s). The minimum is 1s, and the default is 30s.",
         );
 
     // TODO: Charts are broken in the manpage
@@ -341,6 +389,13 @@ use CPU (3) as the default instead.
         .help("Sets the default widget type, use --help for info.")
         .long_help(DEFAULT_WIDGET_TYPE_STR);
 
+    let layout = Arg::new("layout")
+        .long("layout")
+        .action(ArgAction::Set)
+        .value_name("NAME")
+        .help("Sets the layout to start with.")
+        .long_help("Sets the named layout from the config file's [[layouts]] to start with. Layouts can also be switched between while running with \"O\".");
+
     let expanded_on_startup = Arg::new("expanded_on_startup")
         .short('e')
         .long("expanded")
@@ -354,7 +409,7 @@ use CPU (3) as the default instead.
         .action(ArgAction::Set)
         .value_name("TIME")
         .help("Sets the data refresh rate.")
-        .long_help("Sets the data refresh rate. Takes a number in milliseconds or a human duration (e.g. 5s). The minimum is 250ms, and defaults to 1000ms. Smaller values may take more computer resources.");
+        .long_help("Sets the data refresh rate. Takes a number in milliseconds or a human duration (e.g. 5s). The minimum is 100ms, the maximum is 60s, and defaults to 1000ms. Smaller values may take more computer resources.");
 
     let time_delta = Arg::new("time_delta")
         .short('d')
@@ -362,7 +417,19 @@ use CPU (3) as the default instead.
         .action(ArgAction::Set)
         .value_name("TIME")
         .help("The amount of time changed upon zooming.")
-        .long_help("The amount of time changed when zooming in/out. Takes a number in milliseconds or a human duration (e.g. 30s). The minimum is 1s, and defaults to 15s.");
+        .long_help("The amount of time changed when zooming in/out. Takes a number in milliseconds or a human duration (e.g. 30s). The minimum is 1s. If not set, zooming halves or doubles the time shown.");
+
+    let config_save_dry_run = Arg::new("config_save_dry_run")
+        .long("config_save_dry_run")
+        .action(ArgAction::SetTrue)
+        .help("Logs changes instead of saving the config file.")
+        .long_help("When saving the current settings to the config file, logs what would change instead of writing the file.");
+
+    let zoom_all_graphs = Arg::new("zoom_all_graphs")
+        .long("zoom_all_graphs")
+        .action(ArgAction::SetTrue)
+        .help("Zooms every graph together.")
+        .long_help("Zooming in, out, or resetting the zoom on one graph does the same to every other graph.");
 
     let tree = Arg::new("tree")
         .short('T')
@@ -391,6 +458,14 @@ use CPU (3) as the default instead.
             "Displays the network widget with binary prefixes (i.e. kibibits, mebibits) rather than a decimal prefix (i.e. kilobits, megabits). Defaults to decimal prefixes.",
         );
 
+    let decimal_bytes = Arg::new("decimal_bytes")
+        .long("decimal_bytes")
+        .action(ArgAction::SetTrue)
+        .help("Displays sizes in tables with decimal prefixes.")
+        .long_help(
+            "Displays sizes and rates in the process and disk widgets with decimal prefixes (i.e. KB, MB) rather than binary prefixes (i.e. KiB, MiB). Defaults to binary prefixes.",
+        );
+
     let retention = Arg::new("retention")
         .long("retention")
         .action(ArgAction::Set)
@@ -398,6 +473,13 @@ use CPU (3) as the default instead.
         .help("The timespan of data stored.")
         .long_help("How much data is stored at once in terms of time. Takes a number in milliseconds or a human duration (e.g. 20m), with a minimum of 1 minute. Note higher values will take up more memory. Defaults to 10 minutes.");
 
+    let max_data_memory = Arg::new("max_data_memory")
+        .long("max_data_memory")
+        .action(ArgAction::Set)
+        .value_name("BYTES")
+        .help("The most memory the stored data may take up.")
+        .long_help("The most memory the data stored for widgets may take up, as a number of bytes or a size (e.g. 64MiB). Once it is reached, the oldest graph data is dropped first, though the latest data is always kept. Defaults to no limit.");
+
     let version = Arg::new("version")
         .short('V')
         .long("version")
@@ -425,11 +507,14 @@ use CPU (3) as the default instead.
         case_sensitive,
         process_command,
         config_location,
+        config_save_dry_run,
         color,
         mem_as_value,
         default_time_value,
+        min_time_value,
         default_widget_count,
         default_widget_type,
+        debug_timings,
         disable_click,
         dot_marker,
         group,
@@ -437,7 +522,10 @@ use CPU (3) as the default instead.
         hide_table_gap,
         hide_time,
         show_table_scroll_position,
+        scroll_step,
+        min_terminal_size,
         left_legend,
+        layout,
         disable_advanced_kill,
         rate,
         regex,
@@ -446,11 +534,15 @@ use CPU (3) as the default instead.
         network_use_bytes,
         network_use_log,
         network_use_binary_prefix,
+        decimal_bytes,
         current_usage,
         unnormalized_cpu,
         use_old_network_legend,
         whole_word,
+        wrap_navigation,
+        zoom_all_graphs,
         retention,
+        max_data_memory,
         expanded_on_startup,
         #[cfg(feature = "battery")]
         {
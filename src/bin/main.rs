@@ -22,8 +22,9 @@ use tui::{backend::CrosstermBackend, Terminal};
 
 use bottom::{
     canvas::{self, canvas_styling::CanvasStyling},
+    constants::TICK_RATE_IN_MILLISECONDS,
     data_conversion::*,
-    options::*,
+    options::{config_watcher::ConfigWatcher, *},
     *,
 };
 
@@ -46,13 +47,22 @@ fn main() -> Result<()> {
     }
 
     // Read from config file.
-    let config = {
-        let config_path = read_config(matches.get_one::<String>("config_location"))
-            .context("Unable to access the given config file location.")?;
-
-        create_or_get_config(&config_path)
-            .context("Unable to properly parse or create the config file.")?
+    let config_path = read_config(matches.get_one::<String>("config_location"))
+        .context("Unable to access the given config file location.")?;
+    let config_location = match &config_path {
+        Some(path) => format!("the config file at {}", path.display()),
+        None => "the default config".to_string(),
     };
+    let config = create_or_get_config(&config_path)
+        .with_context(|| format!("Unable to properly parse or create {config_location}."))?;
+
+    // Watch the config file for changes, so they can be applied while running.
+    let mut config_watcher = ConfigWatcher::new(config_path, matches.clone(), &config);
+
+    // Use the rows of the layout profile to start with, if one was chosen.
+    let layout_profile = get_layout_profile(&matches, &config);
+    let config = apply_layout_profile(config, layout_profile.as_deref())
+        .context("Found an issue while trying to pick the layout.")?;
 
     // Get widget layout separately
     let (widget_layout, default_widget_id, default_widget_type_option) =
@@ -62,7 +72,8 @@ fn main() -> Result<()> {
     // FIXME: Should move this into build app or config
     let styling = {
         let colour_scheme = get_color_scheme(&matches, &config)?;
-        CanvasStyling::new(colour_scheme, &config)?
+        CanvasStyling::new(colour_scheme, &config)
+            .with_context(|| format!("Found an issue with the colours from {config_location}."))?
     };
 
     // Create an "app" struct, which will control most of the program and store settings/state
@@ -73,7 +84,8 @@ fn main() -> Result<()> {
         default_widget_id,
         &default_widget_type_option,
         &styling,
-    )?;
+    )
+    .with_context(|| format!("Found an issue with the settings from {config_location}."))?;
 
     // Create painter and set colours.
     let mut painter = canvas::Painter::init(widget_layout, styling)?;
@@ -90,14 +102,16 @@ fn main() -> Result<()> {
 
     // Set up the event loop thread; we set this up early to speed up first-time-to-data.
     let (collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
-    let _collection_thread = create_collection_thread(
-        sender.clone(),
+    let latest_data = LatestData::default();
+    let collection_thread = create_collection_thread(
+        DataSender::new(sender.clone(), latest_data.clone()),
         collection_thread_ctrl_receiver,
         termination_lock.clone(),
         termination_cvar.clone(),
         &app.app_config_fields,
         app.filters.clone(),
         app.used_widgets,
+        app.collection_rates.clone(),
     );
 
     // Set up the input handling loop thread.
@@ -130,12 +144,10 @@ fn main() -> Result<()> {
 
     // Set up tui and crossterm
     let mut stdout_val = stdout();
-    execute!(
-        stdout_val,
-        EnterAlternateScreen,
-        EnableMouseCapture,
-        EnableBracketedPaste
-    )?;
+    execute!(stdout_val, EnterAlternateScreen, EnableBracketedPaste)?;
+    if !app.app_config_fields.disable_click {
+        execute!(stdout_val, EnableMouseCapture)?;
+    }
     enable_raw_mode()?;
 
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout_val))?;
@@ -167,7 +179,9 @@ fn main() -> Result<()> {
     try_drawing(&mut terminal, &mut app, &mut painter)?;
 
     loop {
-        if let Ok(recv) = receiver.recv() {
+        // Wait for at most a tick, so things that change with time alone, like status messages
+        // expiring, are still redrawn when nothing else happens.
+        if let Ok(recv) = receiver.recv_timeout(Duration::from_millis(TICK_RATE_IN_MILLISECONDS)) {
             match recv {
                 BottomEvent::Terminate => {
                     break;
@@ -179,6 +193,16 @@ fn main() -> Result<()> {
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
+                    if app.take_config_reload_request() {
+                        apply_config_reload(&mut app, config_watcher.reload());
+                    }
+                    if app.take_config_save_request() {
+                        let result = config_watcher.save(
+                            &app.saved_settings(),
+                            app.app_config_fields.config_save_dry_run,
+                        );
+                        app.on_config_saved(result);
+                    }
                     update_data(&mut app);
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
@@ -192,8 +216,21 @@ fn main() -> Result<()> {
                     update_data(&mut app);
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
-                BottomEvent::Update(data) => {
+                BottomEvent::Update => {
+                    let Some(data) = latest_data.take() else {
+                        continue;
+                    };
                     app.data_collection.eat_data(data);
+                    app.enforce_data_budget();
+                    let due_widgets =
+                        app.take_widgets_due_for_update(app.data_collection.current_instant);
+
+                    if let Some(reloaded) = config_watcher.poll() {
+                        apply_config_reload(&mut app, reloaded);
+                        if app.frozen_state.is_frozen() {
+                            try_drawing(&mut terminal, &mut app, &mut painter)?;
+                        }
+                    }
 
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
@@ -231,20 +268,23 @@ fn main() -> Result<()> {
                         if app.used_widgets.use_disk {
                             app.converted_data.ingest_disk_data(&app.data_collection);
 
-                            for disk in app.states.disk_state.widget_states.values_mut() {
-                                disk.force_data_update();
+                            for (widget_id, disk) in app.states.disk_state.widget_states.iter_mut()
+                            {
+                                if due_widgets.contains(widget_id) {
+                                    disk.force_data_update();
+                                }
                             }
                         }
 
                         // Temperatures
                         if app.used_widgets.use_temp {
-                            app.converted_data.ingest_temp_data(
-                                &app.data_collection,
-                                app.app_config_fields.temperature_type,
-                            );
+                            app.converted_data.ingest_temp_data(&app.data_collection);
 
-                            for temp in app.states.temp_state.widget_states.values_mut() {
-                                temp.force_data_update();
+                            for (widget_id, temp) in app.states.temp_state.widget_states.iter_mut()
+                            {
+                                if due_widgets.contains(widget_id) {
+                                    temp.force_data_update();
+                                }
                             }
                         }
 
@@ -295,8 +335,11 @@ fn main() -> Result<()> {
 
                         // Processes
                         if app.used_widgets.use_proc {
-                            for proc in app.states.proc_state.widget_states.values_mut() {
-                                proc.force_data_update();
+                            for (widget_id, proc) in app.states.proc_state.widget_states.iter_mut()
+                            {
+                                if due_widgets.contains(widget_id) {
+                                    proc.force_data_update();
+                                }
                             }
                         }
 
@@ -318,6 +361,43 @@ fn main() -> Result<()> {
                         .clean_data(app.app_config_fields.retention_ms);
                 }
             }
+
+            if let Some(update_rate) = app.take_update_rate_change() {
+                if collection_thread_ctrl_sender
+                    .send(CollectionThreadEvent::UpdateRate(update_rate))
+                    .is_err()
+                {
+                    break;
+                }
+                wake_collection_thread(&termination_lock, &termination_cvar);
+            }
+
+            // Either picked from the layout switcher, or a reloaded config changed the layout.
+            if let Some(name) = app.take_layout_switch_request() {
+                if let Some(new_painter) =
+                    switch_layout_profile(&mut app, &mut config_watcher, &name)
+                {
+                    painter = new_painter;
+                    if collection_thread_ctrl_sender
+                        .send(CollectionThreadEvent::UsedWidgets(
+                            app.used_widgets,
+                            app.collection_rates.clone(),
+                        ))
+                        .is_err()
+                    {
+                        break;
+                    }
+                    wake_collection_thread(&termination_lock, &termination_cvar);
+                }
+                update_data(&mut app);
+                try_drawing(&mut terminal, &mut app, &mut painter)?;
+            }
+        }
+
+        let is_status_message_expired = app.take_expired_status_message();
+        let is_click_flash_expired = app.take_expired_click_flash();
+        if is_status_message_expired || is_click_flash_expired {
+            try_drawing(&mut terminal, &mut app, &mut painter)?;
         }
     }
 
@@ -326,5 +406,15 @@ fn main() -> Result<()> {
     termination_cvar.notify_all();
     cleanup_terminal(&mut terminal)?;
 
+    // Let any collection in progress finish, but don't hang on one that is stuck.
+    if join_with_timeout(collection_thread, Duration::from_secs(1)).is_err() {
+        bottom::c_info!("The collection thread didn't finish in time, so it was left running.");
+    }
+
+    if let Some(timings) = &app.timings {
+        eprint!("{}", timings.report(&app.widget_map));
+        eprint!("\n{}", app.data_budget.report());
+    }
+
     Ok(())
 }
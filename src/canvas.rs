@@ -1,19 +1,23 @@
 use std::str::FromStr;
 
 use canvas_styling::*;
+use concat_string::concat_string;
 use itertools::izip;
 use tui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Paragraph, Wrap},
     Frame, Terminal,
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::{
         self,
         layout_manager::{BottomColRow, BottomLayout, BottomWidgetType},
+        timings::TimingKind,
         App,
     },
     constants::*,
@@ -26,7 +30,7 @@ mod dialogs;
 mod drawing_utils;
 mod widgets;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColourScheme {
     Default,
     DefaultLight,
@@ -34,7 +38,6 @@ pub enum ColourScheme {
     GruvboxLight,
     Nord,
     NordLight,
-    Custom,
 }
 
 impl FromStr for ColourScheme {
@@ -50,13 +53,50 @@ impl FromStr for ColourScheme {
             "nord" => Ok(ColourScheme::Nord),
             "nord-light" => Ok(ColourScheme::NordLight),
             _ => Err(BottomError::ConfigError(format!(
-                "\"{}\" is an invalid built-in color scheme.",
-                s
+                "\"{}\" is an invalid built-in color scheme. Valid schemes are: {}.",
+                s,
+                ColourScheme::BUILT_IN_NAMES.join(", ")
             ))),
         }
     }
 }
 
+impl ColourScheme {
+    /// The names of all built-in colour schemes, as accepted by [`ColourScheme::from_str`].
+    pub const BUILT_IN_NAMES: [&'static str; 6] = [
+        "default",
+        "default-light",
+        "gruvbox",
+        "gruvbox-light",
+        "nord",
+        "nord-light",
+    ];
+
+    /// Returns the name of the colour scheme, as accepted by [`ColourScheme::from_str`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColourScheme::Default => "default",
+            ColourScheme::DefaultLight => "default-light",
+            ColourScheme::Gruvbox => "gruvbox",
+            ColourScheme::GruvboxLight => "gruvbox-light",
+            ColourScheme::Nord => "nord",
+            ColourScheme::NordLight => "nord-light",
+        }
+    }
+
+    /// Returns the built-in colour scheme after this one, wrapping back around to the first.
+    pub fn next(&self) -> Self {
+        match self {
+            ColourScheme::Default => ColourScheme::DefaultLight,
+            ColourScheme::DefaultLight => ColourScheme::Gruvbox,
+            ColourScheme::Gruvbox => ColourScheme::GruvboxLight,
+            ColourScheme::GruvboxLight => ColourScheme::Nord,
+            ColourScheme::Nord => ColourScheme::NordLight,
+            ColourScheme::NordLight => ColourScheme::Default,
+        }
+    }
+}
+
 /// Handles the canvas' state.
 pub struct Painter {
     pub colours: CanvasStyling,
@@ -213,17 +253,92 @@ impl Painter {
         self.styled_help_text = styled_help_spans.into_iter().map(Line::from).collect();
     }
 
-    fn draw_frozen_indicator<B: Backend>(&self, f: &mut Frame<'_, B>, draw_loc: Rect) {
+    /// Replaces the colours used to draw, e.g. after switching colour schemes.
+    fn set_colours(&mut self, styling: CanvasStyling) {
+        self.colours = styling;
+        self.complete_painter_init();
+    }
+
+    /// Draws a warning over the whole of `draw_loc` that the terminal is smaller than
+    /// `min_size`, in place of the widgets.
+    fn draw_too_small<B: Backend>(
+        &self, f: &mut Frame<'_, B>, min_size: (u16, u16), draw_loc: Rect,
+    ) {
+        let text = format!(
+            "Terminal too small (need {}x{}, have {}x{})",
+            min_size.0, min_size.1, draw_loc.width, draw_loc.height
+        );
+
+        // Centre the text vertically, allowing for it to wrap.
+        let width = usize::from(draw_loc.width.max(1));
+        let text_height = (text.width().saturating_sub(1) / width + 1) as u16;
+        let text_loc = Rect {
+            y: draw_loc.y + draw_loc.height.saturating_sub(text_height) / 2,
+            height: text_height.min(draw_loc.height),
+            ..draw_loc
+        };
+
         f.render_widget(
-            Paragraph::new(Span::styled(
+            Paragraph::new(Span::styled(text, self.colours.invalid_query_style))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            text_loc,
+        );
+    }
+
+    /// Draws the status line at the bottom, which shows a status message if there is one, whether
+    /// the app is frozen, or otherwise key hints for the selected widget. If there is more than
+    /// one layout profile, the one in use is always shown on the right.
+    fn draw_status_line<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect) {
+        let mut draw_loc = Layout::default()
+            .horizontal_margin(1)
+            .constraints([Constraint::Length(1)])
+            .split(draw_loc)[0];
+
+        if app_state.layout_profiles.len() > 1 && !app_state.app_config_fields.use_basic_mode {
+            let label = concat_string!("Layout: ", app_state.active_layout_profile);
+            let split = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(label.width() as u16 + 2),
+                ])
+                .split(draw_loc);
+            draw_loc = split[0];
+
+            f.render_widget(
+                Paragraph::new(Span::styled(label, self.colours.widget_title_style))
+                    .alignment(Alignment::Right),
+                split[1],
+            );
+        }
+
+        let line = if let Some(message) = app_state.status_message() {
+            Line::from(Span::styled(
+                message,
+                self.colours.currently_selected_text_style,
+            ))
+        } else if app_state.frozen_state.is_frozen() {
+            Line::from(Span::styled(
                 "Frozen, press 'f' to unfreeze",
                 self.colours.currently_selected_text_style,
-            )),
-            Layout::default()
-                .horizontal_margin(1)
-                .constraints([Constraint::Length(1)])
-                .split(draw_loc)[0],
-        )
+            ))
+        } else if !app_state.pending_keys().is_empty() {
+            Line::from(vec![
+                Span::styled(app_state.pending_keys(), self.colours.table_header_style),
+                Span::styled(" (waiting for the next key)", self.colours.text_style),
+            ])
+        } else {
+            let hints = app_state.current_widget.widget_type.key_hints();
+            key_hint_line(
+                hints.iter().chain(&GENERAL_KEY_HINTS),
+                draw_loc.width,
+                self.colours.table_header_style,
+                self.colours.text_style,
+            )
+        };
+
+        f.render_widget(Paragraph::new(line), draw_loc)
     }
 
     pub fn draw_data<B: Backend>(
@@ -231,8 +346,19 @@ impl Painter {
     ) -> error::Result<()> {
         use BottomWidgetType::*;
 
+        let frame_start = app_state.timing_start();
+
+        // Redraw everything from scratch with the new colours if the colour scheme was switched.
+        if let Some(styling) = app_state.take_pending_colours() {
+            self.set_colours(styling);
+            terminal.clear()?;
+        }
+
         terminal.draw(|f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.frozen_state.is_frozen() {
+            let (terminal_size, status_draw_loc) = if app_state.app_config_fields.status_bar
+                || app_state.frozen_state.is_frozen()
+                || app_state.status_message().is_some()
+            {
                 let split_loc = Layout::default()
                     .constraints([Constraint::Min(0), Constraint::Length(1)])
                     .split(f.size());
@@ -267,6 +393,7 @@ impl Painter {
                 }
             }
 
+            let (min_width, min_height) = app_state.app_config_fields.min_terminal_size;
             if app_state.help_dialog_state.is_showing_help {
                 let gen_help_len = GENERAL_HELP_TEXT.len() as u16 + 3;
                 let border_len = terminal_height.saturating_sub(gen_help_len) / 2;
@@ -307,13 +434,20 @@ impl Painter {
                     terminal_width * 50 / 100
                 };
 
+                // Marked processes are listed under the question, so make room for them.
+                let listed_height = match (&dd_text, &app_state.dd_err) {
+                    (Some(text), None) => {
+                        u16::try_from(text.height().saturating_sub(2)).unwrap_or(u16::MAX)
+                    }
+                    _ => 0,
+                };
                 let text_height = if cfg!(target_os = "windows")
                     || !app_state.app_config_fields.is_advanced_kill
                 {
                     7
                 } else {
                     22
-                };
+                } + listed_height;
 
                 let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
                 let vertical_dialog_chunk = Layout::default()
@@ -338,15 +472,110 @@ impl Painter {
                 // This is a bit nasty, but it works well... I guess.
                 app_state.delete_dialog_state.is_showing_dd =
                     self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
+            } else if app_state.is_showing_save_config {
+                let text_width = if terminal_width < 100 {
+                    terminal_width * 90 / 100
+                } else {
+                    terminal_width * 50 / 100
+                };
+                let text_height = 6;
+
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let draw_loc = Rect::new(
+                    horizontal_bordering,
+                    vertical_bordering,
+                    text_width.min(terminal_width),
+                    text_height.min(terminal_height),
+                );
+
+                self.draw_save_config_dialog(f, draw_loc);
+            } else if let Some((name, description)) = &app_state.column_description {
+                let text_width = if terminal_width < 100 {
+                    terminal_width * 90 / 100
+                } else {
+                    terminal_width * 50 / 100
+                };
+                // Leave room for the borders, the empty line above the description, and however
+                // many lines it wraps to.
+                let inner_width = usize::from(text_width.saturating_sub(2).max(1));
+                let text_height = (description.width().saturating_sub(1) / inner_width + 4) as u16;
+
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let draw_loc = Rect::new(
+                    horizontal_bordering,
+                    vertical_bordering,
+                    text_width.min(terminal_width),
+                    text_height.min(terminal_height),
+                );
+
+                self.draw_column_description_dialog(f, name, description, draw_loc);
+            } else if let Some(selected) = app_state.layout_switcher_state {
+                let text_width = if terminal_width < 100 {
+                    terminal_width * 90 / 100
+                } else {
+                    terminal_width * 50 / 100
+                };
+                let text_height = app_state.layout_profiles.len() as u16 + 4;
+
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let draw_loc = Rect::new(
+                    horizontal_bordering,
+                    vertical_bordering,
+                    text_width.min(terminal_width),
+                    text_height.min(terminal_height),
+                );
+
+                self.draw_layout_switcher_dialog(f, app_state, selected, draw_loc);
+            } else if let Some(name) = &app_state.snapshot_name {
+                let text_width = if terminal_width < 100 {
+                    terminal_width * 90 / 100
+                } else {
+                    terminal_width * 50 / 100
+                };
+                let text_height = 7;
+
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let draw_loc = Rect::new(
+                    horizontal_bordering,
+                    vertical_bordering,
+                    text_width.min(terminal_width),
+                    text_height.min(terminal_height),
+                );
+
+                let default_name = app_state.process_snapshots.default_name();
+                self.draw_snapshot_name_dialog(f, name, &default_name, draw_loc);
+            } else if let Some(comparison) = &mut app_state.snapshot_comparison {
+                let dialog_width = terminal_width * 90 / 100;
+                let dialog_height = terminal_height * 80 / 100;
+
+                let vertical_bordering = terminal_height.saturating_sub(dialog_height) / 2;
+                let horizontal_bordering = terminal_width.saturating_sub(dialog_width) / 2;
+                let draw_loc = Rect::new(
+                    horizontal_bordering,
+                    vertical_bordering,
+                    dialog_width,
+                    dialog_height,
+                );
+
+                self.draw_snapshot_dialog(f, comparison, app_state.is_force_redraw, draw_loc);
+            } else if f.size().width < min_width || f.size().height < min_height {
+                // The layout can't be split up sensibly in this little space, so don't try. Dialogs
+                // are still drawn above, as their keys work regardless.
+                self.draw_too_small(f, (min_width, min_height), f.size());
             } else if app_state.is_expanded {
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let rect = Layout::default()
                     .margin(0)
                     .constraints([Constraint::Percentage(100)])
                     .split(terminal_size);
+                let draw_start = app_state.timing_start();
                 match &app_state.current_widget.widget_type {
                     Cpu => self.draw_cpu(f, app_state, rect[0], app_state.current_widget.widget_id),
                     CpuLegend => self.draw_cpu(
@@ -399,11 +628,15 @@ impl Painter {
                     ),
                     _ => {}
                 }
+                app_state.record_timing(
+                    TimingKind::Draw(app_state.current_widget.widget_id),
+                    draw_start,
+                );
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let actual_cpu_data_len = app_state.converted_data.cpu_data.len().saturating_sub(1);
@@ -522,8 +755,8 @@ impl Painter {
                 }
             } else {
                 // Draws using the passed in (or default) layout.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
@@ -754,6 +987,8 @@ impl Painter {
                         });
                 }
             }
+
+            self.colours.symbols.draw_ascii_fallback(f);
         })?;
 
         if let Some(updated_current_widget) = app_state
@@ -766,6 +1001,8 @@ impl Painter {
         app_state.is_force_redraw = false;
         app_state.is_determining_widget_boundary = false;
 
+        app_state.finish_frame_timing(frame_start);
+
         Ok(())
     }
 
@@ -775,31 +1012,491 @@ impl Painter {
     ) {
         use BottomWidgetType::*;
         for (widget, widget_draw_loc) in widgets.children.iter().zip(widget_draw_locs) {
+            let widget_draw_loc = match widget.aspect_ratio {
+                Some((width_ratio, height_ratio)) => {
+                    drawing_utils::aspect_rect(*widget_draw_loc, width_ratio, height_ratio)
+                }
+                None => *widget_draw_loc,
+            };
             if widget_draw_loc.width >= 2 && widget_draw_loc.height >= 2 {
+                let draw_start = app_state.timing_start();
                 match &widget.widget_type {
                     Empty => {}
-                    Cpu => self.draw_cpu(f, app_state, *widget_draw_loc, widget.widget_id),
-                    Mem => self.draw_memory_graph(f, app_state, *widget_draw_loc, widget.widget_id),
-                    Net => self.draw_network(f, app_state, *widget_draw_loc, widget.widget_id),
-                    Temp => self.draw_temp_table(f, app_state, *widget_draw_loc, widget.widget_id),
-                    Disk => self.draw_disk_table(f, app_state, *widget_draw_loc, widget.widget_id),
+                    Cpu => self.draw_cpu(f, app_state, widget_draw_loc, widget.widget_id),
+                    Mem => self.draw_memory_graph(f, app_state, widget_draw_loc, widget.widget_id),
+                    Net => self.draw_network(f, app_state, widget_draw_loc, widget.widget_id),
+                    Temp => self.draw_temp_table(f, app_state, widget_draw_loc, widget.widget_id),
+                    Disk => self.draw_disk_table(f, app_state, widget_draw_loc, widget.widget_id),
                     Proc => self.draw_process_widget(
                         f,
                         app_state,
-                        *widget_draw_loc,
+                        widget_draw_loc,
                         true,
                         widget.widget_id,
                     ),
                     Battery => self.draw_battery_display(
                         f,
                         app_state,
-                        *widget_draw_loc,
+                        widget_draw_loc,
                         true,
                         widget.widget_id,
                     ),
                     _ => {}
                 }
+                app_state.record_timing(TimingKind::Draw(widget.widget_id), draw_start);
+            }
+        }
+    }
+}
+
+/// Builds a line out of as many `hints` as fit in `width`, in order. Each hint is a key, and what
+/// it does.
+fn key_hint_line<'a, I>(hints: I, width: u16, key_style: Style, text_style: Style) -> Line<'a>
+where
+    I: IntoIterator<Item = &'a (&'a str, &'a str)>,
+{
+    let mut spans = Vec::new();
+    let mut used_width = 0;
+
+    for &(key, action) in hints {
+        let separator = if spans.is_empty() { "" } else { "  " };
+        let hint_width = separator.len() + key.width() + 1 + action.width();
+        if used_width + hint_width > usize::from(width) {
+            break;
+        }
+        used_width += hint_width;
+
+        if !separator.is_empty() {
+            spans.push(Span::raw(separator));
+        }
+        spans.push(Span::styled(key, key_style));
+        spans.push(Span::styled(concat_string!(" ", action), text_style));
+    }
+
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_built_in_colour_scheme_names() {
+        for name in ColourScheme::BUILT_IN_NAMES {
+            assert!(
+                ColourScheme::from_str(name).is_ok(),
+                "{name} should be valid"
+            );
+        }
+
+        let err = ColourScheme::from_str("solarized").unwrap_err().to_string();
+        assert!(err.contains("\"solarized\" is an invalid built-in color scheme"));
+        for name in ColourScheme::BUILT_IN_NAMES {
+            assert!(err.contains(name));
+        }
+    }
+
+    #[test]
+    fn test_last_render_time() {
+        use std::time::{Duration, Instant};
+
+        use tui::backend::TestBackend;
+
+        use crate::{
+            options::Config,
+            utils::{
+                clock::{Clock, MockClock},
+                test_utils::build_test_app,
+            },
+        };
+
+        /// Moves forward by a millisecond every time it's read, like time passing while drawing.
+        struct SteppingClock(MockClock);
+
+        impl Clock for SteppingClock {
+            fn now(&self) -> Instant {
+                self.0.advance(Duration::from_millis(1));
+                self.0.now()
             }
         }
+
+        let draw = |args: &[&str]| {
+            let (mut app, layout) =
+                build_test_app(args, Config::default(), &CanvasStyling::default());
+            app.set_clock(SteppingClock(MockClock::default()));
+
+            let mut painter = Painter::init(layout, CanvasStyling::default()).unwrap();
+            let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+            assert_eq!(app.last_render_time(), None);
+            painter.draw_data(&mut terminal, &mut app).unwrap();
+
+            app.last_render_time()
+        };
+
+        assert!(matches!(draw(&["btm", "--debug_timings"]), Some(time) if time > Duration::ZERO));
+        assert_eq!(draw(&["btm"]), None);
+    }
+
+    #[test]
+    fn test_colour_scheme_switch_redraws() {
+        use tui::{backend::TestBackend, buffer::Buffer};
+
+        use crate::{
+            options::Config,
+            utils::test_utils::{buffer_lines, build_test_app},
+        };
+
+        let (mut app, layout) =
+            build_test_app(&["btm"], Config::default(), &CanvasStyling::default());
+        let mut painter = Painter::init(layout, CanvasStyling::default()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+
+        // Returns the style of the process table's title, which is drawn from the table's own copy
+        // of the colours, and of the memory graph's title, which is drawn from the painter's.
+        let title_styles = |buffer: &Buffer| {
+            let lines = buffer_lines(buffer);
+            let find = |title: &str| {
+                lines
+                    .iter()
+                    .enumerate()
+                    .find_map(|(y, line)| {
+                        let x = line.find(title)?;
+                        Some(
+                            buffer
+                                .get(line[..x].chars().count() as u16, y as u16)
+                                .style(),
+                        )
+                    })
+                    .unwrap()
+            };
+
+            (find(" Processes "), find(" Memory "))
+        };
+
+        painter.draw_data(&mut terminal, &mut app).unwrap();
+        let before = title_styles(terminal.backend().buffer());
+
+        app.cycle_colour_scheme();
+        painter.draw_data(&mut terminal, &mut app).unwrap();
+        let after = title_styles(terminal.backend().buffer());
+
+        let light = CanvasStyling::new(ColourScheme::DefaultLight, &Config::default()).unwrap();
+        assert_ne!(before, after);
+        assert_eq!(after.0.fg, light.widget_title_style.fg);
+        assert_eq!(after.1.fg, light.widget_title_style.fg);
+    }
+
+    #[test]
+    fn test_ascii_symbols_whole_frame() {
+        use tui::backend::TestBackend;
+
+        use crate::{
+            options::Config,
+            utils::test_utils::{buffer_lines, build_test_app},
+        };
+
+        let draw = |setup: &dyn Fn(&mut App)| {
+            let colours = CanvasStyling::default().with_symbols(Symbols::ASCII);
+            let (mut app, layout) = build_test_app(&["btm"], Config::default(), &colours);
+            setup(&mut app);
+
+            let mut painter = Painter::init(layout, colours).unwrap();
+            let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+            painter.draw_data(&mut terminal, &mut app).unwrap();
+
+            buffer_lines(terminal.backend().buffer())
+        };
+
+        // Every widget's borders are swapped, and so are those of dialogs drawn over them.
+        for lines in [
+            draw(&|_| {}),
+            draw(&|app| app.help_dialog_state.is_showing_help = true),
+        ] {
+            assert!(lines.iter().any(|line| line.contains("+-")));
+            for line in lines {
+                assert!(line.is_ascii(), "{line}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_small_warning() {
+        use tui::backend::TestBackend;
+
+        use crate::{
+            options::Config,
+            utils::test_utils::{buffer_lines, build_test_app},
+        };
+
+        let draw_with = |args: &[&str], width: u16, height: u16, setup: &dyn Fn(&mut App)| {
+            let (mut app, layout) =
+                build_test_app(args, Config::default(), &CanvasStyling::default());
+            setup(&mut app);
+
+            let mut painter = Painter::init(layout, CanvasStyling::default()).unwrap();
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            painter.draw_data(&mut terminal, &mut app).unwrap();
+
+            buffer_lines(terminal.backend().buffer())
+        };
+        let draw = |args: &[&str], width: u16, height: u16| draw_with(args, width, height, &|_| {});
+
+        // This is off by default.
+        let lines = draw(&["btm"], 30, 5);
+        assert!(lines.iter().all(|line| !line.contains("too small")));
+
+        let lines = draw(&["btm", "--min_terminal_size", "40x10"], 30, 5);
+        assert!(lines.iter().all(|line| !line.contains("CPU")));
+        assert_eq!(
+            lines.iter().map(|line| line.trim()).collect::<Vec<_>>(),
+            ["", "Terminal too small (need", "40x10, have 30x5)", "", ""]
+        );
+
+        // The minimum can be raised, or lowered to draw in smaller terminals anyway.
+        let lines = draw(&["btm", "--min_terminal_size", "200x50"], 120, 40);
+        assert!(lines.iter().all(|line| !line.contains("CPU")));
+        assert!(lines[19].contains("Terminal too small (need 200x50, have 120x40)"));
+
+        let lines = draw(&["btm", "--min_terminal_size", "0x0"], 30, 5);
+        assert!(lines.iter().all(|line| !line.contains("too small")));
+
+        // Dialogs are still drawn, since their keys keep working.
+        let lines = draw_with(&["btm", "--min_terminal_size", "200x50"], 120, 40, &|app| {
+            app.delete_dialog_state.is_showing_dd = true;
+            app.dd_err = Some("Permission denied".to_string());
+        });
+        assert!(lines.iter().all(|line| !line.contains("too small")));
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("Failed to kill process.")));
+    }
+
+    #[test]
+    fn test_kill_dialog_marked_processes() {
+        use tui::backend::TestBackend;
+
+        use crate::{
+            app::data_harvester::processes::ProcessHarvest,
+            options::Config,
+            utils::test_utils::{buffer_lines, build_test_app},
+            Pid,
+        };
+
+        let (mut app, layout) =
+            build_test_app(&["btm"], Config::default(), &CanvasStyling::default());
+        let mut painter = Painter::init(layout, CanvasStyling::default()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+
+        let proc = *app.states.proc_state.widget_states.keys().next().unwrap();
+        app.focus_widget(proc);
+
+        let mut ingest = |app: &mut App, pids: &[Pid]| {
+            app.data_collection.process_data.process_harvest = pids
+                .iter()
+                .map(|&pid| {
+                    let process = ProcessHarvest {
+                        pid,
+                        name: format!("process{pid}"),
+                        ..Default::default()
+                    };
+                    (pid, process)
+                })
+                .collect();
+            let state = app.states.proc_state.get_mut_widget_state(proc).unwrap();
+            state.ingest_data(&app.data_collection);
+
+            // The last row is the totals, which can't be marked.
+            let rows = state.table.data();
+            rows[..rows.len() - 1]
+                .iter()
+                .map(|row| row.pid)
+                .collect::<Vec<_>>()
+        };
+        let mut draw = |app: &mut App| {
+            painter.draw_data(&mut terminal, app).unwrap();
+            buffer_lines(terminal.backend().buffer())
+        };
+        let listed = |lines: &[String], pid: Pid| {
+            let entry = format!("\"process{pid}\" with PID {pid}");
+            lines.iter().any(|line| line.contains(&entry))
+        };
+
+        // Mark the first and third rows, and open the dialog.
+        let rows = ingest(&mut app, &[1, 2, 3]);
+        for c in [' ', 'j', 'j', ' ', 'd', 'd'] {
+            app.on_char_key(c);
+        }
+        let lines = draw(&mut app);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("Kill 2 marked processes?")));
+        assert!(listed(&lines, rows[0]));
+        assert!(!listed(&lines, rows[1]));
+        assert!(listed(&lines, rows[2]));
+
+        // Marked processes exiting while the dialog is open doesn't change what it shows.
+        ingest(&mut app, &[rows[1]]);
+        assert_eq!(draw(&mut app), lines);
+
+        // Past a few processes, the rest are only counted.
+        app.on_esc();
+        let rows = ingest(&mut app, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        let state = app.states.proc_state.get_mut_widget_state(proc).unwrap();
+        state.table.clear_marked();
+        app.on_char_key('g');
+        app.on_char_key('g');
+        for _ in &rows {
+            app.on_char_key(' ');
+            app.on_char_key('j');
+        }
+        app.on_char_key('d');
+        app.on_char_key('d');
+        let lines = draw(&mut app);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("Kill 8 marked processes?")));
+        assert!(rows[..5].iter().all(|&pid| listed(&lines, pid)));
+        assert!(rows[5..].iter().all(|&pid| !listed(&lines, pid)));
+        assert!(lines.iter().any(|line| line.contains("…and 3 more")));
+    }
+
+    #[test]
+    fn test_layout_profile_in_status_line() {
+        use tui::backend::TestBackend;
+
+        use crate::{
+            options::{config_writer::SaveOutcome, Config},
+            utils::test_utils::{buffer_lines, build_test_app},
+        };
+
+        let draw = |layout_profiles: &[&str], active: &str| {
+            let (app, layout) =
+                build_test_app(&["btm"], Config::default(), &CanvasStyling::default());
+            let mut app = app.with_layout_profiles(
+                layout_profiles
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+                active,
+            );
+            app.on_config_saved(Ok(SaveOutcome::Unchanged));
+
+            let mut painter = Painter::init(layout, CanvasStyling::default()).unwrap();
+            let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+            painter.draw_data(&mut terminal, &mut app).unwrap();
+
+            buffer_lines(terminal.backend().buffer()).pop().unwrap()
+        };
+
+        // It's shown alongside anything else on the status line.
+        let status = draw(&["default", "wide"], "wide");
+        assert!(status
+            .trim_start()
+            .starts_with("The config file already has these settings"));
+        assert!(status.trim_end().ends_with("Layout: wide"));
+
+        // There's nothing to tell apart with only one layout.
+        let status = draw(&["default"], "default");
+        assert!(!status.contains("Layout"));
+    }
+
+    #[test]
+    fn test_cpu_legend_series_symbols() {
+        use tui::backend::TestBackend;
+
+        use crate::{
+            app::data_harvester::cpu::CpuDataType,
+            data_conversion::CpuWidgetData,
+            options::Config,
+            utils::test_utils::{buffer_lines, build_test_app},
+        };
+
+        let styling = CanvasStyling::no_colour();
+        let (mut app, layout) = build_test_app(
+            &[
+                "btm",
+                "--default_widget_type",
+                "cpu",
+                "--expanded",
+                "--hide_avg_cpu",
+            ],
+            Config::default(),
+            &styling,
+        );
+
+        // CPU0 is low and CPU1 is high.
+        let entry = |index, usage| CpuWidgetData::Entry {
+            data_type: CpuDataType::Cpu(index),
+            data: vec![(-60_000.0, usage), (0.0, usage)],
+            last_entry: usage,
+        };
+        app.converted_data.cpu_data = vec![CpuWidgetData::All, entry(0, 10.0), entry(1, 90.0)];
+        for state in app.states.cpu_state.widget_states.values_mut() {
+            state.update_table(&app.converted_data.cpu_data);
+        }
+
+        let mut painter = Painter::init(layout, styling).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(160, 24)).unwrap();
+        painter.draw_data(&mut terminal, &mut app).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+
+        // The first symbol is kept for the average, even while it's hidden.
+        let (cpu0, cpu1) = (
+            painter.colours.series_symbols[1],
+            painter.colours.series_symbols[2],
+        );
+        assert!(lines
+            .iter()
+            .any(|line| line.contains(&format!("{cpu0} CPU0"))));
+        assert!(lines
+            .iter()
+            .any(|line| line.contains(&format!("{cpu1} CPU1"))));
+
+        // The graph draws each series with the symbol shown next to it in the legend, so the high
+        // one is above the low one.
+        let legend_start = lines
+            .iter()
+            .find_map(|line| line.find("CPU0"))
+            .unwrap()
+            .saturating_sub(4);
+        let graph_row = |symbol: char| {
+            lines.iter().position(|line| {
+                line.char_indices()
+                    .any(|(index, c)| index < legend_start && c == symbol)
+            })
+        };
+        assert!(graph_row(cpu1).unwrap() < graph_row(cpu0).unwrap());
+    }
+
+    #[test]
+    fn test_key_hint_line() {
+        let hints = [("dd", "kill"), ("/", "search"), ("?", "help")];
+        let text = |width: u16| {
+            key_hint_line(&hints, width, Style::default(), Style::default())
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+
+        assert_eq!(text(40), "dd kill  / search  ? help");
+        assert_eq!(text(25), "dd kill  / search  ? help");
+
+        // Hints that don't fit are left out entirely, rather than being cut short.
+        assert_eq!(text(24), "dd kill  / search");
+        assert_eq!(text(7), "dd kill");
+        assert_eq!(text(6), "");
+    }
+
+    #[test]
+    fn test_cycling_colour_schemes() {
+        let mut scheme = ColourScheme::Default;
+        for name in ColourScheme::BUILT_IN_NAMES {
+            assert_eq!(scheme.name(), name);
+            assert_eq!(ColourScheme::from_str(name).unwrap(), scheme);
+            scheme = scheme.next();
+        }
+
+        assert_eq!(scheme, ColourScheme::Default);
     }
 }
@@ -1,20 +1,26 @@
 use anyhow::Context;
 use colour_utils::*;
-use tui::style::{Color, Style};
+use tui::style::{Color, Modifier, Style};
 
 use super::ColourScheme;
 use crate::{
     constants::*,
-    options::{Config, ConfigColours},
+    options::{get_no_color, get_unicode_symbols, Config, ConfigColours},
     utils::error,
 };
 mod colour_utils;
+mod style_resolver;
+mod symbols;
+pub use style_resolver::{Role, StyleOverrides, StyleResolver};
+pub use symbols::Symbols;
 
 pub struct CanvasStyling {
     pub currently_selected_text_colour: Color,
     pub currently_selected_bg_colour: Color,
     pub currently_selected_text_style: Style,
+    pub click_flash_style: Style,
     pub table_header_style: Style,
+    pub table_background_style: Style,
     pub ram_style: Style,
     #[cfg(not(target_os = "windows"))]
     pub cache_style: Style,
@@ -38,6 +44,15 @@ pub struct CanvasStyling {
     pub low_battery_colour: Style,
     pub invalid_query_style: Style,
     pub disabled_text_style: Style,
+    /// Used for values that went up since the last update.
+    pub increase_style: Style,
+    /// Used for values that went down since the last update.
+    pub decrease_style: Style,
+    /// Laid over the contents of widgets that aren't focused, to make the focused one stand out.
+    pub dimmed_style: Style,
+    pub symbols: Symbols,
+    /// Characters to tell graph series apart with, if they can't be told apart by colour.
+    pub series_symbols: &'static [char],
 }
 
 impl Default for CanvasStyling {
@@ -52,7 +67,9 @@ impl Default for CanvasStyling {
             currently_selected_text_style: Style::default()
                 .fg(currently_selected_text_colour)
                 .bg(currently_selected_bg_colour),
+            click_flash_style: Style::default().add_modifier(Modifier::REVERSED),
             table_header_style: Style::default().fg(HIGHLIGHT_COLOUR),
+            table_background_style: Style::default(),
             ram_style: Style::default().fg(FIRST_COLOUR),
             #[cfg(not(target_os = "windows"))]
             cache_style: Style::default().fg(FIFTH_COLOUR),
@@ -93,6 +110,11 @@ impl Default for CanvasStyling {
             low_battery_colour: Style::default().fg(Color::Red),
             invalid_query_style: Style::default().fg(tui::style::Color::Red),
             disabled_text_style: Style::default().fg(Color::DarkGray),
+            increase_style: Style::default().fg(Color::Red),
+            decrease_style: Style::default().fg(Color::Green),
+            dimmed_style: Style::default().add_modifier(Modifier::DIM),
+            symbols: Symbols::default(),
+            series_symbols: &[],
         }
     }
 }
@@ -112,6 +134,14 @@ macro_rules! try_set_colour {
 macro_rules! try_set_colour_list {
     ($field:expr, $colours:expr, $colour_field:ident) => {
         if let Some(colour_list) = &$colours.$colour_field {
+            if colour_list.is_empty() {
+                anyhow::bail!(concat!(
+                    "update '",
+                    stringify!($colour_field),
+                    "' in your config file to have at least one colour"
+                ));
+            }
+
             $field = colour_list
                 .iter()
                 .map(|s| str_to_fg(s))
@@ -127,6 +157,68 @@ macro_rules! try_set_colour_list {
 
 impl CanvasStyling {
     pub fn new(colour_scheme: ColourScheme, config: &Config) -> anyhow::Result<Self> {
+        let styling = if get_no_color(config) {
+            Self::no_colour()
+        } else {
+            Self::with_custom_colours(colour_scheme, config.colors.as_ref())?
+        };
+
+        Ok(styling.with_symbols(Symbols::new(get_unicode_symbols(config))))
+    }
+
+    /// Creates styling without any colours, which tells things apart with modifiers like bold
+    /// or reversed text instead. Graph series are told apart with different symbols.
+    pub fn no_colour() -> Self {
+        let plain = Style::default();
+
+        CanvasStyling {
+            currently_selected_text_colour: Color::Reset,
+            currently_selected_bg_colour: Color::Reset,
+            currently_selected_text_style: plain.add_modifier(Modifier::REVERSED),
+            click_flash_style: plain.add_modifier(Modifier::UNDERLINED),
+            table_header_style: plain.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            table_background_style: plain,
+            ram_style: plain,
+            #[cfg(not(target_os = "windows"))]
+            cache_style: plain,
+            swap_style: plain,
+            arc_style: plain,
+            gpu_colour_styles: vec![plain],
+            rx_style: plain,
+            tx_style: plain,
+            total_rx_style: plain,
+            total_tx_style: plain,
+            all_colour_style: plain,
+            avg_colour_style: plain,
+            cpu_colour_styles: vec![plain],
+            border_style: plain,
+            highlighted_border_style: plain.add_modifier(Modifier::BOLD),
+            text_style: plain,
+            widget_title_style: plain,
+            graph_style: plain,
+            high_battery_colour: plain,
+            medium_battery_colour: plain,
+            low_battery_colour: plain,
+            invalid_query_style: plain.add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            disabled_text_style: plain.add_modifier(Modifier::DIM),
+            increase_style: plain,
+            decrease_style: plain,
+            dimmed_style: plain.add_modifier(Modifier::DIM),
+            symbols: Symbols::default(),
+            series_symbols: &['*', '+', 'x', 'o', '#', '=', '~', '@'],
+        }
+    }
+
+    /// Sets the [`Symbols`] to draw with.
+    pub fn with_symbols(mut self, symbols: Symbols) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Creates the styling for `colour_scheme`, with any `custom_colours` applied on top of it.
+    pub fn with_custom_colours(
+        colour_scheme: ColourScheme, custom_colours: Option<&ConfigColours>,
+    ) -> anyhow::Result<Self> {
         let mut canvas_colours = Self::default();
 
         match colour_scheme {
@@ -146,11 +238,11 @@ impl CanvasStyling {
             ColourScheme::NordLight => {
                 canvas_colours.set_colours_from_palette(&NORD_LIGHT_COLOUR_PALETTE)?;
             }
-            ColourScheme::Custom => {
-                if let Some(colors) = &config.colors {
-                    canvas_colours.set_colours_from_palette(colors)?;
-                }
-            }
+        }
+
+        // Any custom colours are applied on top of the colour scheme.
+        if let Some(colors) = custom_colours {
+            canvas_colours.set_colours_from_palette(colors)?;
         }
 
         Ok(canvas_colours)
@@ -186,6 +278,10 @@ impl CanvasStyling {
         try_set_colour!(self.medium_battery_colour, colours, medium_battery_color);
         try_set_colour!(self.low_battery_colour, colours, low_battery_color);
 
+        // Changes
+        try_set_colour!(self.increase_style, colours, increase_color);
+        try_set_colour!(self.decrease_style, colours, decrease_color);
+
         // Widget text and graphs
         try_set_colour!(self.widget_title_style, colours, widget_title_color);
         try_set_colour!(self.graph_style, colours, graph_color);
@@ -201,6 +297,11 @@ impl CanvasStyling {
         // Tables
         try_set_colour!(self.table_header_style, colours, table_header_color);
 
+        if let Some(table_bg_color) = &colours.table_bg_color {
+            self.table_background_style = Style::default().bg(str_to_colour(table_bg_color)
+                .context("update 'table_bg_color' in your config file")?);
+        }
+
         if let Some(scroll_entry_text_color) = &colours.selected_text_color {
             self.set_scroll_entry_text_color(scroll_entry_text_color)
                 .context("update 'selected_text_color' in your config file")?;
@@ -211,6 +312,11 @@ impl CanvasStyling {
                 .context("update 'selected_bg_color' in your config file")?;
         }
 
+        if let Some(click_flash_color) = &colours.click_flash_color {
+            self.click_flash_style = Style::default().bg(str_to_colour(click_flash_color)
+                .context("update 'click_flash_color' in your config file")?);
+        }
+
         Ok(())
     }
 
@@ -237,7 +343,7 @@ impl CanvasStyling {
 mod test {
     use super::{CanvasStyling, ColourScheme};
     use crate::Config;
-    use tui::style::{Color, Style};
+    use tui::style::{Color, Modifier, Style};
 
     #[test]
     fn default_selected_colour_works() {
@@ -281,6 +387,89 @@ mod test {
         );
     }
 
+    #[test]
+    fn no_colour_has_no_colours() {
+        let colours = CanvasStyling::no_colour();
+
+        let styles = [
+            colours.currently_selected_text_style,
+            colours.click_flash_style,
+            colours.table_header_style,
+            colours.table_background_style,
+            colours.ram_style,
+            colours.swap_style,
+            colours.rx_style,
+            colours.tx_style,
+            colours.border_style,
+            colours.highlighted_border_style,
+            colours.text_style,
+            colours.widget_title_style,
+            colours.graph_style,
+            colours.invalid_query_style,
+            colours.disabled_text_style,
+            colours.increase_style,
+            colours.decrease_style,
+        ];
+        for style in styles
+            .into_iter()
+            .chain(colours.cpu_colour_styles.iter().copied())
+            .chain(colours.gpu_colour_styles.iter().copied())
+        {
+            assert_eq!(style.fg, None);
+            assert_eq!(style.bg, None);
+        }
+
+        // Things are told apart by modifiers instead.
+        assert!(colours
+            .currently_selected_text_style
+            .add_modifier
+            .contains(Modifier::REVERSED));
+        assert_ne!(colours.highlighted_border_style, colours.border_style);
+        assert!(!colours.series_symbols.is_empty());
+    }
+
+    #[test]
+    fn no_colour_draws_no_colours() {
+        use tui::{backend::TestBackend, Terminal};
+
+        use crate::{canvas::Painter, utils::test_utils::build_test_app};
+
+        let colours = CanvasStyling::no_colour();
+        let (mut app, layout) = build_test_app(&["btm"], Config::default(), &colours);
+        let mut painter = Painter::init(layout, colours).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        painter.draw_data(&mut terminal, &mut app).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        assert!(buffer.content.iter().any(|cell| cell.symbol != " "));
+        for (index, cell) in buffer.content.iter().enumerate() {
+            let (x, y) = buffer.pos_of(index);
+            assert_eq!(cell.fg, Color::Reset, "foreground at ({x}, {y})");
+            assert_eq!(cell.bg, Color::Reset, "background at ({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn no_color_flag_overrides_custom_colours() {
+        let config: Config = toml_edit::de::from_str(
+            r##"
+            [flags]
+            no_color = true
+
+            [colors]
+            border_color = "red"
+            "##,
+        )
+        .unwrap();
+
+        let colours = CanvasStyling::new(ColourScheme::Nord, &config).unwrap();
+        assert_eq!(colours.border_style.fg, None);
+        assert_eq!(
+            colours.table_header_style,
+            CanvasStyling::no_colour().table_header_style
+        );
+    }
+
     #[test]
     fn built_in_colour_schemes_work() {
         let config = Config::default();
@@ -291,4 +480,76 @@ mod test {
         CanvasStyling::new(ColourScheme::Nord, &config).unwrap();
         CanvasStyling::new(ColourScheme::NordLight, &config).unwrap();
     }
+
+    #[test]
+    fn custom_colours_override_scheme() {
+        let config: Config = toml_edit::de::from_str(
+            r##"
+            [colors]
+            table_header_color = "#af5f00"
+            border_color = "red"
+            cpu_core_colors = ["LightBlue", "#ff8866", "208"]
+            "##,
+        )
+        .unwrap();
+
+        let colours = CanvasStyling::new(ColourScheme::Nord, &config).unwrap();
+        let nord = CanvasStyling::new(ColourScheme::Nord, &Config::default()).unwrap();
+
+        assert_eq!(colours.table_header_style.fg, Some(Color::Rgb(175, 95, 0)));
+        assert_eq!(colours.border_style.fg, Some(Color::Red));
+        assert_eq!(
+            colours
+                .cpu_colour_styles
+                .iter()
+                .map(|style| style.fg)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(Color::LightBlue),
+                Some(Color::Rgb(255, 136, 102)),
+                Some(Color::Indexed(208))
+            ]
+        );
+
+        // Anything not overridden should be left as the scheme's colour.
+        assert_eq!(colours.text_style, nord.text_style);
+        assert_eq!(
+            colours.highlighted_border_style,
+            nord.highlighted_border_style
+        );
+    }
+
+    #[test]
+    fn invalid_custom_colours() {
+        let config: Config = toml_edit::de::from_str(
+            r##"
+            [colors]
+            border_color = "#zzzzzz"
+            "##,
+        )
+        .unwrap();
+        let err = format!(
+            "{:#}",
+            CanvasStyling::new(ColourScheme::Default, &config)
+                .err()
+                .unwrap()
+        );
+        assert!(err.contains("border_color"));
+        assert!(err.contains("#zzzzzz"));
+
+        let config: Config = toml_edit::de::from_str(
+            r##"
+            [colors]
+            cpu_core_colors = []
+            "##,
+        )
+        .unwrap();
+        let err = format!(
+            "{:#}",
+            CanvasStyling::new(ColourScheme::Default, &config)
+                .err()
+                .unwrap()
+        );
+        assert!(err.contains("cpu_core_colors"));
+    }
 }
@@ -60,7 +60,10 @@ pub fn str_to_fg(input_val: &str) -> error::Result<Style> {
 }
 
 pub fn str_to_colour(input_val: &str) -> error::Result<Color> {
-    if input_val.len() > 1 {
+    if let Ok(index) = input_val.trim().parse::<u8>() {
+        // An index into the terminal's 256 colour palette.
+        Ok(Color::Indexed(index))
+    } else if input_val.len() > 1 {
         if input_val.starts_with('#') {
             convert_hex_to_color(input_val)
         } else if input_val.contains(',') {
@@ -269,4 +272,14 @@ mod test {
 
         assert!(convert_hex_to_color("#हिन्दी").is_err());
     }
+
+    #[test]
+    fn indexed_colours() {
+        assert_eq!(str_to_colour("0").unwrap(), Color::Indexed(0));
+        assert_eq!(str_to_colour("208").unwrap(), Color::Indexed(208));
+        assert_eq!(str_to_colour(" 255 ").unwrap(), Color::Indexed(255));
+
+        assert!(str_to_colour("256").is_err());
+        assert!(str_to_colour("-1").is_err());
+    }
 }
@@ -0,0 +1,128 @@
+use hashbrown::HashMap;
+use tui::style::Style;
+
+use super::CanvasStyling;
+
+/// A semantic role that something being drawn plays, which a [`StyleResolver`] maps to a [`Style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// A widget's border, which may be for the selected widget.
+    Border { selected: bool },
+    /// A widget's title.
+    Title,
+    /// Most text.
+    Text,
+    /// The header of a table.
+    TableHeader,
+    /// The background of a table, including any area not covered by rows.
+    TableBackground,
+    /// The selected row of a table, which is only highlighted if it is focused.
+    SelectedRow { focused: bool },
+    /// A table row that was just clicked.
+    ClickFlash,
+    /// The line of the `n`th series in a graph.
+    GraphLine(usize),
+    /// Something that went wrong, like an invalid query.
+    Error,
+    /// A value that went up since the last update.
+    Increase,
+    /// A value that went down since the last update.
+    Decrease,
+    /// Laid over the contents of a widget that isn't focused.
+    Dimmed,
+}
+
+/// Something that maps [`Role`]s to [`Style`]s.
+pub trait StyleResolver {
+    /// Returns the [`Style`] to use for `role`.
+    fn resolve(&self, role: Role) -> Style;
+}
+
+impl StyleResolver for CanvasStyling {
+    fn resolve(&self, role: Role) -> Style {
+        match role {
+            Role::Border { selected: true } => self.highlighted_border_style,
+            Role::Border { selected: false } => self.border_style,
+            Role::Title => self.widget_title_style,
+            Role::Text | Role::SelectedRow { focused: false } => self.text_style,
+            Role::TableHeader => self.table_header_style,
+            Role::TableBackground => self.table_background_style,
+            Role::SelectedRow { focused: true } => self.currently_selected_text_style,
+            Role::ClickFlash => self.click_flash_style,
+            Role::GraphLine(series) => self
+                .cpu_colour_styles
+                .get(series % self.cpu_colour_styles.len().max(1))
+                .copied()
+                .unwrap_or(self.graph_style),
+            Role::Error => self.invalid_query_style,
+            Role::Increase => self.increase_style,
+            Role::Decrease => self.decrease_style,
+            Role::Dimmed => self.dimmed_style,
+        }
+    }
+}
+
+/// A set of per-widget [`Style`] overrides, which should be consulted before falling back to
+/// another [`StyleResolver`].
+#[derive(Debug, Default, Clone)]
+pub struct StyleOverrides {
+    overrides: HashMap<Role, Style>,
+}
+
+impl StyleOverrides {
+    /// Overrides the [`Style`] used for `role`.
+    pub fn with(mut self, role: Role, style: Style) -> Self {
+        self.overrides.insert(role, style);
+        self
+    }
+
+    /// Returns the overridden [`Style`] for `role`, if there is one.
+    pub fn get(&self, role: Role) -> Option<Style> {
+        self.overrides.get(&role).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tui::style::Color;
+
+    use super::*;
+
+    #[test]
+    fn test_canvas_styling_resolves_roles() {
+        let styling = CanvasStyling::default();
+
+        assert_eq!(
+            styling.resolve(Role::Border { selected: true }),
+            styling.highlighted_border_style
+        );
+        assert_eq!(
+            styling.resolve(Role::Border { selected: false }),
+            styling.border_style
+        );
+        assert_eq!(
+            styling.resolve(Role::SelectedRow { focused: true }),
+            styling.currently_selected_text_style
+        );
+        assert_eq!(
+            styling.resolve(Role::SelectedRow { focused: false }),
+            styling.text_style
+        );
+        assert_eq!(
+            styling.resolve(Role::GraphLine(styling.cpu_colour_styles.len() + 1)),
+            styling.cpu_colour_styles[1]
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides =
+            StyleOverrides::default().with(Role::TableHeader, Style::default().fg(Color::Red));
+
+        assert_eq!(
+            overrides.get(Role::TableHeader),
+            Some(Style::default().fg(Color::Red))
+        );
+        assert_eq!(overrides.get(Role::Text), None);
+    }
+}
@@ -0,0 +1,409 @@
+use std::borrow::Cow;
+
+use tui::{
+    backend::Backend,
+    buffer::Buffer,
+    layout::Rect,
+    symbols::Marker,
+    text::{Span, Text},
+    widgets::Widget,
+    Frame,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The decorative glyphs drawn by widgets, like sort arrows or graph markers. Some terminals and
+/// fonts draw non-ASCII glyphs poorly, so there is also an ASCII-only set of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbols {
+    /// Marks text that was cut short.
+    pub ellipsis: &'static str,
+    /// Marks a column sorted in ascending order.
+    pub sort_ascending: &'static str,
+    /// Marks a column sorted in descending order.
+    pub sort_descending: &'static str,
+    /// The levels of a sparkline, from empty to full.
+    pub sparkline_bars: [char; 9],
+    /// Whether only ASCII glyphs should be drawn.
+    pub ascii_only: bool,
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Self::UNICODE
+    }
+}
+
+impl Symbols {
+    pub const UNICODE: Self = Self {
+        ellipsis: "…",
+        sort_ascending: "▲",
+        sort_descending: "▼",
+        sparkline_bars: [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+        ascii_only: false,
+    };
+
+    pub const ASCII: Self = Self {
+        ellipsis: "...",
+        sort_ascending: "^",
+        sort_descending: "v",
+        sparkline_bars: [' ', '_', '.', '-', '=', '+', '*', '%', '#'],
+        ascii_only: true,
+    };
+
+    /// Returns the [`Symbols`] to use, depending on whether non-ASCII glyphs are allowed.
+    pub fn new(unicode: bool) -> Self {
+        if unicode {
+            Self::UNICODE
+        } else {
+            Self::ASCII
+        }
+    }
+
+    /// Renders `values` as a sparkline of [`Symbols::sparkline_bars`], scaled so the largest value
+    /// is a full bar. If there are more values than fit in `width`, only the most recent (last) ones
+    /// are shown. Negative and non-finite values are treated as zero.
+    pub fn sparkline<'a>(&self, values: &[f64], width: u16) -> Text<'a> {
+        let bars = &self.sparkline_bars;
+        let values = &values[values.len().saturating_sub(usize::from(width))..];
+        let clean = |value: f64| {
+            if value.is_finite() {
+                value.max(0.0)
+            } else {
+                0.0
+            }
+        };
+        let max = values.iter().copied().map(clean).fold(0.0, f64::max);
+
+        let sparkline: String = values
+            .iter()
+            .map(|&value| {
+                if max > 0.0 {
+                    let level = (clean(value) / max * (bars.len() - 1) as f64).round() as usize;
+                    bars[level.min(bars.len() - 1)]
+                } else {
+                    bars[0]
+                }
+            })
+            .collect();
+
+        Text::raw(sparkline)
+    }
+
+    /// Returns the [`Marker`] to draw graphs with. Braille has no ASCII equivalent, so dots are
+    /// always used if only ASCII is allowed.
+    pub fn graph_marker(&self, use_dot: bool) -> Marker {
+        if use_dot || self.ascii_only {
+            Marker::Dot
+        } else {
+            Marker::Braille
+        }
+    }
+
+    /// Swaps the borders, graph axes, and graph markers that tui drew anywhere in the frame for
+    /// ASCII ones, if only ASCII is allowed. This is meant to be drawn after everything else.
+    pub fn draw_ascii_fallback<B: Backend>(&self, f: &mut Frame<'_, B>) {
+        if self.ascii_only {
+            f.render_widget(AsciiFallback, f.size());
+        }
+    }
+
+    /// Returns the ASCII replacement for `symbol`, if it is one of the glyphs tui draws that can't
+    /// be swapped out beforehand, i.e. borders, graph axes, and graph markers.
+    fn line_fallback(symbol: &str) -> Option<char> {
+        let mut chars = symbol.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+
+        match c {
+            '─' | '━' | '═' | '╌' | '┄' => Some('-'),
+            '│' | '┃' | '║' | '╎' | '┆' => Some('|'),
+            '┌' | '┐' | '└' | '┘' | '╭' | '╮' | '╰' | '╯' | '├' | '┤' | '┬' | '┴' | '┼' | '┏'
+            | '┓' | '┗' | '┛' | '┣' | '┫' | '┳' | '┻' | '╋' | '╔' | '╗' | '╚' | '╝' | '╠' | '╣'
+            | '╦' | '╩' | '╬' => Some('+'),
+            '•' => Some('*'),
+            '\u{2800}' => Some(' '),
+            '\u{2801}'..='\u{28FF}' => Some('.'),
+            _ => None,
+        }
+    }
+
+    /// Returns the ASCII replacement for a glyph in a column header, which, unlike the rows, is
+    /// text the app writes itself.
+    fn header_fallback(symbol: &str) -> Option<char> {
+        match symbol {
+            "…" => Some('.'),
+            "▲" => Some('^'),
+            "▼" => Some('v'),
+            "Δ" => Some('d'),
+            _ => Self::line_fallback(symbol),
+        }
+    }
+
+    /// Rewrites a column header, which was already cut short to fit in `width`, with these
+    /// symbols. This does nothing unless only ASCII is allowed.
+    pub fn fit_header<'a>(&self, text: Text<'a>, width: u16) -> Text<'a> {
+        if !self.ascii_only {
+            return text;
+        }
+
+        let mut text = self.fit_text(text, width);
+        for line in &mut text.lines {
+            for span in &mut line.spans {
+                span.content = self.header(std::mem::take(&mut span.content));
+            }
+        }
+
+        text
+    }
+
+    /// Rewrites a column header that isn't cut short with these symbols. This does nothing unless
+    /// only ASCII is allowed.
+    pub fn header<'a>(&self, header: Cow<'a, str>) -> Cow<'a, str> {
+        if !self.ascii_only || header.is_ascii() {
+            return header;
+        }
+
+        header
+            .graphemes(true)
+            .map(|g| match Self::header_fallback(g) {
+                Some(c) => c.to_string(),
+                None => g.to_string(),
+            })
+            .collect::<String>()
+            .into()
+    }
+
+    /// Swaps the ellipsis of `text`, which was already cut short to fit in `width`, for this
+    /// one. Anything else is left alone, as it may be data. This does nothing unless only ASCII
+    /// is allowed.
+    pub fn fit_text<'a>(&self, mut text: Text<'a>, width: u16) -> Text<'a> {
+        if !self.ascii_only {
+            return text;
+        }
+
+        for line in &mut text.lines {
+            let was_truncated = matches!(
+                line.spans.last(),
+                Some(span) if span.content.ends_with(Self::UNICODE.ellipsis)
+            );
+
+            // The ASCII ellipsis is wider, so make room for it.
+            if was_truncated {
+                if let Some((last, others)) = line.spans.split_last_mut() {
+                    let others_width: usize = others.iter().map(Span::width).sum();
+                    let room = usize::from(width).saturating_sub(others_width);
+                    let content =
+                        &last.content[..last.content.len() - Self::UNICODE.ellipsis.len()];
+
+                    let mut kept = String::new();
+                    let mut kept_width = 0;
+                    for g in content.graphemes(true) {
+                        if kept_width + g.width() + self.ellipsis.len() > room {
+                            break;
+                        }
+                        kept_width += g.width();
+                        kept.push_str(g);
+                    }
+                    kept.push_str(&self.ellipsis[..self.ellipsis.len().min(room - kept_width)]);
+
+                    last.content = kept.into();
+                }
+            }
+        }
+
+        text
+    }
+}
+
+/// Replaces the borders, graph axes, and graph markers that tui drew with ASCII ones. Other glyphs
+/// are left alone.
+struct AsciiFallback;
+
+impl Widget for AsciiFallback {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if let Some(replacement) = Symbols::line_fallback(&cell.symbol) {
+                    cell.set_char(replacement);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tui::layout::{Constraint, Layout};
+
+    use super::*;
+    use crate::{
+        app::layout_manager::BottomLayout,
+        canvas::{canvas_styling::CanvasStyling, Painter},
+        components::{
+            data_table::{
+                ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
+                DataToCell, DrawInfo, SelectionState, SortColumn, SortDataTableProps, SortOrder,
+                SortsRow,
+            },
+            tui_widget::pipe_gauge::PipeGauge,
+        },
+        utils::{gen_util::truncate_to_text, test_utils::*},
+    };
+
+    struct Process {
+        name: &'static str,
+        user: &'static str,
+    }
+
+    enum ProcessColumn {
+        Name,
+        User,
+    }
+
+    impl ColumnHeader for ProcessColumn {
+        fn text(&self) -> Cow<'static, str> {
+            match self {
+                ProcessColumn::Name => "Name".into(),
+                ProcessColumn::User => "ΔUser".into(),
+            }
+        }
+    }
+
+    impl SortsRow for ProcessColumn {
+        type DataType = Process;
+
+        fn sort_data(&self, _data: &mut [Process], _descending: bool) {}
+    }
+
+    impl DataToCell<ProcessColumn> for Process {
+        fn to_cell<'a>(
+            &'a self, column: &ProcessColumn, calculated_width: u16,
+        ) -> Option<Text<'a>> {
+            Some(match column {
+                ProcessColumn::Name => truncate_to_text(self.name, calculated_width),
+                ProcessColumn::User => truncate_to_text(self.user, calculated_width),
+            })
+        }
+
+        fn column_widths<C: DataTableColumn<ProcessColumn>>(
+            _data: &[Self], _columns: &[C],
+        ) -> Vec<u16>
+        where
+            Self: Sized,
+        {
+            vec![]
+        }
+    }
+
+    /// Draws a process table with a gauge below it, the same way the painter would.
+    fn draw_layout(symbols: Symbols) -> Vec<String> {
+        let colours = CanvasStyling::default().with_symbols(symbols);
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some(" Processes ".into()),
+                left_to_right: true,
+                ..Default::default()
+            },
+            sort_index: 0,
+            order: SortOrder::Ascending,
+        };
+        let columns = vec![
+            SortColumn::hard(ProcessColumn::Name, 10),
+            SortColumn::hard(ProcessColumn::User, 8),
+        ];
+        let mut table =
+            DataTable::new_sortable(columns, props, DataTableStyling::from_colours(&colours));
+        table.set_data(vec![
+            Process {
+                name: "bash",
+                user: "jürgen",
+            },
+            Process {
+                name: "a_very_long_name",
+                user: "•─…▲",
+            },
+        ]);
+
+        let painter = Painter::init(BottomLayout::init_basic_default(false), colours).unwrap();
+        let buffer = draw_to_buffer(24, 7, |f| {
+            let chunks = Layout::default()
+                .constraints([Constraint::Length(6), Constraint::Length(1)])
+                .split(f.size());
+            let draw_info = DrawInfo {
+                loc: chunks[0],
+                force_redraw: false,
+                recalculate_column_widths: true,
+                selection_state: SelectionState::NotSelected,
+            };
+            table.draw(f, &draw_info, None, &painter);
+            f.render_widget(
+                PipeGauge::default()
+                    .ratio(0.5)
+                    .start_label("RAM")
+                    .inner_label("50%"),
+                chunks[1],
+            );
+            painter.colours.symbols.draw_ascii_fallback(f);
+        });
+
+        buffer_lines(&buffer)
+    }
+
+    #[test]
+    fn test_unicode_symbols() {
+        assert_snapshot(
+            &draw_layout(Symbols::UNICODE),
+            "
+            |┌ Processes ───────────┐
+            |│Name▲       ΔUser     │
+            |│bash        jürgen    │
+            |│a_very_lon… •─…▲      │
+            |│                      │
+            |└──────────────────────┘
+            |RAM[||||||||||      50%]
+            ",
+        );
+    }
+
+    #[test]
+    fn test_ascii_symbols() {
+        // The ellipsis takes up more room, so less of the name fits. Line glyphs in the data are
+        // swapped too, but anything else is drawn as is.
+        assert_snapshot(
+            &draw_layout(Symbols::ASCII),
+            "
+            |+ Processes -----------+
+            ||Name^       dUser     |
+            ||bash        jürgen    |
+            ||a_very_l... *-…▲      |
+            ||                      |
+            |+----------------------+
+            |RAM[||||||||||      50%]
+            ",
+        );
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let sparkline = |symbols: Symbols, values: &[f64], width: u16| {
+            symbols.sparkline(values, width).lines[0].spans[0]
+                .content
+                .to_string()
+        };
+
+        let values = [0.0, 1.0, 2.0, 4.0, 8.0];
+        assert_eq!(sparkline(Symbols::UNICODE, &values, 5), " ▁▂▄█");
+        assert_eq!(sparkline(Symbols::UNICODE, &values, 3), "▂▄█");
+        assert_eq!(sparkline(Symbols::ASCII, &values, 5), " _.=#");
+        assert_eq!(
+            sparkline(Symbols::UNICODE, &[-1.0, f64::NAN, 2.0], 3),
+            "  █"
+        );
+        assert_eq!(sparkline(Symbols::UNICODE, &[0.0, 0.0], 2), "  ");
+        assert_eq!(sparkline(Symbols::UNICODE, &[], 4), "");
+        assert_eq!(sparkline(Symbols::UNICODE, &[1.0], 0), "");
+    }
+}
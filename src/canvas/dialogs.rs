@@ -1,2 +1,7 @@
+pub mod column_description_dialog;
 pub mod dd_dialog;
 pub mod help_dialog;
+pub mod layout_switcher_dialog;
+pub mod save_config_dialog;
+pub mod snapshot_dialog;
+pub mod snapshot_name_dialog;
@@ -0,0 +1,51 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::canvas::Painter;
+
+const COLUMN_DESCRIPTION_BASE: &str = " ── Esc to close ";
+
+impl Painter {
+    /// Draws a dialog with the description of the column named `name`.
+    pub fn draw_column_description_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, name: &str, description: &str, draw_loc: Rect,
+    ) {
+        let title = Line::from(vec![
+            Span::styled(format!(" {name} "), self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        COLUMN_DESCRIPTION_BASE.chars().count() + name.chars().count() + 4
+                    ))
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let text = vec![
+            Line::from(""),
+            Line::from(Span::styled(description, self.colours.text_style)),
+        ];
+
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
@@ -10,14 +10,16 @@ use tui::{
 };
 
 use crate::{
-    app::{App, KillSignal, MAX_PROCESS_SIGNAL},
+    app::{App, KillSignal, ProcessesToKill, MAX_PROCESS_SIGNAL},
     canvas::Painter,
-    widgets::ProcWidgetMode,
 };
 
 const DD_BASE: &str = " Confirm Kill Process ── Esc to close ";
 const DD_ERROR_BASE: &str = " Error ── Esc to close ";
 
+/// How many marked processes are listed in the kill dialog before the rest are only counted.
+const MAX_LISTED_PROCESSES: usize = 5;
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         const SIGNAL_TEXT: [&str; 63] = [
@@ -169,38 +171,55 @@ impl Painter {
                 Line::from(dd_err.clone()),
                 Line::from("Please press ENTER or ESC to close this dialog."),
             ]));
-        } else if let Some(to_kill_processes) = app_state.get_to_delete_processes() {
-            if let Some(first_pid) = to_kill_processes.1.first() {
-                return Some(Text::from(vec![
-                    Line::from(""),
-                    if app_state
-                        .states
-                        .proc_state
-                        .widget_states
-                        .get(&app_state.current_widget.widget_id)
-                        .map(|p| matches!(p.mode, ProcWidgetMode::Grouped))
-                        .unwrap_or(false)
-                    {
-                        if to_kill_processes.1.len() != 1 {
-                            Line::from(format!(
-                                "Kill {} processes with the name \"{}\"?  Press ENTER to confirm.",
-                                to_kill_processes.1.len(),
-                                to_kill_processes.0
-                            ))
-                        } else {
-                            Line::from(format!(
-                                "Kill 1 process with the name \"{}\"?  Press ENTER to confirm.",
-                                to_kill_processes.0
-                            ))
-                        }
+        } else if let Some(to_kill) = app_state.get_to_delete_processes() {
+            let confirm = "Press ENTER to confirm.";
+            let lines = match to_kill {
+                ProcessesToKill::Selected {
+                    name,
+                    pids,
+                    is_grouped,
+                } => {
+                    let first_pid = pids.first()?;
+                    let line = if !is_grouped {
+                        format!("Kill process \"{name}\" with PID {first_pid}?  {confirm}")
+                    } else if pids.len() != 1 {
+                        format!(
+                            "Kill {} processes with the name \"{name}\"?  {confirm}",
+                            pids.len()
+                        )
                     } else {
+                        format!("Kill 1 process with the name \"{name}\"?  {confirm}")
+                    };
+
+                    vec![Line::from(""), Line::from(line)]
+                }
+                ProcessesToKill::Marked(processes) => {
+                    let count = processes.len();
+                    let mut lines = vec![
+                        Line::from(""),
                         Line::from(format!(
-                            "Kill process \"{}\" with PID {}?  Press ENTER to confirm.",
-                            to_kill_processes.0, first_pid
-                        ))
-                    },
-                ]));
-            }
+                            "Kill {count} marked process{}?  {confirm}",
+                            if count == 1 { "" } else { "es" }
+                        )),
+                    ];
+                    lines.extend(
+                        processes
+                            .iter()
+                            .take(MAX_LISTED_PROCESSES)
+                            .map(|(pid, name)| Line::from(format!("\"{name}\" with PID {pid}"))),
+                    );
+                    if count > MAX_LISTED_PROCESSES {
+                        lines.push(Line::from(format!(
+                            "…and {} more",
+                            count - MAX_LISTED_PROCESSES
+                        )));
+                    }
+
+                    lines
+                }
+            };
+
+            return Some(Text::from(lines));
         }
 
         None
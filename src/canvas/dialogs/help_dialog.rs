@@ -16,22 +16,74 @@ const HELP_BASE: &str = " Help ── Esc to close ";
 
 // TODO: [REFACTOR] Make generic dialog boxes to build off of instead?
 impl Painter {
+    /// Returns the help text lines containing `query`, ignoring case, under the heading of the
+    /// section they are from. Sections without any matching lines are left out.
+    fn search_help_text(&self, query: &str) -> Vec<Line<'static>> {
+        let query = query.to_lowercase();
+        let mut lines = Vec::new();
+
+        for section in constants::HELP_TEXT.iter().skip(1) {
+            let mut matches = section[1..]
+                .iter()
+                .filter(|line| line.to_lowercase().contains(&query))
+                .peekable();
+
+            if matches.peek().is_some() {
+                if !lines.is_empty() {
+                    lines.push(Line::from(""));
+                }
+                lines.push(Line::from(Span::styled(
+                    section[0],
+                    self.colours.table_header_style,
+                )));
+                lines.extend(
+                    matches.map(|&line| Line::from(Span::styled(line, self.colours.text_style))),
+                );
+            }
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No matches",
+                self.colours.disabled_text_style,
+            )));
+        }
+
+        lines
+    }
+
     pub fn draw_help_dialog<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     ) {
+        let help_state = &app_state.help_dialog_state;
+        let search = if help_state.is_searching {
+            format!("/{}_ ", help_state.query)
+        } else if !help_state.query.is_empty() {
+            format!("/{} ", help_state.query)
+        } else {
+            String::new()
+        };
         let help_title = Line::from(vec![
             Span::styled(" Help ", self.colours.widget_title_style),
+            Span::styled(search.clone(), self.colours.text_style),
             Span::styled(
                 format!(
                     "─{}─ Esc to close ",
                     "─".repeat(
-                        usize::from(draw_loc.width).saturating_sub(HELP_BASE.chars().count() + 2)
+                        usize::from(draw_loc.width)
+                            .saturating_sub(HELP_BASE.chars().count() + search.width() + 2)
                     )
                 ),
                 self.colours.border_style,
             ),
         ]);
 
+        let help_text = if help_state.query.is_empty() {
+            self.styled_help_text.clone()
+        } else {
+            self.search_help_text(&help_state.query)
+        };
+
         let block = Block::default()
             .title(help_title)
             .style(self.colours.border_style)
@@ -76,9 +128,17 @@ impl Painter {
                     overflow_buffer += buffer;
                 });
 
+            // Search results don't line up with the sections, so count how they wrap instead.
+            if !app_state.help_dialog_state.query.is_empty() {
+                overflow_buffer = help_text
+                    .iter()
+                    .map(|line| line.width().saturating_sub(1) as u16 / paragraph_width)
+                    .sum();
+            }
+
             let max_scroll_index = &mut app_state.help_dialog_state.scroll_state.max_scroll_index;
-            *max_scroll_index = (self.styled_help_text.len() as u16 + 3 + overflow_buffer)
-                .saturating_sub(draw_loc.height + 1);
+            *max_scroll_index =
+                (help_text.len() as u16 + 3 + overflow_buffer).saturating_sub(draw_loc.height + 1);
 
             // Fix if over-scrolled
             let index = &mut app_state
@@ -90,7 +150,7 @@ impl Painter {
         }
 
         f.render_widget(
-            Paragraph::new(self.styled_help_text.clone())
+            Paragraph::new(help_text)
                 .block(block)
                 .style(self.colours.text_style)
                 .alignment(Alignment::Left)
@@ -0,0 +1,70 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{app::App, canvas::Painter};
+
+const LAYOUT_SWITCHER_BASE: &str = " Layouts ── Esc to close ";
+
+impl Painter {
+    /// Draws the list of layout profiles to switch between, with the `selected` one highlighted
+    /// and the one in use marked.
+    pub fn draw_layout_switcher_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, selected: usize, draw_loc: Rect,
+    ) {
+        let title = Line::from(vec![
+            Span::styled(" Layouts ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(LAYOUT_SWITCHER_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let mut text = vec![Line::from("")];
+        text.extend(
+            app_state
+                .layout_profiles
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    let label = if *name == app_state.active_layout_profile {
+                        format!("{name} (current)")
+                    } else {
+                        name.clone()
+                    };
+                    let style = if index == selected {
+                        self.colours.currently_selected_text_style
+                    } else {
+                        self.colours.text_style
+                    };
+
+                    Line::from(Span::styled(label, style))
+                }),
+        );
+        text.push(Line::from(""));
+
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center),
+            draw_loc,
+        );
+    }
+}
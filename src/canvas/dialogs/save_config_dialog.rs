@@ -0,0 +1,57 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::canvas::Painter;
+
+const SAVE_CONFIG_BASE: &str = " Save Config ── Esc to close ";
+
+impl Painter {
+    /// Draws the dialog asking whether to save the current settings to the config file.
+    pub fn draw_save_config_dialog<B: Backend>(&self, f: &mut Frame<'_, B>, draw_loc: Rect) {
+        let title = Line::from(vec![
+            Span::styled(" Save Config ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(SAVE_CONFIG_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let text = vec![
+            Line::from(""),
+            Line::from("Save the current settings to the config file?"),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("y", self.colours.currently_selected_text_style),
+                Span::styled(": Save    ", self.colours.text_style),
+                Span::styled("n", self.colours.currently_selected_text_style),
+                Span::styled(": Cancel", self.colours.text_style),
+            ]),
+        ];
+
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    canvas::Painter,
+    components::data_table::{DataTableStyling, DrawInfo, SelectionState},
+    widgets::SnapshotComparison,
+};
+
+impl Painter {
+    /// Draws the comparison between a process snapshot and the current processes.
+    pub fn draw_snapshot_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, comparison: &mut SnapshotComparison, force_redraw: bool,
+        draw_loc: Rect,
+    ) {
+        comparison.table.styling = DataTableStyling::from_colours(&self.colours);
+
+        let draw_info = DrawInfo {
+            loc: draw_loc,
+            force_redraw,
+            recalculate_column_widths: true,
+            selection_state: SelectionState::Selected,
+        };
+
+        comparison.table.draw(f, &draw_info, None, self);
+    }
+}
@@ -0,0 +1,87 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::canvas::Painter;
+
+const SNAPSHOT_NAME_BASE: &str = " Take Snapshot ── Esc to close ";
+
+impl Painter {
+    /// Draws the dialog asking for a name for a new process snapshot. `default_name` is the name
+    /// used if it's left empty.
+    pub fn draw_snapshot_name_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, name: &str, default_name: &str, draw_loc: Rect,
+    ) {
+        let title = Line::from(vec![
+            Span::styled(" Take Snapshot ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(SNAPSHOT_NAME_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        // Keep the end of a long name in view, as that's where it's being typed.
+        let max_width = usize::from(draw_loc.width.saturating_sub(3));
+        let mut width = 0;
+        let start = name
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| {
+                width += c.width().unwrap_or(0);
+                width <= max_width
+            })
+            .last()
+            .map_or(name.len(), |(index, _)| index);
+        let name = &name[start..];
+
+        let name_line = if name.is_empty() {
+            Line::from(vec![
+                Span::styled(default_name, self.colours.disabled_text_style),
+                Span::styled(" ", self.colours.currently_selected_text_style),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled(name, self.colours.text_style),
+                Span::styled(" ", self.colours.currently_selected_text_style),
+            ])
+        };
+        let text = vec![
+            Line::from(""),
+            Line::from("Name the snapshot:"),
+            name_line,
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Enter", self.colours.currently_selected_text_style),
+                Span::styled(": Take    ", self.colours.text_style),
+                Span::styled("Esc", self.colours.currently_selected_text_style),
+                Span::styled(": Cancel", self.colours.text_style),
+            ]),
+        ];
+
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center),
+            draw_loc,
+        );
+    }
+}
@@ -30,6 +30,31 @@ pub fn should_hide_x_label(
     }
 }
 
+/// Returns the largest [`Rect`] with a `width_ratio:height_ratio` aspect ratio that fits within
+/// `area`, centered in it.
+pub fn aspect_rect(area: Rect, width_ratio: u16, height_ratio: u16) -> Rect {
+    if width_ratio == 0 || height_ratio == 0 {
+        return area;
+    }
+
+    let (width_ratio, height_ratio) = (u32::from(width_ratio), u32::from(height_ratio));
+    let (area_width, area_height) = (u32::from(area.width), u32::from(area.height));
+
+    // Use the full width if the matching height fits, and otherwise the full height.
+    let (width, height) = if area_width * height_ratio <= area_height * width_ratio {
+        (area_width, area_width * height_ratio / width_ratio)
+    } else {
+        (area_height * width_ratio / height_ratio, area_height)
+    };
+
+    Rect::new(
+        area.x + ((area_width - width) / 2) as u16,
+        area.y + ((area_height - height) / 2) as u16,
+        width as u16,
+        height as u16,
+    )
+}
+
 #[cfg(test)]
 mod test {
 
@@ -84,4 +109,25 @@ mod test {
         ));
         assert!(over_timer.is_none());
     }
+
+    #[test]
+    fn test_aspect_rect() {
+        // A wide area is letterboxed on the sides.
+        assert_eq!(
+            aspect_rect(Rect::new(0, 0, 100, 20), 2, 1),
+            Rect::new(30, 0, 40, 20)
+        );
+
+        // A tall area is letterboxed above and below.
+        assert_eq!(
+            aspect_rect(Rect::new(10, 5, 40, 40), 2, 1),
+            Rect::new(10, 15, 40, 20)
+        );
+
+        // An area already at the ratio is left as is.
+        assert_eq!(
+            aspect_rect(Rect::new(3, 4, 20, 10), 2, 1),
+            Rect::new(3, 4, 20, 10)
+        );
+    }
 }
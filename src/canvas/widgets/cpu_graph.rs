@@ -3,7 +3,6 @@ use std::borrow::Cow;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
     terminal::Frame,
 };
 
@@ -119,22 +118,26 @@ impl Painter {
         }
     }
 
+    /// Returns the series to draw, along with the symbol to draw each with if series are told apart
+    /// by symbols rather than colours.
     fn generate_points<'a>(
         &self, cpu_widget_state: &CpuWidgetState, cpu_data: &'a [CpuWidgetData], show_avg_cpu: bool,
-    ) -> Vec<GraphData<'a>> {
+    ) -> (Vec<GraphData<'a>>, Vec<char>) {
         let show_avg_offset = if show_avg_cpu { AVG_POSITION } else { 0 };
 
         let current_scroll_position = cpu_widget_state.table.state.current_index;
         if current_scroll_position == ALL_POSITION {
             // This case ensures the other cases cannot have the position be equal to 0.
-            cpu_data
+            let (points, symbols): (Vec<_>, Vec<_>) = cpu_data
                 .iter()
                 .enumerate()
                 .rev()
                 .filter_map(|(itx, cpu)| {
                     match &cpu {
                         CpuWidgetData::All => None,
-                        CpuWidgetData::Entry { data, .. } => {
+                        CpuWidgetData::Entry {
+                            data, data_type, ..
+                        } => {
                             let style = if show_avg_cpu && itx == AVG_POSITION {
                                 self.colours.avg_colour_style
                             } else if itx == ALL_POSITION {
@@ -145,17 +148,24 @@ impl Painter {
                                     % self.colours.cpu_colour_styles.len()]
                             };
 
-                            Some(GraphData {
-                                points: &data[..],
-                                style,
-                                name: None,
-                            })
+                            Some((
+                                GraphData {
+                                    points: &data[..],
+                                    style,
+                                    name: None,
+                                },
+                                cpu_widget_state.styling.series_symbol(*data_type),
+                            ))
                         }
                     }
                 })
-                .collect::<Vec<_>>()
-        } else if let Some(CpuWidgetData::Entry { data, .. }) =
-            cpu_data.get(current_scroll_position)
+                .unzip();
+
+            // Either every series has a symbol, or none do.
+            (points, symbols.into_iter().flatten().collect())
+        } else if let Some(CpuWidgetData::Entry {
+            data, data_type, ..
+        }) = cpu_data.get(current_scroll_position)
         {
             let style = if show_avg_cpu && current_scroll_position == AVG_POSITION {
                 self.colours.avg_colour_style
@@ -165,13 +175,20 @@ impl Painter {
                     [(offset_position - show_avg_offset) % self.colours.cpu_colour_styles.len()]
             };
 
-            vec![GraphData {
-                points: &data[..],
-                style,
-                name: None,
-            }]
+            (
+                vec![GraphData {
+                    points: &data[..],
+                    style,
+                    name: None,
+                }],
+                cpu_widget_state
+                    .styling
+                    .series_symbol(*data_type)
+                    .into_iter()
+                    .collect(),
+            )
         } else {
-            vec![]
+            (vec![], vec![])
         }
     }
 
@@ -193,7 +210,7 @@ impl Painter {
                 draw_loc,
             );
 
-            let points = self.generate_points(
+            let (points, series_symbols) = self.generate_points(
                 cpu_widget_state,
                 cpu_data,
                 app_state.app_config_fields.show_average_cpu,
@@ -217,11 +234,10 @@ impl Painter {
                 }
             };
 
-            let marker = if app_state.app_config_fields.use_dot {
-                Marker::Dot
-            } else {
-                Marker::Braille
-            };
+            let marker = self
+                .colours
+                .symbols
+                .graph_marker(app_state.app_config_fields.use_dot);
 
             TimeGraph {
                 x_bounds,
@@ -235,6 +251,8 @@ impl Painter {
                 title_style: self.colours.widget_title_style,
                 legend_constraints: None,
                 marker,
+                series_symbols: &series_symbols,
+                time_cursor: cpu_widget_state.time_cursor,
             }
             .draw_time_graph(f, draw_loc, &points);
         }
@@ -3,14 +3,16 @@ use std::borrow::Cow;
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
-    symbols::Marker,
     terminal::Frame,
 };
 
 use crate::{
     app::App,
     canvas::{drawing_utils::should_hide_x_label, Painter},
-    components::time_graph::{GraphData, TimeGraph},
+    components::{
+        time_graph::{value_at, GraphData, TimeGraph},
+        tui_widget::time_chart::Point,
+    },
 };
 
 impl Painter {
@@ -30,6 +32,16 @@ impl Painter {
                 &mut mem_widget_state.autohide_timer,
                 draw_loc,
             );
+            let time_cursor = mem_widget_state.time_cursor;
+            // While scrubbing, each series is labelled with its usage at the time cursor instead.
+            let label = |name: &str, points: &[Point], percent: &str, frac: &str| match time_cursor
+            {
+                Some(cursor) => match value_at(points, -(cursor as f64)) {
+                    Some(value) => format!("{name}:{value:3.0}%"),
+                    None => format!("{name}: N/A"),
+                },
+                None => format!("{name}:{percent}{frac}"),
+            };
             let points = {
                 let mut size = 1;
                 if app_state.converted_data.swap_labels.is_some() {
@@ -50,7 +62,12 @@ impl Painter {
 
                 let mut points = Vec::with_capacity(size);
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.mem_labels {
-                    let mem_label = format!("RAM:{}{}", label_percent, label_frac);
+                    let mem_label = label(
+                        "RAM",
+                        &app_state.converted_data.mem_data,
+                        label_percent,
+                        label_frac,
+                    );
                     points.push(GraphData {
                         points: &app_state.converted_data.mem_data,
                         style: self.colours.ram_style,
@@ -59,7 +76,12 @@ impl Painter {
                 }
                 #[cfg(not(target_os = "windows"))]
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.cache_labels {
-                    let cache_label = format!("CHE:{}{}", label_percent, label_frac);
+                    let cache_label = label(
+                        "CHE",
+                        &app_state.converted_data.cache_data,
+                        label_percent,
+                        label_frac,
+                    );
                     points.push(GraphData {
                         points: &app_state.converted_data.cache_data,
                         style: self.colours.cache_style,
@@ -67,7 +89,12 @@ impl Painter {
                     });
                 }
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.swap_labels {
-                    let swap_label = format!("SWP:{}{}", label_percent, label_frac);
+                    let swap_label = label(
+                        "SWP",
+                        &app_state.converted_data.swap_data,
+                        label_percent,
+                        label_frac,
+                    );
                     points.push(GraphData {
                         points: &app_state.converted_data.swap_data,
                         style: self.colours.swap_style,
@@ -76,7 +103,12 @@ impl Painter {
                 }
                 #[cfg(feature = "zfs")]
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.arc_labels {
-                    let arc_label = format!("ARC:{}{}", label_percent, label_frac);
+                    let arc_label = label(
+                        "ARC",
+                        &app_state.converted_data.arc_data,
+                        label_percent,
+                        label_frac,
+                    );
                     points.push(GraphData {
                         points: &app_state.converted_data.arc_data,
                         style: self.colours.arc_style,
@@ -90,7 +122,7 @@ impl Painter {
                         let gpu_styles = &self.colours.gpu_colour_styles;
                         gpu_data.iter().for_each(|gpu| {
                             let gpu_label =
-                                format!("{}:{}{}", gpu.name, gpu.mem_percent, gpu.mem_total);
+                                label(&gpu.name, &gpu.points, &gpu.mem_percent, &gpu.mem_total);
                             let style = {
                                 if gpu_styles.is_empty() {
                                     tui::style::Style::default()
@@ -115,11 +147,10 @@ impl Painter {
                 points
             };
 
-            let marker = if app_state.app_config_fields.use_dot {
-                Marker::Dot
-            } else {
-                Marker::Braille
-            };
+            let marker = self
+                .colours
+                .symbols
+                .graph_marker(app_state.app_config_fields.use_dot);
 
             TimeGraph {
                 x_bounds,
@@ -133,6 +164,8 @@ impl Painter {
                 title_style: self.colours.widget_title_style,
                 legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
                 marker,
+                series_symbols: self.colours.series_symbols,
+                time_cursor,
             }
             .draw_time_graph(f, draw_loc, &points);
         }
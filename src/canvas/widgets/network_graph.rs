@@ -1,7 +1,6 @@
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
     terminal::Frame,
     text::Text,
     widgets::{Block, Borders, Row, Table},
@@ -14,6 +13,7 @@ use crate::{
         time_graph::{GraphData, TimeGraph},
         tui_widget::time_chart::Point,
     },
+    data_conversion::network_rate_at,
     utils::{data_units::DataUnit, gen_util::*},
 };
 
@@ -59,7 +59,9 @@ impl Painter {
         {
             let network_data_rx = &app_state.converted_data.network_data_rx;
             let network_data_tx = &app_state.converted_data.network_data_tx;
-            let time_start = -(network_widget_state.current_display_time as f64);
+            let time_cursor = network_widget_state.time_cursor;
+            let time_start =
+                -((network_widget_state.current_display_time + time_cursor.unwrap_or(0)) as f64);
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
             let x_bounds = [0, network_widget_state.current_display_time];
             let hide_x_labels = should_hide_x_label(
@@ -90,7 +92,11 @@ impl Painter {
                 app_state.app_config_fields.network_use_binary_prefix,
             );
 
-            let y_labels = labels.iter().map(|label| label.into()).collect::<Vec<_>>();
+            let number_format = app_state.app_config_fields.number_format;
+            let y_labels = labels
+                .iter()
+                .map(|label| number_format.apply(label).into())
+                .collect::<Vec<_>>();
             let y_bounds = [0.0, max_range];
 
             let legend_constraints = if hide_legend {
@@ -99,8 +105,37 @@ impl Painter {
                 (Constraint::Ratio(1, 1), Constraint::Ratio(3, 4))
             };
 
+            // While scrubbing, the legend shows the rates at the time cursor instead.
+            let rates_at_cursor = time_cursor.map(|cursor| {
+                let rate_at = |points| {
+                    network_rate_at(
+                        points,
+                        -(cursor as f64),
+                        &app_state.app_config_fields.network_scale_type,
+                        &app_state.app_config_fields.network_unit_type,
+                        app_state.app_config_fields.network_use_binary_prefix,
+                    )
+                    .unwrap_or_else(|| "N/A".to_string())
+                };
+
+                (rate_at(network_data_rx), rate_at(network_data_tx))
+            });
+
             // TODO: Add support for clicking on legend to only show that value on chart.
-            let points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+            let points = if let Some((rx, tx)) = rates_at_cursor {
+                vec![
+                    GraphData {
+                        points: network_data_rx,
+                        style: self.colours.rx_style,
+                        name: Some(format!("RX: {rx}").into()),
+                    },
+                    GraphData {
+                        points: network_data_tx,
+                        style: self.colours.tx_style,
+                        name: Some(format!("TX: {tx}").into()),
+                    },
+                ]
+            } else if app_state.app_config_fields.use_old_network_legend && !hide_legend {
                 vec![
                     GraphData {
                         points: network_data_rx,
@@ -144,11 +179,10 @@ impl Painter {
                 ]
             };
 
-            let marker = if app_state.app_config_fields.use_dot {
-                Marker::Dot
-            } else {
-                Marker::Braille
-            };
+            let marker = self
+                .colours
+                .symbols
+                .graph_marker(app_state.app_config_fields.use_dot);
 
             TimeGraph {
                 x_bounds,
@@ -162,6 +196,8 @@ impl Painter {
                 title_style: self.colours.widget_title_style,
                 legend_constraints: Some(legend_constraints),
                 marker,
+                series_symbols: self.colours.series_symbols,
+                time_cursor,
             }
             .draw_time_graph(f, draw_loc, &points);
         }
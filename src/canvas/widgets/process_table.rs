@@ -87,6 +87,7 @@ impl Painter {
                 should_get_widget_bounds || proc_widget_state.force_rerender;
 
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            proc_widget_state.update_title();
 
             let draw_info = DrawInfo {
                 loc: draw_loc,
@@ -212,6 +213,12 @@ impl Painter {
                 self.colours.text_style
             };
 
+            let word_boundary_style = if proc_widget_state.proc_search.is_matching_word_boundary {
+                self.colours.currently_selected_text_style
+            } else {
+                self.colours.text_style
+            };
+
             let regex_style = if proc_widget_state.proc_search.is_searching_with_regex {
                 self.colours.currently_selected_text_style
             } else {
@@ -220,12 +227,12 @@ impl Painter {
 
             // TODO: [MOUSE] Mouse support for these in search
             // TODO: [MOVEMENT] Movement support for these in search
-            let (case, whole, regex) = {
+            let (case, whole, regex, word) = {
                 cfg_if::cfg_if! {
                     if #[cfg(target_os = "macos")] {
-                        ("Case(F1)", "Whole(F2)", "Regex(F3)")
+                        ("Case(F1)", "Whole(F2)", "Regex(F3)", "Word(F4)")
                     } else {
-                        ("Case(Alt+C)", "Whole(Alt+W)", "Regex(Alt+R)")
+                        ("Case(Alt+C)", "Whole(Alt+W)", "Regex(Alt+R)", "Word(Alt+B)")
                     }
                 }
             };
@@ -235,6 +242,8 @@ impl Painter {
                 Span::styled(whole, whole_word_style),
                 Span::raw("  "),
                 Span::styled(regex, regex_style),
+                Span::raw("  "),
+                Span::styled(word, word_boundary_style),
             ]);
 
             search_text.push(Line::from(Span::styled(
@@ -1,4 +1,6 @@
-use std::{convert::TryInto, marker::PhantomData};
+use std::{borrow::Cow, cmp::max, marker::PhantomData, mem, slice, time::Duration};
+
+use hashbrown::{HashMap, HashSet};
 
 pub mod column;
 pub use column::*;
@@ -10,7 +12,7 @@ pub mod props;
 pub use props::DataTableProps;
 
 pub mod state;
-pub use state::{DataTableState, ScrollDirection};
+pub use state::{DataTableState, ScrollDirection, ScrollSignal};
 
 pub mod draw;
 pub use draw::*;
@@ -21,6 +23,26 @@ pub use data_type::*;
 pub mod sortable;
 pub use sortable::*;
 
+pub mod row_deltas;
+pub use row_deltas::*;
+
+use crate::{
+    constants::{CLICK_FLASH_DURATION_IN_MILLISECONDS, DOUBLE_CLICK_INTERVAL_IN_MILLISECONDS},
+    utils::clock::{Clock, RealClock},
+};
+
+/// A predicate deciding whether a row should be shown in a [`DataTable`].
+pub type RowFilter<DataType> = Box<dyn Fn(&DataType) -> bool>;
+
+/// Where a [`DataTable`] was scrolled to, to return to later with
+/// [`DataTable::restore_scroll_position`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollPosition {
+    row_id: Option<u64>,
+    current_index: usize,
+    display_start_index: usize,
+}
+
 /// A [`DataTable`] is a component that displays data in a tabular form.
 ///
 /// Note that [`DataTable`] takes a generic type `S`, bounded by [`SortType`]. This controls whether this table
@@ -36,8 +58,27 @@ pub struct DataTable<DataType, Header, S = Unsortable, C = Column<Header>> {
     pub props: DataTableProps,
     pub styling: DataTableStyling,
     data: Vec<DataType>,
+    row_filter: Option<RowFilter<DataType>>,
+    max_rows: Option<usize>,
+    pinned_bottom_rows: usize,
+    expanded_view: bool,
+    marked_ids: HashSet<u64>,
+    row_details: bool,
+    expanded_rows: HashSet<usize>,
+    paged: bool,
+    width_mode: ColumnWidthMode,
+    narrow_mode: NarrowMode,
     sort_type: S,
     first_draw: bool,
+    is_expanded: bool,
+    widths_outdated: bool,
+    /// The column widths each row needs, in the same order as `data`, or [`None`] if the row
+    /// hasn't been formatted yet.
+    row_widths: Vec<Option<Vec<u16>>>,
+    /// The columns `row_widths` were found for, as each column's text, whether it is hidden, and
+    /// whether it is soft-bounded.
+    row_widths_columns: Vec<(Cow<'static, str>, bool, bool)>,
+    clock: Box<dyn Clock>,
     _pd: PhantomData<(DataType, S, Header)>,
 }
 
@@ -51,8 +92,23 @@ impl<DataType: DataToCell<H>, H: ColumnHeader> DataTable<DataType, H, Unsortable
             props,
             styling,
             data: vec![],
+            row_filter: None,
+            max_rows: None,
+            pinned_bottom_rows: 0,
+            expanded_view: false,
+            marked_ids: HashSet::default(),
+            row_details: false,
+            expanded_rows: HashSet::default(),
+            paged: false,
+            width_mode: ColumnWidthMode::default(),
+            narrow_mode: NarrowMode::default(),
             sort_type: Unsortable,
             first_draw: true,
+            is_expanded: false,
+            widths_outdated: false,
+            row_widths: vec![],
+            row_widths_columns: vec![],
+            clock: Box::new(RealClock),
             _pd: PhantomData,
         }
     }
@@ -61,93 +117,640 @@ impl<DataType: DataToCell<H>, H: ColumnHeader> DataTable<DataType, H, Unsortable
 impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H>>
     DataTable<DataType, H, S, C>
 {
+    /// Caps the number of rows kept to `max_rows`, dropping the oldest rows once there are more.
+    /// [`None`] keeps every row.
+    pub fn with_max_rows(mut self, max_rows: Option<usize>) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Only keeps rows that pass `row_filter` whenever data is set or pushed. Widgets apply their
+    /// own searches before handing rows over, so rows have to pass both to be shown.
+    pub fn with_row_filter(mut self, row_filter: RowFilter<DataType>) -> Self {
+        self.row_filter = Some(row_filter);
+        self
+    }
+
+    /// Replaces the row filter, or removes it with [`None`]. This takes effect on the next data
+    /// update.
+    pub fn set_row_filter(&mut self, row_filter: Option<RowFilter<DataType>>) {
+        self.row_filter = row_filter;
+    }
+
+    /// Pins the last `pinned_bottom_rows` rows to the bottom of the table, so they stay visible
+    /// while the rows above them scroll, like a row of totals. Pinned rows can't be selected, and
+    /// aren't counted in the title. At least one row is always left to scroll, so fewer rows may
+    /// be pinned if the table is too short.
+    pub fn with_pinned_bottom_rows(mut self, pinned_bottom_rows: usize) -> Self {
+        self.pinned_bottom_rows = pinned_bottom_rows;
+        self
+    }
+
+    /// Shows the selected row's full values below the table, one "column: value" line per column,
+    /// so cells that are cut short in the table can still be read.
+    pub fn with_expanded_view(mut self, expanded_view: bool) -> Self {
+        self.expanded_view = expanded_view;
+        self
+    }
+
+    /// Shows or hides the selected row's full values below the table. See
+    /// [`DataTable::with_expanded_view`].
+    pub fn set_expanded_view(&mut self, expanded_view: bool) {
+        self.expanded_view = expanded_view;
+    }
+
+    /// Lets rows be expanded with [`DataTable::toggle_row_details`] to show their full values in a
+    /// block beneath them, one "column: value" line per column, pushing the rows below down.
+    pub fn with_row_details(mut self, row_details: bool) -> Self {
+        self.row_details = row_details;
+        self
+    }
+
+    /// Splits the rows into pages that fill the table, rather than scrolling through them. The
+    /// title then shows the current page, and [`DataTable::move_pages`] moves between pages.
+    /// Row details aren't shown while paged, as each page holds a fixed number of rows.
+    pub fn with_paging(mut self, paged: bool) -> Self {
+        self.paged = paged;
+        self
+    }
+
+    /// Switches between paging and scrolling. The selected row stays selected either way, and
+    /// the next draw shows whichever page or window it is in.
+    pub fn set_paged(&mut self, paged: bool) {
+        self.paged = paged;
+    }
+
+    /// Returns whether the rows are split into pages. See [`DataTable::with_paging`].
+    pub fn is_paged(&self) -> bool {
+        self.paged
+    }
+
+    /// Returns the current page and the number of pages, counting from 1, if the table is paged
+    /// and has been drawn. Pinned rows are on every page, so they aren't counted.
+    pub fn page(&self) -> Option<(usize, usize)> {
+        let page_size = self.state.page_size;
+        if !self.paged || page_size == 0 {
+            return None;
+        }
+
+        let last_index = self.last_selectable_index();
+        let num_pages = last_index / page_size + 1;
+        let current_page = self.state.current_index.min(last_index) / page_size;
+
+        Some((current_page + 1, num_pages))
+    }
+
+    /// Moves the selection `pages` pages forward (or back, if negative), keeping it at the same
+    /// place within the page if it can; on the last page, it stops at the last row. Returns
+    /// whether the table is paged, as this does nothing otherwise.
+    pub fn move_pages(&mut self, pages: i64) -> bool {
+        let page_size = self.state.page_size;
+        if !self.paged || page_size == 0 {
+            return false;
+        }
+
+        let change = pages.saturating_mul(page_size as i64);
+        let new_index = (self.state.current_index as i64)
+            .saturating_add(change)
+            .max(0);
+        self.set_position(new_index as usize);
+
+        true
+    }
+
+    /// Sets how the calculated column widths are turned into constraints when drawing. See
+    /// [`ColumnWidthMode`].
+    pub fn with_width_mode(mut self, width_mode: ColumnWidthMode) -> Self {
+        self.width_mode = width_mode;
+        self
+    }
+
+    /// Sets how the table is drawn once it is too narrow to fit all of its columns. See
+    /// [`NarrowMode`].
+    pub fn with_narrow_mode(mut self, narrow_mode: NarrowMode) -> Self {
+        self.narrow_mode = narrow_mode;
+        self
+    }
+
     /// Sets the scroll position to the first value.
     pub fn set_first(&mut self) {
         self.state.current_index = 0;
-        self.state.scroll_direction = ScrollDirection::Up;
+        self.state.window.direction = ScrollDirection::Up;
     }
 
     /// Sets the scroll position to the last value.
     pub fn set_last(&mut self) {
-        self.state.current_index = self.data.len().saturating_sub(1);
-        self.state.scroll_direction = ScrollDirection::Down;
+        self.state.current_index = self.last_selectable_index();
+        self.state.window.direction = ScrollDirection::Down;
+    }
+
+    /// Returns the number of rows that aren't pinned, i.e. the ones that scroll.
+    pub(crate) fn scrolled_len(&self) -> usize {
+        self.data.len().saturating_sub(self.pinned_bottom_rows)
+    }
+
+    /// Returns the index of the last row that can be selected, which is the last row that isn't
+    /// pinned.
+    fn last_selectable_index(&self) -> usize {
+        self.scrolled_len().saturating_sub(1)
     }
 
-    /// Updates the scroll position to be valid for the number of entries.
-    pub fn set_data(&mut self, data: Vec<DataType>) {
-        self.data = data;
-        let max_pos = self.data.len().saturating_sub(1);
+    /// Updates the scroll position to be valid for the number of entries. If rows have a
+    /// [`DataToCell::row_id`], the selected row stays selected wherever it moved to, and marks on
+    /// rows that are gone are dropped.
+    ///
+    /// Soft-bounded columns are also resized to fit the new data.
+    pub fn set_data(&mut self, mut data: Vec<DataType>) {
+        if let Some(row_filter) = &self.row_filter {
+            data.retain(|row| row_filter(row));
+        }
+        let selected_id = self.current_item().and_then(DataType::row_id);
+        let expanded_ids: HashSet<u64> = self
+            .expanded_rows
+            .iter()
+            .filter_map(|&index| self.data.get(index).and_then(DataType::row_id))
+            .collect();
+        let old_data = mem::replace(&mut self.data, data);
+        self.drop_excess_rows();
+        self.reuse_row_widths(&old_data);
+
+        // Like the selection, expanded rows with IDs stay expanded wherever they moved to.
+        if expanded_ids.is_empty() {
+            let len = self.data.len();
+            self.expanded_rows.retain(|&index| index < len);
+        } else {
+            self.expanded_rows = self
+                .data
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| {
+                    row.row_id()
+                        .map(|id| expanded_ids.contains(&id))
+                        .unwrap_or(false)
+                })
+                .map(|(index, _)| index)
+                .collect();
+        }
+        if self.update_desired_widths() {
+            self.widths_outdated = true;
+        }
+
+        if !self.marked_ids.is_empty() {
+            let row_ids: HashSet<u64> = self.data.iter().filter_map(DataType::row_id).collect();
+            self.marked_ids.retain(|id| row_ids.contains(id));
+        }
+
+        if let Some(index) = selected_id.and_then(|selected_id| {
+            self.data
+                .iter()
+                .position(|row| row.row_id() == Some(selected_id))
+        }) {
+            self.set_position(index);
+            return;
+        }
+
+        let max_pos = self.last_selectable_index();
         if self.state.current_index > max_pos {
             self.state.current_index = max_pos;
-            self.state.display_start_index = 0;
-            self.state.scroll_direction = ScrollDirection::Down;
+            self.state.window.reset();
         }
     }
 
+    /// Appends `rows` after the existing data, like for a log. Pinned bottom rows stay at the
+    /// bottom, with the new rows going in above them. If this goes over the maximum number of rows,
+    /// the oldest rows are dropped, and the selection and scroll position move with the rows that
+    /// are kept. If the last selectable row was selected, the new last one is selected instead, so
+    /// the table keeps following new rows.
+    pub fn push_data<I: IntoIterator<Item = DataType>>(&mut self, rows: I) {
+        let is_following = self.state.current_index >= self.last_selectable_index();
+
+        let at = self.scrolled_len();
+        let old_len = self.data.len();
+        let row_filter = &self.row_filter;
+        self.data.splice(
+            at..at,
+            rows.into_iter()
+                .filter(|row| row_filter.as_ref().map(|keep| keep(row)).unwrap_or(true)),
+        );
+        let pushed = self.data.len() - old_len;
+        if self.row_widths.len() > at {
+            self.row_widths.splice(at..at, (0..pushed).map(|_| None));
+        }
+        let dropped = self.drop_excess_rows();
+        self.row_widths.drain(..dropped.min(self.row_widths.len()));
+        if self.update_desired_widths() {
+            self.widths_outdated = true;
+        }
+
+        if is_following {
+            self.set_last();
+        } else {
+            self.state.current_index = self.state.current_index.saturating_sub(dropped);
+        }
+        self.state.window.start = self.state.window.start.saturating_sub(dropped);
+
+        // Pinned rows moved down past the new rows, and every row moved up past the dropped ones.
+        let shift = |index: usize| {
+            let index = if index >= at { index + pushed } else { index };
+            index.checked_sub(dropped)
+        };
+        self.expanded_rows = self
+            .expanded_rows
+            .iter()
+            .filter_map(|&index| shift(index))
+            .collect();
+        let shift_click = |click: Option<(usize, _)>| {
+            click.and_then(|(index, clicked)| Some((shift(index)?, clicked)))
+        };
+        self.state.last_click = shift_click(self.state.last_click);
+        self.state.flash = shift_click(self.state.flash);
+    }
+
+    /// Drops the oldest rows past the maximum number of rows, returning how many were dropped.
+    fn drop_excess_rows(&mut self) -> usize {
+        let excess = match self.max_rows {
+            Some(max_rows) => self.data.len().saturating_sub(max_rows),
+            None => 0,
+        };
+        self.data.drain(..excess);
+
+        excess
+    }
+
+    /// Carries over the widths found for rows of `old_data` to the rows of the current data that
+    /// show the same, so only rows that changed are formatted again.
+    fn reuse_row_widths(&mut self, old_data: &[DataType]) {
+        let mut old_widths = mem::take(&mut self.row_widths);
+        let old_indices: HashMap<u64, usize> = old_data
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| Some((row.row_id()?, index)))
+            .collect();
+
+        self.row_widths = self
+            .data
+            .iter()
+            .map(|row| {
+                let index = *old_indices.get(&row.row_id()?)?;
+                if row.same_cells(&old_data[index]) {
+                    old_widths.get_mut(index)?.take()
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    /// Updates the desired widths of soft-bounded columns to fit both their header and the current
+    /// data. Returns whether any of them changed.
+    fn update_desired_widths(&mut self) -> bool {
+        // Widths found for other columns are no use.
+        let columns: Vec<_> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let is_soft = matches!(column.bounds(), ColumnWidthBounds::Soft { .. });
+                (column.inner().text(), column.is_hidden(), is_soft)
+            })
+            .collect();
+        if columns != self.row_widths_columns {
+            self.row_widths_columns = columns;
+            self.row_widths.clear();
+        }
+        self.row_widths.resize(self.data.len(), None);
+
+        let mut col_widths = DataType::column_widths(&[], &self.columns);
+        for (row, widths) in self.data.iter().zip(&mut self.row_widths) {
+            let widths = widths.get_or_insert_with(|| {
+                DataType::column_widths(slice::from_ref(row), &self.columns)
+            });
+            if col_widths.len() < widths.len() {
+                col_widths.resize(widths.len(), 0);
+            }
+            for (col_width, &width) in col_widths.iter_mut().zip(widths.iter()) {
+                *col_width = max(*col_width, width);
+            }
+        }
+
+        let mut changed = false;
+
+        for (column, &width) in self.columns.iter_mut().zip(&col_widths) {
+            let header_len = u16::try_from(column.header_len()).unwrap_or(u16::MAX);
+            if let ColumnWidthBounds::Soft { desired, .. } = column.bounds_mut() {
+                let new_desired = max(header_len, width);
+                changed |= *desired != new_desired;
+                *desired = new_desired;
+            }
+        }
+
+        changed
+    }
+
     /// Increments the scroll position if possible by a positive/negative offset. If there is a
     /// valid change, this function will also return the new position wrapped in an [`Option`].
     pub fn increment_position(&mut self, change: i64) -> Option<usize> {
-        let max_index = self.data.len();
-        let current_index = self.state.current_index;
+        match self.scroll(change) {
+            ScrollSignal::Moved(index) => Some(index),
+            ScrollSignal::Boundary | ScrollSignal::Unchanged => None,
+        }
+    }
 
-        if change == 0
-            || (change > 0 && current_index == max_index)
-            || (change < 0 && current_index == 0)
-        {
-            return None;
+    /// Like [`DataTable::increment_position`], but reports whether the selection didn't move
+    /// because it was already at the first or last row, e.g. to wrap around. A change that would
+    /// go past either end stops at that end instead.
+    pub fn scroll(&mut self, change: i64) -> ScrollSignal {
+        if change == 0 {
+            return ScrollSignal::Unchanged;
         }
 
-        let csp: Result<i64, _> = self.state.current_index.try_into();
-        if let Ok(csp) = csp {
-            let proposed: Result<usize, _> = (csp + change).try_into();
-            if let Ok(proposed) = proposed {
-                if proposed < self.data.len() {
-                    self.state.current_index = proposed;
-                    self.state.scroll_direction = if change < 0 {
-                        ScrollDirection::Up
-                    } else {
-                        ScrollDirection::Down
-                    };
+        let current_index = i64::try_from(self.state.current_index).unwrap_or(i64::MAX);
+        let last_index = i64::try_from(self.last_selectable_index()).unwrap_or(i64::MAX);
+        let proposed = current_index.saturating_add(change).clamp(0, last_index);
 
-                    return Some(self.state.current_index);
-                }
+        match usize::try_from(proposed) {
+            Ok(proposed) if proposed != self.state.current_index && self.scrolled_len() > 0 => {
+                self.state.current_index = proposed;
+                self.state.window.direction = if change < 0 {
+                    ScrollDirection::Up
+                } else {
+                    ScrollDirection::Down
+                };
+
+                ScrollSignal::Moved(proposed)
             }
+            _ => ScrollSignal::Boundary,
         }
-
-        None
     }
 
     /// Updates the scroll position to a selected index.
     #[allow(clippy::comparison_chain)]
     pub fn set_position(&mut self, new_index: usize) {
-        let new_index = new_index.clamp(0, self.data.len().saturating_sub(1));
+        let new_index = new_index.min(self.last_selectable_index());
         if self.state.current_index < new_index {
-            self.state.scroll_direction = ScrollDirection::Down;
+            self.state.window.direction = ScrollDirection::Down;
         } else if self.state.current_index > new_index {
-            self.state.scroll_direction = ScrollDirection::Up;
+            self.state.window.direction = ScrollDirection::Up;
         }
         self.state.current_index = new_index;
     }
 
+    /// Returns where the table is scrolled to.
+    pub fn scroll_position(&self) -> ScrollPosition {
+        ScrollPosition {
+            row_id: self.current_item().and_then(DataType::row_id),
+            current_index: self.state.current_index,
+            display_start_index: self.state.window.start,
+        }
+    }
+
+    /// Scrolls back to `position`. The row that was selected is selected again if it is still
+    /// there; otherwise the same index is, as far as the current rows allow.
+    pub fn restore_scroll_position(&mut self, position: ScrollPosition) {
+        let index = position
+            .row_id
+            .and_then(|id| self.data.iter().position(|row| row.row_id() == Some(id)))
+            .unwrap_or(position.current_index)
+            .min(self.last_selectable_index());
+
+        self.state.current_index = index;
+        self.state.window.start = position.display_start_index.min(index);
+    }
+
     /// Returns the current scroll index.
     pub fn current_index(&self) -> usize {
         self.state.current_index
     }
 
+    /// Returns the data in the table, in the order it is shown.
+    pub fn data(&self) -> &[DataType] {
+        &self.data
+    }
+
     /// Optionally returns the currently selected item, if there is one.
     pub fn current_item(&self) -> Option<&DataType> {
         self.data.get(self.state.current_index)
     }
 
+    /// Marks the selected row if it isn't marked, or unmarks it if it is. Rows without a
+    /// [`DataToCell::row_id`] can't be marked.
+    pub fn toggle_marked(&mut self) {
+        if let Some(id) = self.current_item().and_then(DataType::row_id) {
+            if !self.marked_ids.remove(&id) {
+                self.marked_ids.insert(id);
+            }
+        }
+    }
+
+    /// Returns whether `row` is marked.
+    pub fn is_marked(&self, row: &DataType) -> bool {
+        row.row_id()
+            .map(|id| self.marked_ids.contains(&id))
+            .unwrap_or(false)
+    }
+
+    /// Returns the marked rows, in the order they are shown.
+    pub fn marked_items(&self) -> impl Iterator<Item = &DataType> {
+        self.data.iter().filter(|row| self.is_marked(row))
+    }
+
+    /// Unmarks every row.
+    pub fn clear_marked(&mut self) {
+        self.marked_ids.clear();
+    }
+
+    /// Expands the row at `index` to show its details beneath it, or collapses it if it is already
+    /// expanded. This does nothing unless enabled with [`DataTable::with_row_details`].
+    pub fn toggle_row_details(&mut self, index: usize) {
+        if self.row_details
+            && !self.paged
+            && index < self.data.len()
+            && !self.expanded_rows.remove(&index)
+        {
+            self.expanded_rows.insert(index);
+        }
+    }
+
+    /// Returns whether the row at `index` is expanded to show its details.
+    pub fn is_row_expanded(&self, index: usize) -> bool {
+        self.expanded_rows.contains(&index)
+    }
+
+    /// How many lines the row at `index` takes up, including its details if it is expanded.
+    fn row_height(&self, index: usize) -> usize {
+        if self.is_row_expanded(index) && !self.paged {
+            1 + self
+                .columns
+                .iter()
+                .filter(|column| !column.is_hidden())
+                .count()
+        } else {
+            1
+        }
+    }
+
+    /// Returns which of the rows shown the last time the table was drawn is at `line`, counting
+    /// from the first row below the header, as an index into the rows shown like
+    /// [`DataTable::tui_selected`]. Lines of an expanded row's details belong to that row.
+    pub fn row_at_line(&self, line: usize) -> Option<usize> {
+        let start = self.state.window.start;
+        let mut row_end = 0;
+
+        (start..self.data.len()).position(|index| {
+            row_end += self.row_height(index);
+            line < row_end
+        })
+    }
+
     /// Returns tui-rs' internal selection.
     pub fn tui_selected(&self) -> Option<usize> {
         self.state.table_state.selected()
     }
+
+    /// Returns the index of the column whose header text matches `header`, if there is one.
+    pub fn column_index(&self, header: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.inner().text() == header)
+    }
+
+    /// Like [`DataTable::column_index`], but ignores case when matching `header`.
+    pub fn column_index_ignore_case(&self, header: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.inner().text().eq_ignore_ascii_case(header))
+    }
+
+    /// Returns the index of the column whose [name](ColumnHeader::name) matches `name` while
+    /// ignoring case, if there is one.
+    pub fn column_index_by_name(&self, name: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.inner().name().eq_ignore_ascii_case(name))
+    }
+
+    /// Hides the columns named in `names`, which are matched against the
+    /// [names](ColumnHeader::name) of the columns while ignoring case. Returns the names that
+    /// didn't match any column.
+    pub fn hide_columns<'a>(&mut self, names: &'a [String]) -> Vec<&'a str> {
+        let mut unknown = Vec::new();
+
+        for name in names {
+            match self.column_index_by_name(name) {
+                Some(index) => self.columns[index].set_is_hidden(true),
+                None => unknown.push(name.as_str()),
+            }
+        }
+
+        if unknown.len() < names.len() {
+            self.widths_outdated = true;
+        }
+
+        unknown
+    }
+
+    /// Returns the [name](ColumnHeader::name) of each column.
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|column| column.inner().name().into_owned())
+            .collect()
+    }
+
+    /// Returns the [name](ColumnHeader::name) of each hidden column.
+    pub fn hidden_column_names(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .filter(|column| column.is_hidden())
+            .map(|column| column.inner().name().into_owned())
+            .collect()
+    }
+
+    /// Sets the displayed header of the column at `index`, which will also recalculate the column
+    /// widths on the next draw, as the header length can affect them.
+    pub fn set_column_header(&mut self, index: usize, header: Cow<'static, str>) {
+        if let Some(column) = self.columns.get_mut(index) {
+            column.set_header(header);
+            self.widths_outdated = true;
+        }
+    }
+
+    /// Returns whether the table is expanded to take up the entire screen.
+    pub fn is_expanded(&self) -> bool {
+        self.is_expanded
+    }
+
+    /// Sets whether the table is expanded. While expanded, columns that don't fit are truncated
+    /// rather than dropped.
+    pub fn set_expanded(&mut self, is_expanded: bool) {
+        self.is_expanded = is_expanded;
+    }
+
+    /// Returns the indices of the columns that were given no width the last time the table was
+    /// drawn, as they didn't fit. Hidden columns aren't included, and nothing is returned if the
+    /// table hasn't been drawn yet.
+    pub fn dropped_columns(&self) -> Vec<usize> {
+        self.state
+            .calculated_widths
+            .iter()
+            .zip(&self.columns)
+            .enumerate()
+            .filter(|(_, (&width, column))| width == 0 && !column.is_hidden())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Sets the [`Clock`] used for time-based behaviour like click flashes and double clicks.
+    pub fn set_clock<T: Clock + 'static>(&mut self, clock: T) {
+        self.clock = Box::new(clock);
+    }
+
+    /// Registers a click on the row at `index`, which also briefly flashes it. Returns whether
+    /// this click was a double click on that row.
+    pub fn click_row(&mut self, index: usize) -> bool {
+        let now = self.clock.now();
+        let interval = Duration::from_millis(DOUBLE_CLICK_INTERVAL_IN_MILLISECONDS);
+        let is_double_click = matches!(
+            self.state.last_click,
+            Some((last_index, last_click))
+                if last_index == index && now.saturating_duration_since(last_click) < interval
+        );
+
+        // Don't let a third click count as another double click. The row still flashes.
+        self.state.flash = Some((index, now));
+        self.state.last_click = if is_double_click {
+            None
+        } else {
+            Some((index, now))
+        };
+
+        is_double_click
+    }
+
+    /// Returns the index of the row that should currently be flashed from a click, if there is one.
+    pub fn flashed_row(&self) -> Option<usize> {
+        let duration = Duration::from_millis(CLICK_FLASH_DURATION_IN_MILLISECONDS);
+
+        self.state.flash.and_then(|(index, clicked)| {
+            (self.clock.now().saturating_duration_since(clicked) < duration).then_some(index)
+        })
+    }
+}
+
+/// Strips the shortcut suffix from a column header, e.g. "Free(n)" becomes "Free".
+fn column_name(header: &str) -> &str {
+    match header
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once('('))
+    {
+        Some((name, shortcut)) if shortcut.chars().count() == 1 => name,
+        _ => header,
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::utils::clock::MockClock;
 
     #[derive(Clone, PartialEq, Eq, Debug)]
     struct TestType {
@@ -171,16 +774,175 @@ mod test {
         }
     }
 
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    struct IdentifiedType {
+        id: u64,
+    }
+
+    impl DataToCell<&'static str> for IdentifiedType {
+        fn to_cell<'a>(
+            &'a self, _column: &&'static str, _calculated_width: u16,
+        ) -> Option<tui::text::Text<'a>> {
+            None
+        }
+
+        fn row_id(&self) -> Option<u64> {
+            Some(self.id)
+        }
+
+        fn column_widths<C: DataTableColumn<&'static str>>(
+            _data: &[Self], _columns: &[C],
+        ) -> Vec<u16>
+        where
+            Self: Sized,
+        {
+            vec![]
+        }
+    }
+
+    thread_local! {
+        /// How many rows of [`FormattedType`] were formatted to find column widths.
+        static ROWS_FORMATTED: std::cell::Cell<usize> = std::cell::Cell::new(0);
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    struct FormattedType {
+        id: u64,
+        value: &'static str,
+    }
+
+    impl DataToCell<&'static str> for FormattedType {
+        fn to_cell<'a>(
+            &'a self, _column: &&'static str, _calculated_width: u16,
+        ) -> Option<tui::text::Text<'a>> {
+            None
+        }
+
+        fn row_id(&self) -> Option<u64> {
+            Some(self.id)
+        }
+
+        fn same_cells(&self, other: &Self) -> bool {
+            self == other
+        }
+
+        fn column_widths<C: DataTableColumn<&'static str>>(
+            data: &[Self], _columns: &[C],
+        ) -> Vec<u16>
+        where
+            Self: Sized,
+        {
+            ROWS_FORMATTED.with(|formatted| formatted.set(formatted.get() + data.len()));
+            vec![data
+                .iter()
+                .map(|row| row.value.len() as u16)
+                .max()
+                .unwrap_or(0)]
+        }
+    }
+
+    #[test]
+    fn test_only_changed_rows_formatted() {
+        let mut table = DataTable::new(
+            [Column::soft("a", None)],
+            DataTableProps::default(),
+            DataTableStyling::default(),
+        );
+        let rows = |values: &[(u64, &'static str)]| {
+            values
+                .iter()
+                .map(|&(id, value)| FormattedType { id, value })
+                .collect::<Vec<_>>()
+        };
+        let formatted = || ROWS_FORMATTED.with(|formatted| formatted.get());
+        let desired =
+            |table: &DataTable<FormattedType, &'static str>| match table.columns[0].bounds() {
+                ColumnWidthBounds::Soft { desired, .. } => desired,
+                _ => unreachable!(),
+            };
+
+        table.set_data(rows(&[(1, "a"), (2, "bb"), (3, "ccc")]));
+        assert_eq!(formatted(), 3);
+        assert_eq!(desired(&table), 3);
+
+        // Rows that moved around but show the same aren't formatted again.
+        table.set_data(rows(&[(3, "ccc"), (1, "a"), (2, "bb")]));
+        assert_eq!(formatted(), 3);
+
+        table.set_data(rows(&[(3, "c"), (1, "a"), (2, "bb"), (4, "dddd")]));
+        assert_eq!(formatted(), 5);
+        assert_eq!(desired(&table), 4);
+
+        table.push_data(rows(&[(5, "eeeee")]));
+        assert_eq!(formatted(), 6);
+        assert_eq!(desired(&table), 5);
+
+        // Changing the columns formats every row again.
+        table.columns[0].set_is_hidden(true);
+        table.set_data(rows(&[
+            (3, "c"),
+            (1, "a"),
+            (2, "bb"),
+            (4, "dddd"),
+            (5, "eeeee"),
+        ]));
+        assert_eq!(formatted(), 11);
+    }
+
+    #[test]
+    fn test_row_ids() {
+        let props = DataTableProps {
+            left_to_right: true,
+            ..Default::default()
+        };
+        let mut table = DataTable::new([Column::hard("a", 10)], props, DataTableStyling::default());
+        let rows = |ids: &[u64]| {
+            ids.iter()
+                .map(|&id| IdentifiedType { id })
+                .collect::<Vec<_>>()
+        };
+
+        table.set_data(rows(&[1, 2, 3]));
+        table.toggle_marked();
+        table.set_position(1);
+        table.toggle_marked();
+
+        // The selected row stays selected after moving.
+        table.set_data(rows(&[3, 2, 1]));
+        assert_eq!(table.current_item(), Some(&IdentifiedType { id: 2 }));
+        table.set_data(rows(&[2, 3, 1]));
+        assert_eq!(table.current_index(), 0);
+        assert_eq!(
+            table.marked_items().collect::<Vec<_>>(),
+            [&IdentifiedType { id: 2 }, &IdentifiedType { id: 1 }]
+        );
+
+        // If it's gone, the index is kept like for rows without IDs, and its mark is dropped.
+        table.set_data(rows(&[3, 1]));
+        assert_eq!(table.current_item(), Some(&IdentifiedType { id: 3 }));
+        table.set_data(rows(&[3, 1, 2]));
+        assert_eq!(
+            table.marked_items().collect::<Vec<_>>(),
+            [&IdentifiedType { id: 1 }]
+        );
+
+        table.set_position(1);
+        table.toggle_marked();
+        assert_eq!(table.marked_items().count(), 0);
+        table.set_position(0);
+        table.toggle_marked();
+        table.clear_marked();
+        assert_eq!(table.marked_items().count(), 0);
+    }
+
     #[test]
     fn test_data_table_operations() {
         let columns = [Column::hard("a", 10), Column::hard("b", 10)];
         let props = DataTableProps {
             title: Some("test".into()),
             table_gap: 1,
-            left_to_right: false,
-            is_basic: false,
             show_table_scroll_position: true,
-            show_current_entry_when_unfocused: false,
+            ..Default::default()
         };
         let styling = DataTableStyling::default();
 
@@ -189,54 +951,305 @@ mod test {
 
         table.set_last();
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
+        assert_eq!(table.state.window.direction, ScrollDirection::Down);
 
         table.set_first();
         assert_eq!(table.current_index(), 0);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
+        assert_eq!(table.state.window.direction, ScrollDirection::Up);
 
         table.set_position(4);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
+        assert_eq!(table.state.window.direction, ScrollDirection::Down);
 
         table.set_position(100);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
+        assert_eq!(table.state.window.direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 4 }));
 
         table.increment_position(-1);
         assert_eq!(table.current_index(), 3);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
+        assert_eq!(table.state.window.direction, ScrollDirection::Up);
         assert_eq!(table.current_item(), Some(&TestType { index: 3 }));
 
         table.increment_position(-3);
         assert_eq!(table.current_index(), 0);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
+        assert_eq!(table.state.window.direction, ScrollDirection::Up);
         assert_eq!(table.current_item(), Some(&TestType { index: 0 }));
 
         table.increment_position(-3);
         assert_eq!(table.current_index(), 0);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
+        assert_eq!(table.state.window.direction, ScrollDirection::Up);
         assert_eq!(table.current_item(), Some(&TestType { index: 0 }));
 
         table.increment_position(1);
         assert_eq!(table.current_index(), 1);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
+        assert_eq!(table.state.window.direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 1 }));
 
         table.increment_position(3);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
+        assert_eq!(table.state.window.direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 4 }));
 
         table.increment_position(10);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
+        assert_eq!(table.state.window.direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 4 }));
 
         table.set_data((0..=2).map(|index| TestType { index }).collect::<Vec<_>>());
         assert_eq!(table.current_index(), 2);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
+        assert_eq!(table.state.window.direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
     }
+
+    #[test]
+    fn test_scroll_signal() {
+        let columns = [Column::hard("a", 10)];
+        let props = DataTableProps {
+            table_gap: 1,
+            ..Default::default()
+        };
+        let mut table = DataTable::new(columns, props, DataTableStyling::default());
+        assert_eq!(table.scroll(1), ScrollSignal::Boundary);
+
+        table.set_data((0..=4).map(|index| TestType { index }).collect::<Vec<_>>());
+        assert_eq!(table.scroll(3), ScrollSignal::Moved(3));
+        assert_eq!(table.scroll(1), ScrollSignal::Moved(4));
+
+        // At the bottom, scrolling further reports the boundary and leaves the selection alone.
+        assert_eq!(table.scroll(1), ScrollSignal::Boundary);
+        assert_eq!(table.current_index(), 4);
+        assert_eq!(table.scroll(0), ScrollSignal::Unchanged);
+
+        // Going past the top stops at the first row, and only then reports the boundary.
+        assert_eq!(table.scroll(-5), ScrollSignal::Moved(0));
+        assert_eq!(table.scroll(-1), ScrollSignal::Boundary);
+        assert_eq!(table.current_index(), 0);
+    }
+
+    #[test]
+    fn test_scroll_overshoot() {
+        let columns = [Column::hard("a", 10)];
+        let mut table = DataTable::new(
+            columns,
+            DataTableProps::default(),
+            DataTableStyling::default(),
+        );
+        table.set_data((0..10).map(|index| TestType { index }).collect::<Vec<_>>());
+
+        // A step that overshoots the last row still reaches it.
+        table.set_position(7);
+        assert_eq!(table.scroll(3), ScrollSignal::Moved(9));
+        assert_eq!(table.scroll(3), ScrollSignal::Boundary);
+
+        // Huge steps don't overflow.
+        assert_eq!(table.scroll(i64::MIN), ScrollSignal::Moved(0));
+        assert_eq!(table.scroll(i64::MAX), ScrollSignal::Moved(9));
+        assert_eq!(table.scroll(i64::MAX), ScrollSignal::Boundary);
+        assert_eq!(table.scroll(i64::MIN), ScrollSignal::Moved(0));
+        assert_eq!(table.scroll(i64::MIN), ScrollSignal::Boundary);
+    }
+
+    #[test]
+    fn test_max_rows() {
+        let columns = [Column::hard("a", 10)];
+        let props = DataTableProps {
+            table_gap: 1,
+            ..Default::default()
+        };
+        let mut table =
+            DataTable::new(columns, props, DataTableStyling::default()).with_max_rows(Some(5));
+        let rows = |range: std::ops::Range<usize>| range.map(|index| TestType { index });
+        let indices = |table: &DataTable<TestType, &'static str>| {
+            table.data().iter().map(|row| row.index).collect::<Vec<_>>()
+        };
+
+        table.set_data(rows(0..8).collect());
+        assert_eq!(indices(&table), [3, 4, 5, 6, 7]);
+
+        // A selection in the middle should stay on the same row as older rows are dropped.
+        table.set_position(3);
+        table.state.window.start = 2;
+        table.push_data(rows(8..10));
+        assert_eq!(indices(&table), [5, 6, 7, 8, 9]);
+        assert_eq!(table.current_item(), Some(&TestType { index: 6 }));
+        assert_eq!(table.state.window.start, 0);
+
+        // If the selected row is dropped, the selection stops at the oldest row left.
+        table.push_data(rows(10..13));
+        assert_eq!(table.current_item(), Some(&TestType { index: 8 }));
+        assert_eq!(table.current_index(), 0);
+
+        // Selecting the last row follows new rows as they come in.
+        table.set_last();
+        table.push_data(rows(13..15));
+        assert_eq!(indices(&table), [10, 11, 12, 13, 14]);
+        assert_eq!(table.current_item(), Some(&TestType { index: 14 }));
+
+        let mut table = table.with_max_rows(None);
+        table.push_data(rows(15..20));
+        assert_eq!(table.data().len(), 10);
+        assert_eq!(table.current_item(), Some(&TestType { index: 19 }));
+    }
+
+    #[test]
+    fn test_push_data_with_pinned_rows() {
+        let columns = [Column::hard("a", 10)];
+        let mut table = DataTable::new(
+            columns,
+            DataTableProps::default(),
+            DataTableStyling::default(),
+        )
+        .with_pinned_bottom_rows(1)
+        .with_max_rows(Some(5));
+        let rows = |range: std::ops::Range<usize>| range.map(|index| TestType { index });
+        let indices = |table: &DataTable<TestType, &'static str>| {
+            table.data().iter().map(|row| row.index).collect::<Vec<_>>()
+        };
+
+        // The last row is pinned, like a row of totals, so new rows go in above it.
+        table.set_data(rows(0..3).chain(rows(100..101)).collect());
+        table.set_last();
+        assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
+        table.push_data(rows(3..5));
+        assert_eq!(indices(&table), [1, 2, 3, 4, 100]);
+
+        // Selecting the last row that isn't pinned follows new rows as they come in.
+        assert_eq!(table.current_item(), Some(&TestType { index: 4 }));
+
+        table.set_position(1);
+        table.push_data(rows(5..6));
+        assert_eq!(indices(&table), [2, 3, 4, 5, 100]);
+        assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
+    }
+
+    #[test]
+    fn test_row_filter() {
+        let columns = [Column::hard("a", 10)];
+        let props = DataTableProps {
+            table_gap: 1,
+            ..Default::default()
+        };
+        let mut table = DataTable::new(columns, props, DataTableStyling::default())
+            .with_row_filter(Box::new(|row: &TestType| row.index % 2 != 1));
+        let rows = |range: std::ops::Range<usize>| range.map(|index| TestType { index });
+        let indices = |table: &DataTable<TestType, &'static str>| {
+            table.data().iter().map(|row| row.index).collect::<Vec<_>>()
+        };
+
+        table.set_data(rows(0..5).collect());
+        assert_eq!(indices(&table), [0, 2, 4]);
+
+        table.push_data(rows(5..8));
+        assert_eq!(indices(&table), [0, 2, 4, 6]);
+
+        table.set_row_filter(None);
+        table.set_data(rows(0..3).collect());
+        assert_eq!(indices(&table), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_column_index() {
+        let columns = [Column::hard("Name", 10), Column::hard("CPU%", 10)];
+        let props = DataTableProps {
+            table_gap: 1,
+            ..Default::default()
+        };
+        let table: DataTable<TestType, &'static str> =
+            DataTable::new(columns, props, DataTableStyling::default());
+
+        assert_eq!(table.column_index("Name"), Some(0));
+        assert_eq!(table.column_index("CPU%"), Some(1));
+        assert_eq!(table.column_index("cpu%"), None);
+        assert_eq!(table.column_index("Command"), None);
+
+        assert_eq!(table.column_index_ignore_case("cpu%"), Some(1));
+        assert_eq!(table.column_index_ignore_case("NAME"), Some(0));
+        assert_eq!(table.column_index_ignore_case("COMMAND"), None);
+    }
+
+    #[test]
+    fn test_hide_columns() {
+        let columns = [
+            Column::hard("Name", 10),
+            Column::hard("Free(n)", 10),
+            Column::hard("User", 10),
+        ];
+        let props = DataTableProps {
+            table_gap: 1,
+            ..Default::default()
+        };
+        let mut table: DataTable<TestType, &'static str> =
+            DataTable::new(columns, props, DataTableStyling::default());
+
+        // Names are matched without the shortcut suffix, but not against the whole header.
+        let names = ["user", "FREE", "Group", "free(n)"].map(String::from);
+        assert_eq!(table.hide_columns(&names), ["Group", "free(n)"]);
+        assert_eq!(
+            table
+                .columns
+                .iter()
+                .map(|column| column.is_hidden())
+                .collect::<Vec<_>>(),
+            [false, true, true]
+        );
+        assert_eq!(table.column_names(), ["Name", "Free", "User"]);
+        assert_eq!(table.hidden_column_names(), ["Free", "User"]);
+        assert_eq!(table.column_index_by_name("NAME"), Some(0));
+        assert_eq!(column_name("Temp °C(t)"), "Temp °C");
+        assert_eq!(column_name("(ab)"), "(ab)");
+    }
+
+    fn clicking_table() -> (DataTable<TestType, &'static str>, MockClock) {
+        let columns = [Column::hard("a", 10)];
+        let props = DataTableProps {
+            table_gap: 1,
+            ..Default::default()
+        };
+        let mut table = DataTable::new(columns, props, DataTableStyling::default());
+        table.set_data((0..=4).map(|index| TestType { index }).collect::<Vec<_>>());
+
+        let clock = MockClock::default();
+        table.set_clock(clock.clone());
+
+        (table, clock)
+    }
+
+    #[test]
+    fn test_click_flash() {
+        let (mut table, clock) = clicking_table();
+        assert_eq!(table.flashed_row(), None);
+
+        table.click_row(2);
+        assert_eq!(table.flashed_row(), Some(2));
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(table.flashed_row(), Some(2));
+
+        clock.advance(Duration::from_millis(CLICK_FLASH_DURATION_IN_MILLISECONDS));
+        assert_eq!(table.flashed_row(), None);
+    }
+
+    #[test]
+    fn test_double_click() {
+        let (mut table, clock) = clicking_table();
+        let interval = Duration::from_millis(DOUBLE_CLICK_INTERVAL_IN_MILLISECONDS);
+
+        assert!(!table.click_row(1));
+        clock.advance(interval / 2);
+        assert!(table.click_row(1));
+        assert_eq!(table.flashed_row(), Some(1));
+
+        // A third click right after shouldn't count as another double click.
+        clock.advance(interval / 2);
+        assert!(!table.click_row(1));
+
+        // Too slow.
+        clock.advance(interval);
+        assert!(!table.click_row(1));
+
+        // Different rows.
+        clock.advance(interval / 4);
+        assert!(!table.click_row(2));
+    }
 }
@@ -3,6 +3,8 @@ use std::{
     cmp::{max, min},
 };
 
+use tui::layout::Constraint;
+
 /// A bound on the width of a column.
 #[derive(Clone, Copy, Debug)]
 pub enum ColumnWidthBounds {
@@ -23,6 +25,19 @@ pub enum ColumnWidthBounds {
     FollowHeader,
 }
 
+/// How calculated column widths are turned into [`Constraint`]s when drawing a table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColumnWidthMode {
+    /// Each column is exactly as wide as was calculated, using [`Constraint::Length`].
+    #[default]
+    Fixed,
+
+    /// Each column uses a [`Constraint::Min`] or [`Constraint::Max`] from its [`ColumnWidthBounds`],
+    /// so tui can flex it if the table ends up further constrained. [`ColumnWidthBounds::Hard`]
+    /// columns still use [`Constraint::Length`].
+    Flexible,
+}
+
 pub trait ColumnHeader {
     /// The "text" version of the column header.
     fn text(&self) -> Cow<'static, str>;
@@ -32,6 +47,21 @@ pub trait ColumnHeader {
     fn header(&self) -> Cow<'static, str> {
         self.text()
     }
+
+    /// The name of the kind of column, which the config refers to it by. Unlike the header, this
+    /// stays the same however the column is currently shown. Defaults to [`ColumnHeader::text`]
+    /// without any shortcut suffix, e.g. "Free" for "Free(n)".
+    fn name(&self) -> Cow<'static, str> {
+        match self.text() {
+            Cow::Borrowed(text) => Cow::Borrowed(super::column_name(text)),
+            Cow::Owned(text) => Cow::Owned(super::column_name(&text).to_string()),
+        }
+    }
+
+    /// A longer description of what this column shows, if there is one. Defaults to [`None`].
+    fn description(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl ColumnHeader for &'static str {
@@ -62,6 +92,13 @@ pub trait DataTableColumn<H: ColumnHeader> {
     /// The actually displayed "header".
     fn header(&self) -> Cow<'static, str>;
 
+    /// Replaces the displayed header with `header`, regardless of what the inner header's text is.
+    fn set_header(&mut self, header: Cow<'static, str>);
+
+    /// A longer description of what this column shows, if there is one. Taken from the inner
+    /// header.
+    fn description(&self) -> Option<&str>;
+
     /// The header length, along with any required additional lengths for things like arrows.
     /// Defaults to getting the length of [`DataTableColumn::header`].
     fn header_len(&self) -> usize {
@@ -79,6 +116,9 @@ pub struct Column<H> {
 
     /// Marks that this column is currently "hidden", and should *always* be skipped.
     is_hidden: bool,
+
+    /// A header to display instead of the inner column header's text, if set.
+    header_override: Option<Cow<'static, str>>,
 }
 
 impl<H: ColumnHeader> DataTableColumn<H> for Column<H> {
@@ -113,7 +153,18 @@ impl<H: ColumnHeader> DataTableColumn<H> for Column<H> {
     }
 
     fn header(&self) -> Cow<'static, str> {
-        self.inner.text()
+        match &self.header_override {
+            Some(header) => header.clone(),
+            None => self.inner.text(),
+        }
+    }
+
+    fn set_header(&mut self, header: Cow<'static, str>) {
+        self.header_override = Some(header);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.inner.description()
     }
 }
 
@@ -123,6 +174,7 @@ impl<H: ColumnHeader> Column<H> {
             inner,
             bounds: ColumnWidthBounds::FollowHeader,
             is_hidden: false,
+            header_override: None,
         }
     }
 
@@ -131,6 +183,7 @@ impl<H: ColumnHeader> Column<H> {
             inner,
             bounds: ColumnWidthBounds::Hard(width),
             is_hidden: false,
+            header_override: None,
         }
     }
 
@@ -142,8 +195,37 @@ impl<H: ColumnHeader> Column<H> {
                 max_percentage,
             },
             is_hidden: false,
+            header_override: None,
         }
     }
+
+    /// Displays `header` instead of the inner column header's text.
+    pub fn with_header<D: Into<Cow<'static, str>>>(mut self, header: D) -> Self {
+        self.header_override = Some(header.into());
+        self
+    }
+
+    /// Starts the column off hidden.
+    pub fn hidden(mut self) -> Self {
+        self.is_hidden = true;
+        self
+    }
+}
+
+/// Below this width there isn't room to separate columns, so the first column that would be sized
+/// is collapsed into the whole width, and the rest are dropped. The same happens at any width that
+/// no column fits in.
+const MIN_SEPARATED_WIDTH: u16 = 4;
+
+/// The header length as a width, clamped to what fits in a [`u16`].
+fn header_width<H: ColumnHeader, C: DataTableColumn<H>>(column: &C) -> u16 {
+    u16::try_from(column.header_len()).unwrap_or(u16::MAX)
+}
+
+/// The most a soft column may take up out of `total_width`, or [`None`] if it has no limit.
+fn soft_max_width(max_percentage: Option<f32>, total_width: u16) -> Option<u16> {
+    // Float to int casts saturate, and NaN becomes 0, so this can't overflow.
+    max_percentage.map(|max_percentage| (max_percentage * f32::from(total_width)).ceil() as u16)
 }
 
 pub trait CalculateColumnWidths<H> {
@@ -151,7 +233,23 @@ pub trait CalculateColumnWidths<H> {
     ///
     /// * `total_width` is the total width on the canvas that the columns can try and work with.
     /// * `left_to_right` is whether to size from left-to-right (`true`) or right-to-left (`false`).
-    fn calculate_column_widths(&self, total_width: u16, left_to_right: bool) -> Vec<u16>;
+    /// * `drop_columns` is whether columns that don't fit are dropped (`true`), or squeezed into
+    ///   whatever width is left (`false`).
+    ///
+    /// The returned widths plus a space between each shown column never add up to more than
+    /// `total_width`. A `total_width` of 0 shows no columns, and one below 4 - or one that no column
+    /// fits in - only shows the first column that would be sized, taking up the whole width.
+    fn calculate_column_widths(
+        &self, total_width: u16, left_to_right: bool, drop_columns: bool,
+    ) -> Vec<u16>;
+
+    /// Turns widths from [`CalculateColumnWidths::calculate_column_widths`] into [`Constraint`]s
+    /// based on `mode`, skipping any column that was given no width.
+    ///
+    /// * `total_width` should be the same width that `calculated_widths` was calculated with.
+    fn column_constraints(
+        &self, calculated_widths: &[u16], total_width: u16, mode: ColumnWidthMode,
+    ) -> Vec<Constraint>;
 }
 
 impl<H, C> CalculateColumnWidths<H> for [C]
@@ -159,79 +257,104 @@ where
     H: ColumnHeader,
     C: DataTableColumn<H>,
 {
-    fn calculate_column_widths(&self, total_width: u16, left_to_right: bool) -> Vec<u16> {
+    fn calculate_column_widths(
+        &self, total_width: u16, left_to_right: bool, drop_columns: bool,
+    ) -> Vec<u16> {
         use itertools::Either;
 
         let mut total_width_left = total_width;
         let mut calculated_widths = vec![0; self.len()];
+        let first_sized = if left_to_right {
+            self.iter().position(|column| !column.is_hidden())
+        } else {
+            self.iter().rposition(|column| !column.is_hidden())
+        };
+
+        if total_width < MIN_SEPARATED_WIDTH {
+            if let Some(index) = first_sized {
+                calculated_widths[index] = total_width;
+            }
+            return calculated_widths;
+        }
+
         let columns = if left_to_right {
             Either::Left(self.iter().zip(calculated_widths.iter_mut()))
         } else {
             Either::Right(self.iter().zip(calculated_widths.iter_mut()).rev())
         };
+        let columns = columns.filter(|(column, _)| !column.is_hidden());
 
-        let mut num_columns = 0;
+        let mut num_columns: u16 = 0;
         for (column, calculated_width) in columns {
-            if column.is_hidden() {
+            if !drop_columns {
+                if total_width_left == 0 {
+                    break;
+                }
+
+                let width = match &column.bounds() {
+                    ColumnWidthBounds::Soft {
+                        desired,
+                        max_percentage,
+                    } => {
+                        let soft_limit =
+                            soft_max_width(*max_percentage, total_width).unwrap_or(*desired);
+                        max(min(soft_limit, *desired), header_width(column))
+                    }
+                    ColumnWidthBounds::Hard(width) => *width,
+                    ColumnWidthBounds::FollowHeader => header_width(column),
+                };
+                let space_taken = min(width, total_width_left);
+
+                if space_taken > 0 {
+                    total_width_left =
+                        total_width_left.saturating_sub(space_taken.saturating_add(1));
+                    *calculated_width = space_taken;
+                    num_columns = num_columns.saturating_add(1);
+                }
+
                 continue;
             }
 
-            match &column.bounds() {
+            let space_taken = match &column.bounds() {
                 ColumnWidthBounds::Soft {
                     desired,
                     max_percentage,
                 } => {
-                    let min_width = column.header_len() as u16;
-                    if min_width > total_width_left {
-                        break;
-                    }
-
+                    let min_width = header_width(column);
                     let soft_limit = max(
-                        if let Some(max_percentage) = max_percentage {
-                            ((*max_percentage * f32::from(total_width)).ceil()) as u16
-                        } else {
-                            *desired
-                        },
+                        soft_max_width(*max_percentage, total_width).unwrap_or(*desired),
                         min_width,
                     );
                     let space_taken = min(min(soft_limit, *desired), total_width_left);
 
                     if min_width > space_taken || min_width == 0 {
                         break;
-                    } else if space_taken > 0 {
-                        total_width_left = total_width_left.saturating_sub(space_taken + 1);
-                        *calculated_width = space_taken;
-                        num_columns += 1;
-                    }
-                }
-                ColumnWidthBounds::Hard(width) => {
-                    let min_width = *width;
-                    if min_width > total_width_left || min_width == 0 {
-                        break;
-                    } else if min_width > 0 {
-                        total_width_left = total_width_left.saturating_sub(min_width + 1);
-                        *calculated_width = min_width;
-                        num_columns += 1;
-                    }
-                }
-                ColumnWidthBounds::FollowHeader => {
-                    let min_width = column.header_len() as u16;
-                    if min_width > total_width_left || min_width == 0 {
-                        break;
-                    } else if min_width > 0 {
-                        total_width_left = total_width_left.saturating_sub(min_width + 1);
-                        *calculated_width = min_width;
-                        num_columns += 1;
                     }
+                    space_taken
                 }
+                ColumnWidthBounds::Hard(width) => *width,
+                ColumnWidthBounds::FollowHeader => header_width(column),
+            };
+
+            if space_taken > total_width_left || space_taken == 0 {
+                break;
+            }
+            total_width_left = total_width_left.saturating_sub(space_taken.saturating_add(1));
+            *calculated_width = space_taken;
+            num_columns = num_columns.saturating_add(1);
+        }
+
+        if num_columns == 0 {
+            if let Some(index) = first_sized {
+                calculated_widths[index] = total_width;
             }
+            return calculated_widths;
         }
 
-        if num_columns > 0 {
+        if let Some(amount_per_slot) = total_width_left.checked_div(num_columns) {
             // Redistribute remaining.
             let mut num_dist = num_columns;
-            let amount_per_slot = total_width_left / num_dist;
-            total_width_left %= num_dist;
+            total_width_left %= num_columns;
 
             for width in calculated_widths.iter_mut() {
                 if num_dist == 0 {
@@ -239,11 +362,10 @@ where
                 }
 
                 if *width > 0 {
+                    *width = width.saturating_add(amount_per_slot);
                     if total_width_left > 0 {
-                        *width += amount_per_slot + 1;
+                        *width = width.saturating_add(1);
                         total_width_left -= 1;
-                    } else {
-                        *width += amount_per_slot;
                     }
 
                     num_dist -= 1;
@@ -253,4 +375,209 @@ where
 
         calculated_widths
     }
+
+    fn column_constraints(
+        &self, calculated_widths: &[u16], total_width: u16, mode: ColumnWidthMode,
+    ) -> Vec<Constraint> {
+        self.iter()
+            .zip(calculated_widths)
+            .filter(|(_, &width)| width > 0)
+            .map(|(column, &width)| match mode {
+                ColumnWidthMode::Fixed => Constraint::Length(width),
+                ColumnWidthMode::Flexible => match column.bounds() {
+                    ColumnWidthBounds::Soft {
+                        max_percentage: Some(max_percentage),
+                        ..
+                    } => Constraint::Max(max(
+                        soft_max_width(Some(max_percentage), total_width).unwrap_or_default(),
+                        header_width(column),
+                    )),
+                    ColumnWidthBounds::Soft {
+                        max_percentage: None,
+                        ..
+                    }
+                    | ColumnWidthBounds::FollowHeader => Constraint::Min(header_width(column)),
+                    ColumnWidthBounds::Hard(width) => Constraint::Length(width),
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::test_utils::random_generator;
+
+    #[test]
+    fn test_column_builder() {
+        let column = Column::soft("Name", Some(0.25));
+        assert!(matches!(
+            column.bounds(),
+            ColumnWidthBounds::Soft {
+                desired: 0,
+                max_percentage: Some(max_percentage),
+            } if max_percentage == 0.25
+        ));
+        assert_eq!(column.header(), "Name");
+        assert_eq!(column.description(), None);
+        assert!(!column.is_hidden());
+
+        let column = Column::hard("Name", 8).with_header("Process").hidden();
+        assert!(matches!(column.bounds(), ColumnWidthBounds::Hard(8)));
+        assert_eq!(column.inner(), &"Name");
+        assert_eq!(column.header(), "Process");
+        assert_eq!(column.description(), None);
+        assert!(column.is_hidden());
+    }
+
+    #[test]
+    fn test_column_constraints() {
+        let mut columns = [
+            Column::hard("PID", 7),
+            Column::soft("Name", Some(0.25)),
+            Column::soft("Command", None),
+            Column::new("CPU%"),
+            Column::new("Mem%"),
+        ];
+        columns[4].set_is_hidden(true);
+        for (column, width) in columns[1..3].iter_mut().zip([20, 40]) {
+            if let ColumnWidthBounds::Soft { desired, .. } = column.bounds_mut() {
+                *desired = width;
+            }
+        }
+
+        let widths = columns.calculate_column_widths(100, true, true);
+        assert_eq!(
+            columns.column_constraints(&widths, 100, ColumnWidthMode::Fixed),
+            widths
+                .iter()
+                .filter(|&&width| width > 0)
+                .map(|&width| Constraint::Length(width))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            columns.column_constraints(&widths, 100, ColumnWidthMode::Flexible),
+            vec![
+                Constraint::Length(7),
+                Constraint::Max(25),
+                Constraint::Min(7),
+                Constraint::Min(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_narrow_widths() {
+        let mut columns = [
+            Column::new("PID"),
+            Column::hard("Name", 8),
+            Column::new("CPU%"),
+        ];
+
+        for drop_columns in [true, false] {
+            assert_eq!(
+                columns.calculate_column_widths(0, true, drop_columns),
+                vec![0, 0, 0]
+            );
+            for width in 1..4 {
+                assert_eq!(
+                    columns.calculate_column_widths(width, true, drop_columns),
+                    vec![width, 0, 0]
+                );
+                assert_eq!(
+                    columns.calculate_column_widths(width, false, drop_columns),
+                    vec![0, 0, width]
+                );
+            }
+        }
+
+        // Hidden columns are never the one collapsed into.
+        columns[0].set_is_hidden(true);
+        assert_eq!(
+            columns.calculate_column_widths(3, true, true),
+            vec![0, 3, 0]
+        );
+
+        // From 4 onwards, columns are sized as usual, but a width that no column fits in still
+        // collapses rather than showing nothing.
+        assert_eq!(
+            columns.calculate_column_widths(4, true, true),
+            vec![0, 4, 0]
+        );
+        assert_eq!(
+            columns.calculate_column_widths(4, true, false),
+            vec![0, 4, 0]
+        );
+        assert_eq!(
+            columns.calculate_column_widths(10, true, true),
+            vec![0, 9, 0]
+        );
+    }
+
+    #[test]
+    fn test_width_invariants() {
+        const HEADERS: [&str; 4] = ["", "PID", "Name", "A long header"];
+
+        let mut random = random_generator(0x2545_f491);
+        let long_header = "x".repeat(usize::from(u16::MAX) + 10);
+        let random_width = |random: &mut dyn FnMut(usize) -> usize| match random(10) {
+            0 => u16::MAX - random(3) as u16,
+            _ => random(120) as u16,
+        };
+
+        for _ in 0..5000 {
+            let columns = (0..random(8))
+                .map(|_| {
+                    let header = match random(20) {
+                        0 => long_header.clone(),
+                        n => HEADERS[n % HEADERS.len()].to_string(),
+                    };
+                    let mut column = match random(3) {
+                        0 => Column::hard(header, random_width(&mut random)),
+                        1 => {
+                            let max_percentage = match random(4) {
+                                0 => None,
+                                1 => Some(f32::NAN),
+                                _ => Some(random(150) as f32 / 100.0),
+                            };
+                            let mut column = Column::soft(header, max_percentage);
+                            if let ColumnWidthBounds::Soft { desired, .. } = column.bounds_mut() {
+                                *desired = random_width(&mut random);
+                            }
+                            column
+                        }
+                        _ => Column::new(header),
+                    };
+                    column.set_is_hidden(random(4) == 0);
+                    column
+                })
+                .collect::<Vec<_>>();
+            let total_width = random_width(&mut random);
+            let left_to_right = random(2) == 0;
+            let drop_columns = random(2) == 0;
+
+            let widths = columns.calculate_column_widths(total_width, left_to_right, drop_columns);
+            let context = format!(
+                "width {total_width}, left to right {left_to_right}, drop {drop_columns}: {widths:?}"
+            );
+
+            assert_eq!(widths.len(), columns.len(), "{context}");
+            let num_shown = widths.iter().filter(|&&width| width > 0).count() as u64;
+            let used = widths.iter().map(|&width| u64::from(width)).sum::<u64>()
+                + num_shown.saturating_sub(1);
+            assert!(used <= u64::from(total_width), "{context}");
+            for (column, &width) in columns.iter().zip(&widths) {
+                assert!(!column.is_hidden() || width == 0, "{context}");
+            }
+            if total_width < MIN_SEPARATED_WIDTH {
+                assert!(num_shown <= 1, "{context}");
+            }
+            if total_width > 0 && columns.iter().any(|column| !column.is_hidden()) {
+                assert!(num_shown > 0, "{context}");
+            }
+
+            columns.column_constraints(&widths, total_width, ColumnWidthMode::Flexible);
+        }
+    }
 }
@@ -8,6 +8,9 @@ where
     H: ColumnHeader,
 {
     /// Given data, a column, and its corresponding width, return what should be displayed in the [`DataTable`](super::DataTable).
+    ///
+    /// This is only called for the rows being drawn, and the result isn't cached, as formatting
+    /// them is cheap next to the rest of a draw. See `benches/process_table.rs`.
     fn to_cell<'a>(&'a self, column: &H, calculated_width: u16) -> Option<Text<'a>>;
 
     /// Apply styling to the generated [`Row`] of cells.
@@ -18,7 +21,35 @@ where
         row
     }
 
-    /// Returns the desired column widths in light of having seen data.
+    /// Apply styling to the cell generated for `column`, on top of the row's styling.
+    ///
+    /// The default implementation just returns the `cell` that is passed in.
+    #[inline(always)]
+    fn style_cell<'a>(&self, _column: &H, cell: Text<'a>, _painter: &Painter) -> Text<'a> {
+        cell
+    }
+
+    /// Returns an opaque identity for this row that stays the same across updates, like a PID.
+    /// This lets a [`DataTable`](super::DataTable) keep the same row selected and marked as rows
+    /// move around from sorting, filtering, or new data.
+    ///
+    /// The default implementation returns [`None`], in which case rows are only known by index.
+    fn row_id(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns whether this row shows exactly the same as `other`, a row from the last update with
+    /// the same [`DataToCell::row_id`]. A [`DataTable`](super::DataTable) then reuses the widths
+    /// it found for `other` instead of formatting this row again.
+    ///
+    /// The default implementation returns `false`, in which case every row is formatted on every
+    /// update.
+    fn same_cells(&self, _other: &Self) -> bool {
+        false
+    }
+
+    /// Returns the desired column widths in light of having seen data. This is also called with
+    /// one row at a time, so a row's widths shouldn't depend on the other rows.
     fn column_widths<C: DataTableColumn<H>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
         Self: Sized;
@@ -1,28 +1,33 @@
-use std::{
-    cmp::{max, min},
-    iter::once,
-};
+use std::iter::once;
 
 use concat_string::concat_string;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Row, Table},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
     Frame,
 };
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use super::{
-    CalculateColumnWidths, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataToCell,
-    SortType,
+    column_name, state::fit_window, CalculateColumnWidths, ColumnHeader, DataTable,
+    DataTableColumn, DataToCell, SortType,
 };
 use crate::{
     app::layout_manager::BottomWidget,
-    canvas::Painter,
-    constants::{SIDE_BORDERS, TABLE_GAP_HEIGHT_LIMIT},
+    canvas::{
+        canvas_styling::{Role, StyleResolver},
+        Painter,
+    },
+    constants::{SIDE_BORDERS, TABLE_GAP_HEIGHT_LIMIT, TOO_SMALL_INDICATOR},
 };
 
+/// Added to the style of marked rows, on top of whatever style the row already has.
+const MARKED_ROW_STYLE: Style = Style::new().add_modifier(Modifier::UNDERLINED);
+
 pub enum SelectionState {
     NotSelected,
     Selected,
@@ -41,6 +46,18 @@ impl SelectionState {
     }
 }
 
+/// How a table is drawn once it is too narrow to fit all of its columns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NarrowMode {
+    /// Columns that don't fit are dropped, starting from the end.
+    #[default]
+    Scroll,
+
+    /// Each row is drawn as a card instead, with one "column: value" line per column, so no
+    /// column is left out. Cards are selected and scrolled through like rows.
+    Cards,
+}
+
 /// A [`DrawInfo`] is information required on each draw call.
 pub struct DrawInfo {
     pub loc: Rect,
@@ -68,12 +85,9 @@ where
     C: DataTableColumn<H>,
 {
     fn block<'a>(&self, draw_info: &'a DrawInfo, data_len: usize) -> Block<'a> {
-        let border_style = match draw_info.selection_state {
-            SelectionState::NotSelected => self.styling.border_style,
-            SelectionState::Selected | SelectionState::Expanded => {
-                self.styling.highlighted_border_style
-            }
-        };
+        let border_style = self.styling.resolve(Role::Border {
+            selected: draw_info.is_on_widget(),
+        });
 
         if !self.props.is_basic {
             let block = Block::default()
@@ -102,25 +116,36 @@ where
         self.props.title.as_ref().map(|title| {
             let current_index = self.state.current_index.saturating_add(1);
             let draw_loc = draw_info.loc;
-            let title_style = self.styling.title_style;
-            let border_style = if draw_info.is_on_widget() {
-                self.styling.highlighted_border_style
-            } else {
-                self.styling.border_style
-            };
+            let title_style = self.styling.resolve(Role::Title);
+            let border_style = self.styling.resolve(Role::Border {
+                selected: draw_info.is_on_widget(),
+            });
 
-            let title = if self.props.show_table_scroll_position {
-                let pos = current_index.to_string();
-                let tot = total_items.to_string();
-                let title_string = concat_string!(title, "(", pos, " of ", tot, ") ");
+            // The page is always shown, as there's no other way to tell where a paged table is.
+            let position = match self.page() {
+                Some((page, num_pages)) => {
+                    let page = page.to_string();
+                    let num_pages = num_pages.to_string();
+                    Some(concat_string!("(page ", page, "/", num_pages, ") "))
+                }
+                None if self.props.show_table_scroll_position => {
+                    let pos = current_index.to_string();
+                    let tot = total_items.to_string();
+                    Some(concat_string!("(", pos, " of ", tot, ") "))
+                }
+                None => None,
+            };
 
-                if title_string.len() + 2 <= draw_loc.width.into() {
-                    title_string
-                } else {
-                    title.to_string()
+            let title = match position {
+                Some(position) => {
+                    let title_string = concat_string!(title, position);
+                    if title_string.len() + 2 <= draw_loc.width.into() {
+                        title_string
+                    } else {
+                        title.to_string()
+                    }
                 }
-            } else {
-                title.to_string()
+                None => title.to_string(),
             };
 
             if draw_info.is_expanded() {
@@ -139,6 +164,176 @@ where
         })
     }
 
+    /// Returns the style of the selected row, which also depends on whether it was just clicked.
+    fn highlight_style(&self, draw_info: &DrawInfo) -> Style {
+        let highlight_style = self.styling.resolve(Role::SelectedRow {
+            focused: draw_info.is_on_widget() || self.props.show_current_entry_when_unfocused,
+        });
+
+        if self.flashed_row() == Some(self.state.current_index) {
+            highlight_style.patch(self.styling.resolve(Role::ClickFlash))
+        } else {
+            highlight_style
+        }
+    }
+
+    /// Whether rows are drawn as cards, which is only the case if [`NarrowMode::Cards`] is set
+    /// and some columns didn't fit the last time the column widths were calculated.
+    fn shows_cards(&self) -> bool {
+        self.narrow_mode == NarrowMode::Cards && !self.dropped_columns().is_empty()
+    }
+
+    /// Draws each row as a card, with one "column: value" line per column that isn't hidden.
+    fn draw_cards<B: Backend>(
+        &mut self, f: &mut Frame<'_, B>, draw_info: &DrawInfo, block: Block<'_>, draw_loc: Rect,
+        painter: &Painter,
+    ) {
+        let highlight_style = self.highlight_style(draw_info);
+        let value_width = self
+            .state
+            .inner_rect
+            .width
+            .saturating_sub(self.key_width() as u16 + 2);
+
+        // Cards are separated by an empty line, which the last card doesn't need.
+        let card_height = self
+            .columns
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .count()
+            .max(1);
+        let num_cards =
+            ((usize::from(self.state.inner_rect.height) + 1) / (card_height + 1)).max(1);
+        let (start, end) =
+            self.state
+                .get_start_position(num_cards, self.data.len(), draw_info.force_redraw);
+        self.state
+            .table_state
+            .select(Some(self.state.current_index.saturating_sub(start)));
+
+        let cards = self.data[start..end].iter().map(|data_row| {
+            let lines = self.value_lines(data_row, value_width);
+            let card = Row::new([Cell::from(Text::from(lines))])
+                .height(card_height as u16)
+                .bottom_margin(1);
+
+            data_row.style_row(card, painter)
+        });
+
+        let table = Table::new(cards)
+            .block(block)
+            .highlight_style(highlight_style)
+            .style(self.styling.resolve(Role::Text))
+            .widths(&[Constraint::Percentage(100)]);
+        f.render_stateful_widget(table, draw_loc, &mut self.state.table_state);
+    }
+
+    /// Builds a "column: value" line, with the column name padded to `key_width`.
+    fn labelled_line<'a>(
+        &self, header: &str, key_width: usize, key_style: Style, cell: Option<Text<'a>>,
+        value_width: u16,
+    ) -> Line<'a> {
+        let name = column_name(header);
+        let padding = " ".repeat(key_width - name.width() + 1);
+        let name = self.styling.symbols.header(name.into());
+        let mut spans = vec![Span::styled(concat_string!(name, ":", padding), key_style)];
+
+        if let Some(cell) = cell {
+            let cell = self.styling.symbols.fit_text(cell, value_width);
+            if let Some(line) = cell.lines.into_iter().next() {
+                spans.extend(line.spans);
+            }
+        }
+
+        Line::from(spans)
+    }
+
+    /// The lines of the expanded view, which are the selected row's full values, or nothing if
+    /// the expanded view is off or there is no selected row.
+    fn expanded_view_lines(&self) -> Vec<Line<'_>> {
+        match self
+            .data
+            .get(self.state.current_index)
+            .filter(|_| self.expanded_view)
+        {
+            Some(data_row) => self.value_lines(data_row, u16::MAX),
+            None => vec![],
+        }
+    }
+
+    /// One "column: value" line per column that isn't hidden, with values cut to `value_width`.
+    fn value_lines<'a>(&self, data_row: &'a DataType, value_width: u16) -> Vec<Line<'a>> {
+        let key_style = self.styling.resolve(Role::TableHeader);
+        let key_width = self.key_width();
+
+        self.columns
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .map(|column| {
+                let cell = data_row.to_cell(column.inner(), value_width);
+                let header = column.inner().text();
+                self.labelled_line(&header, key_width, key_style, cell, value_width)
+            })
+            .collect()
+    }
+
+    /// The width of the longest name of a column that isn't hidden, which "column: value" lines
+    /// pad their names to.
+    fn key_width(&self) -> usize {
+        self.columns
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .map(|column| column_name(&column.inner().text()).width())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Draws the details of expanded rows in the space their rows left beneath them. `details`
+    /// holds the index of each expanded row and the line its details start on.
+    fn draw_row_details<B: Backend>(
+        &self, f: &mut Frame<'_, B>, details: &[(usize, u16)], rows_loc: Rect,
+    ) {
+        for &(index, line) in details {
+            let height =
+                (self.row_height(index) as u16 - 1).min(rows_loc.height.saturating_sub(line));
+            if height == 0 {
+                continue;
+            }
+
+            // Indented, to set the details apart from the rows around them.
+            let indent = 2.min(rows_loc.width);
+            let loc = Rect {
+                x: rows_loc.x + indent,
+                y: rows_loc.y + line,
+                width: rows_loc.width - indent,
+                height,
+            };
+            let value_width = loc.width.saturating_sub(self.key_width() as u16 + 2);
+
+            let lines = self.value_lines(&self.data[index], value_width);
+            f.render_widget(
+                Paragraph::new(lines).style(self.styling.resolve(Role::Text)),
+                loc,
+            );
+        }
+    }
+
+    /// How many lines the expanded view needs at a width of `width`, including the line that
+    /// separates it from the table. Long values are wrapped.
+    fn expanded_view_height(&self, width: u16) -> u16 {
+        let width = usize::from(width.max(1));
+        let lines = self.expanded_view_lines();
+        if lines.is_empty() {
+            return 0;
+        }
+
+        let height: usize = lines
+            .iter()
+            .map(|line| line.width().saturating_sub(1) / width + 1)
+            .sum();
+        u16::try_from(height + 1).unwrap_or(u16::MAX)
+    }
+
     pub fn draw<B: Backend>(
         &mut self, f: &mut Frame<'_, B>, draw_info: &DrawInfo, widget: Option<&mut BottomWidget>,
         painter: &Painter,
@@ -151,7 +346,7 @@ where
             .direction(Direction::Horizontal)
             .split(draw_loc)[0];
 
-        let block = self.block(draw_info, self.data.len());
+        let mut block = self.block(draw_info, self.scrolled_len());
 
         let (inner_width, inner_height) = {
             let inner_rect = block.inner(margined_draw_loc);
@@ -159,30 +354,64 @@ where
             (inner_rect.width, inner_rect.height)
         };
 
-        if inner_width == 0 || inner_height == 0 {
-            f.render_widget(block, margined_draw_loc);
+        let show_header = inner_height > 1;
+        let header_height = u16::from(show_header);
+        let table_gap = if !show_header || draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
+            0
         } else {
-            // Calculate widths
-            if draw_info.recalculate_column_widths {
-                let col_widths = DataType::column_widths(&self.data, &self.columns);
-
-                self.columns
-                    .iter_mut()
-                    .zip(&col_widths)
-                    .for_each(|(column, &width)| {
-                        let header_len = column.header_len() as u16;
-                        if let ColumnWidthBounds::Soft {
-                            desired,
-                            max_percentage: _,
-                        } = &mut column.bounds_mut()
-                        {
-                            *desired = max(header_len, width);
-                        }
-                    });
+            self.props.table_gap
+        };
 
-                self.state.calculated_widths = self
-                    .columns
-                    .calculate_column_widths(inner_width, self.props.left_to_right);
+        // The expanded view takes space from the bottom of the table, but at least one row is
+        // left. It's only shown if there's room for more than the separator.
+        let expanded_view_height = if self.shows_cards() {
+            0
+        } else {
+            let height = self
+                .expanded_view_height(inner_width)
+                .min(inner_height.saturating_sub(table_gap + header_height + 1));
+            if height > 1 {
+                height
+            } else {
+                0
+            }
+        };
+        let num_rows = usize::from(
+            inner_height.saturating_sub(table_gap + header_height + expanded_view_height),
+        );
+
+        if inner_width == 0 || num_rows == 0 {
+            // There's no room to show any rows, so just indicate that rather than trying to.
+            let indicator = Span::styled(TOO_SMALL_INDICATOR, self.styling.resolve(Role::Error));
+            f.render_widget(block.title(indicator), margined_draw_loc);
+        } else {
+            // Pages fill whatever rows are left after the pinned ones, which is only known now,
+            // so the title showing the page has to be generated again.
+            if self.paged {
+                let pinned = self
+                    .pinned_bottom_rows
+                    .min(self.data.len())
+                    .min(num_rows - 1);
+                self.state.page_size = num_rows - pinned;
+                block = self.block(draw_info, self.scrolled_len());
+            }
+
+            // Fill everything inside the borders first, so any space not covered by rows still
+            // gets the table's background.
+            f.render_widget(
+                Block::default().style(self.styling.resolve(Role::TableBackground)),
+                self.state.inner_rect,
+            );
+
+            // Calculate widths
+            if draw_info.recalculate_column_widths || self.widths_outdated {
+                self.widths_outdated = false;
+                self.update_desired_widths();
+                self.state.calculated_widths = self.columns.calculate_column_widths(
+                    inner_width,
+                    self.props.left_to_right,
+                    !self.is_expanded,
+                );
 
                 // Update draw loc in widget map
                 if let Some(widget) = widget {
@@ -192,61 +421,120 @@ where
                 }
             }
 
-            let show_header = inner_height > 1;
-            let header_height = u16::from(show_header);
-            let table_gap = if !show_header || draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
-                0
-            } else {
-                self.props.table_gap
-            };
-
             let columns = &self.columns;
-            if !self.data.is_empty() || !self.first_draw {
+            if (!self.data.is_empty() || !self.first_draw) && self.shows_cards() {
+                self.first_draw = false;
+                self.draw_cards(f, draw_info, block, margined_draw_loc, painter);
+            } else if !self.data.is_empty() || !self.first_draw {
                 self.first_draw = false; // TODO: Doing it this way is fine, but it could be done better (e.g. showing custom no results/entries message)
 
+                let mut details = vec![];
+                let dimmed = (self.props.dim_when_unfocused && !draw_info.is_on_widget())
+                    .then(|| self.styling.resolve(Role::Dimmed));
                 let rows = {
-                    let num_rows =
-                        usize::from(inner_height.saturating_sub(table_gap + header_height));
-                    self.state
-                        .get_start_position(num_rows, draw_info.force_redraw);
-                    let start = self.state.display_start_index;
-                    let end = min(self.data.len(), start + num_rows);
-                    self.state
-                        .table_state
-                        .select(Some(self.state.current_index.saturating_sub(start)));
-
-                    self.data[start..end].iter().map(|data_row| {
-                        let row = Row::new(
-                            columns
-                                .iter()
-                                .zip(&self.state.calculated_widths)
-                                .filter_map(|(column, &width)| {
-                                    data_row.to_cell(column.inner(), width)
-                                }),
+                    // Pinned rows are drawn after whatever part of the other rows fits, and are
+                    // left out of scrolling.
+                    let pinned = self
+                        .pinned_bottom_rows
+                        .min(self.data.len())
+                        .min(num_rows - 1);
+                    let scrolled_len = self.data.len() - pinned;
+
+                    let current_index = self.state.current_index;
+                    let (mut start, mut end) = if self.paged {
+                        // Only the page with the selected row is drawn.
+                        let page_size = num_rows - pinned;
+                        let start = current_index.min(scrolled_len.saturating_sub(1)) / page_size
+                            * page_size;
+                        self.state.window.start = start;
+                        (start, (start + page_size).min(scrolled_len))
+                    } else {
+                        self.state.get_start_position(
+                            num_rows - pinned,
+                            scrolled_len,
+                            draw_info.force_redraw,
+                        )
+                    };
+
+                    // Expanded rows take up more than one line, so fewer rows may fit.
+                    if !self.expanded_rows.is_empty() && !self.paged {
+                        (start, end) = fit_window(
+                            start,
+                            current_index.min(scrolled_len.saturating_sub(1)),
+                            num_rows - pinned,
+                            scrolled_len,
+                            |index| self.row_height(index),
                         );
+                        self.state.window.start = start;
+
+                        let mut line = 0;
+                        for index in start..end {
+                            if self.is_row_expanded(index) {
+                                details.push((index, line + 1));
+                            }
+                            line += self.row_height(index) as u16;
+                        }
+                    }
+
+                    let selected = if current_index < scrolled_len {
+                        current_index.saturating_sub(start)
+                    } else {
+                        (end - start) + (current_index - scrolled_len)
+                    };
+                    self.state.table_state.select(Some(selected));
 
-                        data_row.style_row(row, painter)
-                    })
+                    let scrolled_rows = &self.data[start..end];
+                    let pinned_rows = &self.data[scrolled_len..];
+                    let heights = (start..end).map(|index| self.row_height(index));
+                    let heights = heights.chain(pinned_rows.iter().map(|_| 1));
+                    scrolled_rows.iter().chain(pinned_rows).zip(heights).map(
+                        |(data_row, height)| {
+                            let is_marked = self.is_marked(data_row);
+                            let row = Row::new(
+                                columns
+                                    .iter()
+                                    .zip(&self.state.calculated_widths)
+                                    .filter_map(|(column, &width)| {
+                                        data_row.to_cell(column.inner(), width).map(|cell| {
+                                            let cell = self.styling.symbols.fit_text(cell, width);
+                                            let mut cell =
+                                                data_row.style_cell(column.inner(), cell, painter);
+                                            if is_marked {
+                                                cell.patch_style(MARKED_ROW_STYLE);
+                                            }
+                                            if let Some(dimmed) = dimmed {
+                                                cell.patch_style(dimmed);
+                                            }
+                                            cell
+                                        })
+                                    }),
+                            )
+                            .height(height as u16);
+
+                            data_row.style_row(row, painter)
+                        },
+                    )
                 };
 
                 let headers = self
                     .sort_type
-                    .build_header(columns, &self.state.calculated_widths)
-                    .style(self.styling.header_style)
+                    .build_header(
+                        columns,
+                        &self.state.calculated_widths,
+                        &self.styling.symbols,
+                        self.props.uppercase_headers,
+                    )
+                    .style(self.styling.resolve(Role::TableHeader))
                     .bottom_margin(table_gap);
 
                 let widget = {
-                    let highlight_style = if draw_info.is_on_widget()
-                        || self.props.show_current_entry_when_unfocused
-                    {
-                        self.styling.highlighted_text_style
-                    } else {
-                        self.styling.text_style
-                    };
                     let mut table = Table::new(rows)
                         .block(block)
-                        .highlight_style(highlight_style)
-                        .style(self.styling.text_style);
+                        .highlight_style(self.highlight_style(draw_info))
+                        .style(match dimmed {
+                            Some(dimmed) => self.styling.resolve(Role::Text).patch(dimmed),
+                            None => self.styling.resolve(Role::Text),
+                        });
 
                     if show_header {
                         table = table.header(headers);
@@ -257,30 +545,1016 @@ where
 
                 let table_state = &mut self.state.table_state;
                 f.render_stateful_widget(
-                    widget.widths(
-                        &(self
-                            .state
-                            .calculated_widths
-                            .iter()
-                            .filter_map(|&width| {
-                                if width == 0 {
-                                    None
-                                } else {
-                                    Some(Constraint::Length(width))
-                                }
-                            })
-                            .collect::<Vec<_>>()),
-                    ),
+                    widget.widths(&self.columns.column_constraints(
+                        &self.state.calculated_widths,
+                        inner_width,
+                        self.width_mode,
+                    )),
                     margined_draw_loc,
                     table_state,
                 );
+
+                if !details.is_empty() {
+                    let inner_rect = self.state.inner_rect;
+                    let rows_top = header_height + table_gap;
+                    let rows_loc = Rect {
+                        y: inner_rect.y + rows_top,
+                        height: inner_rect
+                            .height
+                            .saturating_sub(rows_top + expanded_view_height),
+                        ..inner_rect
+                    };
+                    self.draw_row_details(f, &details, rows_loc);
+                }
+
+                if expanded_view_height > 0 {
+                    let inner_rect = self.state.inner_rect;
+                    let expanded_view_loc = Rect {
+                        y: inner_rect.bottom() - expanded_view_height,
+                        height: expanded_view_height,
+                        ..inner_rect
+                    };
+                    let expanded_view =
+                        Paragraph::new(self.expanded_view_lines())
+                            .block(Block::default().borders(Borders::TOP).border_style(
+                                self.styling.resolve(Role::Border { selected: false }),
+                            ))
+                            .style(self.styling.resolve(Role::Text))
+                            .wrap(Wrap { trim: false });
+                    f.render_widget(expanded_view, expanded_view_loc);
+                }
             } else {
                 let table = Table::new(once(Row::new(Text::raw("No data"))))
                     .block(block)
-                    .style(self.styling.text_style)
+                    .style(self.styling.resolve(Role::Text))
                     .widths(&[Constraint::Percentage(100)]);
                 f.render_widget(table, margined_draw_loc);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::cmp::max;
+
+    use tui::{
+        buffer::Buffer,
+        style::{Modifier, Style},
+        text::Text,
+    };
+
+    use super::*;
+    use crate::{
+        app::layout_manager::BottomLayout,
+        canvas::canvas_styling::CanvasStyling,
+        components::data_table::{Column, ColumnWidthBounds, DataTableProps, DataTableStyling},
+        utils::{gen_util::truncate_to_text, test_utils::*},
+    };
+
+    struct TestType {
+        name: &'static str,
+        value: &'static str,
+    }
+
+    impl DataToCell<&'static str> for TestType {
+        fn to_cell<'a>(&'a self, column: &&'static str, calculated_width: u16) -> Option<Text<'a>> {
+            let content = match *column {
+                "Name" => self.name,
+                _ => self.value,
+            };
+
+            Some(truncate_to_text(content, calculated_width))
+        }
+
+        fn column_widths<C: DataTableColumn<&'static str>>(data: &[Self], columns: &[C]) -> Vec<u16>
+        where
+            Self: Sized,
+        {
+            let mut widths = vec![0; columns.len()];
+            for row in data {
+                widths[0] = max(widths[0], row.name.len() as u16);
+                widths[1] = max(widths[1], row.value.len() as u16);
+            }
+
+            widths
+        }
+    }
+
+    fn test_table(show_table_scroll_position: bool) -> DataTable<TestType, &'static str> {
+        let columns = [Column::hard("Name", 8), Column::hard("Value", 6)];
+        let props = DataTableProps {
+            title: Some(" Test ".into()),
+            left_to_right: true,
+            show_table_scroll_position,
+            ..Default::default()
+        };
+        let styling = DataTableStyling {
+            highlighted_text_style: Style::default().add_modifier(Modifier::REVERSED),
+            ..Default::default()
+        };
+
+        let mut table = DataTable::new(columns, props, styling);
+        table.set_data(vec![
+            TestType {
+                name: "init",
+                value: "1",
+            },
+            TestType {
+                name: "a_very_long_name",
+                value: "1234567890",
+            },
+            TestType {
+                name: "bash",
+                value: "3",
+            },
+        ]);
+
+        table
+    }
+
+    fn draw_table(
+        table: &mut DataTable<TestType, &'static str>, width: u16, height: u16,
+        selection_state: SelectionState,
+    ) -> Buffer {
+        let painter = Painter::init(
+            BottomLayout::init_basic_default(false),
+            CanvasStyling::default(),
+        )
+        .unwrap();
+
+        draw_to_buffer(width, height, |f| {
+            let draw_info = DrawInfo {
+                loc: f.size(),
+                force_redraw: false,
+                recalculate_column_widths: true,
+                selection_state,
+            };
+            table.draw(f, &draw_info, None, &painter);
+        })
+    }
+
+    #[test]
+    fn test_border_and_title() {
+        let mut table = test_table(true);
+        let buffer = draw_table(&mut table, 24, 6, SelectionState::NotSelected);
+
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test (1 of 3) ───────┐
+            |│Name        Value     │
+            |│init        1         │
+            |│a_very_lon… 12345678… │
+            |│bash        3         │
+            |└──────────────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_title_without_space_for_position() {
+        let mut table = test_table(true);
+        let buffer = draw_table(&mut table, 16, 3, SelectionState::NotSelected);
+
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────┐
+            |│init          │
+            |└──────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_uppercase_headers() {
+        let mut table = test_table(false);
+        table.props.uppercase_headers = true;
+        table.columns.push(Column::hard("straße", 8));
+
+        let buffer = draw_table(&mut table, 32, 4, SelectionState::NotSelected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────────────┐
+            |│NAME       VALUE    STRASSE   │
+            |│init       1        1         │
+            |└──────────────────────────────┘
+            ",
+        );
+
+        // Only the drawn headers change.
+        assert_eq!(table.column_index("Name"), Some(0));
+        assert_eq!(table.column_index("straße"), Some(2));
+        assert_eq!(table.column_index("NAME"), None);
+    }
+
+    #[test]
+    fn test_dropped_columns() {
+        let mut table = test_table(false);
+        table.columns.push(Column::hard("Extra", 6));
+        assert!(table.dropped_columns().is_empty());
+
+        draw_table(&mut table, 24, 5, SelectionState::NotSelected);
+        assert!(table.dropped_columns().is_empty());
+
+        draw_table(&mut table, 20, 5, SelectionState::NotSelected);
+        assert_eq!(table.dropped_columns(), vec![2]);
+
+        draw_table(&mut table, 12, 5, SelectionState::NotSelected);
+        assert_eq!(table.dropped_columns(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_pinned_bottom_rows() {
+        let mut table = test_table(false).with_pinned_bottom_rows(2);
+        table.set_data(
+            ["a", "b", "c", "d", "e", "f"]
+                .into_iter()
+                .map(|name| TestType { name, value: "1" })
+                .chain([
+                    TestType {
+                        name: "total",
+                        value: "6",
+                    },
+                    TestType {
+                        name: "average",
+                        value: "1",
+                    },
+                ])
+                .collect(),
+        );
+
+        let buffer = draw_table(&mut table, 20, 8, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────┐
+            |│Name      Value   │
+            |│a         1       │
+            |│b         1       │
+            |│c         1       │
+            |│total     6       │
+            |│average   1       │
+            |└──────────────────┘
+            ",
+        );
+
+        // Scrolling only moves the rows above the pinned ones.
+        table.set_position(4);
+        let buffer = draw_table(&mut table, 20, 8, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────┐
+            |│Name      Value   │
+            |│c         1       │
+            |│d         1       │
+            |│e         1       │
+            |│total     6       │
+            |│average   1       │
+            |└──────────────────┘
+            ",
+        );
+        assert_eq!(table.tui_selected(), Some(2));
+
+        // Pinned rows can't be selected, so this stops at the last of the other rows.
+        table.set_position(7);
+        assert_eq!(table.current_index(), 5);
+        table.set_last();
+        assert_eq!(table.current_index(), 5);
+        assert_eq!(table.increment_position(1), None);
+        let buffer = draw_table(&mut table, 20, 8, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────┐
+            |│Name      Value   │
+            |│d         1       │
+            |│e         1       │
+            |│f         1       │
+            |│total     6       │
+            |│average   1       │
+            |└──────────────────┘
+            ",
+        );
+        assert_eq!(table.tui_selected(), Some(2));
+
+        // Pinned rows aren't counted in the scroll position either.
+        table.props.show_table_scroll_position = true;
+        let buffer = draw_table(&mut table, 20, 8, SelectionState::Selected);
+        assert!(buffer_lines(&buffer)[0].contains("(6 of 6)"));
+    }
+
+    #[test]
+    fn test_paged_without_scroll_position() {
+        let mut table = test_table(false).with_paging(true);
+        let buffer = draw_table(&mut table, 24, 5, SelectionState::Selected);
+        assert_eq!(buffer_lines(&buffer)[0], "┌ Test (page 1/2) ─────┐");
+
+        // Only the position within the table depends on the setting.
+        table.set_paged(false);
+        let buffer = draw_table(&mut table, 24, 5, SelectionState::Selected);
+        assert_eq!(buffer_lines(&buffer)[0], "┌ Test ────────────────┐");
+    }
+
+    #[test]
+    fn test_paged() {
+        let mut table = test_table(true).with_paging(true);
+        table.set_data(
+            ["a", "b", "c", "d", "e", "f", "g"]
+                .into_iter()
+                .map(|name| TestType { name, value: "1" })
+                .collect(),
+        );
+        table.set_position(1);
+
+        // Nothing has been drawn yet, so the page size isn't known.
+        assert!(!table.move_pages(1));
+
+        let buffer = draw_table(&mut table, 24, 6, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test (page 1/3) ─────┐
+            |│Name        Value     │
+            |│a           1         │
+            |│b           1         │
+            |│c           1         │
+            |└──────────────────────┘
+            ",
+        );
+
+        // Moving a page keeps the same place within the page.
+        assert!(table.move_pages(1));
+        assert_eq!(table.current_index(), 4);
+        let buffer = draw_table(&mut table, 24, 6, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test (page 2/3) ─────┐
+            |│Name        Value     │
+            |│d           1         │
+            |│e           1         │
+            |│f           1         │
+            |└──────────────────────┘
+            ",
+        );
+        assert_eq!(table.tui_selected(), Some(1));
+
+        // The last page only has the rows that are left, and stops at the last one.
+        assert!(table.move_pages(1));
+        assert_eq!(table.current_index(), 6);
+        let buffer = draw_table(&mut table, 24, 6, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test (page 3/3) ─────┐
+            |│Name        Value     │
+            |│g           1         │
+            |│                      │
+            |│                      │
+            |└──────────────────────┘
+            ",
+        );
+
+        assert!(table.move_pages(-5));
+        assert_eq!(table.current_index(), 0);
+
+        table.set_last();
+        draw_table(&mut table, 24, 6, SelectionState::Selected);
+        assert_eq!(table.page(), Some((3, 3)));
+
+        // Switching back to scrolling keeps the same row selected.
+        table.set_paged(false);
+        assert_eq!(table.page(), None);
+        assert!(!table.move_pages(1));
+        let buffer = draw_table(&mut table, 24, 6, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test (7 of 7) ───────┐
+            |│Name        Value     │
+            |│e           1         │
+            |│f           1         │
+            |│g           1         │
+            |└──────────────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_row_details() {
+        let mut table = test_table(false).with_row_details(true);
+        table.toggle_row_details(0);
+
+        let buffer = draw_table(&mut table, 24, 8, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────┐
+            |│Name        Value     │
+            |│init        1         │
+            |│  Name:  init         │
+            |│  Value: 1            │
+            |│a_very_lon… 12345678… │
+            |│bash        3         │
+            |└──────────────────────┘
+            ",
+        );
+        assert_eq!(table.row_at_line(1), Some(0));
+        assert_eq!(table.row_at_line(2), Some(0));
+        assert_eq!(table.row_at_line(3), Some(1));
+        assert_eq!(table.row_at_line(5), None);
+
+        // Selecting a row pushed out of view scrolls just enough to show it.
+        table.set_position(2);
+        table.toggle_row_details(1);
+        let buffer = draw_table(&mut table, 24, 8, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────┐
+            |│Name        Value     │
+            |│a_very_lon… 12345678… │
+            |│  Name:  a_very_long_…│
+            |│  Value: 1234567890   │
+            |│bash        3         │
+            |│                      │
+            |└──────────────────────┘
+            ",
+        );
+        assert_eq!(table.tui_selected(), Some(1));
+
+        // Collapsing a row pulls the rows below it back up.
+        table.toggle_row_details(1);
+        table.set_first();
+        let buffer = draw_table(&mut table, 24, 8, SelectionState::Selected);
+        assert_eq!(
+            buffer_lines(&buffer)[5..7],
+            ["│a_very_lon… 12345678… │", "│bash        3         │"]
+        );
+        assert!(!table.is_row_expanded(1));
+    }
+
+    #[test]
+    fn test_narrow_cards() {
+        let mut table = test_table(false).with_narrow_mode(NarrowMode::Cards);
+        table.set_position(1);
+
+        // Wide enough for every column, so this is still drawn as a table.
+        let buffer = draw_table(&mut table, 24, 9, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────┐
+            |│Name        Value     │
+            |│init        1         │
+            |│a_very_lon… 12345678… │
+            |│bash        3         │
+            |│                      │
+            |│                      │
+            |│                      │
+            |└──────────────────────┘
+            ",
+        );
+
+        // Too narrow for the value column, so each row becomes a card instead.
+        let buffer = draw_table(&mut table, 16, 9, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────┐
+            |│Name:  init   │
+            |│Value: 1      │
+            |│              │
+            |│Name:  a_very…│
+            |│Value: 123456…│
+            |│              │
+            |│              │
+            |└──────────────┘
+            ",
+        );
+
+        // The whole selected card is highlighted.
+        assert_snapshot(
+            &buffer_style_map(&buffer, |cell| {
+                if cell.modifier.contains(Modifier::REVERSED) {
+                    'H'
+                } else {
+                    '.'
+                }
+            }),
+            "
+            |................
+            |................
+            |................
+            |................
+            |.HHHHHHHHHHHHHH.
+            |.HHHHHHHHHHHHHH.
+            |................
+            |................
+            |................
+            ",
+        );
+
+        // Scrolling moves by whole cards.
+        table.set_position(2);
+        let buffer = draw_table(&mut table, 16, 9, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────┐
+            |│Name:  a_very…│
+            |│Value: 123456…│
+            |│              │
+            |│Name:  bash   │
+            |│Value: 3      │
+            |│              │
+            |│              │
+            |└──────────────┘
+            ",
+        );
+        assert_eq!(table.tui_selected(), Some(1));
+    }
+
+    #[test]
+    fn test_expanded_title() {
+        let mut table = test_table(false);
+        let buffer = draw_table(&mut table, 30, 3, SelectionState::Expanded);
+
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────── Esc to go back ┐
+            |│init           1            │
+            |└────────────────────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_truncation_and_highlight() {
+        let mut table = test_table(false);
+        table.set_position(1);
+        let buffer = draw_table(&mut table, 20, 5, SelectionState::Selected);
+
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────┐
+            |│Name      Value   │
+            |│init      1       │
+            |│a_very_l… 123456… │
+            |└──────────────────┘
+            ",
+        );
+        assert_snapshot(
+            &buffer_style_map(&buffer, |cell| {
+                if cell.modifier.contains(Modifier::REVERSED) {
+                    'H'
+                } else {
+                    '.'
+                }
+            }),
+            "
+            |....................
+            |....................
+            |....................
+            |.HHHHHHHHHHHHHHHHHH.
+            |....................
+            ",
+        );
+    }
+
+    #[test]
+    fn test_expanded_view() {
+        let mut table = test_table(false).with_expanded_view(true);
+        table.set_position(1);
+        let buffer = draw_table(&mut table, 20, 9, SelectionState::Selected);
+
+        // The selected row's values are cut short in the table, but not below it.
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────┐
+            |│Name      Value   │
+            |│init      1       │
+            |│a_very_l… 123456… │
+            |│──────────────────│
+            |│Name:             │
+            |│a_very_long_name  │
+            |│Value: 1234567890 │
+            |└──────────────────┘
+            ",
+        );
+
+        // Without enough room, the table's rows come first.
+        let buffer = draw_table(&mut table, 20, 5, SelectionState::Selected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────┐
+            |│Name      Value   │
+            |│init      1       │
+            |│a_very_l… 123456… │
+            |└──────────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_expanded_does_not_drop_columns() {
+        let mut table = test_table(false);
+        let buffer = draw_table(&mut table, 16, 4, SelectionState::NotSelected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────┐
+            |│Name          │
+            |│init          │
+            |└──────────────┘
+            ",
+        );
+
+        table.set_expanded(true);
+        let buffer = draw_table(&mut table, 16, 4, SelectionState::Expanded);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ── Esc t┐
+            |│Name     Value│
+            |│init     1    │
+            |└──────────────┘
+            ",
+        );
+
+        // Collapsing goes back to dropping columns.
+        table.set_expanded(false);
+        let buffer = draw_table(&mut table, 16, 4, SelectionState::NotSelected);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────┐
+            |│Name          │
+            |│init          │
+            |└──────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_style_overrides() {
+        let mut table = test_table(false);
+        table.styling = std::mem::take(&mut table.styling).with_override(
+            Role::TableHeader,
+            Style::default().add_modifier(Modifier::BOLD),
+        );
+        let buffer = draw_table(&mut table, 20, 4, SelectionState::NotSelected);
+
+        assert_snapshot(
+            &buffer_style_map(&buffer, |cell| {
+                if cell.modifier.contains(Modifier::BOLD) {
+                    'B'
+                } else {
+                    '.'
+                }
+            }),
+            "
+            |....................
+            |.BBBBBBBBBBBBBBBBBB.
+            |....................
+            |....................
+            ",
+        );
+    }
+
+    #[test]
+    fn test_dim_when_unfocused() {
+        let mut table = test_table(false);
+        table.props.dim_when_unfocused = true;
+        table.styling.dimmed_style = Style::default().add_modifier(Modifier::DIM);
+        let dim_map = |buffer: &Buffer| {
+            buffer_style_map(buffer, |cell| {
+                if cell.modifier.contains(Modifier::DIM) {
+                    'D'
+                } else {
+                    '.'
+                }
+            })
+        };
+
+        // The whole table is dimmed, borders included.
+        let buffer = draw_table(&mut table, 20, 6, SelectionState::NotSelected);
+        assert_snapshot(
+            &dim_map(&buffer),
+            "
+            |DDDDDDDDDDDDDDDDDDDD
+            |DDDDDDDDDDDDDDDDDDDD
+            |DDDDDDDDDDDDDDDDDDDD
+            |DDDDDDDDDDDDDDDDDDDD
+            |DDDDDDDDDDDDDDDDDDDD
+            |DDDDDDDDDDDDDDDDDDDD
+            ",
+        );
+
+        let buffer = draw_table(&mut table, 20, 6, SelectionState::Selected);
+        assert!(!buffer
+            .content
+            .iter()
+            .any(|cell| cell.modifier.contains(Modifier::DIM)));
+
+        table.props.dim_when_unfocused = false;
+        let buffer = draw_table(&mut table, 20, 6, SelectionState::NotSelected);
+        assert!(!buffer
+            .content
+            .iter()
+            .any(|cell| cell.modifier.contains(Modifier::DIM)));
+    }
+
+    #[test]
+    fn test_zero_visible_rows() {
+        let mut table = test_table(false);
+        let buffer = draw_table(&mut table, 24, 2, SelectionState::NotSelected);
+
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ─ Too small ────┐
+            |└──────────────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_set_column_header() {
+        let mut table = test_table(false);
+        table.columns = vec![Column::new("Name"), Column::new("Value")];
+        draw_table(&mut table, 24, 4, SelectionState::NotSelected);
+        assert_eq!(table.state.calculated_widths, vec![10, 10]);
+
+        // Changing the header should recalculate the widths, even if the draw doesn't ask for it.
+        table.set_column_header(1, "Value (MiB)".into());
+        let painter = Painter::init(
+            BottomLayout::init_basic_default(false),
+            CanvasStyling::default(),
+        )
+        .unwrap();
+        let buffer = draw_to_buffer(24, 4, |f| {
+            let draw_info = DrawInfo {
+                loc: f.size(),
+                force_redraw: false,
+                recalculate_column_widths: false,
+                selection_state: SelectionState::NotSelected,
+            };
+            table.draw(f, &draw_info, None, &painter);
+        });
+
+        assert_eq!(table.state.calculated_widths, vec![7, 13]);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────┐
+            |│Name    Value (MiB)   │
+            |│init    1             │
+            |└──────────────────────┘
+            ",
+        );
+
+        // A header too long to fit is truncated when expanded, rather than dropping the column.
+        table.set_expanded(true);
+        table.set_column_header(1, "Value (in mebibytes)".into());
+        let buffer = draw_table(&mut table, 24, 4, SelectionState::NotSelected);
+
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────┐
+            |│Name Value (in mebiby…│
+            |│init 1                │
+            |└──────────────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_soft_columns_fit_data() {
+        let mut table = test_table(false);
+        table.columns = vec![Column::soft("Name", Some(0.5)), Column::hard("Value", 10)];
+        table.set_data(vec![TestType {
+            name: "init",
+            value: "1",
+        }]);
+        draw_table(&mut table, 24, 4, SelectionState::NotSelected);
+
+        let desired = |table: &DataTable<TestType, &'static str>| match table.columns[0].bounds() {
+            ColumnWidthBounds::Soft { desired, .. } => desired,
+            _ => unreachable!(),
+        };
+        assert_eq!(desired(&table), 4);
+
+        // New data should resize the column on the next draw, even if it doesn't ask to recalculate.
+        table.set_data(vec![TestType {
+            name: "a_very_long_name",
+            value: "1",
+        }]);
+        assert_eq!(desired(&table), 16);
+
+        let painter = Painter::init(
+            BottomLayout::init_basic_default(false),
+            CanvasStyling::default(),
+        )
+        .unwrap();
+        let buffer = draw_to_buffer(24, 4, |f| {
+            let draw_info = DrawInfo {
+                loc: f.size(),
+                force_redraw: false,
+                recalculate_column_widths: false,
+                selection_state: SelectionState::NotSelected,
+            };
+            table.draw(f, &draw_info, None, &painter);
+        });
+
+        // The column is capped at half of the width.
+        assert_eq!(table.state.calculated_widths, vec![11, 10]);
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────┐
+            |│Name        Value     │
+            |│a_very_lon… 1         │
+            |└──────────────────────┘
+            ",
+        );
+    }
+
+    #[test]
+    fn test_colour_schemes() {
+        use crate::{canvas::ColourScheme, options::Config};
+
+        let header_colour = |scheme: ColourScheme| {
+            let colours = CanvasStyling::new(scheme, &Config::default()).unwrap();
+            let mut table = test_table(false);
+            table.styling = DataTableStyling::from_colours(&colours);
+
+            let buffer = draw_table(&mut table, 24, 4, SelectionState::NotSelected);
+            buffer.get(1, 1).fg
+        };
+
+        let gruvbox = header_colour(ColourScheme::Gruvbox);
+        let nord = header_colour(ColourScheme::Nord);
+        assert_ne!(gruvbox, nord);
+        assert_eq!(
+            Some(nord),
+            CanvasStyling::new(ColourScheme::Nord, &Config::default())
+                .unwrap()
+                .table_header_style
+                .fg
+        );
+    }
+
+    #[test]
+    fn test_selection_visible_after_resize() {
+        const NAMES: [&str; 12] = [
+            "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11",
+        ];
+
+        let mut table = test_table(false);
+        table.set_data(
+            NAMES
+                .into_iter()
+                .map(|name| TestType { name, value: "1" })
+                .collect(),
+        );
+        table.set_position(9);
+        draw_table(&mut table, 24, 16, SelectionState::Selected);
+        assert_eq!(table.state.window.start, 0);
+
+        // Shrinking the terminal so the selected row would be below the table scrolls it back
+        // into view on the next draw.
+        let buffer = draw_table(&mut table, 24, 6, SelectionState::Selected);
+        assert_eq!(table.state.window.start, 7);
+        assert_eq!(table.tui_selected(), Some(2));
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌ Test ────────────────┐
+            |│Name        Value     │
+            |│r7          1         │
+            |│r8          1         │
+            |│r9          1         │
+            |└──────────────────────┘
+            ",
+        );
+
+        // Growing it again doesn't leave empty space below the rows.
+        draw_table(&mut table, 24, 16, SelectionState::Selected);
+        assert_eq!(table.state.window.start, 0);
+        assert_eq!(table.tui_selected(), Some(9));
+    }
+
+    #[test]
+    fn test_table_background() {
+        use tui::style::Color;
+
+        let mut table = test_table(false);
+        table.styling.background_style = Style::default().bg(Color::DarkGray);
+        let buffer = draw_table(&mut table, 24, 8, SelectionState::NotSelected);
+
+        // Everything inside the borders should be filled, including the rows below the last entry.
+        assert_snapshot(
+            &buffer_style_map(
+                &buffer,
+                |cell| {
+                    if cell.bg == Color::DarkGray {
+                        'B'
+                    } else {
+                        '.'
+                    }
+                },
+            ),
+            "
+            |........................
+            |.BBBBBBBBBBBBBBBBBBBBBB.
+            |.BBBBBBBBBBBBBBBBBBBBBB.
+            |.BBBBBBBBBBBBBBBBBBBBBB.
+            |.BBBBBBBBBBBBBBBBBBBBBB.
+            |.BBBBBBBBBBBBBBBBBBBBBB.
+            |.BBBBBBBBBBBBBBBBBBBBBB.
+            |........................
+            ",
+        );
+    }
+
+    #[test]
+    fn test_sparkline_cell() {
+        use crate::canvas::canvas_styling::Symbols;
+
+        struct TrendType {
+            history: Vec<f64>,
+        }
+
+        impl DataToCell<&'static str> for TrendType {
+            fn to_cell<'a>(
+                &'a self, _column: &&'static str, calculated_width: u16,
+            ) -> Option<Text<'a>> {
+                Some(Symbols::UNICODE.sparkline(&self.history, calculated_width))
+            }
+
+            fn column_widths<C: DataTableColumn<&'static str>>(
+                _data: &[Self], columns: &[C],
+            ) -> Vec<u16>
+            where
+                Self: Sized,
+            {
+                vec![0; columns.len()]
+            }
+        }
+
+        let props = DataTableProps {
+            left_to_right: true,
+            ..Default::default()
+        };
+        let mut table = DataTable::new(
+            [Column::hard("Trend", 8)],
+            props,
+            DataTableStyling::default(),
+        );
+        table.set_data(vec![
+            TrendType {
+                history: vec![0.0, 2.0, 4.0, 6.0, 8.0],
+            },
+            TrendType {
+                history: (0..=16).map(f64::from).collect(),
+            },
+        ]);
+
+        let painter = Painter::init(
+            BottomLayout::init_basic_default(false),
+            CanvasStyling::default(),
+        )
+        .unwrap();
+        let buffer = draw_to_buffer(10, 5, |f| {
+            let draw_info = DrawInfo {
+                loc: f.size(),
+                force_redraw: false,
+                recalculate_column_widths: true,
+                selection_state: SelectionState::NotSelected,
+            };
+            table.draw(f, &draw_info, None, &painter);
+        });
+
+        // The second row only has room for its 8 most recent values.
+        assert_snapshot(
+            &buffer_lines(&buffer),
+            "
+            |┌────────┐
+            |│Trend   │
+            |│ ▂▄▆█   │
+            |│▅▅▆▆▇▇██│
+            |└────────┘
+            ",
+        );
+    }
+}
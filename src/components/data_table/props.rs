@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+#[derive(Default)]
 pub struct DataTableProps {
     /// An optional title for the table.
     pub title: Option<Cow<'static, str>>,
@@ -18,4 +19,11 @@ pub struct DataTableProps {
 
     /// Whether to show the current entry as highlighted when not focused.
     pub show_current_entry_when_unfocused: bool,
+
+    /// Whether to draw column headers in uppercase. Only the drawn headers change, so columns
+    /// are still looked up by their actual names.
+    pub uppercase_headers: bool,
+
+    /// Whether to dim the whole table, borders included, while it isn't focused.
+    pub dim_when_unfocused: bool,
 }
@@ -0,0 +1,105 @@
+//! Tracking how the values of a table's rows change from one update to the next.
+
+use std::time::Instant;
+
+use hashbrown::HashMap;
+
+/// How a row's value changed since the previous update.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Delta<T> {
+    /// The row wasn't there in the previous update.
+    New,
+
+    /// The value changed by this much, which may be negative or zero.
+    Change(T),
+}
+
+/// Keeps each row's values from the previous update by [`DataToCell::row_id`], so the values of
+/// the current update can be compared against them.
+///
+/// [`DataToCell::row_id`]: super::DataToCell::row_id
+#[derive(Debug)]
+pub struct RowDeltas<V> {
+    previous: Option<HashMap<u64, V>>,
+    current: HashMap<u64, V>,
+    updated_at: Option<Instant>,
+}
+
+impl<V> Default for RowDeltas<V> {
+    fn default() -> Self {
+        Self {
+            previous: None,
+            current: HashMap::default(),
+            updated_at: None,
+        }
+    }
+}
+
+impl<V> RowDeltas<V> {
+    /// Records `values` as the row values of the update collected at `time`. Recording for the
+    /// same update again only replaces its values, so redrawing or re-sorting rows doesn't turn
+    /// every delta into zero.
+    pub fn record<I: IntoIterator<Item = (u64, V)>>(&mut self, time: Instant, values: I) {
+        if self.updated_at != Some(time) {
+            if self.updated_at.is_some() {
+                self.previous = Some(std::mem::take(&mut self.current));
+            }
+            self.updated_at = Some(time);
+        }
+        self.current = values.into_iter().collect();
+    }
+
+    /// Compares `value` against the previous value of the row with `row_id` using `diff`.
+    ///
+    /// Returns [`None`] if nothing was recorded before the current update, or if `diff` returns
+    /// [`None`] because the values can't be compared.
+    pub fn delta<T, F>(&self, row_id: u64, value: &V, diff: F) -> Option<Delta<T>>
+    where
+        F: FnOnce(&V, &V) -> Option<T>,
+    {
+        match self.previous.as_ref()?.get(&row_id) {
+            Some(previous) => diff(value, previous).map(Delta::Change),
+            None => Some(Delta::New),
+        }
+    }
+
+    /// Forgets all recorded values, like when rows are identified differently from now on.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_row_deltas() {
+        let diff = |a: &i64, b: &i64| Some(a - b);
+        let mut deltas = RowDeltas::default();
+        let start = Instant::now();
+
+        // There's nothing to compare against until the second update.
+        deltas.record(start, [(1, 10), (2, 20)]);
+        assert_eq!(deltas.delta(1, &10, diff), None);
+
+        let next = start + Duration::from_secs(1);
+        deltas.record(next, [(1, 15), (3, 5)]);
+        assert_eq!(deltas.delta(1, &15, diff), Some(Delta::Change(5)));
+        assert_eq!(deltas.delta(3, &5, diff), Some(Delta::New));
+        assert_eq!(deltas.delta(1, &15, |_, _| None::<i64>), None);
+
+        // Recording the same update again compares against the same previous update.
+        deltas.record(next, [(1, 15), (3, 5)]);
+        assert_eq!(deltas.delta(1, &15, diff), Some(Delta::Change(5)));
+
+        deltas.record(next + Duration::from_secs(1), [(1, 12), (3, 5)]);
+        assert_eq!(deltas.delta(1, &12, diff), Some(Delta::Change(-3)));
+        assert_eq!(deltas.delta(3, &5, diff), Some(Delta::Change(0)));
+
+        deltas.clear();
+        assert_eq!(deltas.delta(1, &12, diff), None);
+    }
+}
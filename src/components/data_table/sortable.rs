@@ -1,14 +1,18 @@
-use std::{borrow::Cow, marker::PhantomData};
+use std::{borrow::Cow, cmp::Ordering, fmt, marker::PhantomData};
 
 use concat_string::concat_string;
+use hashbrown::HashSet;
 use itertools::Itertools;
-use tui::widgets::Row;
+use tui::{text::Text, widgets::Row};
 
 use super::{
-    ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps, DataTableState,
-    DataTableStyling, DataToCell,
+    ColumnHeader, ColumnWidthBounds, ColumnWidthMode, DataTable, DataTableColumn, DataTableProps,
+    DataTableState, DataTableStyling, DataToCell, NarrowMode,
+};
+use crate::{
+    canvas::canvas_styling::Symbols,
+    utils::{clock::RealClock, gen_util::truncate_to_text},
 };
-use crate::utils::gen_util::truncate_to_text;
 
 /// Denotes the sort order.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -52,7 +56,9 @@ pub struct Sortable {
 /// and therefore only [`Unsortable`] and [`Sortable`] can implement it.
 pub trait SortType: private::Sealed {
     /// Constructs the table header.
-    fn build_header<H, C>(&self, columns: &[C], widths: &[u16]) -> Row<'_>
+    fn build_header<H, C>(
+        &self, columns: &[C], widths: &[u16], symbols: &Symbols, uppercase: bool,
+    ) -> Row<'_>
     where
         H: ColumnHeader,
         C: DataTableColumn<H>,
@@ -61,7 +67,8 @@ pub trait SortType: private::Sealed {
             if width == 0 {
                 None
             } else {
-                Some(truncate_to_text(&c.header(), width))
+                let header = header_text(c, uppercase);
+                Some(symbols.fit_header(truncate_to_text(&header, width), width))
             }
         }))
     }
@@ -79,14 +86,13 @@ mod private {
 impl SortType for Unsortable {}
 
 impl SortType for Sortable {
-    fn build_header<H, C>(&self, columns: &[C], widths: &[u16]) -> Row<'_>
+    fn build_header<H, C>(
+        &self, columns: &[C], widths: &[u16], symbols: &Symbols, uppercase: bool,
+    ) -> Row<'_>
     where
         H: ColumnHeader,
         C: DataTableColumn<H>,
     {
-        const UP_ARROW: &str = "▲";
-        const DOWN_ARROW: &str = "▼";
-
         Row::new(
             columns
                 .iter()
@@ -97,18 +103,32 @@ impl SortType for Sortable {
                         None
                     } else if index == self.sort_index {
                         let arrow = match self.order {
-                            SortOrder::Ascending => UP_ARROW,
-                            SortOrder::Descending => DOWN_ARROW,
+                            SortOrder::Ascending => symbols.sort_ascending,
+                            SortOrder::Descending => symbols.sort_descending,
                         };
-                        Some(truncate_to_text(&concat_string!(c.header(), arrow), width))
+                        let header = concat_string!(header_text(c, uppercase), arrow);
+                        Some(symbols.fit_header(truncate_to_text(&header, width), width))
                     } else {
-                        Some(truncate_to_text(&c.header(), width))
+                        let header = header_text(c, uppercase);
+                        Some(symbols.fit_header(truncate_to_text(&header, width), width))
                     }
                 }),
         )
     }
 }
 
+/// Returns the header to draw for `column`, in uppercase if `uppercase` is set.
+fn header_text<H: ColumnHeader, C: DataTableColumn<H>>(
+    column: &C, uppercase: bool,
+) -> Cow<'static, str> {
+    let header = column.header();
+    if uppercase {
+        Cow::Owned(header.to_uppercase())
+    } else {
+        header
+    }
+}
+
 pub trait SortsRow {
     type DataType;
 
@@ -116,7 +136,10 @@ pub trait SortsRow {
     fn sort_data(&self, data: &mut [Self::DataType], descending: bool);
 }
 
-#[derive(Debug, Clone)]
+/// Compares what two rows show in a column, in ascending order. Used to sort a column with custom
+/// logic, like natural sorting, in place of its [`SortsRow`] implementation.
+pub type SortComparator = Box<dyn Fn(&str, &str) -> Ordering>;
+
 pub struct SortColumn<T> {
     /// The inner column header.
     inner: T,
@@ -129,6 +152,25 @@ pub struct SortColumn<T> {
 
     /// Marks that this column is currently "hidden", and should *always* be skipped.
     pub is_hidden: bool,
+
+    /// A header to display instead of the inner column header, if set.
+    header_override: Option<Cow<'static, str>>,
+
+    /// Sorts the column instead of its [`SortsRow`] implementation, if set.
+    comparator: Option<SortComparator>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for SortColumn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortColumn")
+            .field("inner", &self.inner)
+            .field("default_order", &self.default_order)
+            .field("bounds", &self.bounds)
+            .field("is_hidden", &self.is_hidden)
+            .field("header_override", &self.header_override)
+            .field("comparator", &self.comparator.is_some())
+            .finish()
+    }
 }
 
 impl<D, T> DataTableColumn<T> for SortColumn<T>
@@ -166,7 +208,18 @@ where
     }
 
     fn header(&self) -> Cow<'static, str> {
-        self.inner.header()
+        match &self.header_override {
+            Some(header) => header.clone(),
+            None => self.inner.header(),
+        }
+    }
+
+    fn set_header(&mut self, header: Cow<'static, str>) {
+        self.header_override = Some(header);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.inner.description()
     }
 
     fn header_len(&self) -> usize {
@@ -186,6 +239,8 @@ where
             bounds: ColumnWidthBounds::FollowHeader,
             is_hidden: false,
             default_order: SortOrder::default(),
+            header_override: None,
+            comparator: None,
         }
     }
 
@@ -197,6 +252,8 @@ where
             bounds: ColumnWidthBounds::Hard(width),
             is_hidden: false,
             default_order: SortOrder::default(),
+            header_override: None,
+            comparator: None,
         }
     }
 
@@ -211,6 +268,8 @@ where
             },
             is_hidden: false,
             default_order: SortOrder::default(),
+            header_override: None,
+            comparator: None,
         }
     }
 
@@ -226,10 +285,87 @@ where
         self
     }
 
+    /// Displays `header` instead of the inner column header's text.
+    pub fn with_header<S: Into<Cow<'static, str>>>(mut self, header: S) -> Self {
+        self.header_override = Some(header.into());
+        self
+    }
+
+    /// Starts the column off hidden.
+    pub fn hidden(mut self) -> Self {
+        self.is_hidden = true;
+        self
+    }
+
+    /// Sorts the column with `comparator`, which is given what each row shows in the column,
+    /// rather than with the built-in sort.
+    pub fn with_comparator<F: Fn(&str, &str) -> Ordering + 'static>(
+        mut self, comparator: F,
+    ) -> Self {
+        self.comparator = Some(Box::new(comparator));
+        self
+    }
+
+    /// Replaces the comparator used to sort the column. [`None`] goes back to the built-in sort.
+    pub fn set_comparator(&mut self, comparator: Option<SortComparator>) {
+        self.comparator = comparator;
+    }
+
     /// Given a [`SortColumn`] and the sort order, sort a mutable slice of associated data.
-    pub fn sort_by(&self, data: &mut [D], order: SortOrder) {
+    pub fn sort_by(&self, data: &mut [D], order: SortOrder)
+    where
+        D: DataToCell<T>,
+    {
         let descending = matches!(order, SortOrder::Descending);
-        self.inner.sort_data(data, descending);
+        match &self.comparator {
+            Some(comparator) => {
+                let text = |row: &D| {
+                    row.to_cell(&self.inner, u16::MAX)
+                        .map(|text| {
+                            text.lines
+                                .iter()
+                                .flat_map(|line| line.spans.iter())
+                                .map(|span| span.content.as_ref())
+                                .collect::<String>()
+                        })
+                        .unwrap_or_default()
+                };
+
+                // Write out each row's text once up front, rather than twice per comparison.
+                let mut keyed: Vec<(String, usize)> = data
+                    .iter()
+                    .enumerate()
+                    .map(|(index, row)| (text(row), index))
+                    .collect();
+                keyed.sort_by(|(a, _), (b, _)| {
+                    let ordering = comparator(a, b);
+                    if descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                });
+
+                let order: Vec<usize> = keyed.into_iter().map(|(_, index)| index).collect();
+                apply_order(data, order);
+            }
+            None => self.inner.sort_data(data, descending),
+        }
+    }
+}
+
+/// Rearranges `data` so that the row at `order[i]` ends up at `i`.
+fn apply_order<D>(data: &mut [D], mut order: Vec<usize>) {
+    for start in 0..order.len() {
+        // Follow each cycle of the permutation, swapping rows into place as we go.
+        let mut current = start;
+        while order[current] != start {
+            let next = order[current];
+            data.swap(current, next);
+            order[current] = current;
+            current = next;
+        }
+        order[current] = current;
     }
 }
 
@@ -261,6 +397,21 @@ where
             },
             first_draw: true,
             data: vec![],
+            row_filter: None,
+            max_rows: None,
+            pinned_bottom_rows: 0,
+            expanded_view: false,
+            marked_ids: HashSet::default(),
+            row_details: false,
+            expanded_rows: HashSet::default(),
+            paged: false,
+            width_mode: ColumnWidthMode::default(),
+            narrow_mode: NarrowMode::default(),
+            is_expanded: false,
+            widths_outdated: false,
+            row_widths: vec![],
+            row_widths_columns: vec![],
+            clock: Box::new(RealClock),
             _pd: PhantomData,
         }
     }
@@ -316,13 +467,131 @@ where
         }
     }
 
+    /// If the column the table is sorted by is hidden, sorts by the first shown column instead,
+    /// in that column's default order. Does nothing if every column is hidden.
+    pub fn sort_by_visible_column(&mut self) {
+        let is_sort_hidden = matches!(
+            self.columns.get(self.sort_type.sort_index),
+            Some(column) if column.is_hidden()
+        );
+
+        if is_sort_hidden {
+            if let Some(index) = self.columns.iter().position(|column| !column.is_hidden()) {
+                self.sort_type.sort_index = index;
+                self.sort_type.order = self.columns[index].default_order;
+            }
+        }
+    }
+
+    /// Keeps only the columns named in `names`, in that order, matching them like
+    /// [`DataTable::hide_columns`]. The table stays sorted by the same column if it is kept, and
+    /// otherwise by the first one. Nothing changes if no names match. Returns the names that didn't
+    /// match any column.
+    pub fn set_columns_by_name<'a>(&mut self, names: &'a [String]) -> Vec<&'a str> {
+        let mut unknown = Vec::new();
+        let mut order: Vec<usize> = Vec::new();
+        for name in names {
+            match self.column_index_by_name(name) {
+                Some(index) if !order.contains(&index) => order.push(index),
+                Some(_) => {}
+                None => unknown.push(name.as_str()),
+            }
+        }
+        if order.is_empty() {
+            return unknown;
+        }
+
+        let mut columns: Vec<Option<SortColumn<H>>> = std::mem::take(&mut self.columns)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.columns = order
+            .iter()
+            .filter_map(|&index| columns[index].take())
+            .collect();
+
+        match order
+            .iter()
+            .position(|&index| index == self.sort_type.sort_index)
+        {
+            Some(index) => self.sort_type.sort_index = index,
+            None => {
+                self.sort_type.sort_index = 0;
+                self.sort_type.order = self.columns[0].default_order;
+            }
+        }
+        self.widths_outdated = true;
+
+        unknown
+    }
+
     /// Returns the current sort index.
     pub fn sort_index(&self) -> usize {
         self.sort_type.sort_index
     }
 
+    /// Returns the index of the column the table is sorted by and the sort order, or [`None`] if
+    /// there is no such column.
+    pub fn active_sort(&self) -> Option<(usize, SortOrder)> {
+        self.columns
+            .get(self.sort_type.sort_index)
+            .map(|_| (self.sort_type.sort_index, self.sort_type.order))
+    }
+
+    /// Moves the selection to the first row after the current one whose value in the sort column
+    /// differs, returning the new position if it moved.
+    pub fn skip_to_next_group(&mut self) -> Option<usize> {
+        let current_index = self.state.current_index;
+        let current = self.sort_value(current_index)?;
+        let next = (current_index + 1..self.scrolled_len())
+            .find(|&index| self.sort_value(index).as_ref() != Some(&current))?;
+
+        self.set_position(next);
+        Some(next)
+    }
+
+    /// Moves the selection to the first row of the current group of rows that share a value in the
+    /// sort column, or to the first row of the previous group if it is already there. Returns the
+    /// new position if it moved.
+    pub fn skip_to_previous_group(&mut self) -> Option<usize> {
+        let current_index = self.state.current_index;
+        let group_start = self.group_start(current_index)?;
+        let previous = if group_start < current_index {
+            group_start
+        } else {
+            self.group_start(group_start.checked_sub(1)?)?
+        };
+
+        self.set_position(previous);
+        Some(previous)
+    }
+
+    /// Returns the first index of the group of rows that share a value in the sort column with the
+    /// row at `index`.
+    fn group_start(&self, index: usize) -> Option<usize> {
+        let value = self.sort_value(index)?;
+        let start = (0..index)
+            .rev()
+            .find(|&other| self.sort_value(other).as_ref() != Some(&value))
+            .map_or(0, |other| other + 1);
+
+        Some(start)
+    }
+
+    /// Returns what the row at `index` shows in the sort column.
+    fn sort_value(&self, index: usize) -> Option<Text<'_>> {
+        let column = self.columns.get(self.sort_type.sort_index)?;
+        self.data.get(index)?.to_cell(column.inner(), u16::MAX)
+    }
+
     /// Given a `needle` coordinate, select the corresponding index and value.
     fn get_range(&self, needle: u16) -> Option<usize> {
+        // Column starts are capped at the end of the table, so very wide columns can't wrap around.
+        let end = self.state.inner_rect.right();
+        if needle >= end {
+            return None;
+        }
+
         let mut start = self.state.inner_rect.x;
         let range = self
             .state
@@ -330,7 +599,7 @@ where
             .iter()
             .map(|width| {
                 let entry_start = start;
-                start += width + 1; // +1 for the gap b/w cols.
+                start = start.saturating_add(width.saturating_add(1)).min(end); // +1 for the gap b/w cols.
 
                 entry_start
             })
@@ -356,13 +625,20 @@ mod test {
     enum ColumnType {
         Index,
         Data,
+        Version,
     }
 
     impl DataToCell<ColumnType> for TestType {
         fn to_cell<'a>(
-            &'a self, _column: &ColumnType, _calculated_width: u16,
+            &'a self, column: &ColumnType, _calculated_width: u16,
         ) -> Option<tui::text::Text<'a>> {
-            None
+            let value = match column {
+                ColumnType::Index => self.index.to_string(),
+                ColumnType::Data => self.data.to_string(),
+                ColumnType::Version => format!("v1.{}", self.data),
+            };
+
+            Some(Text::raw(value))
         }
 
         fn column_widths<C: DataTableColumn<ColumnType>>(_data: &[Self], _columns: &[C]) -> Vec<u16>
@@ -378,6 +654,14 @@ mod test {
             match self {
                 ColumnType::Index => "Index".into(),
                 ColumnType::Data => "Data".into(),
+                ColumnType::Version => "Version".into(),
+            }
+        }
+
+        fn description(&self) -> Option<&str> {
+            match self {
+                ColumnType::Data => Some("The row's value"),
+                ColumnType::Index | ColumnType::Version => None,
             }
         }
     }
@@ -388,7 +672,7 @@ mod test {
         fn sort_data(&self, data: &mut [TestType], descending: bool) {
             match self {
                 ColumnType::Index => data.sort_by_key(|t| t.index),
-                ColumnType::Data => data.sort_by_key(|t| t.data),
+                ColumnType::Data | ColumnType::Version => data.sort_by_key(|t| t.data),
             }
 
             if descending {
@@ -397,6 +681,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_sort_column_builder() {
+        let column = SortColumn::new(ColumnType::Index);
+        assert!(matches!(column.bounds, ColumnWidthBounds::FollowHeader));
+        assert_eq!(column.default_order, SortOrder::Ascending);
+        assert_eq!(column.header(), "Index");
+        assert_eq!(column.description(), None);
+        assert!(!column.is_hidden);
+
+        let column = SortColumn::hard(ColumnType::Data, 8)
+            .default_descending()
+            .with_header("Value")
+            .hidden();
+        assert!(matches!(column.bounds, ColumnWidthBounds::Hard(8)));
+        assert_eq!(column.default_order, SortOrder::Descending);
+        assert_eq!(column.header(), "Value");
+        assert_eq!(column.header_len(), 6);
+        assert_eq!(column.description(), Some("The row's value"));
+        assert!(column.is_hidden);
+    }
+
     #[test]
     fn test_sorting() {
         let columns = [
@@ -407,10 +712,8 @@ mod test {
             let inner = DataTableProps {
                 title: Some("test".into()),
                 table_gap: 1,
-                left_to_right: false,
-                is_basic: false,
                 show_table_scroll_position: true,
-                show_current_entry_when_unfocused: false,
+                ..Default::default()
             };
 
             SortDataTableProps {
@@ -540,4 +843,234 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_sort_comparator() {
+        /// Compares runs of digits by their value, and everything else by character.
+        fn natural_ordering(a: &str, b: &str) -> Ordering {
+            let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+            loop {
+                match (a.peek(), b.peek()) {
+                    (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                        let number = |chars: &mut std::iter::Peekable<std::str::Chars<'_>>| {
+                            let mut value = 0_u64;
+                            while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                                value = value * 10 + u64::from(digit);
+                                chars.next();
+                            }
+                            value
+                        };
+                        match number(&mut a).cmp(&number(&mut b)) {
+                            Ordering::Equal => {}
+                            ordering => return ordering,
+                        }
+                    }
+                    _ => match (a.next(), b.next()) {
+                        (Some(x), Some(y)) if x == y => {}
+                        (x, y) => return x.cmp(&y),
+                    },
+                }
+            }
+        }
+
+        let versions = |data: &[TestType]| {
+            data.iter()
+                .map(|row| format!("v1.{}", row.data))
+                .collect::<Vec<_>>()
+        };
+        let mut data = [10, 2, 1]
+            .into_iter()
+            .enumerate()
+            .map(|(index, data)| TestType { index, data })
+            .collect::<Vec<_>>();
+
+        // The comparator is used over the built-in sort, and is given what each row shows.
+        let mut column = SortColumn::new(ColumnType::Version).with_comparator(|a, b| a.cmp(b));
+        column.sort_by(&mut data, SortOrder::Ascending);
+        assert_eq!(versions(&data), ["v1.1", "v1.10", "v1.2"]);
+
+        column.set_comparator(Some(Box::new(natural_ordering)));
+        column.sort_by(&mut data, SortOrder::Ascending);
+        assert_eq!(versions(&data), ["v1.1", "v1.2", "v1.10"]);
+        column.sort_by(&mut data, SortOrder::Descending);
+        assert_eq!(versions(&data), ["v1.10", "v1.2", "v1.1"]);
+
+        // Without one, it falls back to the built-in sort.
+        column.set_comparator(None);
+        column.sort_by(&mut data, SortOrder::Ascending);
+        assert_eq!(versions(&data), ["v1.1", "v1.2", "v1.10"]);
+    }
+
+    #[test]
+    fn test_apply_order() {
+        let mut data = ["a", "b", "c", "d", "e", "f"];
+        super::apply_order(&mut data, vec![2, 0, 1, 5, 4, 3]);
+        assert_eq!(data, ["c", "a", "b", "f", "e", "d"]);
+    }
+
+    #[test]
+    fn test_active_sort() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data).default_descending(),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                table_gap: 1,
+                ..Default::default()
+            },
+            sort_index: 0,
+            order: SortOrder::Ascending,
+        };
+
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+        assert_eq!(table.active_sort(), Some((0, SortOrder::Ascending)));
+
+        table.set_sort_index(1);
+        assert_eq!(table.active_sort(), Some((1, SortOrder::Descending)));
+
+        table.toggle_order();
+        assert_eq!(table.active_sort(), Some((1, SortOrder::Ascending)));
+
+        // Sorting by a column that doesn't exist means the table isn't really sorted.
+        table.sort_type.sort_index = 2;
+        assert_eq!(table.active_sort(), None);
+    }
+
+    #[test]
+    fn test_sort_by_visible_column() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data).default_descending(),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps::default(),
+            sort_index: 0,
+            order: SortOrder::Ascending,
+        };
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+
+        // A shown sort column is left alone.
+        table.sort_by_visible_column();
+        assert_eq!(table.active_sort(), Some((0, SortOrder::Ascending)));
+
+        table.columns[0].is_hidden = true;
+        table.sort_by_visible_column();
+        assert_eq!(table.active_sort(), Some((1, SortOrder::Descending)));
+
+        // There's nothing to move to if every column is hidden.
+        table.columns[1].is_hidden = true;
+        table.sort_by_visible_column();
+        assert_eq!(table.active_sort(), Some((1, SortOrder::Descending)));
+    }
+
+    #[test]
+    fn test_set_columns_by_name() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data),
+            SortColumn::new(ColumnType::Version).default_descending(),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps::default(),
+            sort_index: 1,
+            order: SortOrder::Descending,
+        };
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+        let headers = |table: &SortDataTable<TestType, ColumnType>| {
+            table
+                .columns
+                .iter()
+                .map(|column| column.header())
+                .collect::<Vec<_>>()
+        };
+
+        // Nothing changes if nothing matches.
+        let names = ["Size"].map(String::from);
+        assert_eq!(table.set_columns_by_name(&names), ["Size"]);
+        assert_eq!(headers(&table), ["Index", "Data", "Version"]);
+
+        // The sort follows its column to where it moved.
+        let names = ["version", "Size", "DATA", "data"].map(String::from);
+        assert_eq!(table.set_columns_by_name(&names), ["Size"]);
+        assert_eq!(headers(&table), ["Version", "Data"]);
+        assert_eq!(table.active_sort(), Some((1, SortOrder::Descending)));
+
+        // If its column is dropped, the table is sorted by the first one instead.
+        let names = ["Version"].map(String::from);
+        assert!(table.set_columns_by_name(&names).is_empty());
+        assert_eq!(headers(&table), ["Version"]);
+        assert_eq!(table.active_sort(), Some((0, SortOrder::Descending)));
+    }
+
+    #[test]
+    fn test_skip_between_groups() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                table_gap: 1,
+                ..Default::default()
+            },
+            sort_index: 1,
+            order: SortOrder::Ascending,
+        };
+
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+        let mut data = [300, 100, 200, 100, 200, 200]
+            .into_iter()
+            .enumerate()
+            .map(|(index, data)| TestType { index, data })
+            .collect::<Vec<_>>();
+        table.columns[table.sort_index()].sort_by(&mut data, table.order());
+        table.set_data(data);
+
+        // Jumping forwards should land on the first row of each following group.
+        assert_eq!(table.skip_to_next_group(), Some(2));
+        assert_eq!(table.current_item().unwrap().data, 200);
+        assert_eq!(table.skip_to_next_group(), Some(5));
+        assert_eq!(table.current_item().unwrap().data, 300);
+        assert_eq!(table.skip_to_next_group(), None);
+        assert_eq!(table.current_index(), 5);
+
+        // Jumping backwards goes to the start of the current group first, then the previous one.
+        table.set_position(4);
+        assert_eq!(table.skip_to_previous_group(), Some(2));
+        assert_eq!(table.skip_to_previous_group(), Some(0));
+        assert_eq!(table.skip_to_previous_group(), None);
+        assert_eq!(table.current_index(), 0);
+    }
+
+    #[test]
+    fn test_select_location_with_wide_columns() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                left_to_right: true,
+                ..Default::default()
+            },
+            sort_index: 0,
+            order: SortOrder::Ascending,
+        };
+        let mut table = DataTable::<TestType, _, _, _>::new_sortable(
+            columns,
+            props,
+            DataTableStyling::default(),
+        );
+
+        // Summing these would overflow a u16, so the column starts should saturate instead.
+        table.state.inner_rect = tui::layout::Rect::new(u16::MAX - 100, 0, 100, 5);
+        table.state.calculated_widths = vec![u16::MAX - 10, u16::MAX - 10];
+
+        assert_eq!(table.try_select_location(u16::MAX - 50, 0), Some(0));
+        assert_eq!(table.try_select_location(u16::MAX, 0), None);
+        assert_eq!(table.try_select_location(0, 0), None);
+        assert_eq!(table.sort_index(), 0);
+        assert_eq!(table.order(), SortOrder::Descending);
+    }
 }
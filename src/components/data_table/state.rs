@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use tui::{layout::Rect, widgets::TableState};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
@@ -10,17 +12,63 @@ pub enum ScrollDirection {
     Down,
 }
 
+/// What came of trying to move the selection of a [`DataTable`](super::DataTable).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScrollSignal {
+    /// The selection moved to this index.
+    Moved(usize),
+
+    /// The selection didn't move, as it would have gone past the first or last row.
+    Boundary,
+
+    /// The selection didn't move, as no change was asked for.
+    Unchanged,
+}
+
+/// Which rows of a table are shown, as they scroll along with the selection.
+///
+/// Each time the window is [updated](ScrollWindow::update), it is moved so that:
+/// - the selected row is shown, i.e. `start <= current < start + visible`.
+/// - it doesn't go past the last row, i.e. `start + visible <= len`.
+/// - it is full, i.e. `visible` is the smaller of the rows that fit and `len`.
+///
+/// `start` may be changed in between, e.g. to restore a previous position, or be left out of
+/// date by new data; it is clamped again on the next update.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ScrollWindow {
+    /// The index of the first row shown.
+    pub start: usize,
+
+    /// The direction of the last attempted scroll, which decides whether a selection that went
+    /// out of view is brought back in at the top or the bottom.
+    pub direction: ScrollDirection,
+}
+
+impl ScrollWindow {
+    /// Moves the window to show `current` out of `len` rows, of which `num_rows` fit, and returns
+    /// the range of rows to show. See [`compute_window`] for how it moves.
+    pub fn update(&mut self, current: usize, num_rows: usize, len: usize) -> (usize, usize) {
+        let (start, end) = compute_window(current, self.start, num_rows, &self.direction, len);
+        self.start = start;
+
+        (start, end)
+    }
+
+    /// Moves the window back to the first row.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
 /// Internal state representation of a [`DataTable`](super::DataTable).
+#[derive(Default)]
 pub struct DataTableState {
-    /// The index from where to start displaying the rows.
-    pub display_start_index: usize,
+    /// The rows shown, as of the last draw.
+    pub window: ScrollWindow,
 
     /// The current scroll position.
     pub current_index: usize,
 
-    /// The direction of the last attempted scroll.
-    pub scroll_direction: ScrollDirection,
-
     /// tui-rs' internal table state.
     pub table_state: TableState,
 
@@ -29,58 +77,259 @@ pub struct DataTableState {
 
     /// The current inner [`Rect`].
     pub inner_rect: Rect,
+
+    /// The index of the last row that was clicked, and when it was clicked, to tell whether the
+    /// next click is a double click.
+    pub last_click: Option<(usize, Instant)>,
+
+    /// The index of the row that was flashed by a click, and when it was clicked.
+    pub flash: Option<(usize, Instant)>,
+
+    /// How many rows fit on each page as of the last draw, if the table is paged.
+    pub page_size: usize,
 }
 
-impl Default for DataTableState {
-    fn default() -> Self {
-        Self {
-            display_start_index: 0,
-            current_index: 0,
-            scroll_direction: ScrollDirection::Down,
-            calculated_widths: vec![],
-            table_state: TableState::default(),
-            inner_rect: Rect::default(),
+impl DataTableState {
+    /// Updates the starting position of a table with `num_items` rows, of which `num_rows` fit, and
+    /// returns the range of rows to display.
+    pub fn get_start_position(
+        &mut self, num_rows: usize, num_items: usize, is_force_redraw: bool,
+    ) -> (usize, usize) {
+        if is_force_redraw {
+            self.window.start = 0;
         }
+
+        self.window.update(self.current_index, num_rows, num_items)
     }
 }
 
-impl DataTableState {
-    /// Gets the starting position of a table.
-    pub fn get_start_position(&mut self, num_rows: usize, is_force_redraw: bool) {
-        let start_index = if is_force_redraw {
-            0
-        } else {
-            self.display_start_index
-        };
-        let current_scroll_position = self.current_index;
-        let scroll_direction = self.scroll_direction;
-
-        self.display_start_index = match scroll_direction {
-            ScrollDirection::Down => {
-                if current_scroll_position < start_index + num_rows {
-                    // If, using the current scroll position, we can see the element
-                    // (so within that and + num_rows) just reuse the current previously
-                    // scrolled position.
-                    start_index
-                } else if current_scroll_position >= num_rows {
-                    // If the current position past the last element visible in the list,
-                    // then skip until we can see that element.
-                    current_scroll_position - num_rows + 1
-                } else {
-                    // Else, if it is not past the last element visible, do not omit anything.
-                    0
-                }
+/// Computes the range of rows to display out of `len` rows, given that `num_rows` of them fit, the
+/// selected row is at `current`, and the previous range started at `previous`. The range only
+/// moves if the selected row would otherwise not be visible, in which case it moves just enough
+/// to show it at the top or bottom, depending on `dir`.
+///
+/// The returned start never passes the selected row or leaves empty space at the end of the
+/// table, even if `previous` is out of date (e.g. the data shrank since the last draw).
+pub fn compute_window(
+    current: usize, previous: usize, num_rows: usize, dir: &ScrollDirection, len: usize,
+) -> (usize, usize) {
+    let start = match dir {
+        ScrollDirection::Down => {
+            if current < previous + num_rows {
+                // If, using the current scroll position, we can see the element
+                // (so within that and + num_rows) just reuse the current previously
+                // scrolled position.
+                previous
+            } else if current >= num_rows {
+                // If the current position past the last element visible in the list,
+                // then skip until we can see that element.
+                current - num_rows + 1
+            } else {
+                // Else, if it is not past the last element visible, do not omit anything.
+                0
+            }
+        }
+        ScrollDirection::Up => {
+            if current <= previous {
+                // If it's past the first element, then show from that element downwards
+                current
+            } else if current >= previous + num_rows {
+                current - num_rows + 1
+            } else {
+                previous
+            }
+        }
+    };
+
+    let start = start.min(len.saturating_sub(num_rows)).min(current);
+    let end = len.min(start + num_rows);
+
+    (start, end)
+}
+
+/// Narrows a range of rows starting at `start`, out of `len` rows, to the rows that fit in
+/// `num_lines` lines when row `index` takes up `height(index)` lines. If that would leave out the
+/// selected row at `current`, the range starts later instead, just enough to show it; if `start`
+/// is past the selected row, the range starts at it. At least one row is always included, even if
+/// it doesn't fit.
+pub fn fit_window(
+    start: usize, current: usize, num_lines: usize, len: usize, height: impl Fn(usize) -> usize,
+) -> (usize, usize) {
+    let mut start = start.min(current);
+
+    loop {
+        let mut end = start;
+        let mut used = 0;
+        while end < len {
+            let row_height = height(end);
+            if end > start && used + row_height > num_lines {
+                break;
             }
-            ScrollDirection::Up => {
-                if current_scroll_position <= start_index {
-                    // If it's past the first element, then show from that element downwards
-                    current_scroll_position
-                } else if current_scroll_position >= start_index + num_rows {
-                    current_scroll_position - num_rows + 1
-                } else {
-                    start_index
+            used += row_height;
+            end += 1;
+        }
+
+        if current < end || start >= current {
+            return (start, end);
+        }
+        start += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::test_utils::random_generator;
+
+    const DOWN: &ScrollDirection = &ScrollDirection::Down;
+    const UP: &ScrollDirection = &ScrollDirection::Up;
+
+    #[test]
+    fn test_scroll_down() {
+        // At the top, nothing needs to move.
+        assert_eq!(compute_window(0, 0, 5, DOWN, 20), (0, 5));
+
+        // In the middle, the window stays put while the selection is still visible...
+        assert_eq!(compute_window(4, 0, 5, DOWN, 20), (0, 5));
+        assert_eq!(compute_window(7, 5, 5, DOWN, 20), (5, 10));
+
+        // ...and moves just enough to show it at the bottom once it isn't.
+        assert_eq!(compute_window(5, 0, 5, DOWN, 20), (1, 6));
+        assert_eq!(compute_window(12, 5, 5, DOWN, 20), (8, 13));
+
+        // At the bottom, the window ends at the last row.
+        assert_eq!(compute_window(19, 14, 5, DOWN, 20), (15, 20));
+        assert_eq!(compute_window(19, 0, 5, DOWN, 20), (15, 20));
+    }
+
+    #[test]
+    fn test_scroll_up() {
+        // At the top, the window starts at the first row.
+        assert_eq!(compute_window(0, 0, 5, UP, 20), (0, 5));
+        assert_eq!(compute_window(0, 10, 5, UP, 20), (0, 5));
+
+        // In the middle, the window stays put while the selection is still visible...
+        assert_eq!(compute_window(8, 6, 5, UP, 20), (6, 11));
+
+        // ...and moves just enough to show it at the top once it isn't.
+        assert_eq!(compute_window(5, 6, 5, UP, 20), (5, 10));
+
+        // At the bottom, jumping back from further down still shows the selection.
+        assert_eq!(compute_window(18, 10, 5, UP, 20), (14, 19));
+        assert_eq!(compute_window(19, 15, 5, UP, 20), (15, 20));
+    }
+
+    #[test]
+    fn test_window_is_clamped() {
+        // If the data shrank, the old start may be past the end or the selection.
+        assert_eq!(compute_window(3, 10, 5, DOWN, 4), (0, 4));
+        assert_eq!(compute_window(7, 10, 5, DOWN, 8), (3, 8));
+        assert_eq!(compute_window(7, 10, 5, UP, 8), (3, 8));
+
+        // Everything fits.
+        assert_eq!(compute_window(2, 0, 10, DOWN, 4), (0, 4));
+
+        // Nothing fits, or there is nothing to show.
+        assert_eq!(compute_window(3, 0, 0, DOWN, 10), (3, 3));
+        assert_eq!(compute_window(0, 0, 5, DOWN, 0), (0, 0));
+    }
+
+    #[test]
+    fn test_window_invariants() {
+        let mut random = random_generator(0x9e37_79b9);
+        let mut window = ScrollWindow::default();
+        let mut current = 0;
+        let mut len = 20;
+        let mut num_rows = 5;
+
+        for _ in 0..5000 {
+            match random(10) {
+                // The data changes size, and the selection is clamped to it like in `set_data`.
+                0 => {
+                    len = random(60);
+                    current = current.min(len.saturating_sub(1));
+                }
+                // The table is resized.
+                1 => num_rows = random(25) + 1,
+                // The start is set from elsewhere, e.g. restored, and may be out of date.
+                2 => window.start = random(80),
+                // The selection jumps to the first or last row.
+                3 => {
+                    let new = if random(2) == 0 {
+                        0
+                    } else {
+                        len.saturating_sub(1)
+                    };
+                    window.direction = if new < current {
+                        ScrollDirection::Up
+                    } else {
+                        ScrollDirection::Down
+                    };
+                    current = new;
+                }
+                // The selection moves by a few rows, or a page.
+                _ => {
+                    let change = random(2 * num_rows + 1) as i64 - num_rows as i64;
+                    let new = (current as i64 + change).clamp(0, len.saturating_sub(1) as i64);
+                    window.direction = if change < 0 {
+                        ScrollDirection::Up
+                    } else {
+                        ScrollDirection::Down
+                    };
+                    current = new as usize;
                 }
             }
-        };
+
+            let (start, end) = window.update(current, num_rows, len);
+            let context = format!("current {current}, len {len}, rows {num_rows}: {start}..{end}");
+
+            assert_eq!(window.start, start, "{context}");
+            assert!(end <= len, "{context}");
+            assert_eq!(end - start, num_rows.min(len), "{context}");
+            if len > 0 {
+                assert!(start <= current && current < end, "{context}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_window_invariants() {
+        let mut random = random_generator(0x9e37_79b9);
+
+        for _ in 0..2000 {
+            let len = random(30) + 1;
+            let heights = (0..len).map(|_| random(4) + 1).collect::<Vec<_>>();
+            let current = random(len);
+            let start = random(len);
+            let num_lines = random(12) + 1;
+
+            let (start, end) = fit_window(start, current, num_lines, len, |index| heights[index]);
+            let context = format!("{heights:?}, current {current}, lines {num_lines}");
+
+            assert!(start <= current && current < end, "{context}");
+            assert!(end <= len, "{context}");
+            assert!(
+                end - start == 1 || heights[start..end].iter().sum::<usize>() <= num_lines,
+                "{context}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fit_window() {
+        let height = |index| if index == 2 { 4 } else { 1 };
+
+        // The tall row pushes the rows after it out of the window.
+        assert_eq!(fit_window(0, 0, 5, 10, height), (0, 2));
+        assert_eq!(fit_window(0, 1, 6, 10, height), (0, 3));
+        assert_eq!(fit_window(0, 1, 7, 10, height), (0, 4));
+
+        // Selecting a row it pushed out moves the window just enough to show it.
+        assert_eq!(fit_window(0, 3, 5, 10, height), (2, 4));
+        assert_eq!(fit_window(0, 4, 5, 10, height), (3, 8));
+
+        // A row taller than the window is still shown.
+        assert_eq!(fit_window(0, 2, 3, 10, height), (2, 3));
+        assert_eq!(fit_window(0, 0, 5, 0, height), (0, 0));
     }
 }
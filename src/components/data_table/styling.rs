@@ -1,26 +1,72 @@
 use tui::style::Style;
 
-use crate::canvas::canvas_styling::CanvasStyling;
+use crate::canvas::canvas_styling::{CanvasStyling, Role, StyleOverrides, StyleResolver, Symbols};
 
 #[derive(Default)]
 pub struct DataTableStyling {
     pub header_style: Style,
+    pub background_style: Style,
     pub border_style: Style,
     pub highlighted_border_style: Style,
     pub text_style: Style,
     pub highlighted_text_style: Style,
+    pub click_flash_style: Style,
     pub title_style: Style,
+    pub dimmed_style: Style,
+    pub overrides: StyleOverrides,
+    pub symbols: Symbols,
 }
 
 impl DataTableStyling {
     pub fn from_colours(colours: &CanvasStyling) -> Self {
         Self {
-            header_style: colours.table_header_style,
-            border_style: colours.border_style,
-            highlighted_border_style: colours.highlighted_border_style,
-            text_style: colours.text_style,
-            highlighted_text_style: colours.currently_selected_text_style,
-            title_style: colours.widget_title_style,
+            header_style: colours.resolve(Role::TableHeader),
+            background_style: colours.resolve(Role::TableBackground),
+            border_style: colours.resolve(Role::Border { selected: false }),
+            highlighted_border_style: colours.resolve(Role::Border { selected: true }),
+            text_style: colours.resolve(Role::Text),
+            highlighted_text_style: colours.resolve(Role::SelectedRow { focused: true }),
+            click_flash_style: colours.resolve(Role::ClickFlash),
+            title_style: colours.resolve(Role::Title),
+            dimmed_style: colours.resolve(Role::Dimmed),
+            overrides: StyleOverrides::default(),
+            symbols: colours.symbols,
+        }
+    }
+
+    /// Restyles with `colours`, e.g. after switching colour schemes. Overrides are kept.
+    pub fn set_colours(&mut self, colours: &CanvasStyling) {
+        let overrides = std::mem::take(&mut self.overrides);
+        *self = Self {
+            overrides,
+            ..Self::from_colours(colours)
+        };
+    }
+
+    /// Overrides the [`Style`] used for `role` in this table only.
+    pub fn with_override(mut self, role: Role, style: Style) -> Self {
+        self.overrides = self.overrides.with(role, style);
+        self
+    }
+}
+
+impl StyleResolver for DataTableStyling {
+    fn resolve(&self, role: Role) -> Style {
+        if let Some(style) = self.overrides.get(role) {
+            return style;
+        }
+
+        match role {
+            Role::Border { selected: true } => self.highlighted_border_style,
+            Role::Border { selected: false } => self.border_style,
+            Role::Title => self.title_style,
+            Role::Text | Role::SelectedRow { focused: false } => self.text_style,
+            Role::TableHeader => self.header_style,
+            Role::TableBackground => self.background_style,
+            Role::SelectedRow { focused: true } => self.highlighted_text_style,
+            Role::ClickFlash => self.click_flash_style,
+            Role::Dimmed => self.dimmed_style,
+            Role::GraphLine(_) | Role::Error | Role::Increase | Role::Decrease => self.text_style,
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
 
 use concat_string::concat_string;
 use tui::{
@@ -12,6 +12,7 @@ use tui::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
+pub use super::tui_widget::time_chart::value_at;
 use super::tui_widget::time_chart::{Axis, Dataset, Point, TimeChart, DEFAULT_LEGEND_CONSTRAINTS};
 
 /// Represents the data required by the [`TimeGraph`].
@@ -55,20 +56,31 @@ pub struct TimeGraph<'a> {
     /// The marker type. Unlike tui-rs' native charts, we assume
     /// only a single type of market.
     pub marker: Marker,
+
+    /// The characters to tell each series apart with, in order, if they can't be told apart by
+    /// colour. If set, series are always drawn with dots, using these rather than the marker's.
+    pub series_symbols: &'a [char],
+
+    /// How many milliseconds before the latest data a time cursor is at, if the graph is being
+    /// scrubbed through. The time window shown then ends at the cursor, which is marked with a
+    /// vertical line.
+    pub time_cursor: Option<u64>,
 }
 
 impl<'a> TimeGraph<'a> {
     /// Generates the [`Axis`] for the x-axis.
     fn generate_x_axis(&self) -> Axis<'_> {
         // Due to how we display things, we need to adjust the time bound values.
-        let time_start = -(self.x_bounds[1] as f64);
-        let adjusted_x_bounds = [time_start, 0.0];
+        let offset = self.time_cursor.unwrap_or(0);
+        let time_start = -((self.x_bounds[1] + offset) as f64);
+        let time_end = -((self.x_bounds[0] + offset) as f64);
+        let adjusted_x_bounds = [time_start, time_end];
 
         if self.hide_x_labels {
             Axis::default().bounds(adjusted_x_bounds)
         } else {
-            let xb_one = (self.x_bounds[1] / 1000).to_string();
-            let xb_zero = (self.x_bounds[0] / 1000).to_string();
+            let xb_one = ((self.x_bounds[1] + offset) / 1000).to_string();
+            let xb_zero = ((self.x_bounds[0] + offset) / 1000).to_string();
 
             let x_labels = vec![
                 Span::styled(concat_string!(xb_one, "s"), self.graph_style),
@@ -95,26 +107,46 @@ impl<'a> TimeGraph<'a> {
             )
     }
 
-    /// Generates a title for the [`TimeGraph`] widget, given the available space.
+    /// Generates a title for the [`TimeGraph`] widget, given the available space. The title is
+    /// followed by the time window currently shown, and how far back the time cursor is, if set.
     fn generate_title(&self, draw_loc: Rect) -> Line<'_> {
+        let time_window = concat_string!(
+            " ",
+            format_time_window(self.x_bounds[1] - self.x_bounds[0]),
+            " "
+        );
+        let mut spans = vec![
+            Span::styled(self.title.as_ref(), self.title_style),
+            Span::styled("─", self.border_style),
+            Span::styled(time_window.clone(), self.title_style),
+        ];
+
+        let cursor = match self.time_cursor {
+            Some(cursor) => {
+                let cursor = concat_string!(" ", format_time_window(cursor), " ago ");
+                spans.push(Span::styled("─", self.border_style));
+                spans.push(Span::styled(cursor.clone(), self.title_style));
+                concat_string!("─", cursor)
+            }
+            None => String::new(),
+        };
+
         if self.is_expanded {
-            let title_base = concat_string!(self.title, "── Esc to go back ");
-            Line::from(vec![
-                Span::styled(self.title.as_ref(), self.title_style),
-                Span::styled(
-                    concat_string!(
-                        "─",
-                        "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                            UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2
-                        )),
-                        "─ Esc to go back "
-                    ),
-                    self.border_style,
+            let title_base =
+                concat_string!(self.title, "─", time_window, cursor, "── Esc to go back ");
+            spans.push(Span::styled(
+                concat_string!(
+                    "─",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2
+                    )),
+                    "─ Esc to go back "
                 ),
-            ])
-        } else {
-            Line::from(Span::styled(self.title.as_ref(), self.title_style))
+                self.border_style,
+            ));
         }
+
+        Line::from(spans)
     }
 
     /// Draws a time graph at [`Rect`] location provided by `draw_loc`. A time graph is used to display data points
@@ -132,30 +164,65 @@ impl<'a> TimeGraph<'a> {
 
         // This is some ugly manual loop unswitching. Maybe unnecessary.
         // TODO: Optimize this step. Cut out unneeded points.
-        let data = graph_data.iter().map(create_dataset).collect();
+        let data = graph_data
+            .iter()
+            .enumerate()
+            .map(|(index, data)| {
+                let symbol = (!self.series_symbols.is_empty())
+                    .then(|| self.series_symbols[index % self.series_symbols.len()]);
+                create_dataset(data, symbol)
+            })
+            .collect();
+        let marker = if self.series_symbols.is_empty() {
+            self.marker
+        } else {
+            Marker::Dot
+        };
         let block = Block::default()
             .title(self.generate_title(draw_loc))
             .borders(Borders::ALL)
             .border_style(self.border_style);
 
-        f.render_widget(
-            TimeChart::new(data)
-                .block(block)
-                .x_axis(x_axis)
-                .y_axis(y_axis)
-                .marker(self.marker)
-                .legend_style(self.graph_style)
-                .hidden_legend_constraints(
-                    self.legend_constraints
-                        .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
-                ),
-            draw_loc,
-        )
+        let chart = TimeChart::new(data)
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .marker(marker)
+            .legend_style(self.graph_style)
+            .hidden_legend_constraints(
+                self.legend_constraints
+                    .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
+            );
+
+        match self.time_cursor {
+            Some(cursor) => f.render_widget(chart.cursor(-(cursor as f64)), draw_loc),
+            None => f.render_widget(chart, draw_loc),
+        }
     }
 }
 
-/// Creates a new [`Dataset`].
-fn create_dataset<'a>(data: &'a GraphData<'a>) -> Dataset<'a> {
+/// Moves a time cursor that is `cursor` milliseconds before the latest point in `points` to the
+/// next older or newer point, returning where it ends up. As points may be spaced unevenly, the
+/// cursor always lands on one. Returns [`None`] if there are no points in that direction.
+pub fn step_time_cursor(points: &[Point], cursor: u64, older: bool) -> Option<u64> {
+    let cursor = -(cursor as f64);
+    let (x, _y) = if older {
+        points.iter().rev().find(|(x, _y)| *x < cursor)
+    } else {
+        points.iter().find(|(x, _y)| *x > cursor)
+    }?;
+
+    Some(-x as u64)
+}
+
+/// Formats a time window of `time_ms` milliseconds to the second, e.g. "1m 30s".
+fn format_time_window(time_ms: u64) -> String {
+    humantime::format_duration(Duration::from_secs(time_ms / 1000)).to_string()
+}
+
+/// Creates a new [`Dataset`], drawn with `symbol` if it is set. The symbol is also shown next to
+/// the name in the legend.
+fn create_dataset<'a>(data: &'a GraphData<'a>, symbol: Option<char>) -> Dataset<'a> {
     let GraphData {
         points,
         style,
@@ -165,12 +232,13 @@ fn create_dataset<'a>(data: &'a GraphData<'a>) -> Dataset<'a> {
     let dataset = Dataset::default()
         .style(*style)
         .data(points)
-        .graph_type(GraphType::Line);
+        .graph_type(GraphType::Line)
+        .point_symbol(symbol);
 
-    if let Some(name) = name {
-        dataset.name(name.as_ref())
-    } else {
-        dataset
+    match (name, symbol) {
+        (Some(name), Some(symbol)) => dataset.name(format!("{symbol} {name}")),
+        (Some(name), None) => dataset.name(name.as_ref()),
+        (None, _) => dataset,
     }
 }
 
@@ -179,14 +247,17 @@ mod test {
     use std::borrow::Cow;
 
     use tui::{
-        layout::Rect,
+        layout::{Constraint, Rect},
         style::{Color, Style},
         symbols::Marker,
         text::{Line, Span},
     };
 
-    use super::TimeGraph;
-    use crate::components::tui_widget::time_chart::Axis;
+    use super::{format_time_window, step_time_cursor, GraphData, TimeGraph};
+    use crate::{
+        components::tui_widget::time_chart::Axis,
+        utils::test_utils::{buffer_lines, draw_to_buffer},
+    };
 
     const Y_LABELS: [Cow<'static, str>; 3] = [
         Cow::Borrowed("0%"),
@@ -207,6 +278,8 @@ mod test {
             title_style: Style::default().fg(Color::Cyan),
             legend_constraints: None,
             marker: Marker::Braille,
+            series_symbols: &[],
+            time_cursor: None,
         }
     }
 
@@ -253,7 +326,11 @@ mod test {
         let title = time_graph.generate_title(draw_loc);
         assert_eq!(
             title,
-            Line::from(Span::styled(" Network ", Style::default().fg(Color::Cyan)))
+            Line::from(vec![
+                Span::styled(" Network ", Style::default().fg(Color::Cyan)),
+                Span::styled("─", Style::default().fg(Color::Blue)),
+                Span::styled(" 15s ", Style::default().fg(Color::Cyan)),
+            ])
         );
 
         time_graph.is_expanded = true;
@@ -262,8 +339,109 @@ mod test {
             title,
             Line::from(vec![
                 Span::styled(" Network ", Style::default().fg(Color::Cyan)),
-                Span::styled("───── Esc to go back ", Style::default().fg(Color::Blue))
+                Span::styled("─", Style::default().fg(Color::Blue)),
+                Span::styled(" 15s ", Style::default().fg(Color::Cyan)),
+                Span::styled("── Esc to go back ", Style::default().fg(Color::Blue))
+            ])
+        );
+    }
+
+    #[test]
+    fn time_graph_time_cursor() {
+        let mut time_graph = create_time_graph();
+        time_graph.time_cursor = Some(30000);
+
+        // The window shown ends at the cursor.
+        let x_axis = time_graph.generate_x_axis();
+        assert_eq!(x_axis.bounds, [-45000.0, -30000.0]);
+        assert_eq!(
+            x_axis.labels,
+            Some(vec![
+                Span::styled("45s", Style::default().fg(Color::Red)),
+                Span::styled("30s", Style::default().fg(Color::Red)),
+            ])
+        );
+
+        let title = time_graph.generate_title(Rect::new(0, 0, 32, 100));
+        assert_eq!(
+            title,
+            Line::from(vec![
+                Span::styled(" Network ", Style::default().fg(Color::Cyan)),
+                Span::styled("─", Style::default().fg(Color::Blue)),
+                Span::styled(" 15s ", Style::default().fg(Color::Cyan)),
+                Span::styled("─", Style::default().fg(Color::Blue)),
+                Span::styled(" 30s ago ", Style::default().fg(Color::Cyan)),
             ])
         );
+
+        // The cursor is marked with a line across the graph, here on its right edge.
+        let points = [(-60000.0, 50.0), (0.0, 50.0)];
+        let graph_data = [GraphData {
+            points: &points,
+            style: Style::default(),
+            name: None,
+        }];
+        let buffer = draw_to_buffer(40, 12, |f| {
+            time_graph.draw_time_graph(f, f.size(), &graph_data);
+        });
+        let lines = buffer_lines(&buffer);
+        assert!(lines[1..8].iter().all(|line| line.ends_with("││")));
+    }
+
+    #[test]
+    fn time_graph_step_time_cursor() {
+        // Points are unevenly spaced, like when an update takes longer than usual.
+        let points = [(-9000.0, 1.0), (-3500.0, 2.0), (-1000.0, 3.0), (0.0, 4.0)];
+
+        assert_eq!(step_time_cursor(&points, 0, true), Some(1000));
+        assert_eq!(step_time_cursor(&points, 1000, true), Some(3500));
+        assert_eq!(step_time_cursor(&points, 2000, true), Some(3500));
+        assert_eq!(step_time_cursor(&points, 9000, true), None);
+
+        assert_eq!(step_time_cursor(&points, 9000, false), Some(3500));
+        assert_eq!(step_time_cursor(&points, 1000, false), Some(0));
+        assert_eq!(step_time_cursor(&points, 0, false), None);
+        assert_eq!(step_time_cursor(&[], 0, true), None);
+    }
+
+    #[test]
+    fn time_graph_format_time_window() {
+        assert_eq!(format_time_window(30 * 1000), "30s");
+        assert_eq!(format_time_window(90 * 1000), "1m 30s");
+        assert_eq!(format_time_window(37_500), "37s");
+        assert_eq!(format_time_window(60 * 60 * 1000), "1h");
+    }
+
+    #[test]
+    fn time_graph_series_symbols() {
+        let mut time_graph = create_time_graph();
+        time_graph.series_symbols = &['*', '+'];
+        time_graph.legend_constraints = Some((Constraint::Ratio(1, 1), Constraint::Ratio(1, 1)));
+
+        let low = [(-15000.0, 10.0), (0.0, 10.0)];
+        let high = [(-15000.0, 90.0), (0.0, 90.0)];
+        let graph_data = [
+            GraphData {
+                points: &low,
+                style: Style::default(),
+                name: Some("RX".into()),
+            },
+            GraphData {
+                points: &high,
+                style: Style::default(),
+                name: Some("TX".into()),
+            },
+        ];
+
+        let buffer = draw_to_buffer(40, 12, |f| {
+            time_graph.draw_time_graph(f, f.size(), &graph_data);
+        });
+        let lines = buffer_lines(&buffer);
+
+        // Each series is drawn with its own symbol, which is also shown in the legend.
+        assert!(lines.iter().any(|line| line.contains("* RX")));
+        assert!(lines.iter().any(|line| line.contains("+ TX")));
+        assert!(lines.iter().any(|line| line.contains("****")));
+        assert!(lines.iter().any(|line| line.contains("++++")));
     }
 }
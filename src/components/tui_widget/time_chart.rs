@@ -82,6 +82,8 @@ pub struct Dataset<'a> {
     graph_type: GraphType,
     /// Style used to plot this dataset
     style: Style,
+    /// The character to plot this dataset with, if not the one from the chart's marker
+    point_symbol: Option<char>,
 }
 
 impl<'a> Default for Dataset<'a> {
@@ -91,6 +93,7 @@ impl<'a> Default for Dataset<'a> {
             data: &[],
             graph_type: GraphType::Scatter,
             style: Style::default(),
+            point_symbol: None,
         }
     }
 }
@@ -119,6 +122,11 @@ impl<'a> Dataset<'a> {
         self.style = style;
         self
     }
+
+    pub fn point_symbol(mut self, point_symbol: Option<char>) -> Dataset<'a> {
+        self.point_symbol = point_symbol;
+        self
+    }
 }
 
 /// A container that holds all the infos about where to display each elements of the chart (axis,
@@ -170,6 +178,8 @@ pub struct TimeChart<'a> {
     hidden_legend_constraints: (Constraint, Constraint),
     /// The marker type.
     marker: Marker,
+    /// Where on the x-axis to draw a vertical line marking a point in time, if anywhere.
+    cursor: Option<f64>,
 }
 
 pub const DEFAULT_LEGEND_CONSTRAINTS: (Constraint, Constraint) =
@@ -190,6 +200,7 @@ impl<'a> TimeChart<'a> {
             datasets,
             hidden_legend_constraints: DEFAULT_LEGEND_CONSTRAINTS,
             marker: Marker::Braille,
+            cursor: None,
         }
     }
 
@@ -223,6 +234,12 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Marks the time at `x` on the x-axis with a vertical line.
+    pub fn cursor(mut self, x: f64) -> TimeChart<'a> {
+        self.cursor = Some(x);
+        self
+    }
+
     /// Set the constraints used to determine whether the legend should be shown or not.
     pub fn hidden_legend_constraints(
         mut self, constraints: (Constraint, Constraint),
@@ -249,7 +266,9 @@ impl<'a> TimeChart<'a> {
         layout.label_y = self.y_axis.labels.as_ref().and(Some(x));
         x += self.max_width_of_labels_left_of_y_axis(area);
 
-        if self.x_axis.labels.is_some() && y > area.top() {
+        // The axis line is kept even when the x-axis labels are hidden, so hiding or showing them
+        // only changes the graph's height by the one row the labels use.
+        if y > area.top() {
             layout.axis_x = Some(y);
             y -= 1;
         }
@@ -440,6 +459,9 @@ impl<'a> Widget for TimeChart<'a> {
 
                 for dataset in &self.datasets {
                     let color = dataset.style.fg.unwrap_or(Color::Reset);
+                    if let Some(point_symbol) = dataset.point_symbol {
+                        ctx.set_cell_char(point_symbol);
+                    }
 
                     let start_bound = self.x_axis.bounds[0];
                     let end_bound = self.x_axis.bounds[1];
@@ -523,6 +545,19 @@ impl<'a> Widget for TimeChart<'a> {
             })
             .render(graph_area, buf);
 
+        if let Some(cursor) = self.cursor {
+            let [start, end] = self.x_axis.bounds;
+            if start < end && (start..=end).contains(&cursor) {
+                let offset = (cursor - start) / (end - start) * f64::from(graph_area.width - 1);
+                let x = graph_area.left() + offset.round() as u16;
+                for y in graph_area.top()..graph_area.bottom() {
+                    buf.get_mut(x, y)
+                        .set_symbol(symbols::line::VERTICAL)
+                        .set_style(self.x_axis.style);
+                }
+            }
+        }
+
         if let Some(legend_area) = layout.legend_area {
             buf.set_style(legend_area, original_style);
             Block::default()
@@ -593,14 +628,24 @@ fn get_end(dataset: &Dataset<'_>, end_bound: f64) -> (usize, Option<usize>) {
         Ok(index) => (index.saturating_add(1), None),
         // In the fail case, this means we did not find an index, and the returned index is where one would *insert*
         // the location. This index is where one would insert to fit inside the dataset - and since this is an end
-        // bound, index is, in a sense, already "+1" for our range later.
-        Err(index) => (index, {
-            let sum = index.checked_add(1);
-            match sum {
-                Some(s) if s < dataset.data.len() => sum,
-                _ => None,
-            }
-        }),
+        // bound, index is, in a sense, already "+1" for our range later. It's also the first point past the bound,
+        // which is interpolated towards from the point before it, if there is one.
+        Err(index) => (
+            index,
+            (index > 0 && index < dataset.data.len()).then_some(index),
+        ),
+    }
+}
+
+/// Returns the value of `data` at `x`, interpolating between the points either side of it if no
+/// point is at `x`, as points may be spaced unevenly. Returns [`None`] if `x` is outside of `data`.
+pub fn value_at(data: &[Point], x: f64) -> Option<f64> {
+    match data.binary_search_by(|(point_x, _y)| partial_ordering(point_x, &x)) {
+        Ok(index) => Some(data[index].1),
+        Err(index) if index > 0 && index < data.len() => {
+            Some(interpolate_point(&data[index - 1], &data[index], x))
+        }
+        Err(_) => None,
     }
 }
 
@@ -663,14 +708,28 @@ mod test {
 
         // Test end point cases (miss and hit)
         assert_eq!(get_end(&dataset, -2.5), (2, None));
-        assert_eq!(get_end(&dataset, -2.4), (2, Some(3)));
-        assert_eq!(get_end(&dataset, -1.4), (3, Some(4)));
+        assert_eq!(get_end(&dataset, -2.4), (2, Some(2)));
+        assert_eq!(get_end(&dataset, -1.4), (3, Some(3)));
         assert_eq!(get_end(&dataset, -1.0), (4, None));
         assert_eq!(get_end(&dataset, 0.0), (5, None));
         assert_eq!(get_end(&dataset, 1.0), (5, None));
         assert_eq!(get_end(&dataset, 100.0), (5, None));
     }
 
+    #[test]
+    fn time_chart_value_at() {
+        // Points are unevenly spaced, like when an update takes longer than usual.
+        let data = [(-3000.0, 8.0), (-2500.0, 15.0), (-1000.0, 6.0), (0.0, 5.0)];
+
+        assert_eq!(value_at(&data, -2500.0), Some(15.0));
+        assert_eq!(value_at(&data, -1750.0), Some(10.5));
+        assert_eq!(value_at(&data, -500.0), Some(5.5));
+        assert_eq!(value_at(&data, 0.0), Some(5.0));
+        assert_eq!(value_at(&data, -3500.0), None);
+        assert_eq!(value_at(&data, 500.0), None);
+        assert_eq!(value_at(&[], 0.0), None);
+    }
+
     struct LegendTestCase {
         chart_area: Rect,
         hidden_legend_constraints: (Constraint, Constraint),
@@ -708,4 +767,27 @@ mod test {
             assert_eq!(layout.legend_area, case.legend_area);
         }
     }
+
+    #[test]
+    fn time_chart_hidden_x_labels_reclaim_one_row() {
+        let data = [(-1.0, 5.0), (0.0, 6.0)];
+        let area = Rect::new(0, 0, 50, 20);
+        let y_axis = || Axis::default().labels(vec!["0%".into(), "100%".into()]);
+
+        let shown = TimeChart::new(vec![Dataset::default().data(&data)])
+            .x_axis(Axis::default().labels(vec!["60s".into(), "0s".into()]))
+            .y_axis(y_axis())
+            .layout(area);
+        let hidden = TimeChart::new(vec![Dataset::default().data(&data)])
+            .x_axis(Axis::default())
+            .y_axis(y_axis())
+            .layout(area);
+
+        assert_eq!(shown.label_x, Some(19));
+        assert_eq!(hidden.label_x, None);
+        assert_eq!(shown.axis_x, Some(18));
+        assert_eq!(hidden.axis_x, Some(19));
+        assert_eq!(shown.graph_area.top(), hidden.graph_area.top());
+        assert_eq!(shown.graph_area.height + 1, hidden.graph_area.height);
+    }
 }
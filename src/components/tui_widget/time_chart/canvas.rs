@@ -138,6 +138,8 @@ trait Grid: Debug {
     fn paint(&mut self, x: usize, y: usize, color: Color);
     fn save(&self) -> Layer;
     fn reset(&mut self);
+    /// Sets the character that points are painted with, if this grid paints with one.
+    fn set_cell_char(&mut self, _cell_char: char) {}
 }
 
 #[derive(Debug, Clone)]
@@ -260,6 +262,10 @@ impl Grid for CharGrid {
         }
     }
 
+    fn set_cell_char(&mut self, cell_char: char) {
+        self.cell_char = cell_char;
+    }
+
     fn paint(&mut self, x: usize, y: usize, color: Color) {
         let index = y * self.width as usize + x;
         if let Some(c) = self.cells.get_mut(index) {
@@ -385,6 +391,12 @@ impl<'a> Context<'a> {
         let mut painter = Painter::from(self);
         shape.draw(&mut painter);
     }
+
+    /// Sets the character that points are painted with from now on. This only applies to
+    /// markers that paint with a single character, not braille.
+    pub fn set_cell_char(&mut self, cell_char: char) {
+        self.grid.set_cell_char(cell_char);
+    }
 }
 
 /// The Canvas widget may be used to draw more detailed figures using braille patterns (each
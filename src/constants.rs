@@ -6,19 +6,48 @@ use crate::options::ConfigColours;
 // Default widget ID
 pub const DEFAULT_WIDGET_ID: u64 = 56709;
 
+// The name of the layout from the config's own rows, or the built-in layout
+pub const DEFAULT_LAYOUT_PROFILE: &str = "default";
+
 // How much data is SHOWN
 pub const DEFAULT_TIME_MILLISECONDS: u64 = 60 * 1000; // Defaults to 1 min.
-pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
-pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment each time
+pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds by default
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
 
 pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
+// The bounds of how fast the screen refreshes, including when changed while running.
+pub const MIN_REFRESH_RATE_IN_MILLISECONDS: u64 = 100;
+pub const MAX_REFRESH_RATE_IN_MILLISECONDS: u64 = 60 * 1000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+// How many entries a table moves by per mouse scroll, and the most it may be set to.
+pub const DEFAULT_SCROLL_STEP: u64 = 1;
+pub const MAX_SCROLL_STEP: u64 = 1000;
+// The smallest terminal, as (width, height), that widgets are drawn in. Off by default.
+pub const DEFAULT_MIN_TERMINAL_SIZE: (u16, u16) = (0, 0);
+
+// How long a clicked table row stays flashed for.
+pub const CLICK_FLASH_DURATION_IN_MILLISECONDS: u64 = 250;
+// How quickly a second click on the same table row must follow the first to count as a double click.
+pub const DOUBLE_CLICK_INTERVAL_IN_MILLISECONDS: u64 = 500;
+
+// How long a status message, like the name of a newly selected colour scheme, is shown for.
+pub const STATUS_MESSAGE_DURATION_IN_MILLISECONDS: u64 = 3000;
+
+// How many previously focused widgets are remembered for jumping back to.
+pub const MAX_FOCUS_HISTORY: usize = 16;
+
+// The most history entries that room is made for up front, which is an hour at the fastest update
+// rate. Longer retentions still work, but grow the history as data comes in.
+pub const MAX_PREALLOCATED_HISTORY_ENTRIES: usize = 60 * 60 * 4;
+
+// How many snapshots of the processes are kept for comparing against.
+pub const MAX_PROCESS_SNAPSHOTS: usize = 5;
 
 // Limits for when we should stop showing table gaps/labels (anything less means not shown)
 pub const TABLE_GAP_HEIGHT_LIMIT: u16 = 7;
+pub const TOO_SMALL_INDICATOR: &str = " Too small ";
 pub const TIME_LABEL_HEIGHT_LIMIT: u16 = 7;
 
 // Side borders
@@ -69,6 +98,7 @@ pub static DEFAULT_LIGHT_MODE_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(||
 
 pub static GRUVBOX_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
     table_header_color: Some("#83a598".into()),
+    table_bg_color: None,
     all_cpu_color: Some("#8ec07c".into()),
     avg_cpu_color: Some("#fb4934".into()),
     cpu_core_colors: Some(vec![
@@ -117,15 +147,19 @@ pub static GRUVBOX_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColo
     text_color: Some("#ebdbb2".into()),
     selected_text_color: Some("#1d2021".into()),
     selected_bg_color: Some("#ebdbb2".into()),
+    click_flash_color: None,
     widget_title_color: Some("#ebdbb2".into()),
     graph_color: Some("#ebdbb2".into()),
     high_battery_color: Some("#98971a".into()),
     medium_battery_color: Some("#fabd2f".into()),
     low_battery_color: Some("#fb4934".into()),
+    increase_color: Some("#fb4934".into()),
+    decrease_color: Some("#98971a".into()),
 });
 
 pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
     table_header_color: Some("#076678".into()),
+    table_bg_color: None,
     all_cpu_color: Some("#8ec07c".into()),
     avg_cpu_color: Some("#fb4934".into()),
     cpu_core_colors: Some(vec![
@@ -174,15 +208,19 @@ pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| Conf
     text_color: Some("#3c3836".into()),
     selected_text_color: Some("#ebdbb2".into()),
     selected_bg_color: Some("#3c3836".into()),
+    click_flash_color: None,
     widget_title_color: Some("#3c3836".into()),
     graph_color: Some("#3c3836".into()),
     high_battery_color: Some("#98971a".into()),
     medium_battery_color: Some("#d79921".into()),
     low_battery_color: Some("#cc241d".into()),
+    increase_color: Some("#cc241d".into()),
+    decrease_color: Some("#98971a".into()),
 });
 
 pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
     table_header_color: Some("#81a1c1".into()),
+    table_bg_color: None,
     all_cpu_color: Some("#88c0d0".into()),
     avg_cpu_color: Some("#8fbcbb".into()),
     cpu_core_colors: Some(vec![
@@ -219,15 +257,19 @@ pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours
     text_color: Some("#e5e9f0".into()),
     selected_text_color: Some("#2e3440".into()),
     selected_bg_color: Some("#88c0d0".into()),
+    click_flash_color: None,
     widget_title_color: Some("#e5e9f0".into()),
     graph_color: Some("#e5e9f0".into()),
     high_battery_color: Some("#a3be8c".into()),
     medium_battery_color: Some("#ebcb8b".into()),
     low_battery_color: Some("#bf616a".into()),
+    increase_color: Some("#bf616a".into()),
+    decrease_color: Some("#a3be8c".into()),
 });
 
 pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
     table_header_color: Some("#5e81ac".into()),
+    table_bg_color: None,
     all_cpu_color: Some("#81a1c1".into()),
     avg_cpu_color: Some("#8fbcbb".into()),
     cpu_core_colors: Some(vec![
@@ -264,16 +306,19 @@ pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigC
     text_color: Some("#2e3440".into()),
     selected_text_color: Some("#f5f5f5".into()),
     selected_bg_color: Some("#5e81ac".into()),
+    click_flash_color: None,
     widget_title_color: Some("#2e3440".into()),
     graph_color: Some("#2e3440".into()),
     high_battery_color: Some("#a3be8c".into()),
     medium_battery_color: Some("#ebcb8b".into()),
     low_battery_color: Some("#bf616a".into()),
+    increase_color: Some("#bf616a".into()),
+    decrease_color: Some("#a3be8c".into()),
 });
 
 // Help text
 pub const HELP_CONTENTS_TEXT: [&str; 10] = [
-    "Either scroll or press the number key to go to the corresponding help menu section:",
+    "Scroll, press / to search, or press the number key to go to the corresponding help menu section:",
     "1 - General",
     "2 - CPU widget",
     "3 - Process widget",
@@ -287,12 +332,14 @@ pub const HELP_CONTENTS_TEXT: [&str; 10] = [
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub const GENERAL_HELP_TEXT: [&str; 32] = [
+pub const GENERAL_HELP_TEXT: [&str; 42] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
     "Ctrl-r           Reset display and any collected data",
+    "<, >             Halve/double the time between data updates",
     "f                Freeze/unfreeze updating with new data",
+    "Left, Right      While frozen, move a graph's time cursor back/forward through its data",
     "Ctrl-Left,       ",
     "Shift-Left,      Move widget selection left",
     "H, A             ",
@@ -305,19 +352,27 @@ pub const GENERAL_HELP_TEXT: [&str; 32] = [
     "Ctrl-Down,       ",
     "Shift-Down,      Move widget selection down",
     "J, S             ",
+    "Ctrl-o           Move widget selection back to the previously selected widget",
     "Left, h          Move left within widget",
     "Down, j          Move down within widget",
     "Up, k            Move up within widget",
     "Right, l         Move right within widget",
-    "?                Open help menu",
+    "?, F1            Open help menu",
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
+    "}, {             Jump to the next/previous change in the sorted column",
     "e                Toggle expanding the currently selected widget",
+    "T                Switch to the next colour scheme",
+    "b                Switch sizes between binary and decimal prefixes",
+    "R                Reload the config file",
+    "Ctrl-s           Save the current settings to the config file",
+    "O                Switch between the layouts in the config file",
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
     "=                Reset zoom",
     "PgUp, PgDown     Scroll up/down a table by a page",
     "Ctrl-u, Ctrl-d   Scroll up/down a table by half a page",
+    "Ctrl-p           Switch a table between scrolling and pages",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
 ];
@@ -327,27 +382,32 @@ pub const CPU_HELP_TEXT: [&str; 2] = [
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
 ];
 
-pub const PROCESS_HELP_TEXT: [&str; 15] = [
+pub const PROCESS_HELP_TEXT: [&str; 19] = [
     "3 - Process widget",
-    "dd, F9           Kill the selected process",
+    "dd, F9           Kill the selected process, or the marked processes",
+    "Space            Mark/unmark the selected process for killing",
     "c                Sort by CPU usage, press again to reverse",
     "m                Sort by memory usage, press again to reverse",
     "p                Sort by PID name, press again to reverse",
     "n                Sort by process name, press again to reverse",
     "Tab              Group/un-group processes with the same name",
     "Ctrl-f, /        Open process search widget",
+    "x                Clear the process filter kept from a closed search",
+    "v                Take a named snapshot of the processes to compare against later",
+    "V                Compare the processes against the snapshots taken",
     "P                Toggle between showing the full command or just the process name",
     "s, F6            Open process sort widget",
-    "I                Invert current sort",
+    "I, F7            Invert current sort",
     "%                Toggle between values and percentages for memory usage",
     "t, F5            Toggle tree mode",
     "+, -, click      Collapse/expand a branch while in tree mode",
     "click on header  Sorts the entries by that column, click again to invert the sort",
 ];
 
-pub const SEARCH_HELP_TEXT: [&str; 48] = [
+pub const SEARCH_HELP_TEXT: [&str; 50] = [
     "4 - Process search widget",
-    "Esc              Close the search widget (retains the filter)",
+    "Esc              Close the search widget (retains the filter by default)",
+    "Enter            Close the search widget and keep the filter",
     "Ctrl-a           Skip to the start of the search query",
     "Ctrl-e           Skip to the end of the search query",
     "Ctrl-u           Clear the current search query",
@@ -358,6 +418,7 @@ pub const SEARCH_HELP_TEXT: [&str; 48] = [
     "Alt-c, F1        Toggle matching case",
     "Alt-w, F2        Toggle matching the entire word",
     "Alt-r, F3        Toggle using regex",
+    "Alt-b, F4        Toggle matching at word boundaries",
     "Left, Alt-h      Move cursor left",
     "Right, Alt-l     Move cursor right",
     "",
@@ -396,22 +457,25 @@ pub const SEARCH_HELP_TEXT: [&str; 48] = [
     "TiB              ex: read > 1 tib",
 ];
 
-pub const SORT_HELP_TEXT: [&str; 6] = [
+pub const SORT_HELP_TEXT: [&str; 7] = [
     "5 - Sort widget",
     "Down, 'j'        Scroll down in list",
     "Up, 'k'          Scroll up in list",
     "Mouse scroll     Scroll through sort widget",
     "Esc              Close the sort widget",
     "Enter            Sort by current selected column",
+    "?                Show what the selected column means",
 ];
 
-pub const TEMP_HELP_WIDGET: [&str; 3] = [
+pub const TEMP_HELP_WIDGET: [&str; 5] = [
     "6 - Temperature widget",
     "'s'              Sort by sensor name, press again to reverse",
     "'t'              Sort by temperature, press again to reverse",
+    "'I', F7          Invert the current sort",
+    "'u'              Switch between Celsius, Fahrenheit, and Kelvin",
 ];
 
-pub const DISK_HELP_WIDGET: [&str; 9] = [
+pub const DISK_HELP_WIDGET: [&str; 11] = [
     "7 - Disk widget",
     "'d'              Sort by disk name, press again to reverse",
     "'m'              Sort by disk mount, press again to reverse",
@@ -421,6 +485,8 @@ pub const DISK_HELP_WIDGET: [&str; 9] = [
     "'p'              Sort by disk usage percentage, press again to reverse",
     "'r'              Sort by disk read activity, press again to reverse",
     "'w'              Sort by disk write activity, press again to reverse",
+    "'I', F7          Invert the current sort",
+    "'%'              Toggle between values and percentages for disk free space",
 ];
 
 pub const BATTERY_HELP_TEXT: [&str; 3] = [
@@ -447,6 +513,45 @@ pub const HELP_TEXT: [&[&str]; HELP_CONTENTS_TEXT.len()] = [
     &BASIC_MEM_HELP_TEXT,
 ];
 
+// Key hints shown in the status bar for the selected widget, most useful first. These are short
+// versions of the help text above, and are followed by the general hints if there is room.
+pub const GENERAL_KEY_HINTS: [(&str, &str); 4] = [
+    ("?", "help"),
+    ("e", "expand"),
+    ("f", "freeze"),
+    ("q", "quit"),
+];
+pub const GRAPH_KEY_HINTS: [(&str, &str); 2] = [("+/-", "zoom"), ("=", "reset zoom")];
+pub const PROCESS_KEY_HINTS: [(&str, &str); 6] = [
+    ("dd", "kill"),
+    ("/", "search"),
+    ("s", "sort"),
+    ("t", "tree"),
+    ("Tab", "group"),
+    ("P", "command"),
+];
+pub const SEARCH_KEY_HINTS: [(&str, &str); 5] = [
+    ("Enter", "keep filter"),
+    ("Esc", "close"),
+    ("Alt-c", "case"),
+    ("Alt-w", "whole word"),
+    ("Alt-r", "regex"),
+];
+pub const SORT_KEY_HINTS: [(&str, &str); 3] = [
+    ("Enter", "sort"),
+    ("?", "describe column"),
+    ("Esc", "close"),
+];
+pub const TEMP_KEY_HINTS: [(&str, &str); 3] =
+    [("s", "sort name"), ("t", "sort temp"), ("u", "unit")];
+pub const DISK_KEY_HINTS: [(&str, &str); 3] = [
+    ("u", "sort usage"),
+    ("r/w", "sort activity"),
+    ("%", "percentages"),
+];
+pub const BATTERY_KEY_HINTS: [(&str, &str); 1] = [("Left/Right", "switch battery")];
+pub const BASIC_MEM_KEY_HINTS: [(&str, &str); 1] = [("%", "percentages")];
+
 // Default layouts
 pub const DEFAULT_LAYOUT: &str = r##"
 [[row]]
@@ -544,13 +649,21 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #temperature_type = "celsius"
 # The default time interval (in milliseconds).
 #default_time_value = "60s"
-# The time delta on each zoom in/out action (in milliseconds).
+# The time delta on each zoom in/out action (in milliseconds). If unset, zooming halves or doubles the time interval.
 #time_delta = 15000
+# The smallest time interval graphs can be zoomed in to.
+#min_time_value = "30s"
+# Whether zooming one graph zooms every graph.
+#zoom_all_graphs = false
+# Whether saving the current settings (Ctrl-s) only logs what would change in this file.
+#config_save_dry_run = false
 # Hides the time scale.
 #hide_time = false
 # Override layout default widget
 #default_widget_type = "proc"
 #default_widget_count = 1
+# The layout from [[layouts]] to start with, or "default" for the one made by [[row]].
+#layout = "default"
 # Expand selected widget upon starting the app
 #expanded_on_startup = true
 # Use basic mode
@@ -561,8 +674,10 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #hide_table_gap = false
 # Show the battery widgets
 #battery = false
-# Disable mouse clicks
+# Disable the mouse, including clicks and scrolling
 #disable_click = false
+# Record how long drawing and updating each widget takes, and print it on exit
+#debug_timings = false
 # Built-in themes.  Valid values are "default", "default-light", "gruvbox", "gruvbox-light", "nord", "nord-light"
 #color = "default"
 # Show memory values in the processes widget as values by default
@@ -571,6 +686,12 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #tree = false
 # Shows an indicator in table widgets tracking where in the list you are.
 #show_table_scroll_position = false
+# How many entries table widgets move by per mouse scroll.
+#scroll_step = 1
+# The smallest terminal size, as "WIDTHxHEIGHT", to draw widgets in. Smaller terminals show a warning instead.
+#min_terminal_size = "0x0"
+# Wrap around to the other end of a table when moving past its first or last entry.
+#wrap_navigation = false
 # Show processes as their commands by default in the process widget.
 #process_command = false
 # Displays the network widget with binary prefixes.
@@ -579,14 +700,38 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #network_use_bytes = false
 # Displays the network widget with a log scale.
 #network_use_log = false
+# Displays sizes in the process and disk widgets with decimal prefixes.
+#decimal_bytes = false
 # Hides advanced options to stop a process on Unix-like systems.
 #disable_advanced_kill = false
+# Keeps the process filter applied when closing the search with Esc.
+#keep_filter_on_close = true
+# Keeps the selected process while searching, and scrolls back to where the table was once the search is cleared.
+#restore_scroll_after_search = false
+# Set to false to only draw ASCII glyphs, for terminals or fonts that draw others poorly.
+#unicode_symbols = true
+# Draws the column headers of tables in uppercase.
+#uppercase_headers = false
+# Dims tables that aren't focused, so the focused widget stands out.
+#dim_unfocused_tables = false
+# Draws without colours. Defaults to true if the NO_COLOR environment variable is set.
+#no_color = false
+# Shows key hints for the selected widget at the bottom. If false, that row is only shown for status messages.
+#status_bar = true
 # Shows GPU(s) memory
 #enable_gpu_memory = false
 # Shows cache and buffer memory
 #enable_cache_memory = false
 # How much data is stored at once in terms of time.
 #retention = "10m"
+# The most memory the stored data may take up. Once it is reached, the oldest graph data is dropped first.
+#max_data_memory = "64MiB"
+# Groups the digits of large numbers, such as "1,234,567".
+#thousands_separator = false
+# The character used to group digits if thousands_separator is enabled.
+#grouping_separator = ","
+# The character used as the decimal point.
+#decimal_separator = "."
 
 # These are flags around the process widget.
 
@@ -599,6 +744,8 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #[colors] # Uncomment if you want to use custom colors
 # Represents the colour of table headers (processes, CPU, disks, temperature).
 #table_header_color="LightBlue"
+# Represents the background colour of tables, including any space below the last row.
+#table_bg_color="Black"
 # Represents the colour of the label each widget has.
 #widget_title_color="Gray"
 # Represents the average CPU color.
@@ -627,12 +774,18 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #selected_text_color="Black"
 # Represents the background colour of text that is selected.
 #selected_bg_color="LightBlue"
+# Represents the background colour a table row briefly flashes when clicked. If unset, the row's colours are reversed.
+#click_flash_color="White"
 # Represents the colour of the lines and text of the graph.
 #graph_color="Gray"
 # Represents the colours of the battery based on charge
 #high_battery_color="green"
 #medium_battery_color="yellow"
 #low_battery_color="red"
+# Represents the colour of values that went up, like in the process table's change columns.
+#increase_color="red"
+# Represents the colour of values that went down, like in the process table's change columns.
+#decrease_color="green"
 
 # Layout - layouts follow a pattern like this:
 # [[row]] represents a row in the application.
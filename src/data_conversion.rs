@@ -1,17 +1,16 @@
 //! This mainly concerns converting collected data into things that the canvas
 //! can actually handle.
 
+use std::mem::size_of;
+
 use kstring::KString;
 
 use crate::app::data_harvester::memory::MemHarvest;
-use crate::app::{
-    data_farmer::DataCollection,
-    data_harvester::{cpu::CpuDataType, temperature::TemperatureType},
-    AxisScaling,
-};
-use crate::components::tui_widget::time_chart::Point;
+use crate::app::{data_farmer::DataCollection, data_harvester::cpu::CpuDataType, AxisScaling};
+use crate::components::tui_widget::time_chart::{value_at, Point};
 use crate::utils::data_units::DataUnit;
 use crate::utils::gen_util::*;
+use crate::utils::units::{ByteStandard, NumberFormat};
 use crate::widgets::{DiskWidgetData, TempWidgetData};
 
 #[derive(Debug, Default)]
@@ -96,14 +95,85 @@ pub struct ConvertedData {
 }
 
 impl ConvertedData {
+    /// The graph points, which mirror the collected history.
+    fn graph_points(&self) -> Vec<&Vec<Point>> {
+        let mut points = vec![
+            &self.network_data_rx,
+            &self.network_data_tx,
+            &self.mem_data,
+            &self.swap_data,
+        ];
+        #[cfg(not(target_os = "windows"))]
+        points.push(&self.cache_data);
+        #[cfg(feature = "zfs")]
+        points.push(&self.arc_data);
+        #[cfg(feature = "gpu")]
+        if let Some(gpu_data) = &self.gpu_data {
+            points.extend(gpu_data.iter().map(|gpu| &gpu.points));
+        }
+        points.extend(self.cpu_data.iter().filter_map(|cpu| match cpu {
+            CpuWidgetData::All => None,
+            CpuWidgetData::Entry { data, .. } => Some(data),
+        }));
+
+        points
+    }
+
+    /// Like [`ConvertedData::graph_points`], but mutable.
+    fn graph_points_mut(&mut self) -> Vec<&mut Vec<Point>> {
+        let mut points = vec![
+            &mut self.network_data_rx,
+            &mut self.network_data_tx,
+            &mut self.mem_data,
+            &mut self.swap_data,
+        ];
+        #[cfg(not(target_os = "windows"))]
+        points.push(&mut self.cache_data);
+        #[cfg(feature = "zfs")]
+        points.push(&mut self.arc_data);
+        #[cfg(feature = "gpu")]
+        if let Some(gpu_data) = &mut self.gpu_data {
+            points.extend(gpu_data.iter_mut().map(|gpu| &mut gpu.points));
+        }
+        points.extend(self.cpu_data.iter_mut().filter_map(|cpu| match cpu {
+            CpuWidgetData::All => None,
+            CpuWidgetData::Entry { data, .. } => Some(data),
+        }));
+
+        points
+    }
+
+    /// Roughly how many bytes the converted data takes up, including spare room in its vectors.
+    pub fn approx_bytes(&self) -> u64 {
+        let points: usize = self
+            .graph_points()
+            .iter()
+            .map(|points| points.capacity() * size_of::<Point>())
+            .sum();
+        let rows = self.cpu_data.capacity() * size_of::<CpuWidgetData>()
+            + self.battery_data.capacity() * size_of::<ConvertedBatteryData>()
+            + self.disk_data.capacity() * size_of::<DiskWidgetData>()
+            + self.temp_data.capacity() * size_of::<TempWidgetData>();
+
+        (points + rows) as u64
+    }
+
+    /// Frees the spare room in the graph points, which would otherwise only be reused by the next
+    /// conversion.
+    pub fn shrink_to_fit(&mut self) {
+        for points in self.graph_points_mut() {
+            points.shrink_to_fit();
+        }
+    }
+
     // TODO: Can probably heavily reduce this step to avoid clones.
     pub fn ingest_disk_data(&mut self, data: &DataCollection) {
         self.disk_data.clear();
 
         data.disk_harvest
             .iter()
-            .zip(&data.io_labels)
-            .for_each(|(disk, (io_read, io_write))| {
+            .zip(&data.io_rates)
+            .for_each(|(disk, io_rates)| {
                 // Because this sometimes does *not* equal to disk.total.
                 let summed_total_bytes = match (disk.used_space, disk.free_space) {
                     (Some(used), Some(free)) => Some(used + free),
@@ -117,22 +187,23 @@ impl ConvertedData {
                     used_bytes: disk.used_space,
                     total_bytes: disk.total_space,
                     summed_total_bytes,
-                    io_read: io_read.into(),
-                    io_write: io_write.into(),
+                    io_read: io_rates.map(|(read, _)| read),
+                    io_write: io_rates.map(|(_, write)| write),
+                    byte_standard: ByteStandard::default(),
+                    number_format: NumberFormat::default(),
                 });
             });
 
         self.disk_data.shrink_to_fit();
     }
 
-    pub fn ingest_temp_data(&mut self, data: &DataCollection, temperature_type: TemperatureType) {
+    pub fn ingest_temp_data(&mut self, data: &DataCollection) {
         self.temp_data.clear();
 
         data.temp_harvest.iter().for_each(|temp_harvest| {
             self.temp_data.push(TempWidgetData {
                 sensor: KString::from_ref(&temp_harvest.name),
-                temperature_value: temp_harvest.temperature.ceil() as u64,
-                temperature_type,
+                temperature: temp_harvest.temperature,
             });
         });
 
@@ -142,8 +213,14 @@ impl ConvertedData {
     pub fn ingest_cpu_data(&mut self, current_data: &DataCollection) {
         let current_time = current_data.current_instant;
 
-        // (Re-)initialize the vector if the lengths don't match...
-        if let Some((_time, data)) = &current_data.timed_data_vec.last() {
+        // (Re-)initialize the vector if the lengths don't match... This uses the latest entry
+        // with CPU data, as the CPU may not have been collected on every update.
+        if let Some((_time, data)) = current_data
+            .timed_data_vec
+            .iter()
+            .rev()
+            .find(|(_time, data)| !data.cpu_data.is_empty())
+        {
             if data.cpu_data.len() + 1 != self.cpu_data.len() {
                 self.cpu_data = Vec::with_capacity(data.cpu_data.len() + 1);
                 self.cpu_data.push(CpuWidgetData::All);
@@ -311,6 +388,10 @@ pub fn get_rx_tx_data_points(
     let current_time = data.current_instant;
 
     for (time, data) in &data.timed_data_vec {
+        // Entries from updates where the network wasn't collected are skipped.
+        let (Some(rx_data), Some(tx_data)) = (data.rx_data, data.tx_data) else {
+            continue;
+        };
         let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
 
         let (rx_data, tx_data) = match scale_type {
@@ -319,22 +400,20 @@ pub fn get_rx_tx_data_points(
                     match unit_type {
                         DataUnit::Byte => {
                             // As dividing by 8 is equal to subtracting 4 in base 2!
-                            ((data.rx_data).log2() - 4.0, (data.tx_data).log2() - 4.0)
+                            (rx_data.log2() - 4.0, tx_data.log2() - 4.0)
                         }
-                        DataUnit::Bit => ((data.rx_data).log2(), (data.tx_data).log2()),
+                        DataUnit::Bit => (rx_data.log2(), tx_data.log2()),
                     }
                 } else {
                     match unit_type {
-                        DataUnit::Byte => {
-                            ((data.rx_data / 8.0).log10(), (data.tx_data / 8.0).log10())
-                        }
-                        DataUnit::Bit => ((data.rx_data).log10(), (data.tx_data).log10()),
+                        DataUnit::Byte => ((rx_data / 8.0).log10(), (tx_data / 8.0).log10()),
+                        DataUnit::Bit => (rx_data.log10(), tx_data.log10()),
                     }
                 }
             }
             AxisScaling::Linear => match unit_type {
-                DataUnit::Byte => (data.rx_data / 8.0, data.tx_data / 8.0),
-                DataUnit::Bit => (data.rx_data, data.tx_data),
+                DataUnit::Byte => (rx_data / 8.0, tx_data / 8.0),
+                DataUnit::Bit => (rx_data, tx_data),
             },
         };
 
@@ -348,6 +427,46 @@ pub fn get_rx_tx_data_points(
     (rx, tx)
 }
 
+/// Returns the rate at `x` in points from [`get_rx_tx_data_points`], undoing the scaling applied
+/// to them, e.g. "1.2MiB/s".
+pub fn network_rate_at(
+    points: &[Point], x: f64, scale_type: &AxisScaling, unit_type: &DataUnit,
+    use_binary_prefix: bool,
+) -> Option<String> {
+    let value = value_at(points, x)?;
+    let bits = match scale_type {
+        AxisScaling::Log => {
+            if use_binary_prefix {
+                match unit_type {
+                    DataUnit::Byte => 2_f64.powf(value + 4.0),
+                    DataUnit::Bit => 2_f64.powf(value),
+                }
+            } else {
+                match unit_type {
+                    DataUnit::Byte => 10_f64.powf(value) * 8.0,
+                    DataUnit::Bit => 10_f64.powf(value),
+                }
+            }
+        }
+        AxisScaling::Linear => match unit_type {
+            DataUnit::Byte => value * 8.0,
+            DataUnit::Bit => value,
+        },
+    };
+
+    let (quantity, unit) = match unit_type {
+        DataUnit::Byte => ((bits / 8.0) as u64, "B/s"),
+        DataUnit::Bit => (bits as u64, "b/s"),
+    };
+    let (value, unit) = if use_binary_prefix {
+        get_binary_prefix(quantity, unit)
+    } else {
+        get_decimal_prefix(quantity, unit)
+    };
+
+    Some(format!("{value:.1}{unit}"))
+}
+
 pub fn convert_network_data_points(
     data: &DataCollection, need_four_points: bool, scale_type: &AxisScaling, unit_type: &DataUnit,
     use_binary_prefix: bool,
@@ -470,50 +589,6 @@ pub fn convert_network_data_points(
     }
 }
 
-/// Returns a string given a value that is converted to the closest binary variant.
-/// If the value is greater than a gibibyte, then it will return a decimal place.
-pub fn binary_byte_string(value: u64) -> String {
-    let converted_values = get_binary_bytes(value);
-    if value >= GIBI_LIMIT {
-        format!("{:.*}{}", 1, converted_values.0, converted_values.1)
-    } else {
-        format!("{:.*}{}", 0, converted_values.0, converted_values.1)
-    }
-}
-
-/// Returns a string given a value that is converted to the closest SI-variant.
-/// If the value is greater than a giga-X, then it will return a decimal place.
-pub fn dec_bytes_per_string(value: u64) -> String {
-    let converted_values = get_decimal_bytes(value);
-    if value >= GIGA_LIMIT {
-        format!("{:.*}{}", 1, converted_values.0, converted_values.1)
-    } else {
-        format!("{:.*}{}", 0, converted_values.0, converted_values.1)
-    }
-}
-
-/// Returns a string given a value that is converted to the closest SI-variant, per second.
-/// If the value is greater than a giga-X, then it will return a decimal place.
-pub fn dec_bytes_per_second_string(value: u64) -> String {
-    let converted_values = get_decimal_bytes(value);
-    if value >= GIGA_LIMIT {
-        format!("{:.*}{}/s", 1, converted_values.0, converted_values.1)
-    } else {
-        format!("{:.*}{}/s", 0, converted_values.0, converted_values.1)
-    }
-}
-
-/// Returns a string given a value that is converted to the closest SI-variant.
-/// If the value is greater than a giga-X, then it will return a decimal place.
-pub fn dec_bytes_string(value: u64) -> String {
-    let converted_values = get_decimal_bytes(value);
-    if value >= GIGA_LIMIT {
-        format!("{:.*}{}", 1, converted_values.0, converted_values.1)
-    } else {
-        format!("{:.*}{}", 0, converted_values.0, converted_values.1)
-    }
-}
-
 #[cfg(feature = "battery")]
 pub fn convert_battery_harvest(current_data: &DataCollection) -> Vec<ConvertedBatteryData> {
     current_data
@@ -663,68 +738,3 @@ pub fn convert_gpu_data(
         None
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_binary_byte_string() {
-        assert_eq!(binary_byte_string(0), "0B".to_string());
-        assert_eq!(binary_byte_string(1), "1B".to_string());
-        assert_eq!(binary_byte_string(1000), "1000B".to_string());
-        assert_eq!(binary_byte_string(1023), "1023B".to_string());
-        assert_eq!(binary_byte_string(KIBI_LIMIT), "1KiB".to_string());
-        assert_eq!(binary_byte_string(KIBI_LIMIT + 1), "1KiB".to_string());
-        assert_eq!(binary_byte_string(MEBI_LIMIT), "1MiB".to_string());
-        assert_eq!(binary_byte_string(GIBI_LIMIT), "1.0GiB".to_string());
-        assert_eq!(binary_byte_string(2 * GIBI_LIMIT), "2.0GiB".to_string());
-        assert_eq!(
-            binary_byte_string((2.5 * GIBI_LIMIT as f64) as u64),
-            "2.5GiB".to_string()
-        );
-        assert_eq!(
-            binary_byte_string((10.34 * TEBI_LIMIT as f64) as u64),
-            "10.3TiB".to_string()
-        );
-        assert_eq!(
-            binary_byte_string((10.36 * TEBI_LIMIT as f64) as u64),
-            "10.4TiB".to_string()
-        );
-    }
-
-    #[test]
-    fn test_dec_bytes_per_second_string() {
-        assert_eq!(dec_bytes_per_second_string(0), "0B/s".to_string());
-        assert_eq!(dec_bytes_per_second_string(1), "1B/s".to_string());
-        assert_eq!(dec_bytes_per_second_string(900), "900B/s".to_string());
-        assert_eq!(dec_bytes_per_second_string(999), "999B/s".to_string());
-        assert_eq!(dec_bytes_per_second_string(KILO_LIMIT), "1KB/s".to_string());
-        assert_eq!(
-            dec_bytes_per_second_string(KILO_LIMIT + 1),
-            "1KB/s".to_string()
-        );
-        assert_eq!(dec_bytes_per_second_string(KIBI_LIMIT), "1KB/s".to_string());
-        assert_eq!(dec_bytes_per_second_string(MEGA_LIMIT), "1MB/s".to_string());
-        assert_eq!(
-            dec_bytes_per_second_string(GIGA_LIMIT),
-            "1.0GB/s".to_string()
-        );
-        assert_eq!(
-            dec_bytes_per_second_string(2 * GIGA_LIMIT),
-            "2.0GB/s".to_string()
-        );
-        assert_eq!(
-            dec_bytes_per_second_string((2.5 * GIGA_LIMIT as f64) as u64),
-            "2.5GB/s".to_string()
-        );
-        assert_eq!(
-            dec_bytes_per_second_string((10.34 * TERA_LIMIT as f64) as u64),
-            "10.3TB/s".to_string()
-        );
-        assert_eq!(
-            dec_bytes_per_second_string((10.36 * TERA_LIMIT as f64) as u64),
-            "10.4TB/s".to_string()
-        );
-    }
-}
@@ -18,7 +18,7 @@ use std::{
     path::PathBuf,
     sync::Mutex,
     sync::{
-        mpsc::{Receiver, Sender},
+        mpsc::{Receiver, SendError, Sender},
         Arc, Condvar,
     },
     thread::{self, JoinHandle},
@@ -29,6 +29,8 @@ use app::{
     data_harvester,
     frozen_state::FrozenState,
     layout_manager::{UsedWidgets, WidgetDirection},
+    timings::TimingKind,
+    update_rates::CollectionRates,
     App, AppConfigFields, DataFilters,
 };
 use constants::*;
@@ -42,15 +44,22 @@ use crossterm::{
     terminal::{disable_raw_mode, LeaveAlternateScreen},
 };
 use data_conversion::*;
-use options::*;
+use options::{
+    config_watcher::{ConfigWatcher, ReloadedConfig},
+    *,
+};
 use utils::error;
 
 pub mod app;
 pub mod utils {
+    pub mod clock;
     pub mod data_units;
     pub mod error;
     pub mod gen_util;
     pub mod logging;
+    #[cfg(test)]
+    pub mod test_utils;
+    pub mod units;
 }
 pub mod args;
 pub mod canvas;
@@ -75,32 +84,97 @@ pub enum BottomEvent {
     KeyInput(KeyEvent),
     MouseInput(MouseEvent),
     PasteEvent(String),
-    Update(Box<data_harvester::Data>),
+    /// New data is waiting in [`LatestData`].
+    Update,
     Clean,
     Terminate,
 }
 
+/// Hands the newest collected data from the collection thread to the main thread.
+///
+/// Data that the main thread hasn't taken yet is replaced rather than queued up, so if drawing falls
+/// behind, it skips straight to the newest data on the next update instead of working through a
+/// backlog, and key presses never have to wait behind old data. Process changes from the replaced
+/// data are carried over, as they are only sent once.
+#[derive(Debug, Default, Clone)]
+pub struct LatestData(Arc<Mutex<Option<Box<data_harvester::Data>>>>);
+
+impl LatestData {
+    /// Stores `data`, replacing any data that hasn't been taken yet. Returns whether the slot was
+    /// empty, in which case the main thread needs to be sent a [`BottomEvent::Update`].
+    pub fn put(&self, mut data: Box<data_harvester::Data>) -> bool {
+        let mut latest = self.0.lock().unwrap();
+        let replaced = latest.take();
+        let was_empty = replaced.is_none();
+        if let Some(replaced) = replaced {
+            data.carry_over(*replaced);
+        }
+        *latest = Some(data);
+
+        was_empty
+    }
+
+    /// Takes the newest data, if there is any.
+    pub fn take(&self) -> Option<Box<data_harvester::Data>> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Sends collected data to the main thread through a [`LatestData`], only sending a
+/// [`BottomEvent::Update`] if the main thread has taken the previous data.
+#[derive(Debug, Clone)]
+pub struct DataSender {
+    sender: Sender<BottomEvent>,
+    latest_data: LatestData,
+}
+
+impl DataSender {
+    pub fn new(sender: Sender<BottomEvent>, latest_data: LatestData) -> Self {
+        Self {
+            sender,
+            latest_data,
+        }
+    }
+
+    /// Sends `data`, failing if the main thread has stopped listening.
+    pub fn send(&self, data: Box<data_harvester::Data>) -> Result<(), SendError<BottomEvent>> {
+        if self.latest_data.put(data) {
+            self.sender.send(BottomEvent::Update)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// Events sent to the collection thread.
 #[derive(Debug)]
 pub enum CollectionThreadEvent {
     Reset,
+    /// Changes how long to wait between each data update, in milliseconds.
+    UpdateRate(u64),
+    /// Changes which data is collected and how often, e.g. after switching layouts.
+    UsedWidgets(UsedWidgets, CollectionRates),
 }
 
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
+    // Mouse capture isn't enabled if the mouse is disabled, but some terminals may still send
+    // mouse events, so drop them here too.
+    if app.app_config_fields.disable_click {
+        return;
+    }
+
     match event.kind {
         MouseEventKind::ScrollUp => app.handle_scroll_up(),
         MouseEventKind::ScrollDown => app.handle_scroll_down(),
         MouseEventKind::Down(button) => {
             let (x, y) = (event.column, event.row);
-            if !app.app_config_fields.disable_click {
-                match button {
-                    crossterm::event::MouseButton::Left => {
-                        // Trigger left click widget activity
-                        app.on_left_mouse_up(x, y);
-                    }
-                    crossterm::event::MouseButton::Right => {}
-                    _ => {}
+            match button {
+                crossterm::event::MouseButton::Left => {
+                    // Trigger left click widget activity
+                    app.on_left_mouse_up(x, y);
                 }
+                crossterm::event::MouseButton::Right => {}
+                _ => {}
             }
         }
         _ => {}
@@ -112,9 +186,18 @@ pub fn handle_key_event_or_break(
 ) -> bool {
     // c_debug!("KeyEvent: {:?}", event);
 
+    // Some terminals also report when a key is released, which shouldn't count as another press.
+    if event.kind == KeyEventKind::Release {
+        return false;
+    }
+
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
+        if event.code == KeyCode::Char('q')
+            && !app.is_in_search_widget()
+            && !app.help_dialog_state.is_showing_help
+            && app.snapshot_name.is_none()
+        {
             return true;
         }
         match event.code {
@@ -130,11 +213,13 @@ pub fn handle_key_event_or_break(
             KeyCode::Tab => app.on_tab(),
             KeyCode::Backspace => app.on_backspace(),
             KeyCode::Delete => app.on_delete(),
-            KeyCode::F(1) => app.toggle_ignore_case(),
+            KeyCode::F(1) => app.on_f1(),
             KeyCode::F(2) => app.toggle_search_whole_word(),
             KeyCode::F(3) => app.toggle_search_regex(),
+            KeyCode::F(4) => app.toggle_search_word_boundary(),
             KeyCode::F(5) => app.toggle_tree_mode(),
             KeyCode::F(6) => app.toggle_sort_menu(),
+            KeyCode::F(7) => app.invert_sort(),
             KeyCode::F(9) => app.start_killing_process(),
             KeyCode::PageDown => app.on_page_down(),
             KeyCode::PageUp => app.on_page_up(),
@@ -147,6 +232,7 @@ pub fn handle_key_event_or_break(
                 KeyCode::Char('c') | KeyCode::Char('C') => app.toggle_ignore_case(),
                 KeyCode::Char('w') | KeyCode::Char('W') => app.toggle_search_whole_word(),
                 KeyCode::Char('r') | KeyCode::Char('R') => app.toggle_search_regex(),
+                KeyCode::Char('b') | KeyCode::Char('B') => app.toggle_search_word_boundary(),
                 KeyCode::Char('h') => app.on_left_key(),
                 KeyCode::Char('l') => app.on_right_key(),
                 _ => {}
@@ -162,6 +248,8 @@ pub fn handle_key_event_or_break(
                 KeyCode::Right => app.move_widget_selection(&WidgetDirection::Right),
                 KeyCode::Up => app.move_widget_selection(&WidgetDirection::Up),
                 KeyCode::Down => app.move_widget_selection(&WidgetDirection::Down),
+                KeyCode::Char('o') => app.focus_previous_widget(),
+                KeyCode::Char('s') => app.open_save_config(),
                 KeyCode::Char('r') => {
                     if reset_sender.send(CollectionThreadEvent::Reset).is_ok() {
                         app.reset();
@@ -174,6 +262,7 @@ pub fn handle_key_event_or_break(
                 KeyCode::Char('h') => app.on_backspace(),
                 KeyCode::Char('d') => app.scroll_half_page_down(),
                 KeyCode::Char('u') => app.scroll_half_page_up(),
+                KeyCode::Char('p') => app.toggle_paging(),
                 // KeyCode::Char('j') => {}, // Move down
                 // KeyCode::Char('k') => {}, // Move up
                 // KeyCode::Char('h') => {}, // Move right
@@ -251,6 +340,37 @@ pub fn create_or_get_config(config_path: &Option<PathBuf>) -> error::Result<Conf
     }
 }
 
+/// Applies the result of reloading the config file, or reports why it couldn't be reloaded.
+pub fn apply_config_reload(app: &mut App, reloaded: anyhow::Result<ReloadedConfig>) {
+    match reloaded {
+        Ok(reloaded) => app.apply_reloaded_config(reloaded),
+        Err(err) => app.on_config_reload_error(&err),
+    }
+}
+
+/// Switches `app` to the layout profile called `name`, built from the config last loaded by
+/// `config_watcher`, and returns the painter to draw it with. If the layout can't be built, this
+/// is reported and the current layout is kept.
+pub fn switch_layout_profile(
+    app: &mut App, config_watcher: &mut ConfigWatcher, name: &str,
+) -> Option<canvas::Painter> {
+    let switched = app.current_styling().and_then(|styling| {
+        let (layout, new_app) = config_watcher.build_layout_profile(name, &styling)?;
+        Ok((canvas::Painter::init(layout, styling)?, new_app))
+    });
+
+    match switched {
+        Ok((painter, new_app)) => {
+            app.switch_layout(name, new_app);
+            Some(painter)
+        }
+        Err(err) => {
+            app.on_layout_switch_error(name, &err);
+            None
+        }
+    }
+}
+
 pub fn try_drawing(
     terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
     app: &mut App, painter: &mut canvas::Painter,
@@ -327,15 +447,22 @@ pub fn panic_hook(panic_info: &PanicInfo<'_>) {
 }
 
 pub fn update_data(app: &mut App) {
+    let update_start = app.timings.is_some().then(Instant::now);
+
     let data_source = match &app.frozen_state {
         FrozenState::NotFrozen => &app.data_collection,
         FrozenState::Frozen(data) => data,
     };
 
-    for proc in app.states.proc_state.widget_states.values_mut() {
+    for (widget_id, proc) in app.states.proc_state.widget_states.iter_mut() {
         if proc.force_update_data {
+            let start = app.timings.is_some().then(Instant::now);
             proc.ingest_data(data_source);
             proc.force_update_data = false;
+
+            if let (Some(timings), Some(start)) = (&mut app.timings, start) {
+                timings.record_since(TimingKind::Update(*widget_id), start);
+            }
         }
     }
 
@@ -356,19 +483,29 @@ pub fn update_data(app: &mut App) {
     }
     {
         let data = &app.converted_data.temp_data;
-        for temp in app.states.temp_state.widget_states.values_mut() {
+        for (widget_id, temp) in app.states.temp_state.widget_states.iter_mut() {
             if temp.force_update_data {
+                let start = app.timings.is_some().then(Instant::now);
                 temp.ingest_data(data);
                 temp.force_update_data = false;
+
+                if let (Some(timings), Some(start)) = (&mut app.timings, start) {
+                    timings.record_since(TimingKind::Update(*widget_id), start);
+                }
             }
         }
     }
     {
         let data = &app.converted_data.disk_data;
-        for disk in app.states.disk_state.widget_states.values_mut() {
+        for (widget_id, disk) in app.states.disk_state.widget_states.iter_mut() {
             if disk.force_update_data {
+                let start = app.timings.is_some().then(Instant::now);
                 disk.ingest_data(data);
                 disk.force_update_data = false;
+
+                if let (Some(timings), Some(start)) = (&mut app.timings, start) {
+                    timings.record_since(TimingKind::Update(*widget_id), start);
+                }
             }
         }
     }
@@ -404,6 +541,12 @@ pub fn update_data(app: &mut App) {
         app.converted_data.network_data_tx = tx;
         app.states.net_state.force_update = None;
     }
+
+    app.refresh_snapshot_comparison();
+
+    if let (Some(timings), Some(update_start)) = (&mut app.timings, update_start) {
+        timings.record_since(TimingKind::UpdateData, update_start);
+    }
 }
 
 pub fn create_input_thread(
@@ -438,8 +581,8 @@ pub fn create_input_thread(
                                     break;
                                 }
                             }
-                            Event::Key(key) if key.kind == KeyEventKind::Press => {
-                                // For now, we only care about key down events. This may change in the future.
+                            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                                // Key releases are ignored, but presses and repeats from holding a key are handled.
                                 if sender.send(BottomEvent::KeyInput(key)).is_err() {
                                     break;
                                 }
@@ -473,71 +616,633 @@ pub fn create_input_thread(
 }
 
 pub fn create_collection_thread(
-    sender: Sender<BottomEvent>, control_receiver: Receiver<CollectionThreadEvent>,
+    sender: DataSender, control_receiver: Receiver<CollectionThreadEvent>,
     termination_lock: Arc<Mutex<bool>>, termination_cvar: Arc<Condvar>,
     app_config_fields: &AppConfigFields, filters: DataFilters, used_widget_set: UsedWidgets,
+    collection_rates: CollectionRates,
 ) -> JoinHandle<()> {
-    let temp_type = app_config_fields.temperature_type;
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
     let unnormalized_cpu = app_config_fields.unnormalized_cpu;
     let show_average_cpu = app_config_fields.show_average_cpu;
-    let update_time = app_config_fields.update_rate;
+    let mut update_time = app_config_fields.update_rate;
 
     thread::spawn(move || {
         let mut data_state = data_harvester::DataCollector::new(filters);
 
         data_state.set_data_collection(used_widget_set);
-        data_state.set_temperature_type(temp_type);
+        data_state.set_collection_rates(collection_rates);
         data_state.set_use_current_cpu_total(use_current_cpu_total);
         data_state.set_unnormalized_cpu(unnormalized_cpu);
         data_state.set_show_average_cpu(show_average_cpu);
 
         data_state.init();
 
+        let mut pending = Vec::new();
         loop {
-            // Check once at the very top... don't block though.
-            if let Ok(is_terminated) = termination_lock.try_lock() {
-                if *is_terminated {
-                    drop(is_terminated);
-                    break;
-                }
+            // Check once at the very top.
+            if is_terminated(&termination_lock) {
+                break;
             }
 
-            if let Ok(message) = control_receiver.try_recv() {
+            pending.extend(control_receiver.try_iter());
+            for message in pending.drain(..) {
                 // trace!("Received message in collection thread: {:?}", message);
                 match message {
                     CollectionThreadEvent::Reset => {
-                        data_state.data.cleanup();
+                        data_state.reset_generation();
+                    }
+                    CollectionThreadEvent::UpdateRate(rate) => {
+                        update_time = rate;
+                    }
+                    CollectionThreadEvent::UsedWidgets(used_widgets, collection_rates) => {
+                        data_state.set_data_collection(used_widgets);
+                        data_state.set_collection_rates(collection_rates);
+                        data_state.init_sources();
                     }
                 }
             }
 
             data_state.update_data();
 
-            // Yet another check to bail if needed... do not block!
-            if let Ok(is_terminated) = termination_lock.try_lock() {
-                if *is_terminated {
-                    drop(is_terminated);
-                    break;
-                }
+            // Yet another check to bail if needed.
+            if is_terminated(&termination_lock) {
+                break;
             }
 
-            let event = BottomEvent::Update(Box::from(data_state.data));
-            data_state.data = data_harvester::Data::default();
-            if sender.send(event).is_err() {
+            let data = std::mem::take(&mut data_state.data);
+            if sender.send(Box::new(data)).is_err() {
                 break;
             }
 
-            // This is actually used as a "sleep" that can be interrupted by another thread.
-            if let Ok((is_terminated, _)) = termination_cvar.wait_timeout(
-                termination_lock.lock().unwrap(),
+            // This is actually used as a "sleep" that can be interrupted by another thread, either to
+            // shut down or to handle a control message right away.
+            if wait_for_termination(
+                &termination_lock,
+                &termination_cvar,
                 Duration::from_millis(update_time),
+                &control_receiver,
+                &mut pending,
             ) {
-                if *is_terminated {
-                    drop(is_terminated);
-                    break;
-                }
+                break;
             }
         }
     })
 }
+
+/// Returns whether the app is shutting down. A poisoned lock means another thread panicked, which
+/// counts too.
+fn is_terminated(termination_lock: &Mutex<bool>) -> bool {
+    match termination_lock.lock() {
+        Ok(is_terminated) => *is_terminated,
+        Err(_) => true,
+    }
+}
+
+/// Waits for up to `timeout` for the app to shut down, returning whether it did. This also stops
+/// early once a control message arrives, which is moved to `pending`. Both are checked before
+/// waiting, so a shutdown or message sent just before this is called isn't missed.
+fn wait_for_termination(
+    termination_lock: &Mutex<bool>, termination_cvar: &Condvar, timeout: Duration,
+    control_receiver: &Receiver<CollectionThreadEvent>, pending: &mut Vec<CollectionThreadEvent>,
+) -> bool {
+    let Ok(guard) = termination_lock.lock() else {
+        return true;
+    };
+    let result = termination_cvar.wait_timeout_while(guard, timeout, |is_terminated| {
+        pending.extend(control_receiver.try_iter());
+        !*is_terminated && pending.is_empty()
+    });
+    match result {
+        Ok((is_terminated, _)) => *is_terminated,
+        Err(_) => true,
+    }
+}
+
+/// Wakes the collection thread if it is waiting, so it handles a control message that was just
+/// sent right away rather than after the current update interval.
+pub fn wake_collection_thread(termination_lock: &Mutex<bool>, termination_cvar: &Condvar) {
+    // While the lock is held, the thread is either already waiting, or has yet to check for
+    // messages, so this can't be missed.
+    let _guard = termination_lock.lock();
+    termination_cvar.notify_all();
+}
+
+/// Waits up to `timeout` for the thread of `handle` to finish, and joins it if it did. Otherwise,
+/// `handle` is given back, as the thread may be stuck, like on a stale mount.
+pub fn join_with_timeout<T>(
+    handle: JoinHandle<T>, timeout: Duration,
+) -> Result<thread::Result<T>, JoinHandle<T>> {
+    let start = Instant::now();
+    while !handle.is_finished() {
+        if start.elapsed() >= timeout {
+            return Err(handle);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    Ok(handle.join())
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::KeyEventState;
+
+    use super::*;
+    use crate::{canvas::canvas_styling::CanvasStyling, utils::test_utils::build_test_app};
+
+    fn key(code: KeyCode, kind: KeyEventKind) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn test_app(args: &[&str]) -> App {
+        build_test_app(args, Config::default(), &CanvasStyling::default()).0
+    }
+
+    #[test]
+    fn test_key_release_is_ignored() {
+        let mut app = test_app(&["btm"]);
+        let (sender, _receiver) = std::sync::mpsc::channel();
+
+        assert!(!handle_key_event_or_break(
+            key(KeyCode::Char('?'), KeyEventKind::Release),
+            &mut app,
+            &sender
+        ));
+        assert!(!app.help_dialog_state.is_showing_help);
+
+        assert!(!handle_key_event_or_break(
+            key(KeyCode::Char('?'), KeyEventKind::Press),
+            &mut app,
+            &sender
+        ));
+        assert!(app.help_dialog_state.is_showing_help);
+
+        app.help_dialog_state.is_showing_help = false;
+        assert!(!handle_key_event_or_break(
+            key(KeyCode::Char('q'), KeyEventKind::Release),
+            &mut app,
+            &sender
+        ));
+        assert!(handle_key_event_or_break(
+            key(KeyCode::Char('q'), KeyEventKind::Repeat),
+            &mut app,
+            &sender
+        ));
+    }
+
+    #[test]
+    fn test_reload_changed_layout() {
+        use crate::app::layout_manager::BottomWidgetType;
+
+        let path = std::env::temp_dir().join(format!(
+            "bottom-reload-layout-test-{}.toml",
+            std::process::id()
+        ));
+        let rows = |second: &str| {
+            format!("[[row]]\n[[row.child]]\ntype = \"proc\"\n[[row.child]]\ntype = \"{second}\"\n")
+        };
+        fs::write(&path, rows("temp")).unwrap();
+
+        let matches = args::build_app().get_matches_from(["btm"]);
+        let config: Config = toml_edit::de::from_str(&rows("temp")).unwrap();
+        let mut watcher = ConfigWatcher::new(Some(path.clone()), matches, &config);
+        let (_, mut app) = watcher
+            .build_layout_profile(DEFAULT_LAYOUT_PROFILE, &CanvasStyling::default())
+            .unwrap();
+        assert!(app.used_widgets.use_temp);
+        assert!(!app.used_widgets.use_disk);
+
+        let proc = |app: &App| {
+            app.widget_map
+                .values()
+                .find(|widget| widget.widget_type == BottomWidgetType::Proc)
+                .unwrap()
+                .widget_id
+        };
+        let proc_id = proc(&app);
+        app.states
+            .proc_state
+            .get_mut_widget_state(proc_id)
+            .unwrap()
+            .table
+            .state
+            .current_index = 5;
+
+        fs::write(&path, rows("disk")).unwrap();
+        apply_config_reload(&mut app, watcher.reload());
+        let name = app.take_layout_switch_request().unwrap();
+        assert_eq!(name, DEFAULT_LAYOUT_PROFILE);
+        assert!(switch_layout_profile(&mut app, &mut watcher, &name).is_some());
+
+        assert_eq!(app.active_layout_profile, DEFAULT_LAYOUT_PROFILE);
+        assert_eq!(
+            app.status_message(),
+            Some("Reloaded the config file and its layout")
+        );
+        assert!(app.used_widgets.use_disk);
+        assert!(!app.used_widgets.use_temp);
+        assert_eq!(app.states.disk_state.widget_states.len(), 1);
+        assert!(app.states.temp_state.widget_states.is_empty());
+        assert_eq!(
+            app.states
+                .proc_state
+                .get_widget_state(proc(&app))
+                .unwrap()
+                .table
+                .state
+                .current_index,
+            5
+        );
+
+        // Reloading an unchanged layout doesn't rebuild it.
+        apply_config_reload(&mut app, watcher.reload());
+        assert!(app.take_layout_switch_request().is_none());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_reload_reordered_layout_profiles() {
+        let path = std::env::temp_dir().join(format!(
+            "bottom-reload-profiles-test-{}.toml",
+            std::process::id()
+        ));
+        let profiles = |names: [&str; 2]| {
+            names
+                .iter()
+                .map(|name| {
+                    format!(
+                        "[[layouts]]\nname = \"{name}\"\n[[layouts.row]]\n[[layouts.row.child]]\ntype = \"{name}\"\n"
+                    )
+                })
+                .collect::<String>()
+        };
+        fs::write(&path, profiles(["temp", "disk"])).unwrap();
+
+        let matches = args::build_app().get_matches_from(["btm"]);
+        let config: Config = toml_edit::de::from_str(&profiles(["temp", "disk"])).unwrap();
+        let mut watcher = ConfigWatcher::new(Some(path.clone()), matches, &config);
+        let (_, mut app) = watcher
+            .build_layout_profile(DEFAULT_LAYOUT_PROFILE, &CanvasStyling::default())
+            .unwrap();
+        assert_eq!(
+            app.layout_profiles,
+            [DEFAULT_LAYOUT_PROFILE, "temp", "disk"]
+        );
+
+        app.open_layout_switcher();
+        app.on_down_key();
+        app.on_down_key();
+        assert_eq!(app.layout_switcher_state, Some(2));
+
+        // The open switcher follows "disk" to where it moved, rather than staying on the third.
+        fs::write(&path, profiles(["disk", "temp"])).unwrap();
+        apply_config_reload(&mut app, watcher.reload());
+        assert_eq!(
+            app.layout_profiles,
+            [DEFAULT_LAYOUT_PROFILE, "disk", "temp"]
+        );
+        assert_eq!(app.layout_switcher_state, Some(1));
+
+        app.on_enter();
+        let name = app.take_layout_switch_request().unwrap();
+        assert_eq!(name, "disk");
+        assert!(switch_layout_profile(&mut app, &mut watcher, &name).is_some());
+        assert!(app.used_widgets.use_disk);
+        assert!(!app.used_widgets.use_temp);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    /// Runs a session of mouse events over the temperature table, returning which widget ends up
+    /// selected and which row of the table is selected.
+    fn run_mouse_session(app: &mut App) -> (u64, usize) {
+        use crate::{app::layout_manager::BottomWidgetType, widgets::TempWidgetData};
+
+        let widget_id = |app: &App, widget_type: BottomWidgetType| {
+            app.widget_map
+                .values()
+                .find(|widget| widget.widget_type == widget_type)
+                .unwrap()
+                .widget_id
+        };
+        let temp = widget_id(app, BottomWidgetType::Temp);
+        let cpu = widget_id(app, BottomWidgetType::Cpu);
+
+        let data = (0..10)
+            .map(|index| TempWidgetData {
+                sensor: index.to_string().into(),
+                temperature: 40.0,
+            })
+            .collect::<Vec<_>>();
+        app.states
+            .temp_state
+            .get_mut_widget_state(temp)
+            .unwrap()
+            .ingest_data(&data);
+        app.focus_widget(temp);
+        if let Some(widget) = app.widget_map.get_mut(&cpu) {
+            widget.top_left_corner = Some((0, 0));
+            widget.bottom_right_corner = Some((10, 10));
+        }
+
+        let mouse = |kind| MouseEvent {
+            kind,
+            column: 5,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_event(mouse(MouseEventKind::ScrollDown), app);
+        handle_mouse_event(mouse(MouseEventKind::ScrollDown), app);
+        handle_mouse_event(mouse(MouseEventKind::Moved), app);
+        let scrolled_index = app.states.temp_state.widget_states[&temp]
+            .table
+            .current_index();
+        handle_mouse_event(
+            mouse(MouseEventKind::Down(crossterm::event::MouseButton::Left)),
+            app,
+        );
+
+        (app.current_widget.widget_id, scrolled_index)
+    }
+
+    #[test]
+    fn test_disable_click_ignores_mouse() {
+        use crate::app::layout_manager::BottomWidgetType;
+
+        let mut app = test_app(&["btm"]);
+        let (selected_widget, selected_row) = run_mouse_session(&mut app);
+        assert_eq!(
+            app.widget_map[&selected_widget].widget_type,
+            BottomWidgetType::Cpu
+        );
+        assert_eq!(selected_row, 2);
+
+        // With the mouse disabled, neither the scrolling nor the click should do anything.
+        let mut app = test_app(&["btm", "--disable_click"]);
+        let (selected_widget, selected_row) = run_mouse_session(&mut app);
+        assert_eq!(
+            app.widget_map[&selected_widget].widget_type,
+            BottomWidgetType::Temp
+        );
+        assert_eq!(selected_row, 0);
+    }
+
+    #[test]
+    fn test_scroll_only_reaches_widgets_that_accept_it() {
+        use crate::app::{
+            data_harvester::processes::ProcessHarvest, layout_manager::BottomWidgetType,
+        };
+
+        let mut app = test_app(&["btm"]);
+        let widget_id = |app: &App, widget_type: BottomWidgetType| {
+            app.widget_map
+                .values()
+                .find(|widget| widget.widget_type == widget_type)
+                .unwrap()
+                .widget_id
+        };
+        let scroll_down = MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        // Scrolling over a graph zooms out.
+        let cpu = widget_id(&app, BottomWidgetType::Cpu);
+        app.focus_widget(cpu);
+        let display_time = app.states.cpu_state.widget_states[&cpu].current_display_time;
+        handle_mouse_event(scroll_down, &mut app);
+        assert!(app.states.cpu_state.widget_states[&cpu].current_display_time > display_time);
+
+        // Scrolling over a table moves the selection.
+        let proc = widget_id(&app, BottomWidgetType::Proc);
+        app.data_collection.process_data.process_harvest = (1..=3)
+            .map(|pid| {
+                let process = ProcessHarvest {
+                    pid,
+                    name: format!("process-{pid}"),
+                    ..Default::default()
+                };
+                (pid, process)
+            })
+            .collect();
+        let proc_state = app.states.proc_state.get_mut_widget_state(proc).unwrap();
+        proc_state.ingest_data(&app.data_collection);
+        app.focus_widget(proc);
+        handle_mouse_event(scroll_down, &mut app);
+        let current_index = |app: &App| {
+            app.states.proc_state.widget_states[&proc]
+                .table
+                .current_index()
+        };
+        assert_eq!(current_index(&app), 1);
+
+        // The search bar doesn't take scrolling, so the table below it stays where it was.
+        app.on_slash();
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::ProcSearch);
+        assert!(!app.current_widget.widget_type.accepts_scroll());
+        handle_mouse_event(scroll_down, &mut app);
+        assert_eq!(current_index(&app), 1);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::ProcSearch);
+    }
+
+    #[test]
+    fn test_latest_data_skips_backlog() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let latest_data = LatestData::default();
+
+        // A collector that is much faster than the main thread.
+        let newest = {
+            let sender = DataSender::new(sender.clone(), latest_data.clone());
+            thread::spawn(move || {
+                let mut newest = Instant::now();
+                for _ in 0..100 {
+                    let data = data_harvester::Data::default();
+                    newest = data.collection_time;
+                    sender.send(Box::new(data)).unwrap();
+                }
+                newest
+            })
+            .join()
+            .unwrap()
+        };
+        sender
+            .send(BottomEvent::KeyInput(key(
+                KeyCode::Char('q'),
+                KeyEventKind::Press,
+            )))
+            .unwrap();
+
+        // Only one update is waiting ahead of the key press, and it has the newest data.
+        assert!(matches!(receiver.try_recv(), Ok(BottomEvent::Update)));
+        assert!(matches!(receiver.try_recv(), Ok(BottomEvent::KeyInput(_))));
+        assert_eq!(latest_data.take().unwrap().collection_time, newest);
+        assert!(latest_data.take().is_none());
+    }
+
+    #[test]
+    fn test_latest_data_keeps_process_changes() {
+        use data_harvester::processes::{ProcessDiffer, ProcessHarvest, ProcessUpdate};
+
+        let process = |pid, cpu_usage_percent| ProcessHarvest {
+            pid,
+            cpu_usage_percent,
+            ..Default::default()
+        };
+        let latest_data = LatestData::default();
+        let mut differ = ProcessDiffer::default();
+        differ.update(vec![process(1, 0.0), process(2, 0.0)]);
+
+        // Neither change is lost if the first is replaced before being taken, even by data
+        // without any processes.
+        for processes in [
+            vec![process(1, 5.0), process(2, 0.0)],
+            vec![process(1, 5.0)],
+        ] {
+            latest_data.put(Box::new(data_harvester::Data {
+                processes: Some(differ.update(processes)),
+                ..Default::default()
+            }));
+        }
+        latest_data.put(Box::default());
+
+        let Some(ProcessUpdate::Diff(diff)) = latest_data.take().unwrap().processes else {
+            panic!("expected a diff");
+        };
+        assert_eq!(
+            diff.changed.into_values().collect::<Vec<_>>(),
+            [process(1, 5.0)]
+        );
+        assert_eq!(diff.removed, [2]);
+    }
+
+    #[test]
+    fn test_slow_collection_does_not_block_input() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let (reset_sender, _reset_receiver) = std::sync::mpsc::channel();
+        let (release, stuck) = std::sync::mpsc::channel::<()>();
+        let latest_data = LatestData::default();
+        let mut app = test_app(&["btm"]);
+
+        // A collector stuck partway through collecting, e.g. on a stale mount.
+        let collector = {
+            let sender = DataSender::new(sender.clone(), latest_data.clone());
+            thread::spawn(move || {
+                stuck.recv().unwrap();
+                sender.send(Box::default()).unwrap();
+            })
+        };
+
+        // Quitting is handled without waiting on the collector.
+        sender
+            .send(BottomEvent::KeyInput(key(
+                KeyCode::Char('q'),
+                KeyEventKind::Press,
+            )))
+            .unwrap();
+        let Ok(BottomEvent::KeyInput(event)) = receiver.try_recv() else {
+            panic!("expected the key press");
+        };
+        assert!(handle_key_event_or_break(event, &mut app, &reset_sender));
+        assert!(!collector.is_finished());
+
+        // Shutting down only waits on it for so long.
+        let collector = join_with_timeout(collector, Duration::from_millis(50)).unwrap_err();
+
+        release.send(()).unwrap();
+        join_with_timeout(collector, Duration::from_secs(60))
+            .unwrap()
+            .unwrap();
+        assert!(matches!(receiver.try_recv(), Ok(BottomEvent::Update)));
+        assert!(latest_data.take().is_some());
+    }
+
+    #[test]
+    fn test_termination_before_wait_is_not_missed() {
+        let termination_lock = Mutex::new(false);
+        let termination_cvar = Condvar::new();
+        let timeout = Duration::from_secs(60);
+        assert!(!is_terminated(&termination_lock));
+
+        // Nothing is waiting yet when this is signalled, so the notification itself is lost.
+        *termination_lock.lock().unwrap() = true;
+        termination_cvar.notify_all();
+
+        let (_control_sender, control_receiver) = std::sync::mpsc::channel();
+        let start = Instant::now();
+        assert!(wait_for_termination(
+            &termination_lock,
+            &termination_cvar,
+            timeout,
+            &control_receiver,
+            &mut Vec::new(),
+        ));
+        assert!(start.elapsed() < timeout);
+        assert!(is_terminated(&termination_lock));
+    }
+
+    #[test]
+    fn test_control_messages_end_the_wait() {
+        let termination_lock = Arc::new(Mutex::new(false));
+        let termination_cvar = Arc::new(Condvar::new());
+        let timeout = Duration::from_secs(60);
+        let (control_sender, control_receiver) = std::sync::mpsc::channel();
+        let mut pending = Vec::new();
+
+        // Messages sent before waiting are all picked up, without waiting at all.
+        control_sender.send(CollectionThreadEvent::Reset).unwrap();
+        control_sender
+            .send(CollectionThreadEvent::UpdateRate(1000))
+            .unwrap();
+        let start = Instant::now();
+        assert!(!wait_for_termination(
+            &termination_lock,
+            &termination_cvar,
+            timeout,
+            &control_receiver,
+            &mut pending,
+        ));
+        assert!(start.elapsed() < timeout);
+        assert!(matches!(
+            pending[..],
+            [
+                CollectionThreadEvent::Reset,
+                CollectionThreadEvent::UpdateRate(1000)
+            ]
+        ));
+
+        // A message sent while waiting wakes it up.
+        pending.clear();
+        let waker = {
+            let termination_lock = termination_lock.clone();
+            let termination_cvar = termination_cvar.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                control_sender
+                    .send(CollectionThreadEvent::UpdateRate(2000))
+                    .unwrap();
+                wake_collection_thread(&termination_lock, &termination_cvar);
+            })
+        };
+        assert!(!wait_for_termination(
+            &termination_lock,
+            &termination_cvar,
+            timeout,
+            &control_receiver,
+            &mut pending,
+        ));
+        assert!(start.elapsed() < timeout);
+        assert!(matches!(
+            pending[..],
+            [CollectionThreadEvent::UpdateRate(2000)]
+        ));
+        waker.join().unwrap();
+    }
+}
@@ -16,16 +16,21 @@ use serde::{Deserialize, Serialize};
 use starship_battery::Manager;
 
 use crate::{
-    app::{filter::Filter, layout_manager::*, *},
+    app::{data_budget::parse_bytes, filter::Filter, layout_manager::*, *},
     canvas::{canvas_styling::CanvasStyling, ColourScheme},
     constants::*,
     utils::{
         data_units::DataUnit,
         error::{self, BottomError},
+        units::{ByteStandard, NumberFormat},
     },
     widgets::*,
 };
 
+pub mod config_watcher;
+
+pub mod config_writer;
+
 pub mod layout_options;
 
 pub mod process_columns;
@@ -38,6 +43,7 @@ pub struct Config {
     pub flags: Option<ConfigFlags>,
     pub colors: Option<ConfigColours>,
     pub row: Option<Vec<Row>>,
+    pub layouts: Option<Vec<LayoutProfile>>,
     pub disk_filter: Option<IgnoreList>,
     pub mount_filter: Option<IgnoreList>,
     pub temp_filter: Option<IgnoreList>,
@@ -45,9 +51,9 @@ pub struct Config {
     pub processes: Option<ProcessConfig>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(untagged)]
-enum StringOrNum {
+pub enum StringOrNum {
     String(String),
     Num(u64),
 }
@@ -79,7 +85,11 @@ pub struct ConfigFlags {
     regex: Option<bool>,
     basic: Option<bool>,
     default_time_value: Option<StringOrNum>,
+    min_time_value: Option<StringOrNum>,
     time_delta: Option<StringOrNum>,
+    zoom_all_graphs: Option<bool>,
+    layout: Option<String>,
+    config_save_dry_run: Option<bool>,
     autohide_time: Option<bool>,
     hide_time: Option<bool>,
     default_widget_type: Option<String>,
@@ -89,25 +99,42 @@ pub struct ConfigFlags {
     hide_table_gap: Option<bool>,
     battery: Option<bool>,
     disable_click: Option<bool>,
+    debug_timings: Option<bool>,
     no_write: Option<bool>,
     /// For built-in colour palettes.
     color: Option<String>,
     mem_as_value: Option<bool>,
     tree: Option<bool>,
     show_table_scroll_position: Option<bool>,
+    scroll_step: Option<u64>,
+    min_terminal_size: Option<String>,
+    wrap_navigation: Option<bool>,
+    keep_filter_on_close: Option<bool>,
+    restore_scroll_after_search: Option<bool>,
+    unicode_symbols: Option<bool>,
+    uppercase_headers: Option<bool>,
+    dim_unfocused_tables: Option<bool>,
+    no_color: Option<bool>,
+    status_bar: Option<bool>,
     process_command: Option<bool>,
     disable_advanced_kill: Option<bool>,
     network_use_bytes: Option<bool>,
     network_use_log: Option<bool>,
     network_use_binary_prefix: Option<bool>,
+    decimal_bytes: Option<bool>,
+    thousands_separator: Option<bool>,
+    grouping_separator: Option<char>,
+    decimal_separator: Option<char>,
     enable_gpu_memory: Option<bool>,
     enable_cache_memory: Option<bool>,
     retention: Option<StringOrNum>,
+    max_data_memory: Option<StringOrNum>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct ConfigColours {
     pub table_header_color: Option<Cow<'static, str>>,
+    pub table_bg_color: Option<Cow<'static, str>>,
     pub all_cpu_color: Option<Cow<'static, str>>,
     pub avg_cpu_color: Option<Cow<'static, str>>,
     pub cpu_core_colors: Option<Vec<Cow<'static, str>>>,
@@ -127,22 +154,14 @@ pub struct ConfigColours {
     pub text_color: Option<Cow<'static, str>>,
     pub selected_text_color: Option<Cow<'static, str>>,
     pub selected_bg_color: Option<Cow<'static, str>>,
+    pub click_flash_color: Option<Cow<'static, str>>,
     pub widget_title_color: Option<Cow<'static, str>>,
     pub graph_color: Option<Cow<'static, str>>,
     pub high_battery_color: Option<Cow<'static, str>>,
     pub medium_battery_color: Option<Cow<'static, str>>,
     pub low_battery_color: Option<Cow<'static, str>>,
-}
-
-impl ConfigColours {
-    /// Returns `true` if there is a [`ConfigColours`] that is empty or there isn't one at all.
-    pub fn is_empty(&self) -> bool {
-        if let Ok(serialized_string) = toml_edit::ser::to_string(self) {
-            return serialized_string.is_empty();
-        }
-
-        true
-    }
+    pub increase_color: Option<Cow<'static, str>>,
+    pub decrease_color: Option<Cow<'static, str>>,
 }
 
 /// Workaround as per https://github.com/serde-rs/serde/issues/1030
@@ -197,13 +216,10 @@ pub fn build_app(
     let matches = &matches;
     let config = &config;
 
-    let retention_ms =
-        get_retention(matches, config).context("Update `retention` in your config file.")?;
-    let autohide_time = is_flag_enabled!(autohide_time, matches, config);
-    let default_time_value = get_default_time_value(matches, config, retention_ms)
-        .context("Update 'default_time_value' in your config file.")?;
-
-    let use_basic_mode = is_flag_enabled!(basic, matches, config);
+    let app_config_fields = get_app_config_fields(matches, config)?;
+    let autohide_time = app_config_fields.autohide_time;
+    let default_time_value = app_config_fields.default_time_value;
+    let use_basic_mode = app_config_fields.use_basic_mode;
     let expanded_upon_startup = is_flag_enabled!(expanded_on_startup, matches, config);
 
     // For processes
@@ -235,11 +251,6 @@ pub fn build_app(
     let show_memory_as_values = is_flag_enabled!(mem_as_value, matches, config);
     let is_default_tree = is_flag_enabled!(tree, matches, config);
     let is_default_command = is_flag_enabled!(process_command, matches, config);
-    let is_advanced_kill = !(is_flag_enabled!(disable_advanced_kill, matches, config));
-
-    let network_unit_type = get_network_unit_type(matches, config);
-    let network_scale_type = get_network_scale_type(matches, config);
-    let network_use_binary_prefix = is_flag_enabled!(network_use_binary_prefix, matches, config);
 
     let proc_columns: Option<IndexSet<ProcWidgetColumn>> = {
         let columns = config
@@ -259,35 +270,6 @@ pub fn build_app(
         }
     };
 
-    let app_config_fields = AppConfigFields {
-        update_rate: get_update_rate(matches, config)
-            .context("Update 'rate' in your config file.")?,
-        temperature_type: get_temperature(matches, config)
-            .context("Update 'temperature_type' in your config file.")?,
-        show_average_cpu: get_show_average_cpu(matches, config),
-        use_dot: is_flag_enabled!(dot_marker, matches, config),
-        left_legend: is_flag_enabled!(left_legend, matches, config),
-        use_current_cpu_total: is_flag_enabled!(current_usage, matches, config),
-        unnormalized_cpu: is_flag_enabled!(unnormalized_cpu, matches, config),
-        use_basic_mode,
-        default_time_value,
-        time_interval: get_time_interval(matches, config, retention_ms)
-            .context("Update 'time_delta' in your config file.")?,
-        hide_time: is_flag_enabled!(hide_time, matches, config),
-        autohide_time,
-        use_old_network_legend: is_flag_enabled!(use_old_network_legend, matches, config),
-        table_gap: u16::from(!(is_flag_enabled!(hide_table_gap, matches, config))),
-        disable_click: is_flag_enabled!(disable_click, matches, config),
-        enable_gpu_memory: get_enable_gpu_memory(matches, config),
-        enable_cache_memory: get_enable_cache_memory(matches, config),
-        show_table_scroll_position: is_flag_enabled!(show_table_scroll_position, matches, config),
-        is_advanced_kill,
-        network_scale_type,
-        network_unit_type,
-        network_use_binary_prefix,
-        retention_ms,
-    };
-
     let table_config = ProcTableConfig {
         is_case_sensitive,
         is_match_whole_word,
@@ -296,6 +278,8 @@ pub fn build_app(
         is_command: is_default_command,
     };
 
+    let mut config_warnings = Vec::new();
+
     for row in &widget_layout.rows {
         for col in &row.children {
             for col_row in &col.children {
@@ -369,28 +353,85 @@ pub fn build_app(
                                 ProcWidgetMode::Normal
                             };
 
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidgetState::new(
-                                    &app_config_fields,
-                                    mode,
-                                    table_config,
-                                    styling,
-                                    &proc_columns,
-                                ),
+                            // The widget's own list of columns wins over the one for every
+                            // process widget.
+                            let mut unknown = Vec::new();
+                            let widget_columns: IndexSet<ProcWidgetColumn> = widget
+                                .columns
+                                .iter()
+                                .filter_map(|name| {
+                                    let column = ProcWidgetColumn::from_name(name);
+                                    if column.is_none() {
+                                        unknown.push(name.as_str());
+                                    }
+                                    column
+                                })
+                                .collect();
+                            config_warnings.extend(unknown_columns_warning(
+                                "columns",
+                                &unknown,
+                                &ProcWidgetColumn::ALL.map(|column| column.name().to_string()),
+                            ));
+                            let widget_columns = if widget_columns.is_empty() {
+                                proc_columns.clone()
+                            } else {
+                                Some(widget_columns)
+                            };
+
+                            let mut proc_state = ProcWidgetState::new(
+                                &app_config_fields,
+                                mode,
+                                table_config,
+                                styling,
+                                &widget_columns,
                             );
+                            if !widget.hidden_columns.is_empty() {
+                                let unknown =
+                                    proc_state.hide_columns_by_name(&widget.hidden_columns);
+                                config_warnings.extend(unknown_columns_warning(
+                                    "hidden_columns",
+                                    &unknown,
+                                    &proc_state.table.column_names(),
+                                ));
+                            }
+                            proc_state.table.set_paged(widget.paged);
+                            proc_state_map.insert(widget.widget_id, proc_state);
                         }
                         Disk => {
-                            disk_state_map.insert(
-                                widget.widget_id,
-                                DiskTableWidget::new(&app_config_fields, styling),
-                            );
+                            let mut disk_state = DiskTableWidget::new(&app_config_fields, styling);
+                            if !widget.columns.is_empty() {
+                                let valid = disk_state.table.column_names();
+                                let unknown = disk_state.table.set_columns_by_name(&widget.columns);
+                                config_warnings
+                                    .extend(unknown_columns_warning("columns", &unknown, &valid));
+                            }
+                            let unknown = disk_state.table.hide_columns(&widget.hidden_columns);
+                            disk_state.table.sort_by_visible_column();
+                            config_warnings.extend(unknown_columns_warning(
+                                "hidden_columns",
+                                &unknown,
+                                &disk_state.table.column_names(),
+                            ));
+                            disk_state.table.set_paged(widget.paged);
+                            disk_state_map.insert(widget.widget_id, disk_state);
                         }
                         Temp => {
-                            temp_state_map.insert(
-                                widget.widget_id,
-                                TempWidgetState::new(&app_config_fields, styling),
-                            );
+                            let mut temp_state = TempWidgetState::new(&app_config_fields, styling);
+                            if !widget.columns.is_empty() {
+                                let valid = temp_state.table.column_names();
+                                let unknown = temp_state.table.set_columns_by_name(&widget.columns);
+                                config_warnings
+                                    .extend(unknown_columns_warning("columns", &unknown, &valid));
+                            }
+                            let unknown = temp_state.table.hide_columns(&widget.hidden_columns);
+                            temp_state.table.sort_by_visible_column();
+                            config_warnings.extend(unknown_columns_warning(
+                                "hidden_columns",
+                                &unknown,
+                                &temp_state.table.column_names(),
+                            ));
+                            temp_state.table.set_paged(widget.paged);
+                            temp_state_map.insert(widget.widget_id, temp_state);
                         }
                         Battery => {
                             battery_state_map
@@ -403,6 +444,35 @@ pub fn build_app(
         }
     }
 
+    // If the layout doesn't have the widget asked for at startup, say so rather than silently
+    // focusing something else, and fall back to the first widget.
+    if !use_basic_mode {
+        if let (Some(default_widget_type), default_widget_count) =
+            get_default_widget_and_count(matches, config)?
+        {
+            let available = widget_map
+                .values()
+                .filter(|widget| widget.widget_type == default_widget_type)
+                .count() as u64;
+
+            if available < default_widget_count {
+                config_warnings.push(format!(
+                    "Couldn't focus {} widget #{default_widget_count} at startup, as the layout only has {available}",
+                    default_widget_type.get_pretty_name()
+                ));
+
+                if let Some(first_widget_id) = widget_map
+                    .values()
+                    .filter(|widget| widget.widget_type != Empty)
+                    .map(|widget| widget.widget_id)
+                    .min()
+                {
+                    initial_widget_id = first_widget_id;
+                }
+            }
+        }
+    }
+
     let basic_table_widget_state = if use_basic_mode {
         Some(match initial_widget_type {
             Proc | Disk | Temp => BasicTableWidgetState {
@@ -468,7 +538,10 @@ pub fn build_app(
         temp_filter,
         net_filter,
     };
-    let is_expanded = expanded_upon_startup && !use_basic_mode;
+    let is_expanded = (expanded_upon_startup
+        || widget_map.values().any(|widget| widget.expanded_on_startup))
+        && !use_basic_mode;
+    let colour_scheme = get_color_scheme(matches, config)?;
 
     Ok(App::new(
         app_config_fields,
@@ -478,7 +551,119 @@ pub fn build_app(
         used_widgets,
         filters,
         is_expanded,
-    ))
+    )
+    .with_colour_scheme(colour_scheme, config.colors.clone())
+    .with_layout_profiles(
+        get_layout_profile_names(config),
+        get_layout_profile(matches, config)
+            .as_deref()
+            .unwrap_or(DEFAULT_LAYOUT_PROFILE),
+    )
+    .with_config_warnings(config_warnings))
+}
+
+/// Describes the names in a widget's `hidden_columns` or `columns`, given by `key`, that didn't
+/// match any of its columns, if there were any. These aren't an error, so a typo doesn't stop
+/// bottom from starting.
+fn unknown_columns_warning(key: &str, unknown: &[&str], valid: &[String]) -> Option<String> {
+    if unknown.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Unknown {key} {}; valid columns are {}",
+            unknown.join(", "),
+            valid.join(", ")
+        ))
+    }
+}
+
+/// Gets the settings stored in [`AppConfigFields`], with command-line arguments taking priority
+/// over the config.
+pub fn get_app_config_fields(matches: &ArgMatches, config: &Config) -> Result<AppConfigFields> {
+    let retention_ms =
+        get_retention(matches, config).context("Update `retention` in your config file.")?;
+    let min_time_value = get_min_time_value(matches, config, retention_ms)
+        .context("Update 'min_time_value' in your config file.")?;
+    let default_time_value = get_default_time_value(matches, config, min_time_value, retention_ms)
+        .context("Update 'default_time_value' in your config file.")?;
+
+    Ok(AppConfigFields {
+        update_rate: get_update_rate(matches, config)
+            .context("Update 'rate' in your config file.")?,
+        temperature_type: get_temperature(matches, config)
+            .context("Update 'temperature_type' in your config file.")?,
+        show_average_cpu: get_show_average_cpu(matches, config),
+        use_dot: is_flag_enabled!(dot_marker, matches, config),
+        left_legend: is_flag_enabled!(left_legend, matches, config),
+        use_current_cpu_total: is_flag_enabled!(current_usage, matches, config),
+        unnormalized_cpu: is_flag_enabled!(unnormalized_cpu, matches, config),
+        use_basic_mode: is_flag_enabled!(basic, matches, config),
+        default_time_value,
+        min_time_value,
+        time_interval: get_time_interval(matches, config, retention_ms)
+            .context("Update 'time_delta' in your config file.")?,
+        zoom_all_graphs: is_flag_enabled!(zoom_all_graphs, matches, config),
+        config_save_dry_run: is_flag_enabled!(config_save_dry_run, matches, config),
+        hide_time: is_flag_enabled!(hide_time, matches, config),
+        autohide_time: is_flag_enabled!(autohide_time, matches, config),
+        use_old_network_legend: is_flag_enabled!(use_old_network_legend, matches, config),
+        // Basic mode is meant to be compact, so it never has a gap under table headers.
+        table_gap: u16::from(
+            !(is_flag_enabled!(hide_table_gap, matches, config)
+                || is_flag_enabled!(basic, matches, config)),
+        ),
+        disable_click: is_flag_enabled!(disable_click, matches, config),
+        debug_timings: is_flag_enabled!(debug_timings, matches, config),
+        enable_gpu_memory: get_enable_gpu_memory(matches, config),
+        enable_cache_memory: get_enable_cache_memory(matches, config),
+        show_table_scroll_position: is_flag_enabled!(show_table_scroll_position, matches, config),
+        scroll_step: get_scroll_step(matches, config)
+            .context("Update 'scroll_step' in your config file.")?,
+        min_terminal_size: get_min_terminal_size(matches, config)
+            .context("Update 'min_terminal_size' in your config file.")?,
+        wrap_navigation: is_flag_enabled!(wrap_navigation, matches, config),
+        keep_filter_on_close: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.keep_filter_on_close)
+            .unwrap_or(true),
+        restore_scroll_after_search: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.restore_scroll_after_search)
+            .unwrap_or(false),
+        unicode_symbols: get_unicode_symbols(config),
+        uppercase_headers: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.uppercase_headers)
+            .unwrap_or(false),
+        dim_unfocused_tables: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.dim_unfocused_tables)
+            .unwrap_or(false),
+        no_color: get_no_color(config),
+        status_bar: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.status_bar)
+            .unwrap_or(true),
+        is_advanced_kill: !(is_flag_enabled!(disable_advanced_kill, matches, config)),
+        network_scale_type: get_network_scale_type(matches, config),
+        network_unit_type: get_network_unit_type(matches, config),
+        network_use_binary_prefix: is_flag_enabled!(network_use_binary_prefix, matches, config),
+        byte_standard: if is_flag_enabled!(decimal_bytes, matches, config) {
+            ByteStandard::Decimal
+        } else {
+            ByteStandard::Binary
+        },
+        number_format: get_number_format(config)
+            .context("Update 'grouping_separator' or 'decimal_separator' in your config file.")?,
+        retention_ms,
+        max_data_memory: get_max_data_memory(matches, config)
+            .context("Update 'max_data_memory' in your config file.")?,
+    })
 }
 
 pub fn get_widget_layout(
@@ -531,6 +716,32 @@ pub fn get_widget_layout(
             total_row_height_ratio: total_height_ratio,
         };
 
+        // The widget to start expanded is also focused, as only the focused widget is shown while
+        // expanded. This wins over a layout widget set with `default = true`, but not over a
+        // default widget type given as a flag, which is expanded instead.
+        let expanded_widget_ids: Vec<u64> = ret_bottom_layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+            .filter(|widget| widget.expanded_on_startup)
+            .map(|widget| widget.widget_id)
+            .collect();
+        match expanded_widget_ids[..] {
+            [] => {}
+            [widget_id] => {
+                if default_widget_type.is_none() {
+                    default_widget_id = widget_id;
+                }
+            }
+            _ => {
+                return Err(error::BottomError::ConfigError(
+                    "only one widget in the layout can set expanded_on_startup.".to_string(),
+                ));
+            }
+        }
+
         // Confirm that we have at least ONE widget left - if not, error out!
         if iter_id > 0 {
             ret_bottom_layout.get_movement_mappings();
@@ -563,13 +774,61 @@ fn get_update_rate(matches: &ArgMatches, config: &Config) -> error::Result<u64>
         DEFAULT_REFRESH_RATE_IN_MILLISECONDS
     };
 
-    if update_rate < 250 {
+    if update_rate < MIN_REFRESH_RATE_IN_MILLISECONDS {
+        return Err(BottomError::ConfigError(format!(
+            "set your update rate to be at least {MIN_REFRESH_RATE_IN_MILLISECONDS} ms."
+        )));
+    }
+
+    if update_rate > MAX_REFRESH_RATE_IN_MILLISECONDS {
+        return Err(BottomError::ConfigError(format!(
+            "set your update rate to be at most {MAX_REFRESH_RATE_IN_MILLISECONDS} ms."
+        )));
+    }
+
+    Ok(update_rate)
+}
+
+fn get_scroll_step(matches: &ArgMatches, config: &Config) -> error::Result<u64> {
+    let scroll_step = if let Some(scroll_step) = matches.get_one::<String>("scroll_step") {
+        scroll_step.parse::<u64>().map_err(|_| {
+            BottomError::ConfigError("could not parse as a valid 64-bit unsigned integer".into())
+        })?
+    } else if let Some(scroll_step) = config.flags.as_ref().and_then(|flags| flags.scroll_step) {
+        scroll_step
+    } else {
+        DEFAULT_SCROLL_STEP
+    };
+
+    if scroll_step == 0 {
         return Err(BottomError::ConfigError(
-            "set your update rate to be at least 250 ms.".to_string(),
+            "set your scroll step to be at least 1.".to_string(),
         ));
     }
 
-    Ok(update_rate)
+    Ok(scroll_step.min(MAX_SCROLL_STEP))
+}
+
+fn get_min_terminal_size(matches: &ArgMatches, config: &Config) -> error::Result<(u16, u16)> {
+    let size = if let Some(size) = matches.get_one::<String>("min_terminal_size") {
+        size.as_str()
+    } else if let Some(size) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.min_terminal_size.as_deref())
+    {
+        size
+    } else {
+        return Ok(DEFAULT_MIN_TERMINAL_SIZE);
+    };
+
+    size.split_once(['x', 'X'])
+        .and_then(|(width, height)| Some((width.trim().parse().ok()?, height.trim().parse().ok()?)))
+        .ok_or_else(|| {
+            BottomError::ConfigError(format!(
+                "could not parse \"{size}\" as a size like \"40x10\"."
+            ))
+        })
 }
 
 fn get_temperature(
@@ -623,8 +882,40 @@ fn try_parse_ms(s: &str) -> error::Result<u64> {
     }
 }
 
-fn get_default_time_value(
+fn get_min_time_value(
     matches: &ArgMatches, config: &Config, retention_ms: u64,
+) -> error::Result<u64> {
+    let min_time = if let Some(min_time_value) = matches.get_one::<String>("min_time_value") {
+        try_parse_ms(min_time_value)?
+    } else if let Some(flags) = &config.flags {
+        if let Some(min_time_value) = &flags.min_time_value {
+            match min_time_value {
+                StringOrNum::String(s) => try_parse_ms(s)?,
+                StringOrNum::Num(n) => *n,
+            }
+        } else {
+            STALE_MIN_MILLISECONDS
+        }
+    } else {
+        STALE_MIN_MILLISECONDS
+    };
+
+    if min_time < 1000 {
+        return Err(BottomError::ConfigError(
+            "set your minimum time value to be at least 1s.".to_string(),
+        ));
+    } else if min_time > retention_ms {
+        return Err(BottomError::ConfigError(format!(
+            "set your minimum time value to be at most {}.",
+            humantime::Duration::from(Duration::from_millis(retention_ms))
+        )));
+    }
+
+    Ok(min_time)
+}
+
+fn get_default_time_value(
+    matches: &ArgMatches, config: &Config, min_time_value: u64, retention_ms: u64,
 ) -> error::Result<u64> {
     let default_time =
         if let Some(default_time_value) = matches.get_one::<String>("default_time_value") {
@@ -642,10 +933,11 @@ fn get_default_time_value(
             DEFAULT_TIME_MILLISECONDS
         };
 
-    if default_time < 30000 {
-        return Err(BottomError::ConfigError(
-            "set your default value to be at least 30s.".to_string(),
-        ));
+    if default_time < min_time_value {
+        return Err(BottomError::ConfigError(format!(
+            "set your default value to be at least {}.",
+            humantime::Duration::from(Duration::from_millis(min_time_value))
+        )));
     } else if default_time > retention_ms {
         return Err(BottomError::ConfigError(format!(
             "set your default value to be at most {}.",
@@ -658,20 +950,16 @@ fn get_default_time_value(
 
 fn get_time_interval(
     matches: &ArgMatches, config: &Config, retention_ms: u64,
-) -> error::Result<u64> {
+) -> error::Result<Option<u64>> {
     let time_interval = if let Some(time_interval) = matches.get_one::<String>("time_delta") {
         try_parse_ms(time_interval)?
-    } else if let Some(flags) = &config.flags {
-        if let Some(time_interval) = &flags.time_delta {
-            match time_interval {
-                StringOrNum::String(s) => try_parse_ms(s)?,
-                StringOrNum::Num(n) => *n,
-            }
-        } else {
-            TIME_CHANGE_MILLISECONDS
+    } else if let Some(time_interval) = config.flags.as_ref().and_then(|f| f.time_delta.as_ref()) {
+        match time_interval {
+            StringOrNum::String(s) => try_parse_ms(s)?,
+            StringOrNum::Num(n) => *n,
         }
     } else {
-        TIME_CHANGE_MILLISECONDS
+        return Ok(None);
     };
 
     if time_interval < 1000 {
@@ -685,7 +973,101 @@ fn get_time_interval(
         )));
     }
 
-    Ok(time_interval)
+    Ok(Some(time_interval))
+}
+
+/// Returns how to write numbers, from `thousands_separator` and the separator characters. The
+/// grouping character defaults to a comma, or to a period if commas are used for decimals.
+fn get_number_format(config: &Config) -> error::Result<NumberFormat> {
+    let Some(flags) = &config.flags else {
+        return Ok(NumberFormat::default());
+    };
+
+    let decimal = flags.decimal_separator.unwrap_or('.');
+    let grouping = flags.thousands_separator.unwrap_or(false).then(|| {
+        flags
+            .grouping_separator
+            .unwrap_or(if decimal == ',' { '.' } else { ',' })
+    });
+
+    if decimal.is_ascii_digit() || matches!(grouping, Some(grouping) if grouping.is_ascii_digit()) {
+        return Err(BottomError::ConfigError(
+            "number separators can't be digits.".to_string(),
+        ));
+    }
+    if grouping == Some(decimal) {
+        return Err(BottomError::ConfigError(format!(
+            "the grouping and decimal separators must differ, but both are '{decimal}'."
+        )));
+    }
+
+    Ok(NumberFormat { grouping, decimal })
+}
+
+/// Returns whether decorative glyphs, like sort arrows and borders, may use non-ASCII symbols.
+pub fn get_unicode_symbols(config: &Config) -> bool {
+    config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.unicode_symbols)
+        .unwrap_or(true)
+}
+
+/// Returns whether to draw without colours, either because the config says so or because the
+/// `NO_COLOR` environment variable is set to something.
+pub fn get_no_color(config: &Config) -> bool {
+    config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.no_color)
+        .unwrap_or_else(|| matches!(std::env::var_os("NO_COLOR"), Some(value) if !value.is_empty()))
+}
+
+/// Returns the name of the layout profile to start with, if one was chosen.
+pub fn get_layout_profile(matches: &ArgMatches, config: &Config) -> Option<String> {
+    if let Some(layout) = matches.get_one::<String>("layout") {
+        Some(layout.clone())
+    } else {
+        config.flags.as_ref().and_then(|flags| flags.layout.clone())
+    }
+}
+
+/// Returns the names of the layouts that can be switched between, starting with
+/// [`DEFAULT_LAYOUT_PROFILE`] for the config's own `row`s (or the built-in layout).
+pub fn get_layout_profile_names(config: &Config) -> Vec<String> {
+    std::iter::once(DEFAULT_LAYOUT_PROFILE.to_string())
+        .chain(
+            config
+                .layouts
+                .iter()
+                .flatten()
+                .map(|profile| profile.name.clone()),
+        )
+        .collect()
+}
+
+/// Returns `config` with its `row`s replaced by those of the layout profile called `name`. If
+/// `name` is [`None`] or [`DEFAULT_LAYOUT_PROFILE`], `config` is returned as it is.
+pub fn apply_layout_profile(mut config: Config, name: Option<&str>) -> error::Result<Config> {
+    match name {
+        None | Some(DEFAULT_LAYOUT_PROFILE) => Ok(config),
+        Some(name) => {
+            let profile = config
+                .layouts
+                .iter()
+                .flatten()
+                .find(|profile| profile.name == name)
+                .ok_or_else(|| {
+                    BottomError::ConfigError(format!(
+                        "there is no layout called \"{name}\"; the layouts are {}.",
+                        get_layout_profile_names(&config).join(", ")
+                    ))
+                })?;
+
+            config.row = profile.row.clone();
+            Ok(config)
+        }
+    }
 }
 
 fn get_default_widget_and_count(
@@ -836,24 +1218,15 @@ pub fn get_color_scheme(matches: &ArgMatches, config: &Config) -> error::Result<
     if let Some(color) = matches.get_one::<String>("color") {
         // Highest priority is always command line flags...
         return ColourScheme::from_str(color);
-    } else if let Some(colors) = &config.colors {
-        if !colors.is_empty() {
-            // Then, give priority to custom colours...
-            return Ok(ColourScheme::Custom);
-        } else if let Some(flags) = &config.flags {
-            // Last priority is config file flags...
-            if let Some(color) = &flags.color {
-                return ColourScheme::from_str(color);
-            }
-        }
     } else if let Some(flags) = &config.flags {
-        // Last priority is config file flags...
+        // Then config file flags...
         if let Some(color) = &flags.color {
             return ColourScheme::from_str(color);
         }
     }
 
-    // And lastly, the final case is just "default".
+    // And lastly, the final case is just "default". Note that any custom colours are applied on
+    // top of whichever scheme is picked.
     Ok(ColourScheme::Default)
 }
 
@@ -904,16 +1277,46 @@ fn get_retention(matches: &ArgMatches, config: &Config) -> error::Result<u64> {
     }
 }
 
+fn get_max_data_memory(matches: &ArgMatches, config: &Config) -> error::Result<Option<u64>> {
+    let max_data_memory =
+        if let Some(max_data_memory) = matches.get_one::<String>("max_data_memory") {
+            StringOrNum::String(max_data_memory.clone())
+        } else if let Some(max_data_memory) = config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.max_data_memory.clone())
+        {
+            max_data_memory
+        } else {
+            return Ok(None);
+        };
+
+    match max_data_memory {
+        StringOrNum::String(s) => parse_bytes(&s).map(Some).ok_or_else(|| {
+            BottomError::ConfigError(
+                "could not parse as a size in bytes, such as \"64MiB\"".to_string(),
+            )
+        }),
+        StringOrNum::Num(n) => Ok(Some(n)),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use clap::ArgMatches;
 
-    use super::{get_color_scheme, get_time_interval, get_widget_layout, Config};
+    use super::{
+        get_app_config_fields, get_color_scheme, get_time_interval, get_widget_layout, Config,
+    };
     use crate::{
-        app::App,
+        app::{layout_manager::BottomWidgetType, App},
         canvas::canvas_styling::CanvasStyling,
+        constants::{
+            DEFAULT_MIN_TERMINAL_SIZE, DEFAULT_SCROLL_STEP, MAX_SCROLL_STEP, STALE_MIN_MILLISECONDS,
+        },
         options::{
-            get_default_time_value, get_retention, get_update_rate, try_parse_ms, ConfigFlags,
+            get_default_time_value, get_max_data_memory, get_min_terminal_size, get_retention,
+            get_scroll_step, get_update_rate, try_parse_ms, ConfigFlags,
         },
     };
 
@@ -948,7 +1351,7 @@ mod test {
 
             assert_eq!(
                 get_time_interval(&matches, &config, 60 * 60 * 1000),
-                Ok(2 * 60 * 1000)
+                Ok(Some(2 * 60 * 1000))
             );
         }
 
@@ -957,7 +1360,7 @@ mod test {
             let matches = app.get_matches_from(default_time_args);
 
             assert_eq!(
-                get_default_time_value(&matches, &config, 60 * 60 * 1000),
+                get_default_time_value(&matches, &config, STALE_MIN_MILLISECONDS, 60 * 60 * 1000),
                 Ok(5 * 60 * 1000)
             );
         }
@@ -975,7 +1378,7 @@ mod test {
 
             assert_eq!(
                 get_time_interval(&matches, &config, 60 * 60 * 1000),
-                Ok(2 * 60 * 1000)
+                Ok(Some(2 * 60 * 1000))
             );
         }
 
@@ -984,7 +1387,7 @@ mod test {
             let matches = app.get_matches_from(default_time_args);
 
             assert_eq!(
-                get_default_time_value(&matches, &config, 60 * 60 * 1000),
+                get_default_time_value(&matches, &config, STALE_MIN_MILLISECONDS, 60 * 60 * 1000),
                 Ok(5 * 60 * 1000)
             );
         }
@@ -1008,11 +1411,11 @@ mod test {
 
         assert_eq!(
             get_time_interval(&matches, &config, 60 * 60 * 1000),
-            Ok(2 * 60 * 1000)
+            Ok(Some(2 * 60 * 1000))
         );
 
         assert_eq!(
-            get_default_time_value(&matches, &config, 60 * 60 * 1000),
+            get_default_time_value(&matches, &config, STALE_MIN_MILLISECONDS, 60 * 60 * 1000),
             Ok(5 * 60 * 1000)
         );
 
@@ -1021,6 +1424,60 @@ mod test {
         assert_eq!(get_retention(&matches, &config), Ok(600000));
     }
 
+    #[test]
+    fn update_rate_bounds() {
+        let app = crate::args::build_app();
+        let config = Config::default();
+        let update_rate = |rate: &str| {
+            let matches = app.clone().get_matches_from(["btm", "--rate", rate]);
+            get_update_rate(&matches, &config)
+        };
+
+        assert_eq!(update_rate("100"), Ok(100));
+        assert!(update_rate("99").is_err());
+        assert_eq!(update_rate("60s"), Ok(60 * 1000));
+        assert!(update_rate("60001").is_err());
+
+        // The config is held to the same bounds.
+        let app = crate::args::build_app();
+        let matches = app.get_matches_from(["btm"]);
+        let config = Config {
+            flags: Some(ConfigFlags {
+                rate: Some(2.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(get_update_rate(&matches, &config)
+            .unwrap_err()
+            .to_string()
+            .contains("at least 100 ms"));
+    }
+
+    #[test]
+    fn max_data_memory() {
+        let app = crate::args::build_app();
+        let matches = app.clone().get_matches_from(["btm"]);
+
+        let mut config = Config::default();
+        assert_eq!(get_max_data_memory(&matches, &config), Ok(None));
+
+        config.flags = Some(ConfigFlags {
+            max_data_memory: Some("64MiB".to_string().into()),
+            ..Default::default()
+        });
+        assert_eq!(get_max_data_memory(&matches, &config), Ok(Some(64 << 20)));
+
+        // The command line takes priority over the config.
+        let matches = app
+            .clone()
+            .get_matches_from(["btm", "--max_data_memory", "1000"]);
+        assert_eq!(get_max_data_memory(&matches, &config), Ok(Some(1000)));
+
+        let matches = app.get_matches_from(["btm", "--max_data_memory", "lots"]);
+        assert!(get_max_data_memory(&matches, &config).is_err());
+    }
+
     #[test]
     fn config_number_times_as_string() {
         let app = crate::args::build_app();
@@ -1039,11 +1496,11 @@ mod test {
 
         assert_eq!(
             get_time_interval(&matches, &config, 60 * 60 * 1000),
-            Ok(2 * 60 * 1000)
+            Ok(Some(2 * 60 * 1000))
         );
 
         assert_eq!(
-            get_default_time_value(&matches, &config, 60 * 60 * 1000),
+            get_default_time_value(&matches, &config, STALE_MIN_MILLISECONDS, 60 * 60 * 1000),
             Ok(5 * 60 * 1000)
         );
 
@@ -1070,11 +1527,11 @@ mod test {
 
         assert_eq!(
             get_time_interval(&matches, &config, 60 * 60 * 1000),
-            Ok(2 * 60 * 1000)
+            Ok(Some(2 * 60 * 1000))
         );
 
         assert_eq!(
-            get_default_time_value(&matches, &config, 60 * 60 * 1000),
+            get_default_time_value(&matches, &config, STALE_MIN_MILLISECONDS, 60 * 60 * 1000),
             Ok(5 * 60 * 1000)
         );
 
@@ -1083,6 +1540,399 @@ mod test {
         assert_eq!(get_retention(&matches, &config), Ok(600000));
     }
 
+    #[test]
+    fn scroll_step() {
+        let app = crate::args::build_app();
+        let mut config = Config::default();
+
+        let matches = app.clone().get_matches_from(["btm"]);
+        assert_eq!(get_scroll_step(&matches, &config), Ok(DEFAULT_SCROLL_STEP));
+
+        config.flags = Some(ConfigFlags {
+            scroll_step: Some(3),
+            ..Default::default()
+        });
+        assert_eq!(get_scroll_step(&matches, &config), Ok(3));
+
+        // The command-line flag takes priority over the config.
+        let matches = app.clone().get_matches_from(["btm", "--scroll_step", "5"]);
+        assert_eq!(get_scroll_step(&matches, &config), Ok(5));
+
+        let matches = app.clone().get_matches_from(["btm", "--scroll_step", "0"]);
+        assert!(get_scroll_step(&matches, &config).is_err());
+
+        // Larger steps than any table could need are capped.
+        let max = u64::MAX.to_string();
+        let matches = app.get_matches_from(["btm", "--scroll_step", &max]);
+        assert_eq!(get_scroll_step(&matches, &config), Ok(MAX_SCROLL_STEP));
+    }
+
+    #[test]
+    fn min_terminal_size() {
+        let app = crate::args::build_app();
+        let mut config = Config::default();
+
+        let matches = app.clone().get_matches_from(["btm"]);
+        assert_eq!(
+            get_min_terminal_size(&matches, &config),
+            Ok(DEFAULT_MIN_TERMINAL_SIZE)
+        );
+
+        config.flags = Some(ConfigFlags {
+            min_terminal_size: Some("80X24".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(get_min_terminal_size(&matches, &config), Ok((80, 24)));
+
+        let matches = app
+            .clone()
+            .get_matches_from(["btm", "--min_terminal_size", "0x0"]);
+        assert_eq!(get_min_terminal_size(&matches, &config), Ok((0, 0)));
+
+        for invalid in ["80", "80x", "x24", "ax24", "80x24x2"] {
+            let matches = app
+                .clone()
+                .get_matches_from(["btm", "--min_terminal_size", invalid]);
+            assert!(
+                get_min_terminal_size(&matches, &config).is_err(),
+                "{invalid} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_basic_mode_table_gap() {
+        let app = crate::args::build_app();
+        let config = Config::default();
+
+        let matches = app.clone().get_matches_from(["btm"]);
+        let fields = get_app_config_fields(&matches, &config).unwrap();
+        assert_eq!(fields.table_gap, 1);
+
+        let matches = app.get_matches_from(["btm", "--basic"]);
+        let fields = get_app_config_fields(&matches, &config).unwrap();
+        assert!(fields.use_basic_mode);
+        assert_eq!(fields.table_gap, 0);
+    }
+
+    #[test]
+    fn test_hidden_columns() {
+        use crate::components::data_table::DataTableColumn;
+
+        let layout = r#"
+            [[row]]
+              [[row.child]]
+                type = "proc"
+                hidden_columns = ["User", "state"]
+              [[row.child]]
+                type = "disk"
+                hidden_columns = ["Mount", "Size"]
+              [[row.child]]
+                type = "temp"
+                hidden_columns = ["sensor"]
+            "#;
+        let config: Config = toml_edit::de::from_str(layout).unwrap();
+        let matches = crate::args::build_app().get_matches_from(["btm"]);
+        let app = create_app(config, matches);
+
+        let hidden = |columns: Vec<(String, bool)>| {
+            columns
+                .into_iter()
+                .filter_map(|(name, is_hidden)| is_hidden.then_some(name))
+                .collect::<Vec<_>>()
+        };
+        let proc = app.states.proc_state.widget_states.values().next().unwrap();
+        let proc_columns = proc
+            .table
+            .column_names()
+            .into_iter()
+            .zip(proc.table.columns.iter().map(|column| column.is_hidden()));
+        assert_eq!(hidden(proc_columns.collect()), ["User", "State"]);
+
+        let disk = app.states.disk_state.widget_states.values().next().unwrap();
+        let disk_columns = disk
+            .table
+            .column_names()
+            .into_iter()
+            .zip(disk.table.columns.iter().map(|column| column.is_hidden()));
+        assert_eq!(hidden(disk_columns.collect()), ["Mount"]);
+
+        // Hiding the column a table is sorted by moves the sort to one that is shown.
+        let temp = app.states.temp_state.widget_states.values().next().unwrap();
+        assert!(temp.table.columns[0].is_hidden());
+        assert_eq!(temp.table.sort_index(), 1);
+
+        let warning = app.status_message().unwrap();
+        assert!(warning.starts_with("Unknown hidden_columns Size; valid columns are Disk, Mount"));
+
+        // Saving writes back what is hidden, which drops the unknown name.
+        let mut document: toml_edit::Document = layout.parse().unwrap();
+        super::config_writer::update_document(&mut document, &app.saved_settings());
+        let saved = document.to_string();
+        assert!(saved.contains(r#"hidden_columns = ["User", "state"]"#));
+        assert!(saved.contains(r#"hidden_columns = ["Mount"]"#));
+    }
+
+    #[test]
+    fn test_widget_columns() {
+        use crate::components::data_table::DataTableColumn;
+
+        let layout = r#"
+            [processes]
+            columns = ["PID", "Name", "User"]
+
+            [[row]]
+              [[row.child]]
+                type = "proc"
+                columns = ["cpu%", "Count", "name", "Nope"]
+              [[row.child]]
+                type = "proc"
+              [[row.child]]
+                type = "disk"
+                columns = ["free", "DISK"]
+                hidden_columns = ["Disk"]
+              [[row.child]]
+                type = "temp"
+                columns = ["temp"]
+            "#;
+        let config: Config = toml_edit::de::from_str(layout).unwrap();
+        let matches = crate::args::build_app().get_matches_from(["btm"]);
+        let mut app = create_app(config, matches);
+
+        // The widget's own list wins over the one for every process widget.
+        let mut procs: Vec<_> = app.states.proc_state.widget_states.iter().collect();
+        procs.sort_by_key(|(id, _)| **id);
+        assert_eq!(procs[0].1.table.column_names(), ["CPU%", "PID", "Name"]);
+        assert_eq!(procs[1].1.table.column_names(), ["PID", "Name", "User"]);
+
+        let warning = app.status_message().unwrap();
+        assert!(warning.starts_with("Unknown columns Nope; valid columns are PID, Name, CPU%"));
+
+        let disk = app
+            .states
+            .disk_state
+            .widget_states
+            .values_mut()
+            .next()
+            .unwrap();
+        assert_eq!(disk.table.column_names(), ["Free", "Disk"]);
+        assert!(disk.table.columns[1].is_hidden());
+
+        // Columns are still found by their kind after being moved around, and however they're
+        // shown.
+        disk.toggle_free_percentage();
+        disk.sort_by_column("Free");
+        assert_eq!(disk.table.sort_index(), 0);
+        assert_eq!(disk.table.hidden_column_names(), ["Disk"]);
+
+        let temp = app.states.temp_state.widget_states.values().next().unwrap();
+        assert_eq!(temp.table.column_names(), ["Temp"]);
+        assert_eq!(temp.table.sort_index(), 0);
+    }
+
+    #[test]
+    fn test_proc_aspect_is_rejected() {
+        let layout = |widget_type: &str| {
+            toml_edit::de::from_str::<Config>(&format!(
+                r#"
+                [[row]]
+                  [[row.child]]
+                    type = "{widget_type}"
+                    aspect = "2:1"
+                "#
+            ))
+            .unwrap()
+        };
+        let matches = crate::args::build_app().get_matches_from(["btm"]);
+
+        assert!(get_widget_layout(&matches, &layout("net")).is_ok());
+        assert!(get_widget_layout(&matches, &layout("proc")).is_err());
+    }
+
+    #[test]
+    fn test_widget_rates() {
+        use crate::app::update_rates::DataSource;
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[row]]
+              [[row.child]]
+                type = "cpu"
+                rate = 100
+              [[row.child]]
+                type = "temp"
+                rate = "5s"
+              [[row.child]]
+                type = "proc"
+                rate = 2000
+              [[row.child]]
+                type = "proc"
+            "#,
+        )
+        .unwrap();
+        let matches = crate::args::build_app().get_matches_from(["btm"]);
+        let app = create_app(config, matches);
+
+        assert_eq!(app.collection_rates.get(DataSource::Cpu), Some(1000));
+        assert_eq!(app.collection_rates.get(DataSource::Temp), Some(5000));
+        assert_eq!(app.collection_rates.get(DataSource::Proc), None);
+    }
+
+    #[test]
+    fn test_expanded_widget() {
+        let layout = |default: &str| {
+            toml_edit::de::from_str::<Config>(&format!(
+                r#"
+                [[row]]
+                  [[row.child]]
+                    type = "cpu"
+                    {default}
+                  [[row.child]]
+                    type = "proc"
+                    expanded_on_startup = true
+                "#
+            ))
+            .unwrap()
+        };
+        let app = crate::args::build_app();
+
+        let expanded = create_app(layout(""), app.clone().get_matches_from(["btm"]));
+        assert!(expanded.is_expanded);
+        assert_eq!(expanded.current_widget.widget_type, BottomWidgetType::Proc);
+
+        let with_default = create_app(
+            layout("default = true"),
+            app.clone().get_matches_from(["btm"]),
+        );
+        assert!(with_default.is_expanded);
+        assert_eq!(
+            with_default.current_widget.widget_type,
+            BottomWidgetType::Proc
+        );
+
+        let with_default_type = create_app(
+            layout("default = true"),
+            app.clone()
+                .get_matches_from(["btm", "--default_widget_type", "cpu"]),
+        );
+        assert!(with_default_type.is_expanded);
+        assert_eq!(
+            with_default_type.current_widget.widget_type,
+            BottomWidgetType::Cpu
+        );
+
+        let twice = layout("expanded_on_startup = true");
+        assert!(get_widget_layout(&app.clone().get_matches_from(["btm"]), &twice).is_err());
+
+        let basic = create_app(layout(""), app.get_matches_from(["btm", "--basic"]));
+        assert!(!basic.is_expanded);
+    }
+
+    #[test]
+    fn test_layout_profiles() {
+        use super::{apply_layout_profile, get_layout_profile, get_layout_profile_names};
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [flags]
+            layout = "tables"
+
+            [[row]]
+              [[row.child]]
+                type = "cpu"
+
+            [[layouts]]
+              name = "tables"
+              [[layouts.row]]
+                [[layouts.row.child]]
+                  type = "proc"
+                [[layouts.row.child]]
+                  type = "disk"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(get_layout_profile_names(&config), ["default", "tables"]);
+
+        let app = crate::args::build_app();
+        let matches = app.clone().get_matches_from(["btm"]);
+        assert_eq!(
+            get_layout_profile(&matches, &config).as_deref(),
+            Some("tables")
+        );
+        let tables = apply_layout_profile(config.clone(), Some("tables")).unwrap();
+        let tables_app = create_app(tables, matches);
+        assert_eq!(tables_app.active_layout_profile, "tables");
+        assert!(tables_app.used_widgets.use_disk);
+        assert!(!tables_app.used_widgets.use_cpu);
+
+        let matches = app.get_matches_from(["btm", "--layout", "default"]);
+        let layout = get_layout_profile(&matches, &config);
+        assert_eq!(layout.as_deref(), Some("default"));
+        let default = apply_layout_profile(config.clone(), layout.as_deref()).unwrap();
+        assert_eq!(default.row, config.row);
+
+        let err = apply_layout_profile(config, Some("missing")).unwrap_err();
+        assert!(err.to_string().contains("default, tables"));
+    }
+
+    #[test]
+    fn test_number_format() {
+        use super::{get_number_format, NumberFormat};
+
+        let format = |flags: &str| {
+            let config: Config = toml_edit::de::from_str(&format!("[flags]\n{flags}")).unwrap();
+            get_number_format(&config)
+        };
+
+        assert_eq!(format("").unwrap(), NumberFormat::default());
+        assert_eq!(
+            format("thousands_separator = true").unwrap(),
+            NumberFormat {
+                grouping: Some(','),
+                decimal: '.'
+            }
+        );
+        assert_eq!(
+            format("thousands_separator = true\ndecimal_separator = \",\"").unwrap(),
+            NumberFormat {
+                grouping: Some('.'),
+                decimal: ','
+            }
+        );
+        assert_eq!(
+            format("grouping_separator = \" \"").unwrap(),
+            NumberFormat::default()
+        );
+        assert!(format("thousands_separator = true\ngrouping_separator = \".\"").is_err());
+        assert!(format("decimal_separator = \"5\"").is_err());
+    }
+
+    #[test]
+    fn test_default_widget() {
+        let app = crate::args::build_app();
+
+        let matches = app
+            .clone()
+            .get_matches_from(["btm", "--default_widget_type", "proc"]);
+        let focused = create_app(Config::default(), matches);
+        assert_eq!(focused.current_widget.widget_type, BottomWidgetType::Proc);
+        assert!(focused.status_message().is_none());
+
+        let matches = app.get_matches_from([
+            "btm",
+            "--default_widget_type",
+            "temp",
+            "--default_widget_count",
+            "5",
+        ]);
+        let fallback = create_app(Config::default(), matches);
+        assert_eq!(fallback.current_widget.widget_type, BottomWidgetType::Cpu);
+        assert_eq!(
+            fallback.status_message(),
+            Some("Couldn't focus Temperature widget #5 at startup, as the layout only has 1")
+        );
+    }
+
     fn create_app(config: Config, matches: ArgMatches) -> App {
         let (layout, id, ty) = get_widget_layout(&matches, &config).unwrap();
         let styling =
@@ -0,0 +1,228 @@
+//! Watching the config file for changes, so they can be applied without restarting.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+
+use super::{
+    apply_layout_profile, build_app,
+    config_writer::{self, SaveOutcome, SavedSettings},
+    get_app_config_fields, get_color_scheme, get_layout_profile, get_layout_profile_names,
+    get_no_color, get_unicode_symbols, get_widget_layout,
+    layout_options::Row,
+    Config, ConfigColours,
+};
+use crate::{
+    app::{layout_manager::BottomLayout, App, AppConfigFields},
+    canvas::{canvas_styling::CanvasStyling, ColourScheme},
+};
+
+/// The settings from a config file that was reloaded.
+pub struct ReloadedConfig {
+    pub app_config_fields: AppConfigFields,
+    /// The settings the config file had before it was reloaded, so only the ones that changed are
+    /// applied.
+    pub previous_app_config_fields: AppConfigFields,
+    pub colour_scheme: ColourScheme,
+    pub custom_colours: Option<ConfigColours>,
+    pub styling: CanvasStyling,
+    /// Whether anything the styling is built from, like the colour scheme or the custom colours,
+    /// differs from the config that was loaded before.
+    pub colours_changed: bool,
+    /// Whether the layout differs from the one that was loaded before, in which case it should be
+    /// rebuilt with [`ConfigWatcher::build_layout_profile`].
+    pub layout_changed: bool,
+    /// The names of the layout profiles in the reloaded config.
+    pub layout_profiles: Vec<String>,
+}
+
+/// Keeps track of when the config file was last modified, and reloads it if that changes.
+pub struct ConfigWatcher {
+    path: Option<PathBuf>,
+    matches: ArgMatches,
+    config: Config,
+    layout_profile: Option<String>,
+    rows: Option<Vec<Row>>,
+    last_modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Creates a watcher for the config file at `path`, which was loaded as `config`. The command
+    /// line arguments in `matches` still take priority over anything in a reloaded config.
+    pub fn new(path: Option<PathBuf>, matches: ArgMatches, config: &Config) -> Self {
+        let last_modified = path.as_deref().and_then(modified_time);
+        let layout_profile = get_layout_profile(&matches, config);
+        let rows = apply_layout_profile(config.clone(), layout_profile.as_deref())
+            .ok()
+            .and_then(|config| config.row);
+
+        Self {
+            path,
+            matches,
+            config: config.clone(),
+            layout_profile,
+            rows,
+            last_modified,
+        }
+    }
+
+    /// Reloads the config file if it was modified since it was last loaded, returning [`None`] if
+    /// it was not.
+    pub fn poll(&mut self) -> Option<Result<ReloadedConfig>> {
+        let path = self.path.as_deref()?;
+        let modified = modified_time(path);
+
+        if modified.is_some() && modified != self.last_modified {
+            Some(self.reload())
+        } else {
+            None
+        }
+    }
+
+    /// Reloads the config file, regardless of whether it was modified. If this fails, nothing
+    /// should be applied, and the previously loaded settings should be kept.
+    pub fn reload(&mut self) -> Result<ReloadedConfig> {
+        let path = self
+            .path
+            .as_deref()
+            .context("There is no config file to reload.")?;
+
+        // Record this first, so a broken config isn't repeatedly reloaded until it changes again.
+        self.last_modified = modified_time(path);
+
+        let config_string = fs::read_to_string(path)
+            .with_context(|| format!("Unable to read the config file at {}.", path.display()))?;
+        let config: Config = toml_edit::de::from_str(&config_string)
+            .with_context(|| format!("Unable to parse the config file at {}.", path.display()))?;
+
+        let app_config_fields = get_app_config_fields(&self.matches, &config)?;
+        let colour_scheme = get_color_scheme(&self.matches, &config)?;
+        let styling = CanvasStyling::new(colour_scheme, &config)?;
+
+        let previous = &self.config;
+        let previous_app_config_fields = get_app_config_fields(&self.matches, previous)?;
+        let colours_changed = colour_scheme != get_color_scheme(&self.matches, previous)?
+            || config.colors != previous.colors
+            || get_no_color(&config) != get_no_color(previous)
+            || get_unicode_symbols(&config) != get_unicode_symbols(previous);
+        let layout_changed = !matches!(
+            apply_layout_profile(config.clone(), self.layout_profile.as_deref()),
+            Ok(profile_config) if profile_config.row == self.rows
+        );
+        let layout_profiles = get_layout_profile_names(&config);
+        self.config = config.clone();
+
+        Ok(ReloadedConfig {
+            app_config_fields,
+            previous_app_config_fields,
+            colour_scheme,
+            custom_colours: config.colors,
+            styling,
+            colours_changed,
+            layout_changed,
+            layout_profiles,
+        })
+    }
+
+    /// Builds the layout profile called `name` from the last loaded config, along with an [`App`]
+    /// for it with fresh widget states, drawn with `styling`. Reloads afterwards compare against
+    /// this layout.
+    pub fn build_layout_profile(
+        &mut self, name: &str, styling: &CanvasStyling,
+    ) -> Result<(BottomLayout, App)> {
+        let config = apply_layout_profile(self.config.clone(), Some(name))?;
+        let (layout, default_widget_id, default_widget_type) =
+            get_widget_layout(&self.matches, &config)?;
+        let rows = config.row.clone();
+        let app = build_app(
+            self.matches.clone(),
+            config,
+            &layout,
+            default_widget_id,
+            &default_widget_type,
+            styling,
+        )?;
+
+        self.layout_profile = Some(name.to_string());
+        self.rows = rows;
+
+        Ok((layout, app))
+    }
+
+    /// Saves `settings` to the config file, like [`config_writer::save_config`]. The saved file
+    /// isn't reloaded afterwards, as it has the settings that are already in use.
+    pub fn save(&mut self, settings: &SavedSettings, dry_run: bool) -> Result<SaveOutcome> {
+        let path = self
+            .path
+            .as_deref()
+            .context("There is no config file to save to.")?;
+
+        let outcome = config_writer::save_config(path, settings, dry_run)?;
+        if outcome == SaveOutcome::Saved {
+            self.last_modified = modified_time(path);
+        }
+
+        Ok(outcome)
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::args;
+
+    #[test]
+    fn test_reload_config() {
+        let path = std::env::temp_dir().join(format!(
+            "bottom-config-watcher-test-{}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, "[flags]\nrate = 2000\n").unwrap();
+
+        let matches = args::build_app().get_matches_from(["btm"]);
+        let config: Config = toml_edit::de::from_str("[flags]\nrate = 2000\n").unwrap();
+        let mut watcher = ConfigWatcher::new(Some(path.clone()), matches, &config);
+        assert!(watcher.poll().is_none());
+
+        fs::write(
+            &path,
+            "[flags]\nrate = 5000\n[colors]\ntable_header_color = \"Red\"\n",
+        )
+        .unwrap();
+        let reloaded = watcher.reload().unwrap();
+        assert_eq!(reloaded.app_config_fields.update_rate, 5000);
+        assert_eq!(reloaded.previous_app_config_fields.update_rate, 2000);
+        assert!(reloaded.custom_colours.is_some());
+        assert!(reloaded.colours_changed);
+        assert!(!reloaded.layout_changed);
+
+        fs::write(
+            &path,
+            "[flags]\nrate = 3000\n[colors]\ntable_header_color = \"Red\"\n",
+        )
+        .unwrap();
+        let reloaded = watcher.reload().unwrap();
+        assert_eq!(reloaded.previous_app_config_fields.update_rate, 5000);
+        assert!(!reloaded.colours_changed);
+
+        fs::write(&path, "[[row]]\n[[row.child]]\ntype = \"cpu\"\n").unwrap();
+        assert!(watcher.reload().unwrap().layout_changed);
+
+        fs::write(&path, "[flags]\nrate = \"not a rate\"\n").unwrap();
+        assert!(watcher.reload().is_err());
+
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.poll().is_none());
+    }
+}
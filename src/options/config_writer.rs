@@ -0,0 +1,532 @@
+//! Saving the settings that can be changed while running back to the config file.
+//!
+//! The file is edited in place rather than regenerated, so comments, formatting, and any keys
+//! that aren't saved are kept as they were.
+
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use anyhow::{Context, Result};
+use toml_edit::{value, Array, Document, Item, Table, Value};
+
+use super::try_parse_ms;
+use crate::{app::layout_manager::BottomWidgetType, constants::DEFAULT_LAYOUT_PROFILE};
+
+/// The settings that can be changed while running, as they should be saved to `[flags]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedSettings {
+    pub color: &'static str,
+    pub rate: u64,
+    pub temperature_type: &'static str,
+    pub decimal_bytes: bool,
+    /// The settings of the first process widget, if there is one.
+    pub process: Option<SavedProcessSettings>,
+    /// The name of the layout in use.
+    pub layout_profile: String,
+    /// Each widget in the layout in use, in the order they are listed in the config.
+    pub widgets: Vec<SavedWidget>,
+}
+
+/// The settings of a widget in the layout that have a matching key in its layout entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedWidget {
+    pub widget_type: BottomWidgetType,
+    /// The names of the columns to hide, if the widget is a table.
+    pub hidden_columns: Option<Vec<String>>,
+}
+
+/// The view settings of a process widget that have a matching flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavedProcessSettings {
+    pub group: bool,
+    pub tree: bool,
+    pub process_command: bool,
+    pub mem_as_value: bool,
+}
+
+/// What happened when saving to the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveOutcome {
+    /// The config file was written.
+    Saved,
+    /// The config file already had these settings, so it was left alone.
+    Unchanged,
+    /// Nothing was written, as this was a dry run. The changes were logged instead.
+    DryRun,
+}
+
+/// Saves `settings` to the config file at `path`, creating it if it doesn't exist. The file is
+/// written to a temporary file next to it first and then renamed over it, so it is never left
+/// half-written. If `dry_run` is set, the changes are only logged.
+pub fn save_config(path: &Path, settings: &SavedSettings, dry_run: bool) -> Result<SaveOutcome> {
+    let old = match fs::read_to_string(path) {
+        Ok(old) => old,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Unable to read the config file at {}.", path.display()))
+        }
+    };
+
+    let mut document: Document = old
+        .parse()
+        .with_context(|| format!("Unable to parse the config file at {}.", path.display()))?;
+    update_document(&mut document, settings);
+    let new = document.to_string();
+
+    if new == old {
+        Ok(SaveOutcome::Unchanged)
+    } else if dry_run {
+        let _diff = diff_lines(&old, &new);
+        crate::c_info!("Saving to {} would change:\n{_diff}", path.display());
+        Ok(SaveOutcome::DryRun)
+    } else {
+        write_atomically(path, &new)
+            .with_context(|| format!("Unable to write the config file at {}.", path.display()))?;
+        Ok(SaveOutcome::Saved)
+    }
+}
+
+/// Sets the keys in `[flags]` and the layout for `settings`. Keys that already have the same value
+/// are left untouched, and comments around the ones that change are kept.
+pub fn update_document(document: &mut Document, settings: &SavedSettings) {
+    update_flags(document, settings);
+    update_layout(document, settings);
+}
+
+fn update_flags(document: &mut Document, settings: &SavedSettings) {
+    let flags = document
+        .entry("flags")
+        .or_insert_with(toml_edit::table)
+        .as_table_mut();
+
+    // Something other than a table was called flags; a config like that wouldn't load anyways.
+    let Some(flags) = flags else {
+        return;
+    };
+
+    set_flag(flags, "color", settings.color.into());
+    set_duration_flag(flags, "rate", settings.rate);
+    set_flag(flags, "temperature_type", settings.temperature_type.into());
+    set_flag(flags, "decimal_bytes", settings.decimal_bytes.into());
+
+    if let Some(process) = &settings.process {
+        set_flag(flags, "group", process.group.into());
+        set_flag(flags, "tree", process.tree.into());
+        set_flag(flags, "process_command", process.process_command.into());
+        set_flag(flags, "mem_as_value", process.mem_as_value.into());
+    }
+}
+
+/// Sets `hidden_columns` in the layout entry of each table widget. The layout is left alone if it
+/// isn't in the file, or no longer matches the one in use, e.g. if it was edited since starting.
+fn update_layout(document: &mut Document, settings: &SavedSettings) {
+    let rows = if settings.layout_profile == DEFAULT_LAYOUT_PROFILE {
+        document.get_mut("row")
+    } else {
+        document
+            .get_mut("layouts")
+            .and_then(Item::as_array_of_tables_mut)
+            .and_then(|layouts| {
+                layouts.iter_mut().find(|layout| {
+                    layout.get("name").and_then(Item::as_str)
+                        == Some(settings.layout_profile.as_str())
+                })
+            })
+            .and_then(|layout| layout.get_mut("row"))
+    };
+    let Some(rows) = rows.and_then(Item::as_array_of_tables_mut) else {
+        return;
+    };
+
+    // Widgets are either children of a row, or children of a column in a row.
+    let mut entries: Vec<&mut Table> = Vec::new();
+    for row in rows.iter_mut() {
+        let Some(children) = row.get_mut("child").and_then(Item::as_array_of_tables_mut) else {
+            continue;
+        };
+        for child in children.iter_mut() {
+            if child.contains_key("type") {
+                entries.push(child);
+            } else if let Some(widgets) = child
+                .get_mut("child")
+                .and_then(Item::as_array_of_tables_mut)
+            {
+                entries.extend(widgets.iter_mut());
+            }
+        }
+    }
+
+    let is_same_layout = entries.len() == settings.widgets.len()
+        && entries
+            .iter()
+            .zip(&settings.widgets)
+            .all(|(entry, widget)| {
+                entry
+                    .get("type")
+                    .and_then(Item::as_str)
+                    .and_then(|widget_type| widget_type.parse::<BottomWidgetType>().ok())
+                    .as_ref()
+                    == Some(&widget.widget_type)
+            });
+    if !is_same_layout {
+        return;
+    }
+
+    for (entry, widget) in entries.into_iter().zip(&settings.widgets) {
+        if let Some(hidden_columns) = &widget.hidden_columns {
+            set_hidden_columns(entry, hidden_columns);
+        }
+    }
+}
+
+/// Sets `hidden_columns` in a widget's layout entry, or removes it if nothing is hidden. Names
+/// are compared ignoring case and order, as that is how they are matched when loading.
+fn set_hidden_columns(widget: &mut Table, hidden_columns: &[String]) {
+    let sorted = |names: &mut Vec<String>| {
+        names
+            .iter_mut()
+            .for_each(|name| name.make_ascii_lowercase());
+        names.sort_unstable();
+    };
+
+    let old_value = widget.get("hidden_columns").and_then(Item::as_value);
+    let mut old_names = old_value
+        .and_then(Value::as_array)
+        .map(|old| {
+            old.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let mut new_names = hidden_columns.to_vec();
+    sorted(&mut old_names);
+    sorted(&mut new_names);
+    if old_names == new_names {
+        return;
+    }
+
+    if hidden_columns.is_empty() {
+        widget.remove("hidden_columns");
+    } else {
+        let mut new_value =
+            Value::Array(hidden_columns.iter().map(String::as_str).collect::<Array>());
+        if let Some(old_value) = old_value {
+            *new_value.decor_mut() = old_value.decor().clone();
+        }
+        widget["hidden_columns"] = value(new_value);
+    }
+}
+
+/// Sets a flag that is a duration in milliseconds. It is left alone if it already has the same
+/// duration, even if it is written differently, like `"1s"` rather than `1000`.
+fn set_duration_flag(flags: &mut Table, key: &str, ms: u64) {
+    let old_ms = match flags.get(key).and_then(Item::as_value) {
+        Some(Value::String(old)) => try_parse_ms(old.value()).ok(),
+        Some(Value::Integer(old)) => u64::try_from(*old.value()).ok(),
+        _ => None,
+    };
+
+    if old_ms != Some(ms) {
+        set_flag(flags, key, (ms as i64).into());
+    }
+}
+
+fn set_flag(flags: &mut Table, key: &str, mut new_value: Value) {
+    let old_value = flags.get(key).and_then(Item::as_value);
+    let is_same = match (old_value, &new_value) {
+        (Some(Value::String(old)), Value::String(new)) => old.value() == new.value(),
+        (Some(Value::Integer(old)), Value::Integer(new)) => old.value() == new.value(),
+        (Some(Value::Boolean(old)), Value::Boolean(new)) => old.value() == new.value(),
+        _ => false,
+    };
+
+    if !is_same {
+        // Keep any comment after the old value.
+        if let Some(old_value) = old_value {
+            *new_value.decor_mut() = old_value.decor().clone();
+        }
+        flags[key] = value(new_value);
+    }
+}
+
+/// Writes `contents` to a new file next to the one at `path`, and then renames it over it. If
+/// `path` is a symlink, the file it points to is replaced instead, and the new file gets the
+/// permissions of the one it replaces.
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let target = match fs::canonicalize(path) {
+        Ok(target) => target,
+        Err(err) if err.kind() == ErrorKind::NotFound => path.to_path_buf(),
+        Err(err) => return Err(err),
+    };
+    let permissions = match fs::metadata(&target) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+
+    let (temp_path, mut file) = create_temp_file(&target)?;
+    let result = (|| {
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, &target)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Creates a file that didn't exist before in the same directory as `target`, so it can be renamed
+/// over it.
+fn create_temp_file(target: &Path) -> std::io::Result<(PathBuf, fs::File)> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    let file_name = target.file_name().unwrap_or_default().to_string_lossy();
+    loop {
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let temp_path =
+            target.with_file_name(format!(".{file_name}.{}.{count}.tmp", std::process::id()));
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+        {
+            Ok(file) => return Ok((temp_path, file)),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Returns the lines that differ between `old` and `new`, prefixed with "-" if removed and "+" if
+/// added.
+fn diff_lines(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // The length of the longest common subsequence of old[i..] and new[j..].
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            diff.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        } else {
+            diff.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn settings() -> SavedSettings {
+        SavedSettings {
+            color: "nord",
+            rate: 2000,
+            temperature_type: "kelvin",
+            decimal_bytes: true,
+            process: Some(SavedProcessSettings {
+                group: false,
+                tree: true,
+                process_command: false,
+                mem_as_value: false,
+            }),
+            layout_profile: DEFAULT_LAYOUT_PROFILE.to_string(),
+            widgets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_update_document_keeps_comments() {
+        let mut document: Document = "# My config\n[flags]\n# How often to refresh\nrate = 1000 # fast\ntree = true # keep\nunknown_key = \"kept\"\n\n[colors]\ntable_header_color = \"Red\"\n"
+            .parse()
+            .unwrap();
+        update_document(&mut document, &settings());
+
+        assert_eq!(
+            document.to_string(),
+            "# My config\n[flags]\n# How often to refresh\nrate = 2000 # fast\ntree = true # keep\nunknown_key = \"kept\"\ncolor = \"nord\"\ntemperature_type = \"kelvin\"\ndecimal_bytes = true\ngroup = false\nprocess_command = false\nmem_as_value = false\n\n[colors]\ntable_header_color = \"Red\"\n"
+        );
+    }
+
+    #[test]
+    fn test_update_document_hidden_columns() {
+        let layout = "[[row]]\n  [[row.child]]\n    type = \"cpu\"\n  [[row.child]]\n    [[row.child.child]]\n      type = \"proc\"\n      hidden_columns = [\"state\", \"User\"] # mine\n    [[row.child.child]]\n      type = \"disk\"\n      hidden_columns = [\"Mount\"]\n  [[row.child]]\n    type = \"temp\"\n";
+        let widget = |widget_type, hidden_columns: Option<&[&str]>| SavedWidget {
+            widget_type,
+            hidden_columns: hidden_columns
+                .map(|names| names.iter().map(|name| name.to_string()).collect()),
+        };
+        let mut settings = SavedSettings {
+            widgets: vec![
+                widget(BottomWidgetType::Cpu, None),
+                widget(BottomWidgetType::Proc, Some(&["User", "State"])),
+                widget(BottomWidgetType::Disk, Some(&[])),
+                widget(BottomWidgetType::Temp, Some(&["Temp"])),
+            ],
+            ..settings()
+        };
+
+        // The same names in a different order or case are left alone.
+        let mut document: Document = layout.parse().unwrap();
+        update_layout(&mut document, &settings);
+        assert_eq!(
+            document.to_string(),
+            "[[row]]\n  [[row.child]]\n    type = \"cpu\"\n  [[row.child]]\n    [[row.child.child]]\n      type = \"proc\"\n      hidden_columns = [\"state\", \"User\"] # mine\n    [[row.child.child]]\n      type = \"disk\"\n  [[row.child]]\n    type = \"temp\"\nhidden_columns = [\"Temp\"]\n"
+        );
+
+        // Layouts that don't match the one in use aren't touched.
+        settings.widgets.pop();
+        let mut document: Document = layout.parse().unwrap();
+        update_layout(&mut document, &settings);
+        assert_eq!(document.to_string(), layout);
+
+        // Nor are layouts other than the one in use.
+        let profiles = format!(
+            "[[layouts]]\nname = \"other\"\n{}",
+            layout.replace("row", "layouts.row")
+        );
+        settings
+            .widgets
+            .push(widget(BottomWidgetType::Temp, Some(&["Temp"])));
+        let mut document: Document = profiles.parse().unwrap();
+        update_layout(&mut document, &settings);
+        assert_eq!(document.to_string(), profiles);
+
+        settings.layout_profile = "other".to_string();
+        update_layout(&mut document, &settings);
+        assert!(document.to_string().contains("hidden_columns = [\"Temp\"]"));
+        assert!(!document.to_string().contains("\"Mount\""));
+    }
+
+    #[test]
+    fn test_save_config() {
+        let path = std::env::temp_dir().join(format!(
+            "bottom-config-writer-test-{}.toml",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            save_config(&path, &settings(), true).unwrap(),
+            SaveOutcome::DryRun
+        );
+        assert!(!path.exists());
+
+        assert_eq!(
+            save_config(&path, &settings(), false).unwrap(),
+            SaveOutcome::Saved
+        );
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("rate = 2000"));
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(!fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .any(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.starts_with(&format!(".{file_name}.")) && name.ends_with(".tmp")
+            }));
+
+        assert_eq!(
+            save_config(&path, &settings(), false).unwrap(),
+            SaveOutcome::Unchanged
+        );
+
+        fs::write(&path, "[flags\n").unwrap();
+        assert!(save_config(&path, &settings(), false).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[flags\n");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_update_document_keeps_same_duration() {
+        let mut document: Document = "[flags]\nrate = \"2s\" # slow\n".parse().unwrap();
+        update_document(&mut document, &settings());
+        assert!(document.to_string().contains("rate = \"2s\" # slow\n"));
+
+        let mut document: Document = "[flags]\nrate = \"1s\" # slow\n".parse().unwrap();
+        update_document(&mut document, &settings());
+        assert!(document.to_string().contains("rate = 2000 # slow\n"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_config_through_symlink() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let dir = std::env::temp_dir().join(format!(
+            "bottom-config-writer-symlink-test-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        let target = dir.join("real.toml");
+        let link = dir.join("config.toml");
+        fs::write(&target, "[flags]\n").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o600)).unwrap();
+        symlink(&target, &link).unwrap();
+
+        assert_eq!(
+            save_config(&link, &settings(), false).unwrap(),
+            SaveOutcome::Saved
+        );
+
+        // The link is kept, and the file it points to keeps its permissions.
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert!(fs::read_to_string(&target).unwrap().contains("rate = 2000"));
+        assert_eq!(
+            fs::metadata(&target).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            diff_lines(
+                "[flags]\nrate = 1000\ntree = true\n",
+                "[flags]\nrate = 2000\ntree = true\ncolor = \"nord\"\n"
+            ),
+            "-rate = 1000\n+rate = 2000\n+color = \"nord\"\n"
+        );
+        assert_eq!(diff_lines("a\n", "a\n"), "");
+    }
+}
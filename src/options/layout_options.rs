@@ -1,18 +1,22 @@
 use serde::{Deserialize, Serialize};
 
+use super::{try_parse_ms, StringOrNum};
 use crate::app::layout_manager::*;
-use crate::error::Result;
+use crate::error::{BottomError, Result};
 
 /// Represents a row.  This has a length of some sort (optional) and a vector
 /// of children.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
 #[serde(rename = "row")]
 pub struct Row {
     pub ratio: Option<u32>,
     pub child: Option<Vec<RowChildren>>,
 }
 
-fn new_cpu(left_legend: bool, iter_id: &mut u64) -> BottomColRow {
+fn new_cpu(
+    left_legend: bool, iter_id: &mut u64, update_rate: Option<u64>,
+    aspect_ratio: Option<(u16, u16)>, expanded_on_startup: bool,
+) -> BottomColRow {
     let cpu_id = *iter_id;
     *iter_id += 1;
     let legend_id = *iter_id;
@@ -25,13 +29,19 @@ fn new_cpu(left_legend: bool, iter_id: &mut u64) -> BottomColRow {
                 .parent_reflector(Some((WidgetDirection::Right, 1))),
             BottomWidget::new(BottomWidgetType::Cpu, cpu_id)
                 .width_ratio(17)
-                .flex_grow(true),
+                .flex_grow(true)
+                .update_rate(update_rate)
+                .aspect_ratio(aspect_ratio)
+                .expanded_on_startup(expanded_on_startup),
         ])
     } else {
         BottomColRow::new(vec![
             BottomWidget::new(BottomWidgetType::Cpu, cpu_id)
                 .width_ratio(17)
-                .flex_grow(true),
+                .flex_grow(true)
+                .update_rate(update_rate)
+                .aspect_ratio(aspect_ratio)
+                .expanded_on_startup(expanded_on_startup),
             BottomWidget::new(BottomWidgetType::CpuLegend, legend_id)
                 .width_ratio(3)
                 .canvas_handle_width(true)
@@ -98,18 +108,28 @@ impl Row {
                         }
 
                         children.push(match widget_type {
-                            BottomWidgetType::Cpu => {
-                                BottomCol::new(vec![new_cpu(left_legend, iter_id)])
-                                    .col_width_ratio(width_ratio)
-                            }
+                            BottomWidgetType::Cpu => BottomCol::new(vec![new_cpu(
+                                left_legend,
+                                iter_id,
+                                widget.update_rate()?,
+                                widget.aspect_ratio()?,
+                                widget.expanded_on_startup(),
+                            )])
+                            .col_width_ratio(width_ratio),
                             BottomWidgetType::Proc => {
+                                widget.reject_aspect()?;
                                 let proc_id = *iter_id;
                                 let proc_search_id = *iter_id + 1;
                                 *iter_id += 2;
                                 BottomCol::new(vec![
                                     BottomColRow::new(vec![
                                         new_proc_sort(*iter_id),
-                                        new_proc(proc_id),
+                                        new_proc(proc_id)
+                                            .hidden_columns(widget.hidden_columns())
+                                            .columns(widget.columns())
+                                            .paged(widget.paged())
+                                            .update_rate(widget.update_rate()?)
+                                            .expanded_on_startup(widget.expanded_on_startup()),
                                     ])
                                     .total_widget_ratio(3)
                                     .flex_grow(true),
@@ -122,7 +142,13 @@ impl Row {
                             _ => BottomCol::new(vec![BottomColRow::new(vec![BottomWidget::new(
                                 widget_type,
                                 *iter_id,
-                            )])])
+                            )
+                            .hidden_columns(widget.hidden_columns())
+                            .columns(widget.columns())
+                            .paged(widget.paged())
+                            .update_rate(widget.update_rate()?)
+                            .aspect_ratio(widget.aspect_ratio()?)
+                            .expanded_on_startup(widget.expanded_on_startup())])])
                             .col_width_ratio(width_ratio),
                         });
                     }
@@ -161,11 +187,18 @@ impl Row {
                             match widget_type {
                                 BottomWidgetType::Cpu => {
                                     col_row_children.push(
-                                        new_cpu(left_legend, iter_id)
-                                            .col_row_height_ratio(col_row_height_ratio),
+                                        new_cpu(
+                                            left_legend,
+                                            iter_id,
+                                            widget.update_rate()?,
+                                            widget.aspect_ratio()?,
+                                            widget.expanded_on_startup(),
+                                        )
+                                        .col_row_height_ratio(col_row_height_ratio),
                                     );
                                 }
                                 BottomWidgetType::Proc => {
+                                    widget.reject_aspect()?;
                                     contains_proc = true;
                                     let proc_id = *iter_id;
                                     let proc_search_id = *iter_id + 1;
@@ -173,7 +206,12 @@ impl Row {
                                     col_row_children.push(
                                         BottomColRow::new(vec![
                                             new_proc_sort(*iter_id),
-                                            new_proc(proc_id),
+                                            new_proc(proc_id)
+                                                .hidden_columns(widget.hidden_columns())
+                                                .columns(widget.columns())
+                                                .paged(widget.paged())
+                                                .update_rate(widget.update_rate()?)
+                                                .expanded_on_startup(widget.expanded_on_startup()),
                                         ])
                                         .col_row_height_ratio(col_row_height_ratio)
                                         .total_widget_ratio(3),
@@ -188,7 +226,13 @@ impl Row {
                                     BottomColRow::new(vec![BottomWidget::new(
                                         widget_type,
                                         *iter_id,
-                                    )])
+                                    )
+                                    .hidden_columns(widget.hidden_columns())
+                                    .columns(widget.columns())
+                                    .paged(widget.paged())
+                                    .update_rate(widget.update_rate()?)
+                                    .aspect_ratio(widget.aspect_ratio()?)
+                                    .expanded_on_startup(widget.expanded_on_startup())])
                                     .col_row_height_ratio(col_row_height_ratio),
                                 ),
                             }
@@ -224,12 +268,19 @@ impl Row {
     }
 }
 
+/// A named layout, which can be chosen at startup or switched to while running.
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct LayoutProfile {
+    pub name: String,
+    pub row: Option<Vec<Row>>,
+}
+
 /// Represents a child of a Row - either a Col (column) or a FinalWidget.
 ///
 /// A Col can also have an optional length and children.  We only allow columns
 /// to have FinalWidgets as children, lest we get some amount of mutual
 /// recursion between Row and Col.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum RowChildren {
     Widget(FinalWidget),
@@ -240,10 +291,83 @@ pub enum RowChildren {
 }
 
 /// Represents a widget.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
 pub struct FinalWidget {
     pub ratio: Option<u32>,
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// The names of columns to hide by default, if this widget is a table. Names are matched
+    /// against the kind of each column, ignoring case.
+    pub hidden_columns: Option<Vec<String>>,
+    /// The names of the only columns to show, in order, if this widget is a table. These are
+    /// matched like `hidden_columns`.
+    pub columns: Option<Vec<String>>,
+    /// Whether to split this widget's rows into pages rather than scrolling, if it is a table.
+    pub paged: Option<bool>,
+    /// How often to update this widget's data, if it should be less often than the global rate.
+    pub rate: Option<StringOrNum>,
+    /// The width to height ratio to draw this widget at, like "2:1".
+    pub aspect: Option<String>,
+    /// Whether to start with this widget expanded. Only one widget can set this.
+    pub expanded_on_startup: Option<bool>,
+}
+
+impl FinalWidget {
+    fn hidden_columns(&self) -> Vec<String> {
+        self.hidden_columns.clone().unwrap_or_default()
+    }
+
+    fn columns(&self) -> Vec<String> {
+        self.columns.clone().unwrap_or_default()
+    }
+
+    fn update_rate(&self) -> Result<Option<u64>> {
+        self.rate
+            .as_ref()
+            .map(|rate| match rate {
+                StringOrNum::String(s) => try_parse_ms(s),
+                StringOrNum::Num(n) => Ok(*n),
+            })
+            .transpose()
+    }
+
+    fn expanded_on_startup(&self) -> bool {
+        self.expanded_on_startup.unwrap_or(false)
+    }
+
+    fn paged(&self) -> bool {
+        self.paged.unwrap_or(false)
+    }
+
+    fn aspect_ratio(&self) -> Result<Option<(u16, u16)>> {
+        self.aspect
+            .as_ref()
+            .map(|aspect| {
+                aspect
+                    .split_once(':')
+                    .and_then(|(width, height)| {
+                        Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+                    })
+                    .filter(|&(width, height)| width > 0 && height > 0)
+                    .ok_or_else(|| {
+                        BottomError::ConfigError(format!(
+                            "\"{aspect}\" is not a valid aspect ratio, use a ratio like \"2:1\"."
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    /// Process widgets are laid out together with their sort and search boxes, so they can't keep
+    /// an aspect ratio of their own.
+    fn reject_aspect(&self) -> Result<()> {
+        match &self.aspect {
+            Some(_) => Err(BottomError::ConfigError(format!(
+                "aspect can't be set on \"{}\" widgets.",
+                self.widget_type
+            ))),
+            None => Ok(()),
+        }
+    }
 }
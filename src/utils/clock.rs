@@ -0,0 +1,52 @@
+//! Sources of the current time, so time-based behaviour (e.g. double clicks or key timeouts) can be
+//! driven deterministically in tests.
+
+use std::time::Instant;
+
+/// A source of the current time.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// A [`Clock`] that just reads the system time. This is what should be used outside of tests.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A [`Clock`] that only moves forward when told to. Clones share the same time, so a test can keep
+/// one around to advance after handing another off.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: std::rc::Rc<std::cell::Cell<Instant>>,
+}
+
+#[cfg(test)]
+impl Default for MockClock {
+    fn default() -> Self {
+        Self {
+            now: std::rc::Rc::new(std::cell::Cell::new(Instant::now())),
+        }
+    }
+}
+
+#[cfg(test)]
+impl MockClock {
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: std::time::Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
@@ -0,0 +1,213 @@
+//! Helpers for tests, mostly around checking what actually gets drawn to the screen.
+//!
+//! There is also [`build_test_app`], to build an [`App`] the same way `main` does, and
+//! [`random_generator`], for tests that check invariants over many random inputs.
+//!
+//! The general flow is to render into a [`TestBackend`] with [`draw_to_buffer`], turn the result
+//! into rows of text with [`buffer_lines`] (and optionally [`buffer_style_map`]), and then compare
+//! it against an expected block written directly in the test with [`assert_snapshot`].
+
+use std::ops::Range;
+
+use tui::{
+    backend::TestBackend,
+    buffer::{Buffer, Cell},
+    Frame, Terminal,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::{layout_manager::BottomLayout, App},
+    args,
+    canvas::canvas_styling::CanvasStyling,
+    options::{build_app, get_widget_layout, Config},
+};
+
+/// Builds an [`App`] from command-line `args` and a `config`, the same way `main` does. The layout
+/// it was built with is also returned, for drawing it with a [`Painter`](crate::canvas::Painter).
+pub fn build_test_app(
+    args: &[&str], config: Config, styling: &CanvasStyling,
+) -> (App, BottomLayout) {
+    let matches = args::build_app().get_matches_from(args);
+    let (layout, default_widget_id, default_widget_type) =
+        get_widget_layout(&matches, &config).unwrap();
+    let app = build_app(
+        matches,
+        config,
+        &layout,
+        default_widget_id,
+        &default_widget_type,
+        styling,
+    )
+    .unwrap();
+
+    (app, layout)
+}
+
+/// Draws to a [`TestBackend`] of the given size with `draw`, and returns the resulting [`Buffer`].
+pub fn draw_to_buffer<F>(width: u16, height: u16, draw: F) -> Buffer
+where
+    F: FnOnce(&mut Frame<'_, TestBackend>),
+{
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(draw).unwrap();
+
+    terminal.backend().buffer().clone()
+}
+
+/// Converts each row of cells in a [`Buffer`] by `convert`, and joins them into one [`String`] per row.
+/// Cells hidden behind a wide character are skipped, so a row reads the same as it would on screen.
+fn map_rows<F: Fn(&Cell) -> String>(buffer: &Buffer, convert: F) -> Vec<String> {
+    let area = buffer.area;
+
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut row = String::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = buffer.get(x, y);
+                row.push_str(&convert(cell));
+                x += (cell.symbol.width() as u16).max(1);
+            }
+
+            row.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Returns the text of a [`Buffer`], one [`String`] per row, with trailing whitespace stripped.
+pub fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+    map_rows(buffer, |cell| cell.symbol.clone())
+}
+
+/// Returns a "style map" of a [`Buffer`] that lines up with [`buffer_lines`]; each cell is
+/// replaced with the character returned by `marker`. For example, marking highlighted cells with
+/// `'H'` and everything else with `' '` makes it easy to see which rows/columns are highlighted.
+pub fn buffer_style_map<F: Fn(&Cell) -> char>(buffer: &Buffer, marker: F) -> Vec<String> {
+    map_rows(buffer, |cell| {
+        let mark = marker(cell);
+        mark.to_string().repeat(cell.symbol.width().max(1))
+    })
+}
+
+/// Replaces the characters at `columns` of line `row` with `mask`, which is useful for hiding
+/// volatile parts of a snapshot, like timestamps. The range is in characters, not bytes.
+pub fn mask_region(lines: &mut [String], row: usize, columns: Range<usize>, mask: char) {
+    if let Some(line) = lines.get_mut(row) {
+        *line = line
+            .chars()
+            .enumerate()
+            .map(|(index, c)| if columns.contains(&index) { mask } else { c })
+            .collect();
+    }
+}
+
+/// Strips trailing whitespace from each line, as well as any leading and trailing empty lines.
+pub fn strip_trailing_whitespace(text: &str) -> Vec<String> {
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect();
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].to_vec(),
+        _ => vec![],
+    }
+}
+
+/// Parses an expected snapshot block written inline in a test. Each line of the snapshot must start
+/// with a `|` (after any indentation), so blocks can be indented to match the surrounding code and
+/// leading spaces in the snapshot itself are kept. Trailing whitespace is ignored.
+fn parse_snapshot(expected: &str) -> Vec<String> {
+    strip_trailing_whitespace(expected)
+        .into_iter()
+        .map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix('|')
+                .unwrap_or_else(|| panic!("snapshot line {line:?} does not start with '|'"))
+                .to_string()
+        })
+        .collect()
+}
+
+/// Asserts that `actual` matches the `expected` snapshot, panicking with a line-by-line diff if not.
+/// See [`parse_snapshot`] for the expected format.
+#[track_caller]
+pub fn assert_snapshot(actual: &[String], expected: &str) {
+    let actual = strip_trailing_whitespace(&actual.join("\n"));
+    let expected = parse_snapshot(expected);
+
+    if actual != expected {
+        let num_lines = actual.len().max(expected.len());
+        let diff = (0..num_lines)
+            .map(|index| {
+                let a = actual.get(index).map(String::as_str);
+                let e = expected.get(index).map(String::as_str);
+
+                if a == e {
+                    format!("  |{}", a.unwrap_or_default())
+                } else {
+                    let mut lines = vec![];
+                    if let Some(e) = e {
+                        lines.push(format!("- |{e}"));
+                    }
+                    if let Some(a) = a {
+                        lines.push(format!("+ |{a}"));
+                    }
+                    lines.join("\n")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        panic!("snapshot mismatch (- expected, + actual):\n{diff}");
+    }
+}
+
+/// A small xorshift generator, so the sequence is the same on every run. Each call returns a
+/// number in `0..n`.
+pub fn random_generator(seed: u64) -> impl FnMut(usize) -> usize {
+    let mut state = seed;
+    move |n: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tui::widgets::{Block, Borders};
+
+    use super::*;
+
+    #[test]
+    fn test_snapshot_helpers() {
+        let buffer = draw_to_buffer(10, 3, |f| {
+            f.render_widget(
+                Block::default().borders(Borders::ALL).title("12:34"),
+                f.size(),
+            );
+        });
+        let mut lines = buffer_lines(&buffer);
+        mask_region(&mut lines, 0, 1..6, '*');
+
+        assert_snapshot(
+            &lines,
+            "
+            |┌*****───┐
+            |│        │
+            |└────────┘
+            ",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "snapshot mismatch")]
+    fn test_snapshot_mismatch() {
+        assert_snapshot(&["abc".to_string()], "|abd");
+    }
+}
@@ -0,0 +1,305 @@
+//! Converting and formatting values for display in a chosen unit. Data is stored in one base unit
+//! and only converted when it is turned into rows, so switching units doesn't need new data.
+
+use crate::app::data_harvester::temperature::TemperatureType;
+
+/// Formats `value` with one decimal place if it is below 100, and as a whole number otherwise.
+pub fn format_value(value: f64) -> String {
+    // Compare against where rounding to one decimal would hit 100, so nothing shows as "100.0".
+    if value.abs() < 99.95 {
+        format!("{value:.1}")
+    } else {
+        format!("{value:.0}")
+    }
+}
+
+/// How to write the digits of numbers, e.g. with a separator between thousands or a comma before
+/// the decimal places. This only changes how numbers are shown; sorting still uses the values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// The character between each group of three whole digits, if they should be grouped.
+    pub grouping: Option<char>,
+    /// The character before the decimal places.
+    pub decimal: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            grouping: None,
+            decimal: '.',
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Rewrites the first number in `formatted`, as written by the other functions here, in this
+    /// format. Anything around the number, like a unit, is kept as it is.
+    pub fn apply(&self, formatted: &str) -> String {
+        if *self == Self::default() {
+            return formatted.to_string();
+        }
+
+        let Some(start) = formatted.find(|c: char| c.is_ascii_digit()) else {
+            return formatted.to_string();
+        };
+        let digits_len = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+        let whole_end = start + digits_len(&formatted[start..]);
+        let whole = &formatted[start..whole_end];
+        let mut result = String::with_capacity(formatted.len() + whole.len() / 3 + 1);
+        result.push_str(&formatted[..start]);
+        // The first group takes whatever is left over, so every later group has three digits.
+        let first_group_len = match whole.len() % 3 {
+            0 => 3,
+            len => len,
+        };
+        result.push_str(&whole[..first_group_len]);
+        for group in whole.as_bytes()[first_group_len..].chunks(3) {
+            if let Some(grouping) = self.grouping {
+                result.push(grouping);
+            }
+            result.extend(group.iter().map(|&digit| char::from(digit)));
+        }
+
+        let mut rest = &formatted[whole_end..];
+        if let Some(decimals) = rest.strip_prefix('.') {
+            let decimals_len = digits_len(decimals);
+            if decimals_len > 0 {
+                result.push(self.decimal);
+                result.push_str(&decimals[..decimals_len]);
+                rest = &decimals[decimals_len..];
+            }
+        }
+        result.push_str(rest);
+
+        result
+    }
+}
+
+/// Which prefixes to use when showing a number of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteStandard {
+    /// Powers of 1024, e.g. KiB or MiB.
+    #[default]
+    Binary,
+    /// Powers of 1000 (SI), e.g. KB or MB.
+    Decimal,
+}
+
+impl ByteStandard {
+    /// Returns the other standard.
+    pub fn toggle(self) -> Self {
+        match self {
+            ByteStandard::Binary => ByteStandard::Decimal,
+            ByteStandard::Decimal => ByteStandard::Binary,
+        }
+    }
+
+    /// Returns how much each prefix is worth compared to the previous one.
+    fn base(self) -> f64 {
+        match self {
+            ByteStandard::Binary => 1024.0,
+            ByteStandard::Decimal => 1000.0,
+        }
+    }
+
+    /// Returns the units to show, from bytes up to exabytes.
+    fn units(self) -> [&'static str; 7] {
+        match self {
+            ByteStandard::Binary => ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"],
+            ByteStandard::Decimal => ["B", "KB", "MB", "GB", "TB", "PB", "EB"],
+        }
+    }
+}
+
+/// The most decimal places [`format_bytes`] shows, which keeps it within [`MAX_BYTES_WIDTH`].
+pub const MAX_BYTES_PRECISION: usize = 2;
+
+/// The longest string [`format_bytes`] returns, so columns showing bytes can be sized up front.
+/// This still holds after a [`NumberFormat`] is applied, as only values without decimal places can
+/// have four whole digits and so gain a separator.
+pub const MAX_BYTES_WIDTH: usize = 9;
+
+/// Formats `bytes` using the largest unit of `standard` it has at least one of, e.g. "1.5 GiB" or
+/// "1.6 GB". Values below 100 get `precision` decimal places (up to [`MAX_BYTES_PRECISION`]), and
+/// larger values and plain bytes are shown as whole numbers, so the result is never longer than
+/// [`MAX_BYTES_WIDTH`].
+pub fn format_bytes(bytes: u64, standard: ByteStandard, precision: usize) -> String {
+    let precision = precision.min(MAX_BYTES_PRECISION);
+    let base = standard.base();
+    let units = standard.units();
+
+    let mut value = bytes as f64;
+    let mut index = 0;
+    while index + 1 < units.len() && value >= base {
+        value /= base;
+        index += 1;
+    }
+
+    if index == 0 {
+        return format!("{bytes} {}", units[0]);
+    }
+
+    // Rounding can carry a value up to the next unit, e.g. 1023.9 KiB, so move up if it does.
+    if value.round() >= base && index + 1 < units.len() {
+        value /= base;
+        index += 1;
+    }
+
+    let decimals = if value < 100.0 - 0.5 * 10_f64.powi(-(precision as i32)) {
+        precision
+    } else {
+        0
+    };
+
+    format!("{value:.decimals$} {}", units[index])
+}
+
+/// Formats a rate of `bytes` per second like [`format_bytes`], e.g. "1.5 GiB/s".
+pub fn format_bytes_per_second(bytes: u64, standard: ByteStandard, precision: usize) -> String {
+    format!("{}/s", format_bytes(bytes, standard, precision))
+}
+
+/// Converts a temperature in degrees Celsius to `unit`.
+pub fn convert_temperature(celsius: f32, unit: TemperatureType) -> f32 {
+    match unit {
+        TemperatureType::Celsius => celsius,
+        TemperatureType::Kelvin => celsius + 273.15,
+        TemperatureType::Fahrenheit => (celsius * (9.0 / 5.0)) + 32.0,
+    }
+}
+
+/// Returns the suffix shown after a temperature in `unit`.
+pub fn temperature_suffix(unit: TemperatureType) -> &'static str {
+    match unit {
+        TemperatureType::Celsius => "°C",
+        TemperatureType::Kelvin => "K",
+        TemperatureType::Fahrenheit => "°F",
+    }
+}
+
+/// Formats a temperature in degrees Celsius as `unit`, without a suffix.
+pub fn format_temperature(celsius: f32, unit: TemperatureType) -> String {
+    format_value(f64::from(convert_temperature(celsius, unit)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(0.0), "0.0");
+        assert_eq!(format_value(45.66), "45.7");
+        assert_eq!(format_value(-12.34), "-12.3");
+        assert_eq!(format_value(99.94), "99.9");
+        assert_eq!(format_value(99.96), "100");
+        assert_eq!(format_value(312.4), "312");
+        assert_eq!(format_value(1234.6), "1235");
+    }
+
+    #[test]
+    fn test_format_bytes_boundaries() {
+        use ByteStandard::*;
+
+        assert_eq!(format_bytes(0, Binary, 1), "0 B");
+        assert_eq!(format_bytes(0, Decimal, 1), "0 B");
+
+        assert_eq!(format_bytes(999, Decimal, 1), "999 B");
+        assert_eq!(format_bytes(1000, Decimal, 1), "1.0 KB");
+        assert_eq!(format_bytes(1023, Binary, 1), "1023 B");
+        assert_eq!(format_bytes(1024, Binary, 1), "1.0 KiB");
+        assert_eq!(format_bytes(1023, Decimal, 1), "1.0 KB");
+
+        assert_eq!(format_bytes(1_610_612_736, Binary, 1), "1.5 GiB");
+        assert_eq!(format_bytes(1_610_612_736, Decimal, 1), "1.6 GB");
+
+        // Just under the next unit should round up into it, rather than showing 1024 of this one.
+        assert_eq!(format_bytes(1024 * 1024 - 1, Binary, 1), "1.0 MiB");
+        assert_eq!(format_bytes(1023 * 1024, Binary, 1), "1023 KiB");
+        assert_eq!(format_bytes(999_999, Decimal, 1), "1.0 MB");
+
+        assert_eq!(format_bytes(1 << 60, Binary, 1), "1.0 EiB");
+        assert_eq!(format_bytes(u64::MAX, Binary, 1), "16.0 EiB");
+        assert_eq!(format_bytes(u64::MAX, Decimal, 1), "18.4 EB");
+    }
+
+    #[test]
+    fn test_format_bytes_precision() {
+        use ByteStandard::*;
+
+        assert_eq!(format_bytes(1_234_567, Decimal, 0), "1 MB");
+        assert_eq!(format_bytes(1_234_567, Decimal, 2), "1.23 MB");
+        assert_eq!(format_bytes(1_234_567, Decimal, 5), "1.23 MB");
+        assert_eq!(format_bytes(99_990_000, Decimal, 1), "100 MB");
+        assert_eq!(format_bytes(99_940_000, Decimal, 1), "99.9 MB");
+        assert_eq!(format_bytes(512_000_000, Decimal, 2), "512 MB");
+        assert_eq!(format_bytes_per_second(1536, Binary, 1), "1.5 KiB/s");
+    }
+
+    #[test]
+    fn test_format_bytes_width() {
+        let grouped = NumberFormat {
+            grouping: Some(','),
+            decimal: '.',
+        };
+        let mut bytes: u64 = 1;
+        while let Some(next) = bytes.checked_mul(3) {
+            for standard in [ByteStandard::Binary, ByteStandard::Decimal] {
+                for value in [bytes - 1, bytes, bytes + 1] {
+                    let formatted = format_bytes(value, standard, MAX_BYTES_PRECISION);
+                    assert!(
+                        formatted.len() <= MAX_BYTES_WIDTH,
+                        "{formatted:?} is too long"
+                    );
+
+                    let grouped = grouped.apply(&formatted);
+                    assert!(
+                        grouped.chars().count() <= MAX_BYTES_WIDTH,
+                        "{grouped:?} is too long"
+                    );
+                }
+            }
+            bytes = next;
+        }
+    }
+
+    #[test]
+    fn test_number_format() {
+        let spaced = NumberFormat {
+            grouping: Some(' '),
+            decimal: '.',
+        };
+        assert_eq!(spaced.apply("1000000"), "1 000 000");
+        assert_eq!(spaced.apply("100000"), "100 000");
+        assert_eq!(spaced.apply("1000"), "1 000");
+        assert_eq!(spaced.apply("999"), "999");
+        assert_eq!(spaced.apply("1234567.5%"), "1 234 567.5%");
+        assert_eq!(spaced.apply("1023 KiB"), "1 023 KiB");
+        assert_eq!(spaced.apply("N/A"), "N/A");
+
+        let comma_decimal = NumberFormat {
+            grouping: Some('.'),
+            decimal: ',',
+        };
+        assert_eq!(comma_decimal.apply("12.5%"), "12,5%");
+        assert_eq!(comma_decimal.apply("1234.56"), "1.234,56");
+        assert_eq!(comma_decimal.apply("-1500.0"), "-1.500,0");
+        assert_eq!(comma_decimal.apply("1.5 GiB/s"), "1,5 GiB/s");
+
+        assert_eq!(NumberFormat::default().apply("1234.5"), "1234.5");
+    }
+
+    #[test]
+    fn test_format_temperature() {
+        assert_eq!(format_temperature(45.0, TemperatureType::Celsius), "45.0");
+        assert_eq!(format_temperature(45.0, TemperatureType::Fahrenheit), "113");
+        assert_eq!(
+            format_temperature(10.0, TemperatureType::Fahrenheit),
+            "50.0"
+        );
+        assert_eq!(format_temperature(45.0, TemperatureType::Kelvin), "318");
+        assert_eq!(temperature_suffix(TemperatureType::Kelvin), "K");
+    }
+}
@@ -6,9 +6,12 @@ use tui::{style::Style, text::Text, widgets::Row};
 use crate::{
     app::{data_harvester::cpu::CpuDataType, AppConfigFields},
     canvas::{canvas_styling::CanvasStyling, Painter},
-    components::data_table::{
-        Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
-        DataToCell,
+    components::{
+        data_table::{
+            Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
+            DataToCell,
+        },
+        time_graph::value_at,
     },
     data_conversion::CpuWidgetData,
     utils::gen_util::truncate_to_text,
@@ -19,6 +22,8 @@ pub struct CpuWidgetStyling {
     pub all: Style,
     pub avg: Style,
     pub entries: Vec<Style>,
+    /// Characters to tell the series apart with, if they can't be told apart by colour.
+    pub series_symbols: &'static [char],
 }
 
 impl CpuWidgetStyling {
@@ -33,8 +38,23 @@ impl CpuWidgetStyling {
             all: colours.all_colour_style,
             avg: colours.avg_colour_style,
             entries,
+            series_symbols: colours.series_symbols,
         }
     }
+
+    /// Returns the symbol for the series of `data_type`, if series are told apart by symbols. The
+    /// graph and the legend both use this, so a series has the same symbol in each.
+    pub fn series_symbol(&self, data_type: CpuDataType) -> Option<char> {
+        if self.series_symbols.is_empty() {
+            return None;
+        }
+
+        let index = match data_type {
+            CpuDataType::Avg => 0,
+            CpuDataType::Cpu(index) => index + 1,
+        };
+        Some(self.series_symbols[index % self.series_symbols.len()])
+    }
 }
 
 pub enum CpuWidgetColumn {
@@ -56,20 +76,30 @@ pub enum CpuWidgetTableData {
     Entry {
         data_type: CpuDataType,
         last_entry: f64,
+        /// The symbol the series is drawn with in the graph, if there is one.
+        symbol: Option<char>,
     },
 }
 
 impl CpuWidgetTableData {
-    pub fn from_cpu_widget_data(data: &CpuWidgetData) -> CpuWidgetTableData {
+    /// Creates a legend entry for `data`, showing its usage at `time_cursor` if it is set, or
+    /// its latest usage otherwise.
+    pub fn from_cpu_widget_data(
+        data: &CpuWidgetData, time_cursor: Option<u64>, styling: &CpuWidgetStyling,
+    ) -> CpuWidgetTableData {
         match data {
             CpuWidgetData::All => CpuWidgetTableData::All,
             CpuWidgetData::Entry {
                 data_type,
-                data: _,
+                data,
                 last_entry,
             } => CpuWidgetTableData::Entry {
                 data_type: *data_type,
-                last_entry: *last_entry,
+                last_entry: match time_cursor {
+                    Some(cursor) => value_at(data, -(cursor as f64)).unwrap_or(0.0),
+                    None => *last_entry,
+                },
+                symbol: styling.series_symbol(*data_type),
             },
         }
     }
@@ -94,27 +124,32 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
             CpuWidgetTableData::Entry {
                 data_type,
                 last_entry,
+                symbol,
             } => {
                 if calculated_width == 0 {
                     None
                 } else {
                     match column {
-                        CpuWidgetColumn::CPU => match data_type {
-                            CpuDataType::Avg => Some(truncate_to_text("AVG", calculated_width)),
-                            CpuDataType::Cpu(index) => {
-                                let index_str = index.to_string();
-                                let text = if calculated_width < CPU_TRUNCATE_BREAKPOINT {
-                                    truncate_to_text(&index_str, calculated_width)
-                                } else {
-                                    truncate_to_text(
-                                        &concat_string!("CPU", index_str),
-                                        calculated_width,
-                                    )
-                                };
-
-                                Some(text)
-                            }
-                        },
+                        CpuWidgetColumn::CPU => {
+                            let name = match data_type {
+                                CpuDataType::Avg => "AVG".to_string(),
+                                CpuDataType::Cpu(index) => {
+                                    let index_str = index.to_string();
+                                    if calculated_width < CPU_TRUNCATE_BREAKPOINT {
+                                        index_str
+                                    } else {
+                                        concat_string!("CPU", index_str)
+                                    }
+                                }
+                            };
+
+                            // Show the symbol the series is drawn with, like the graph legends.
+                            let text = match symbol {
+                                Some(symbol) => concat_string!(symbol.to_string(), " ", name),
+                                None => name,
+                            };
+                            Some(truncate_to_text(&text, calculated_width))
+                        }
                         CpuWidgetColumn::Use => Some(truncate_to_text(
                             &format!("{:.0}%", last_entry.round()),
                             calculated_width,
@@ -129,10 +164,7 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
         let style = match self {
             CpuWidgetTableData::All => painter.colours.all_colour_style,
-            CpuWidgetTableData::Entry {
-                data_type,
-                last_entry: _,
-            } => match data_type {
+            CpuWidgetTableData::Entry { data_type, .. } => match data_type {
                 CpuDataType::Avg => painter.colours.avg_colour_style,
                 CpuDataType::Cpu(index) => {
                     painter.colours.cpu_colour_styles
@@ -159,6 +191,8 @@ pub struct CpuWidgetState {
     pub is_legend_hidden: bool,
     pub show_avg: bool,
     pub autohide_timer: Option<Instant>,
+    /// How far back a time cursor is scrubbed to in milliseconds, if it is.
+    pub time_cursor: Option<u64>,
     pub table: DataTable<CpuWidgetTableData, CpuWidgetColumn>,
     pub styling: CpuWidgetStyling,
 }
@@ -180,6 +214,9 @@ impl CpuWidgetState {
             is_basic: false,
             show_table_scroll_position: false, // TODO: Should this be possible?
             show_current_entry_when_unfocused: true,
+            uppercase_headers: config.uppercase_headers,
+            // The legend belongs to the graph, so it shouldn't dim while the graph is focused.
+            dim_when_unfocused: false,
         };
 
         let styling = DataTableStyling::from_colours(colours);
@@ -189,15 +226,30 @@ impl CpuWidgetState {
             is_legend_hidden: false,
             show_avg: config.show_average_cpu,
             autohide_timer,
+            time_cursor: None,
             table: DataTable::new(COLUMNS, props, styling),
             styling: CpuWidgetStyling::from_colours(colours),
         }
     }
 
+    /// Restyles the legend and graph with `colours`, e.g. after switching colour schemes.
+    pub fn set_colours(&mut self, colours: &CanvasStyling) {
+        self.table.styling.set_colours(colours);
+        self.styling = CpuWidgetStyling::from_colours(colours);
+    }
+
+    /// Brings the legend's settings in line with `config`, e.g. after reloading the config file.
+    pub fn set_table_settings(&mut self, config: &AppConfigFields) {
+        self.table.props.table_gap = config.table_gap;
+        self.table.props.uppercase_headers = config.uppercase_headers;
+    }
+
     pub fn update_table(&mut self, data: &[CpuWidgetData]) {
         self.table.set_data(
             data.iter()
-                .map(CpuWidgetTableData::from_cpu_widget_data)
+                .map(|data| {
+                    CpuWidgetTableData::from_cpu_widget_data(data, self.time_cursor, &self.styling)
+                })
                 .collect(),
         );
     }
@@ -10,7 +10,12 @@ use crate::{
         ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
         SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
-    utils::gen_util::{get_decimal_bytes, sort_partial_fn, truncate_to_text},
+    utils::{
+        gen_util::{hash_row_id, sort_partial_fn, truncate_to_text},
+        units::{
+            format_bytes, format_bytes_per_second, ByteStandard, NumberFormat, MAX_BYTES_WIDTH,
+        },
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -21,39 +26,42 @@ pub struct DiskWidgetData {
     pub used_bytes: Option<u64>,
     pub total_bytes: Option<u64>,
     pub summed_total_bytes: Option<u64>,
-    pub io_read: KString,
-    pub io_write: KString,
+    pub io_read: Option<u64>,
+    pub io_write: Option<u64>,
+    pub byte_standard: ByteStandard,
+    pub number_format: NumberFormat,
 }
 
 impl DiskWidgetData {
     pub fn total_space(&self) -> KString {
-        if let Some(total_bytes) = self.total_bytes {
-            let converted_total_space = get_decimal_bytes(total_bytes);
-            format!(
-                "{:.*}{}",
-                0, converted_total_space.0, converted_total_space.1
-            )
-            .into()
-        } else {
-            "N/A".into()
-        }
+        self.format_bytes(self.total_bytes, format_bytes)
     }
 
     pub fn free_space(&self) -> KString {
-        if let Some(free_bytes) = self.free_bytes {
-            let converted_free_space = get_decimal_bytes(free_bytes);
-            format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1).into()
-        } else {
-            "N/A".into()
-        }
+        self.format_bytes(self.free_bytes, format_bytes)
     }
 
     pub fn used_space(&self) -> KString {
-        if let Some(used_bytes) = self.used_bytes {
-            let converted_free_space = get_decimal_bytes(used_bytes);
-            format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1).into()
-        } else {
-            "N/A".into()
+        self.format_bytes(self.used_bytes, format_bytes)
+    }
+
+    pub fn io_read_rate(&self) -> KString {
+        self.format_bytes(self.io_read, format_bytes_per_second)
+    }
+
+    pub fn io_write_rate(&self) -> KString {
+        self.format_bytes(self.io_write, format_bytes_per_second)
+    }
+
+    fn format_bytes(
+        &self, bytes: Option<u64>, format: fn(u64, ByteStandard, usize) -> String,
+    ) -> KString {
+        match bytes {
+            Some(bytes) => self
+                .number_format
+                .apply(&format(bytes, self.byte_standard, 1))
+                .into(),
+            None => "N/A".into(),
         }
     }
 
@@ -69,7 +77,7 @@ impl DiskWidgetData {
 
     pub fn free_percent_string(&self) -> KString {
         match self.free_percent() {
-            Some(val) => format!("{:.1}%", val).into(),
+            Some(val) => self.number_format.apply(&format!("{:.1}%", val)).into(),
             None => "N/A".into(),
         }
     }
@@ -90,7 +98,7 @@ impl DiskWidgetData {
 
     pub fn used_percent_string(&self) -> KString {
         match self.used_percent() {
-            Some(val) => format!("{:.1}%", val).into(),
+            Some(val) => self.number_format.apply(&format!("{:.1}%", val)).into(),
             None => "N/A".into(),
         }
     }
@@ -116,13 +124,28 @@ impl ColumnHeader for DiskWidgetColumn {
             DiskWidgetColumn::Used => "Used(u)",
             DiskWidgetColumn::Free => "Free(n)",
             DiskWidgetColumn::UsedPercent => "Used%(p)",
-            DiskWidgetColumn::FreePercent => "Free%",
+            DiskWidgetColumn::FreePercent => "Free%(n)",
             DiskWidgetColumn::Total => "Total(t)",
             DiskWidgetColumn::IoRead => "R/s(r)",
             DiskWidgetColumn::IoWrite => "W/s(w)",
         }
         .into()
     }
+
+    fn name(&self) -> Cow<'static, str> {
+        match self {
+            DiskWidgetColumn::Disk => "Disk",
+            DiskWidgetColumn::Mount => "Mount",
+            DiskWidgetColumn::Used => "Used",
+            // This is the same column, whichever way the free space is shown.
+            DiskWidgetColumn::Free | DiskWidgetColumn::FreePercent => "Free",
+            DiskWidgetColumn::UsedPercent => "Used%",
+            DiskWidgetColumn::Total => "Total",
+            DiskWidgetColumn::IoRead => "R/s",
+            DiskWidgetColumn::IoWrite => "W/s",
+        }
+        .into()
+    }
 }
 
 impl DataToCell<DiskWidgetColumn> for DiskWidgetData {
@@ -143,13 +166,17 @@ impl DataToCell<DiskWidgetColumn> for DiskWidgetData {
                 truncate_to_text(&self.free_percent_string(), calculated_width)
             }
             DiskWidgetColumn::Total => truncate_to_text(&self.total_space(), calculated_width),
-            DiskWidgetColumn::IoRead => truncate_to_text(&self.io_read, calculated_width),
-            DiskWidgetColumn::IoWrite => truncate_to_text(&self.io_write, calculated_width),
+            DiskWidgetColumn::IoRead => truncate_to_text(&self.io_read_rate(), calculated_width),
+            DiskWidgetColumn::IoWrite => truncate_to_text(&self.io_write_rate(), calculated_width),
         };
 
         Some(text)
     }
 
+    fn row_id(&self) -> Option<u64> {
+        Some(hash_row_id(&(&self.name, &self.mount_point)))
+    }
+
     fn column_widths<C: DataTableColumn<DiskWidgetColumn>>(
         data: &[Self], _columns: &[C],
     ) -> Vec<u16>
@@ -167,9 +194,17 @@ impl DataToCell<DiskWidgetColumn> for DiskWidgetData {
     }
 }
 
+/// The width of columns showing a number of bytes.
+const BYTES_WIDTH: u16 = MAX_BYTES_WIDTH as u16;
+
+/// The width of columns showing a number of bytes per second, which also fits their headers.
+const RATE_WIDTH: u16 = MAX_BYTES_WIDTH as u16 + 2;
+
 pub struct DiskTableWidget {
     pub table: SortDataTable<DiskWidgetData, DiskWidgetColumn>,
     pub force_update_data: bool,
+    byte_standard: ByteStandard,
+    number_format: NumberFormat,
 }
 
 impl SortsRow for DiskWidgetColumn {
@@ -217,12 +252,12 @@ impl DiskTableWidget {
         let columns = [
             SortColumn::soft(DiskWidgetColumn::Disk, Some(0.2)),
             SortColumn::soft(DiskWidgetColumn::Mount, Some(0.2)),
-            SortColumn::hard(DiskWidgetColumn::Used, 8).default_descending(),
-            SortColumn::hard(DiskWidgetColumn::Free, 8).default_descending(),
-            SortColumn::hard(DiskWidgetColumn::Total, 9).default_descending(),
+            SortColumn::hard(DiskWidgetColumn::Used, BYTES_WIDTH).default_descending(),
+            SortColumn::hard(DiskWidgetColumn::Free, BYTES_WIDTH).default_descending(),
+            SortColumn::hard(DiskWidgetColumn::Total, BYTES_WIDTH).default_descending(),
             SortColumn::hard(DiskWidgetColumn::UsedPercent, 9).default_descending(),
-            SortColumn::hard(DiskWidgetColumn::IoRead, 10).default_descending(),
-            SortColumn::hard(DiskWidgetColumn::IoWrite, 11).default_descending(),
+            SortColumn::hard(DiskWidgetColumn::IoRead, RATE_WIDTH).default_descending(),
+            SortColumn::hard(DiskWidgetColumn::IoWrite, RATE_WIDTH).default_descending(),
         ];
 
         let props = SortDataTableProps {
@@ -233,6 +268,8 @@ impl DiskTableWidget {
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
                 show_current_entry_when_unfocused: false,
+                uppercase_headers: config.uppercase_headers,
+                dim_when_unfocused: config.dim_unfocused_tables,
             },
             sort_index: 0,
             order: SortOrder::Ascending,
@@ -241,8 +278,10 @@ impl DiskTableWidget {
         let styling = DataTableStyling::from_colours(colours);
 
         Self {
-            table: SortDataTable::new_sortable(columns, props, styling),
+            table: SortDataTable::new_sortable(columns, props, styling).with_row_details(true),
             force_update_data: false,
+            byte_standard: config.byte_standard,
+            number_format: config.number_format,
         }
     }
 
@@ -252,16 +291,177 @@ impl DiskTableWidget {
         self.force_update_data = true;
     }
 
+    /// Restyles the table with `colours`, e.g. after switching colour schemes.
+    pub fn set_colours(&mut self, colours: &CanvasStyling) {
+        self.table.styling.set_colours(colours);
+    }
+
+    /// Brings the table's settings in line with `config`, e.g. after reloading the config file.
+    pub fn set_table_settings(&mut self, config: &AppConfigFields) {
+        let props = &mut self.table.props;
+        props.table_gap = config.table_gap;
+        props.show_table_scroll_position = config.show_table_scroll_position;
+        props.uppercase_headers = config.uppercase_headers;
+        props.dim_when_unfocused = config.dim_unfocused_tables;
+    }
+
+    /// Sets whether sizes are shown with binary or decimal prefixes.
+    pub fn set_byte_standard(&mut self, byte_standard: ByteStandard) {
+        self.byte_standard = byte_standard;
+        self.force_data_update();
+    }
+
+    /// Sets how numbers are written.
+    pub fn set_number_format(&mut self, number_format: NumberFormat) {
+        self.number_format = number_format;
+        self.force_data_update();
+    }
+
     pub fn ingest_data(&mut self, data: &[DiskWidgetData]) {
         let mut data = data.to_vec();
+        for row in &mut data {
+            row.byte_standard = self.byte_standard;
+            row.number_format = self.number_format;
+        }
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
             column.sort_by(&mut data, self.table.order());
         }
         self.table.set_data(data);
     }
 
-    pub fn set_index(&mut self, index: usize) {
-        self.table.set_sort_index(index);
-        self.force_data_update();
+    /// Sorts by the column with the given [name](ColumnHeader::name), if the table has it.
+    pub fn sort_by_column(&mut self, name: &str) {
+        if let Some(index) = self.table.column_index_by_name(name) {
+            self.table.set_sort_index(index);
+            self.force_data_update();
+        }
+    }
+
+    /// Toggles the free space column between showing values and percentages.
+    pub fn toggle_free_percentage(&mut self) {
+        for column in self.table.columns.iter_mut() {
+            let inner = column.inner_mut();
+            match inner {
+                DiskWidgetColumn::Free => *inner = DiskWidgetColumn::FreePercent,
+                DiskWidgetColumn::FreePercent => *inner = DiskWidgetColumn::Free,
+                _ => continue,
+            }
+
+            self.force_data_update();
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_toggle_free_percentage() {
+        let mut disk = DiskTableWidget::new(&AppConfigFields::default(), &CanvasStyling::default());
+        let free_column = |disk: &DiskTableWidget| {
+            disk.table
+                .columns
+                .iter()
+                .map(|column| column.inner().text())
+                .find(|text| text.starts_with("Free"))
+                .unwrap()
+        };
+        assert_eq!(free_column(&disk), "Free(n)");
+
+        disk.toggle_free_percentage();
+        assert_eq!(free_column(&disk), "Free%(n)");
+        assert!(disk.force_update_data);
+
+        disk.toggle_free_percentage();
+        assert_eq!(free_column(&disk), "Free(n)");
+    }
+
+    #[test]
+    fn test_set_colours_keeps_overrides() {
+        use tui::style::{Color, Style};
+
+        use crate::canvas::canvas_styling::{Role, StyleResolver};
+
+        let mut disk = DiskTableWidget::new(&AppConfigFields::default(), &CanvasStyling::default());
+        let header = Style::default().fg(Color::Magenta);
+        disk.table.styling =
+            std::mem::take(&mut disk.table.styling).with_override(Role::TableHeader, header);
+
+        let colours = CanvasStyling::no_colour();
+        disk.set_colours(&colours);
+        assert_eq!(disk.table.styling.resolve(Role::TableHeader), header);
+        assert_eq!(
+            disk.table.styling.resolve(Role::Text),
+            colours.resolve(Role::Text)
+        );
+    }
+
+    #[test]
+    fn test_byte_standard() {
+        let mut disk = DiskTableWidget::new(&AppConfigFields::default(), &CanvasStyling::default());
+        let row = DiskWidgetData {
+            name: "sda".into(),
+            mount_point: "/".into(),
+            free_bytes: Some(1024),
+            used_bytes: None,
+            total_bytes: Some(1_610_612_736),
+            summed_total_bytes: None,
+            io_read: Some(1536),
+            io_write: None,
+            byte_standard: ByteStandard::default(),
+            number_format: NumberFormat::default(),
+        };
+
+        disk.ingest_data(std::slice::from_ref(&row));
+        let data = disk.table.data();
+        assert_eq!(data[0].total_space(), "1.5 GiB");
+        assert_eq!(data[0].free_space(), "1.0 KiB");
+        assert_eq!(data[0].used_space(), "N/A");
+        assert_eq!(data[0].io_read_rate(), "1.5 KiB/s");
+
+        disk.set_byte_standard(ByteStandard::Decimal);
+        assert!(disk.force_update_data);
+        disk.ingest_data(&[row]);
+        let data = disk.table.data();
+        assert_eq!(data[0].total_space(), "1.6 GB");
+        assert_eq!(data[0].free_space(), "1.0 KB");
+        assert_eq!(data[0].io_read_rate(), "1.5 KB/s");
+        assert_eq!(data[0].io_write_rate(), "N/A");
+    }
+
+    #[test]
+    fn test_number_format() {
+        let mut disk = DiskTableWidget::new(&AppConfigFields::default(), &CanvasStyling::default());
+        let row = |name: &'static str, total_bytes: u64| DiskWidgetData {
+            name: name.into(),
+            mount_point: "/".into(),
+            free_bytes: Some(1023),
+            used_bytes: Some(total_bytes - 1023),
+            total_bytes: Some(total_bytes),
+            summed_total_bytes: Some(total_bytes),
+            io_read: None,
+            io_write: None,
+            byte_standard: ByteStandard::default(),
+            number_format: NumberFormat::default(),
+        };
+
+        disk.set_number_format(NumberFormat {
+            grouping: Some(' '),
+            decimal: ',',
+        });
+        assert!(disk.force_update_data);
+        disk.set_byte_standard(ByteStandard::Binary);
+        disk.sort_by_column("Total");
+        disk.ingest_data(&[row("big", 1_500_000_000), row("small", 999_000)]);
+
+        // Sorting by total still uses the values, so "1,4 GiB" comes before "976 KiB" when
+        // descending.
+        let data = disk.table.data();
+        assert_eq!(data[0].total_space(), "1,4 GiB");
+        assert_eq!(data[0].used_percent_string(), "100,0%");
+        assert_eq!(data[1].free_space(), "1 023 B");
+        assert_eq!(data[1].total_space(), "976 KiB");
     }
 }
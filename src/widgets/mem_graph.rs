@@ -3,6 +3,8 @@ use std::time::Instant;
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// How far back a time cursor is scrubbed to in milliseconds, if it is.
+    pub time_cursor: Option<u64>,
 }
 
 impl MemWidgetState {
@@ -10,6 +12,7 @@ impl MemWidgetState {
         MemWidgetState {
             current_display_time,
             autohide_timer,
+            time_cursor: None,
         }
     }
 }
@@ -15,7 +15,12 @@ use crate::{
     canvas::canvas_styling::CanvasStyling,
     components::data_table::{
         Column, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps,
-        DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        DataTableStyling, RowDeltas, ScrollPosition, SortColumn, SortDataTable, SortDataTableProps,
+        SortOrder, SortsRow,
+    },
+    utils::{
+        gen_util::hash_row_id,
+        units::{ByteStandard, NumberFormat},
     },
     Pid,
 };
@@ -34,6 +39,7 @@ pub struct ProcessSearchState {
     pub search_state: AppSearchState,
     pub is_ignoring_case: bool,
     pub is_searching_whole_word: bool,
+    pub is_matching_word_boundary: bool,
     pub is_searching_with_regex: bool,
 }
 
@@ -43,6 +49,7 @@ impl Default for ProcessSearchState {
             search_state: AppSearchState::default(),
             is_ignoring_case: true,
             is_searching_whole_word: false,
+            is_matching_word_boundary: false,
             is_searching_with_regex: false,
         }
     }
@@ -57,6 +64,10 @@ impl ProcessSearchState {
         self.is_searching_whole_word = !self.is_searching_whole_word;
     }
 
+    pub fn search_toggle_word_boundary(&mut self) {
+        self.is_matching_word_boundary = !self.is_matching_word_boundary;
+    }
+
     pub fn search_toggle_regex(&mut self) {
         self.is_searching_with_regex = !self.is_searching_with_regex;
     }
@@ -76,12 +87,14 @@ type StringPidMap = HashMap<String, Vec<Pid>>;
 fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
     use ProcColumn::*;
 
+    // Numbers may be written with separators, so their columns are sized to their values rather
+    // than to their headers.
     match column {
-        CpuPercent => SortColumn::new(CpuPercent).default_descending(),
-        MemoryVal => SortColumn::new(MemoryVal).default_descending(),
-        MemoryPercent => SortColumn::new(MemoryPercent).default_descending(),
-        Pid => SortColumn::new(Pid),
-        Count => SortColumn::new(Count),
+        CpuPercent => SortColumn::soft(CpuPercent, None).default_descending(),
+        MemoryVal => SortColumn::soft(MemoryVal, None).default_descending(),
+        MemoryPercent => SortColumn::soft(MemoryPercent, None).default_descending(),
+        Pid => SortColumn::soft(Pid, None),
+        Count => SortColumn::soft(Count, None),
         Name => SortColumn::soft(Name, Some(0.3)),
         Command => SortColumn::soft(Command, Some(0.3)),
         ReadPerSecond => SortColumn::hard(ReadPerSecond, 8).default_descending(),
@@ -91,6 +104,8 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
         User => SortColumn::soft(User, Some(0.05)),
         State => SortColumn::hard(State, 7),
         Time => SortColumn::new(Time),
+        CpuDelta => SortColumn::hard(CpuDelta, 8).default_descending(),
+        MemDelta => SortColumn::hard(MemDelta, 10).default_descending(),
     }
 }
 
@@ -117,6 +132,90 @@ pub enum ProcWidgetColumn {
     User,
     State,
     Time,
+    CpuDelta,
+    MemDelta,
+}
+
+impl ProcWidgetColumn {
+    /// Every kind of column, in the order they can be listed in the config.
+    pub const ALL: [Self; 13] = [
+        ProcWidgetColumn::PidOrCount,
+        ProcWidgetColumn::ProcNameOrCommand,
+        ProcWidgetColumn::Cpu,
+        ProcWidgetColumn::Mem,
+        ProcWidgetColumn::ReadPerSecond,
+        ProcWidgetColumn::WritePerSecond,
+        ProcWidgetColumn::TotalRead,
+        ProcWidgetColumn::TotalWrite,
+        ProcWidgetColumn::User,
+        ProcWidgetColumn::State,
+        ProcWidgetColumn::Time,
+        ProcWidgetColumn::CpuDelta,
+        ProcWidgetColumn::MemDelta,
+    ];
+
+    /// Returns the name the config refers to this kind of column by.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcWidgetColumn::PidOrCount => "PID",
+            ProcWidgetColumn::ProcNameOrCommand => "Name",
+            ProcWidgetColumn::Cpu => "CPU%",
+            ProcWidgetColumn::Mem => "Mem",
+            ProcWidgetColumn::ReadPerSecond => "R/s",
+            ProcWidgetColumn::WritePerSecond => "W/s",
+            ProcWidgetColumn::TotalRead => "T.Read",
+            ProcWidgetColumn::TotalWrite => "T.Write",
+            ProcWidgetColumn::User => "User",
+            ProcWidgetColumn::State => "State",
+            ProcWidgetColumn::Time => "Time",
+            ProcWidgetColumn::CpuDelta => "dCPU%",
+            ProcWidgetColumn::MemDelta => "dMem",
+        }
+    }
+
+    /// Returns the kind of column `name` refers to, ignoring case. Either way a column can be
+    /// shown works, e.g. both "PID" and "Count".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "cpu%" => Some(ProcWidgetColumn::Cpu),
+            "mem" => Some(ProcWidgetColumn::Mem),
+            "mem%" => Some(ProcWidgetColumn::Mem),
+            "pid" => Some(ProcWidgetColumn::PidOrCount),
+            "count" => Some(ProcWidgetColumn::PidOrCount),
+            "name" => Some(ProcWidgetColumn::ProcNameOrCommand),
+            "command" => Some(ProcWidgetColumn::ProcNameOrCommand),
+            "read" | "r/s" | "rps" => Some(ProcWidgetColumn::ReadPerSecond),
+            "write" | "w/s" | "wps" => Some(ProcWidgetColumn::WritePerSecond),
+            "tread" | "t.read" => Some(ProcWidgetColumn::TotalRead),
+            "twrite" | "t.write" => Some(ProcWidgetColumn::TotalWrite),
+            "state" => Some(ProcWidgetColumn::State),
+            "user" => Some(ProcWidgetColumn::User),
+            "time" => Some(ProcWidgetColumn::Time),
+            "dcpu%" | "δcpu%" => Some(ProcWidgetColumn::CpuDelta),
+            "dmem" | "δmem" => Some(ProcWidgetColumn::MemDelta),
+            _ => None,
+        }
+    }
+}
+
+impl From<&ProcColumn> for ProcWidgetColumn {
+    fn from(column: &ProcColumn) -> Self {
+        match column {
+            ProcColumn::CpuPercent => ProcWidgetColumn::Cpu,
+            ProcColumn::MemoryVal | ProcColumn::MemoryPercent => ProcWidgetColumn::Mem,
+            ProcColumn::Pid | ProcColumn::Count => ProcWidgetColumn::PidOrCount,
+            ProcColumn::Name | ProcColumn::Command => ProcWidgetColumn::ProcNameOrCommand,
+            ProcColumn::ReadPerSecond => ProcWidgetColumn::ReadPerSecond,
+            ProcColumn::WritePerSecond => ProcWidgetColumn::WritePerSecond,
+            ProcColumn::TotalRead => ProcWidgetColumn::TotalRead,
+            ProcColumn::TotalWrite => ProcWidgetColumn::TotalWrite,
+            ProcColumn::State => ProcWidgetColumn::State,
+            ProcColumn::User => ProcWidgetColumn::User,
+            ProcColumn::Time => ProcWidgetColumn::Time,
+            ProcColumn::CpuDelta => ProcWidgetColumn::CpuDelta,
+            ProcColumn::MemDelta => ProcWidgetColumn::MemDelta,
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for ProcWidgetColumn {
@@ -124,24 +223,9 @@ impl<'de> Deserialize<'de> for ProcWidgetColumn {
     where
         D: serde::Deserializer<'de>,
     {
-        let value = String::deserialize(deserializer)?.to_lowercase();
-        match value.as_str() {
-            "cpu%" => Ok(ProcWidgetColumn::Cpu),
-            "mem" => Ok(ProcWidgetColumn::Mem),
-            "mem%" => Ok(ProcWidgetColumn::Mem),
-            "pid" => Ok(ProcWidgetColumn::PidOrCount),
-            "count" => Ok(ProcWidgetColumn::PidOrCount),
-            "name" => Ok(ProcWidgetColumn::ProcNameOrCommand),
-            "command" => Ok(ProcWidgetColumn::ProcNameOrCommand),
-            "read" | "r/s" | "rps" => Ok(ProcWidgetColumn::ReadPerSecond),
-            "write" | "w/s" | "wps" => Ok(ProcWidgetColumn::WritePerSecond),
-            "tread" | "t.read" => Ok(ProcWidgetColumn::TotalRead),
-            "twrite" | "t.write" => Ok(ProcWidgetColumn::TotalWrite),
-            "state" => Ok(ProcWidgetColumn::State),
-            "user" => Ok(ProcWidgetColumn::User),
-            "time" => Ok(ProcWidgetColumn::Time),
-            _ => Err(D::Error::custom("doesn't match any column type")),
-        }
+        let value = String::deserialize(deserializer)?;
+        ProcWidgetColumn::from_name(&value)
+            .ok_or_else(|| D::Error::custom("doesn't match any column type"))
     }
 }
 
@@ -172,6 +256,35 @@ pub struct ProcWidgetState {
     default_sort_order: SortOrder,
 
     pub is_sort_open: bool,
+
+    /// Whether sorting is frozen, in which case new data keeps the order rows were last shown in.
+    sort_frozen: bool,
+
+    /// Whether memory and IO are shown with binary or decimal prefixes.
+    byte_standard: ByteStandard,
+
+    /// How numbers are written.
+    number_format: NumberFormat,
+
+    /// The indices of columns hidden by the config, which stay hidden even when toggling modes.
+    config_hidden_columns: HashSet<usize>,
+
+    /// The PID that was selected before switching to grouped mode, to select again when switching back.
+    ungrouped_selected_pid: Option<Pid>,
+
+    /// The CPU and memory usage of each row in the previous update, for the delta columns.
+    deltas: RowDeltas<(f32, MemUsage)>,
+
+    /// Whether searching keeps the selection, and clearing the search scrolls back to where the
+    /// table was before searching.
+    restore_scroll_after_search: bool,
+
+    /// Where the table was scrolled to before it was filtered by a search.
+    pre_search_position: Option<ScrollPosition>,
+
+    /// Whether to return to [`ProcWidgetState::pre_search_position`] on the next data update, as
+    /// the search was cleared.
+    is_restoring_scroll: bool,
     pub force_rerender: bool,
     pub force_update_data: bool,
 }
@@ -187,6 +300,8 @@ impl ProcWidgetState {
             is_basic: false,
             show_table_scroll_position: false,
             show_current_entry_when_unfocused: false,
+            uppercase_headers: config.uppercase_headers,
+            dim_when_unfocused: config.dim_unfocused_tables,
         };
         let styling = DataTableStyling::from_colours(colours);
 
@@ -204,6 +319,8 @@ impl ProcWidgetState {
             is_basic: config.use_basic_mode,
             show_table_scroll_position: config.show_table_scroll_position,
             show_current_entry_when_unfocused: false,
+            uppercase_headers: config.uppercase_headers,
+            dim_when_unfocused: config.dim_unfocused_tables,
         };
         let props = SortDataTableProps {
             inner: inner_props,
@@ -212,7 +329,8 @@ impl ProcWidgetState {
         };
         let styling = DataTableStyling::from_colours(colours);
 
-        DataTable::new_sortable(columns, props, styling)
+        // The last row is always the totals.
+        DataTable::new_sortable(columns, props, styling).with_pinned_bottom_rows(1)
     }
 
     pub fn new(
@@ -277,6 +395,8 @@ impl ProcWidgetState {
                             ProcWidgetColumn::User => User,
                             ProcWidgetColumn::State => State,
                             ProcWidgetColumn::Time => Time,
+                            ProcWidgetColumn::CpuDelta => CpuDelta,
+                            ProcWidgetColumn::MemDelta => MemDelta,
                         };
 
                         make_column(col)
@@ -303,23 +423,7 @@ impl ProcWidgetState {
 
         let column_mapping = columns
             .iter()
-            .map(|col| {
-                use ProcColumn::*;
-
-                match col.inner() {
-                    CpuPercent => ProcWidgetColumn::Cpu,
-                    MemoryVal | MemoryPercent => ProcWidgetColumn::Mem,
-                    Pid | Count => ProcWidgetColumn::PidOrCount,
-                    Name | Command => ProcWidgetColumn::ProcNameOrCommand,
-                    ReadPerSecond => ProcWidgetColumn::ReadPerSecond,
-                    WritePerSecond => ProcWidgetColumn::WritePerSecond,
-                    TotalRead => ProcWidgetColumn::TotalRead,
-                    TotalWrite => ProcWidgetColumn::TotalWrite,
-                    State => ProcWidgetColumn::State,
-                    User => ProcWidgetColumn::User,
-                    Time => ProcWidgetColumn::Time,
-                }
-            })
+            .map(|col| ProcWidgetColumn::from(col.inner()))
             .collect::<IndexSet<_>>();
 
         let (default_sort_index, default_sort_order) =
@@ -353,6 +457,15 @@ impl ProcWidgetState {
             id_pid_map,
             column_mapping,
             is_sort_open: false,
+            sort_frozen: false,
+            byte_standard: config.byte_standard,
+            number_format: config.number_format,
+            config_hidden_columns: HashSet::default(),
+            ungrouped_selected_pid: None,
+            deltas: RowDeltas::default(),
+            restore_scroll_after_search: config.restore_scroll_after_search,
+            pre_search_position: None,
+            is_restoring_scroll: false,
             mode,
             force_rerender: true,
             force_update_data: false,
@@ -364,6 +477,51 @@ impl ProcWidgetState {
         table
     }
 
+    /// Restyles the tables with `colours`, e.g. after switching colour schemes.
+    pub fn set_colours(&mut self, colours: &CanvasStyling) {
+        self.table.styling.set_colours(colours);
+        self.sort_table.styling.set_colours(colours);
+        self.force_rerender = true;
+    }
+
+    /// Brings the process and sort tables' settings in line with `config`, e.g. after reloading
+    /// the config file.
+    pub fn set_table_settings(&mut self, config: &AppConfigFields) {
+        let props = &mut self.table.props;
+        props.table_gap = config.table_gap;
+        props.show_table_scroll_position = config.show_table_scroll_position;
+        props.uppercase_headers = config.uppercase_headers;
+        props.dim_when_unfocused = config.dim_unfocused_tables;
+
+        let sort_props = &mut self.sort_table.props;
+        sort_props.table_gap = config.table_gap;
+        sort_props.uppercase_headers = config.uppercase_headers;
+        sort_props.dim_when_unfocused = config.dim_unfocused_tables;
+
+        self.force_rerender = true;
+    }
+
+    /// Sets whether memory and IO are shown with binary or decimal prefixes.
+    pub fn set_byte_standard(&mut self, byte_standard: ByteStandard) {
+        self.byte_standard = byte_standard;
+        self.force_data_update();
+    }
+
+    /// Sets how numbers are written.
+    pub fn set_number_format(&mut self, number_format: NumberFormat) {
+        self.number_format = number_format;
+        self.force_data_update();
+    }
+
+    /// Sets whether clearing the search scrolls back to where the table was before searching.
+    pub fn set_restore_scroll_after_search(&mut self, restore_scroll_after_search: bool) {
+        self.restore_scroll_after_search = restore_scroll_after_search;
+        if !restore_scroll_after_search {
+            self.pre_search_position = None;
+            self.is_restoring_scroll = false;
+        }
+    }
+
     pub fn is_using_command(&self) -> bool {
         self.column_mapping
             .get_index_of(&ProcWidgetColumn::ProcNameOrCommand)
@@ -395,15 +553,117 @@ impl ProcWidgetState {
     /// This function *only* updates the displayed process data. If there is a need to update the actual *stored* data,
     /// call it before this function.
     pub fn ingest_data(&mut self, data_collection: &DataCollection) {
-        let data = match &self.mode {
+        // Every process is recorded, not just the shown ones, so processes that the search or a
+        // collapsed branch hid in the previous update aren't shown as new. Groups are recorded
+        // while they're built instead.
+        if !matches!(self.mode, ProcWidgetMode::Grouped) {
+            let is_mem_percent = self.is_mem_percent();
+            self.deltas.record(
+                data_collection.current_instant,
+                data_collection
+                    .process_data
+                    .process_harvest
+                    .values()
+                    .map(|process| {
+                        let usage = (
+                            process.cpu_usage_percent,
+                            MemUsage::of(process, is_mem_percent),
+                        );
+                        (process.pid as u64, usage)
+                    }),
+            );
+        }
+
+        let mut data = match &self.mode {
             ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
-                self.get_normal_data(&data_collection.process_data.process_harvest)
+                self.get_normal_data(data_collection)
             }
             ProcWidgetMode::Tree { collapsed_pids } => {
                 self.get_tree_data(collapsed_pids, data_collection)
             }
         };
+        for row in &mut data {
+            row.byte_standard = self.byte_standard;
+            row.number_format = self.number_format;
+        }
+
+        let mut totals = self.totals_row(&data_collection.process_data.process_harvest);
+        totals.byte_standard = self.byte_standard;
+        totals.number_format = self.number_format;
+        data.push(totals);
         self.table.set_data(data);
+
+        if let ProcWidgetMode::Normal = self.mode {
+            if let Some(pid) = self.ungrouped_selected_pid.take() {
+                if let Some(index) = self.table.data().iter().position(|row| row.pid == pid) {
+                    self.table.set_position(index);
+                }
+            }
+        }
+
+        if self.is_restoring_scroll {
+            self.is_restoring_scroll = false;
+            if let Some(position) = self.pre_search_position.take() {
+                self.table.restore_scroll_position(position);
+            }
+        }
+    }
+
+    /// Fills in how the CPU and memory usage of `row` changed since the previous update.
+    fn with_deltas(&self, mut row: ProcWidgetData) -> ProcWidgetData {
+        let usage = (row.cpu_usage_percent, row.mem_usage.clone());
+        row.cpu_delta = self
+            .deltas
+            .delta(row.row_id, &usage, |(cpu, _), (previous, _)| {
+                Some(f64::from(*cpu) - f64::from(*previous))
+            });
+        row.mem_delta = self
+            .deltas
+            .delta(row.row_id, &usage, |(_, mem), (_, previous)| {
+                mem.change_since(previous)
+            });
+        row
+    }
+
+    /// Sums up the processes that are shown into a row of totals. Each process is only counted
+    /// once, even if it is also part of a group or a collapsed branch. If the table is filtered,
+    /// only the processes that match are counted, and the row says how many of all of them match.
+    fn totals_row(&self, process_harvest: &BTreeMap<Pid, ProcessHarvest>) -> ProcWidgetData {
+        let mut sum = ProcessHarvest::default();
+        let mut num_processes = 0;
+        let add = |process: &ProcessHarvest| {
+            sum.add(process);
+            num_processes += 1;
+        };
+
+        let search_query = self.get_query();
+        if let ProcWidgetMode::Grouped = self.mode {
+            // Groups are matched as a whole, and only the groups that matched are kept here.
+            self.id_pid_map
+                .values()
+                .flatten()
+                .filter_map(|pid| process_harvest.get(pid))
+                .for_each(add);
+        } else {
+            let is_using_command = self.is_using_command();
+            process_harvest
+                .values()
+                .filter(|process| {
+                    search_query
+                        .as_ref()
+                        .map(|query| query.check(process, is_using_command))
+                        .unwrap_or(true)
+                })
+                .for_each(add);
+        }
+
+        sum.name = if search_query.is_some() {
+            format!("Matching: {num_processes} of {}", process_harvest.len())
+        } else {
+            format!("Total: {num_processes}")
+        };
+
+        ProcWidgetData::from_data(&sum, false, self.is_mem_percent()).totals(num_processes as u64)
     }
 
     fn get_tree_data(
@@ -417,6 +677,13 @@ impl ProcWidgetState {
         let search_query = self.get_query();
         let is_using_command = self.is_using_command();
         let is_mem_percent = self.is_mem_percent();
+        let to_row = |process: &ProcessHarvest| {
+            self.with_deltas(ProcWidgetData::from_data(
+                process,
+                is_using_command,
+                is_mem_percent,
+            ))
+        };
 
         let ProcessData {
             process_harvest,
@@ -442,17 +709,23 @@ impl ProcWidgetState {
                 }
             })
             .collect::<HashSet<_>>();
+        let root_pids = orphan_pids.iter().copied().collect::<HashSet<_>>();
 
         #[inline]
         fn is_ancestor_shown(
-            current_process: &ProcessHarvest, kept_pids: &HashSet<Pid>,
+            current_process: &ProcessHarvest, kept_pids: &HashSet<Pid>, root_pids: &HashSet<Pid>,
             process_harvest: &BTreeMap<Pid, ProcessHarvest>,
         ) -> bool {
+            // Roots may still have a parent PID (e.g. a reused one), but nothing above them is shown.
+            if root_pids.contains(&current_process.pid) {
+                return false;
+            }
+
             if let Some(ppid) = current_process.parent_pid {
                 if kept_pids.contains(&ppid) {
                     true
                 } else if let Some(parent) = process_harvest.get(&ppid) {
-                    is_ancestor_shown(parent, kept_pids, process_harvest)
+                    is_ancestor_shown(parent, kept_pids, root_pids, process_harvest)
                 } else {
                     false
                 }
@@ -461,6 +734,10 @@ impl ProcWidgetState {
             }
         }
 
+        // Processes with a descendant that matches the search, which are shown expanded even if
+        // they were collapsed.
+        let mut matching_ancestor_pids: HashSet<Pid> = HashSet::default();
+
         // A process is shown under the filtered tree if at least one of these conditions hold:
         // - The process itself matches.
         // - The process contains some descendant that matches.
@@ -488,13 +765,21 @@ impl ProcWidgetState {
                             .filter(|pid| visited_pids.get(*pid).copied().unwrap_or(false))
                             .collect_vec();
 
+                        if search_query.is_some()
+                            && shown_children.iter().any(|pid| {
+                                kept_pids.contains(*pid) || matching_ancestor_pids.contains(*pid)
+                            })
+                        {
+                            matching_ancestor_pids.insert(process.pid);
+                        }
+
                         // Show the entry if it is:
                         // - Matches the filter.
                         // - Has at least one child (doesn't have to be direct) that matches the filter.
                         // - Is the child of a shown process.
                         let is_shown = is_process_matching
                             || !shown_children.is_empty()
-                            || is_ancestor_shown(process, &kept_pids, process_harvest);
+                            || is_ancestor_shown(process, &kept_pids, &root_pids, process_harvest);
                         visited_pids.insert(process.pid, is_shown);
 
                         if is_shown {
@@ -521,7 +806,7 @@ impl ProcWidgetState {
                     }
                 } else {
                     let is_shown = is_process_matching
-                        || is_ancestor_shown(process, &kept_pids, process_harvest);
+                        || is_ancestor_shown(process, &kept_pids, &root_pids, process_harvest);
 
                     if is_shown {
                         filtered_tree.insert(process.pid, vec![]);
@@ -541,9 +826,7 @@ impl ProcWidgetState {
             .iter()
             .filter_map(|pid| {
                 if filtered_tree.contains_key(pid) {
-                    process_harvest.get(pid).map(|process| {
-                        ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
-                    })
+                    process_harvest.get(pid).map(to_row)
                 } else {
                     None
                 }
@@ -553,7 +836,11 @@ impl ProcWidgetState {
         stack.sort_unstable_by_key(|p| p.pid);
 
         let column = self.table.columns.get(self.table.sort_index()).unwrap();
-        sort_skip_pid_asc(column.inner(), &mut stack, self.table.order());
+        if self.sort_frozen {
+            keep_previous_order(self.table.data(), &mut stack);
+        } else {
+            sort_skip_pid_asc(column.inner(), &mut stack, self.table.order());
+        }
 
         let mut length_stack = vec![stack.len()];
         stack.reverse();
@@ -564,28 +851,28 @@ impl ProcWidgetState {
             let disabled = !kept_pids.contains(&process.pid);
             let is_last = *siblings_left == 0;
 
-            if collapsed_pids.contains(&process.pid) {
+            if collapsed_pids.contains(&process.pid)
+                && !matching_ancestor_pids.contains(&process.pid)
+            {
                 let mut summed_process = process.clone();
+                let mut hidden_descendants = 0;
 
                 if let Some(children_pids) = filtered_tree.get(&process.pid) {
                     let mut sum_queue = children_pids
                         .iter()
-                        .filter_map(|child| {
-                            process_harvest.get(child).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                            })
-                        })
+                        .filter_map(|child| process_harvest.get(child).map(to_row))
                         .collect_vec();
 
                     while let Some(process) = sum_queue.pop() {
+                        // This adds up the deltas of the hidden processes too.
                         summed_process.add(&process);
+                        hidden_descendants += 1;
 
                         if let Some(pids) = filtered_tree.get(&process.pid) {
-                            sum_queue.extend(pids.iter().filter_map(|child| {
-                                process_harvest.get(child).map(|p| {
-                                    ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                                })
-                            }));
+                            sum_queue.extend(
+                                pids.iter()
+                                    .filter_map(|child| process_harvest.get(child).map(to_row)),
+                            );
                         }
                     }
                 }
@@ -601,7 +888,14 @@ impl ProcWidgetState {
                     )
                 };
 
-                data.push(summed_process.prefix(Some(prefix)).disabled(disabled));
+                let suffix = (hidden_descendants > 0).then(|| format!(" (+{hidden_descendants})"));
+
+                data.push(
+                    summed_process
+                        .prefix(Some(prefix))
+                        .suffix(suffix)
+                        .disabled(disabled),
+                );
             } else {
                 let prefix = if prefixes.is_empty() {
                     String::default()
@@ -629,14 +923,16 @@ impl ProcWidgetState {
 
                     let mut children = children_pids
                         .iter()
-                        .filter_map(|child_pid| {
-                            process_harvest.get(child_pid).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                            })
-                        })
+                        .filter_map(|child_pid| process_harvest.get(child_pid).map(to_row))
                         .collect_vec();
 
-                    column.sort_by(&mut children, self.table.order().rev());
+                    // Children are popped off the stack from the back, so they're sorted in reverse.
+                    if self.sort_frozen {
+                        keep_previous_order(self.table.data(), &mut children);
+                        children.reverse();
+                    } else {
+                        column.sort_by(&mut children, self.table.order().rev());
+                    }
 
                     length_stack.push(children.len());
                     stack.extend(children);
@@ -656,24 +952,17 @@ impl ProcWidgetState {
         data
     }
 
-    fn get_normal_data(
-        &mut self, process_harvest: &BTreeMap<Pid, ProcessHarvest>,
-    ) -> Vec<ProcWidgetData> {
-        let search_query = self.get_query();
+    fn get_normal_data(&mut self, data_collection: &DataCollection) -> Vec<ProcWidgetData> {
+        let process_harvest = &data_collection.process_data.process_harvest;
         let is_using_command = self.is_using_command();
         let is_mem_percent = self.is_mem_percent();
 
-        let filtered_iter = process_harvest.values().filter(|process| {
-            search_query
-                .as_ref()
-                .map(|query| query.check(process, is_using_command))
-                .unwrap_or(true)
-        });
-
         let mut id_pid_map: HashMap<String, Vec<Pid>> = HashMap::default();
-        let mut filtered_data: Vec<ProcWidgetData> = if let ProcWidgetMode::Grouped = self.mode {
-            let mut id_process_mapping: HashMap<&String, ProcessHarvest> = HashMap::default();
-            for process in filtered_iter {
+        let mut id_process_mapping: HashMap<&String, ProcessHarvest> = HashMap::default();
+        if let ProcWidgetMode::Grouped = self.mode {
+            // Groups are searched by their combined values and members, so they are built before
+            // filtering.
+            for process in process_harvest.values() {
                 let id = if is_using_command {
                     &process.command
                 } else {
@@ -695,6 +984,51 @@ impl ProcWidgetState {
                 }
             }
 
+            // Every group is recorded, not just the ones that match the search, so groups that
+            // didn't match in the previous update aren't shown as new.
+            self.deltas.record(
+                data_collection.current_instant,
+                id_process_mapping.iter().map(|(id, process)| {
+                    let usage = (
+                        process.cpu_usage_percent,
+                        MemUsage::of(process, is_mem_percent),
+                    );
+                    (hash_row_id(id), usage)
+                }),
+            );
+        }
+
+        let search_query = self.get_query();
+        let is_kept = |process: &ProcessHarvest| {
+            search_query
+                .as_ref()
+                .map(|query| query.check(process, is_using_command))
+                .unwrap_or(true)
+        };
+
+        let mut filtered_data: Vec<ProcWidgetData> = if let ProcWidgetMode::Grouped = self.mode {
+            id_process_mapping.retain(|id, process| {
+                let is_kept = search_query
+                    .as_ref()
+                    .map(|query| {
+                        let members: Vec<&ProcessHarvest> = id_pid_map
+                            .get(*id)
+                            .map(|pids| {
+                                pids.iter()
+                                    .filter_map(|pid| process_harvest.get(pid))
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        query.check_group(process, &members, is_using_command)
+                    })
+                    .unwrap_or(true);
+                if !is_kept {
+                    id_pid_map.remove(*id);
+                }
+
+                is_kept
+            });
+
             id_process_mapping
                 .values()
                 .map(|process| {
@@ -706,33 +1040,42 @@ impl ProcWidgetState {
 
                     let num_similar = id_pid_map.get(id).map(|val| val.len()).unwrap_or(1) as u64;
 
-                    ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
-                        .num_similar(num_similar)
+                    self.with_deltas(
+                        ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
+                            .num_similar(num_similar)
+                            .keyed_by_id(),
+                    )
                 })
                 .collect()
         } else {
-            filtered_iter
-                .map(|process| ProcWidgetData::from_data(process, is_using_command, is_mem_percent))
+            process_harvest
+                .values()
+                .filter(|process| is_kept(process))
+                .map(|process| {
+                    self.with_deltas(ProcWidgetData::from_data(
+                        process,
+                        is_using_command,
+                        is_mem_percent,
+                    ))
+                })
                 .collect()
         };
 
         self.id_pid_map = id_pid_map;
 
-        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+        if self.sort_frozen {
+            keep_previous_order(self.table.data(), &mut filtered_data);
+        } else if let Some(column) = self.table.columns.get(self.table.sort_index()) {
             sort_skip_pid_asc(column.inner(), &mut filtered_data, self.table.order());
         }
 
         filtered_data
     }
 
-    #[inline(always)]
-    fn get_mut_proc_col(&mut self, index: usize) -> Option<&mut ProcColumn> {
-        self.table.columns.get_mut(index).map(|col| col.inner_mut())
-    }
-
     pub fn toggle_mem_percentage(&mut self) {
         if let Some(index) = self.column_mapping.get_index_of(&ProcWidgetColumn::Mem) {
-            if let Some(mem) = self.get_mut_proc_col(index) {
+            if let Some(col) = self.table.columns.get_mut(index) {
+                let mem = col.inner_mut();
                 match mem {
                     ProcColumn::MemoryVal => {
                         *mem = ProcColumn::MemoryPercent;
@@ -755,6 +1098,21 @@ impl ProcWidgetState {
         self.force_update_data = true;
     }
 
+    /// Freezes or unfreezes sorting. While frozen, new data is still taken in, but rows keep the
+    /// order they were last shown in, with any new rows added to the end. Unfreezing re-sorts.
+    /// In tree mode, this only affects the order of siblings.
+    pub fn freeze_sort(&mut self, frozen: bool) {
+        if self.sort_frozen != frozen {
+            self.sort_frozen = frozen;
+            self.force_data_update();
+        }
+    }
+
+    /// Returns whether sorting is currently frozen.
+    pub fn is_sort_frozen(&self) -> bool {
+        self.sort_frozen
+    }
+
     /// Forces an entire rerender and update of the data stored.
     #[inline]
     pub fn force_rerender_and_update(&mut self) {
@@ -777,9 +1135,58 @@ impl ProcWidgetState {
         }
     }
 
-    /// Marks the selected column as shown.
+    /// Hides the columns named in `names` by default. Names are matched against the kind of each
+    /// column with [`ProcWidgetColumn::from_name`], so they work however the column is currently
+    /// shown. Returns the names that didn't match any column.
+    pub fn hide_columns_by_name<'a>(&mut self, names: &'a [String]) -> Vec<&'a str> {
+        let mut unknown = Vec::new();
+        for name in names {
+            let index = ProcWidgetColumn::from_name(name)
+                .and_then(|column| self.column_mapping.get_index_of(&column));
+
+            match index {
+                Some(index) => {
+                    self.table.columns[index].is_hidden = true;
+                    self.config_hidden_columns.insert(index);
+                }
+                None => unknown.push(name.as_str()),
+            }
+        }
+
+        if self
+            .config_hidden_columns
+            .contains(&self.table.sort_index())
+        {
+            self.table.set_sort_index(self.default_sort_index);
+            self.table.set_order(self.default_sort_order);
+        }
+        // The default may have been hidden too.
+        self.table.sort_by_visible_column();
+        self.sort_table.set_data(self.column_text());
+        self.force_rerender_and_update();
+
+        unknown
+    }
+
+    /// Returns the names of the columns hidden by the config, leaving out any that are only hidden
+    /// while grouping.
+    pub fn hidden_column_names(&self) -> Vec<String> {
+        let mut hidden: Vec<usize> = self.config_hidden_columns.iter().copied().collect();
+        hidden.sort_unstable();
+
+        hidden
+            .into_iter()
+            .filter_map(|index| self.column_mapping.get_index(index))
+            .map(|column| column.name().to_string())
+            .collect()
+    }
+
+    /// Marks the selected column as shown. This wins over the config hiding it, so it also stops
+    /// counting as hidden by the config.
     fn show_column(&mut self, column: ProcWidgetColumn) {
         if let Some(index) = self.column_mapping.get_index_of(&column) {
+            self.config_hidden_columns.remove(&index);
+
             if let Some(col) = self.table.columns.get_mut(index) {
                 col.is_hidden = false;
             }
@@ -794,9 +1201,30 @@ impl ProcWidgetState {
         }
     }
 
+    /// Returns the rows of processes, or groups of processes, without the totals.
+    pub fn processes(&self) -> &[ProcWidgetData] {
+        match self.table.data().split_last() {
+            Some((last, processes)) if last.is_totals => processes,
+            _ => self.table.data(),
+        }
+    }
+
+    /// Returns the row of totals, if the table has been given data.
+    pub fn totals(&self) -> Option<&ProcWidgetData> {
+        self.table.data().last().filter(|row| row.is_totals)
+    }
+
+    /// Returns the selected process, or group of processes, if a row other than the totals is
+    /// selected.
+    pub fn selected_process(&self) -> Option<&ProcWidgetData> {
+        self.table
+            .current_item()
+            .filter(|process| !process.is_totals)
+    }
+
     pub fn toggle_current_tree_branch_entry(&mut self) {
         if let ProcWidgetMode::Tree { collapsed_pids } = &mut self.mode {
-            if let Some(process) = self.table.current_item() {
+            if let Some(process) = self.table.current_item().filter(|p| !p.is_totals) {
                 let pid = process.pid;
 
                 if !collapsed_pids.remove(&pid) {
@@ -832,6 +1260,12 @@ impl ProcWidgetState {
                     }
                     _ => unreachable!(),
                 }
+
+                // Groups are known by their name or command, so they can't be compared to the
+                // last update any more.
+                if let ProcWidgetMode::Grouped = self.mode {
+                    self.deltas.clear();
+                }
                 self.sort_table.set_data(self.column_text());
                 self.force_rerender_and_update();
             }
@@ -845,9 +1279,11 @@ impl ProcWidgetState {
     /// (handled by internal method calls), and go back to the "defaults".
     ///
     /// Otherwise, if count is disabled, then if the columns exist, the User and State columns should be re-enabled,
-    /// and the mode switched to [`ProcWidgetMode::Normal`].
+    /// and the mode switched to [`ProcWidgetMode::Normal`]. Whatever process was selected before grouping is
+    /// selected again, if it still exists.
     pub fn toggle_tab(&mut self) {
         if !matches!(self.mode, ProcWidgetMode::Tree { .. }) {
+            let selected_pid = self.selected_process().map(|process| process.pid);
             if let Some(index) = self
                 .column_mapping
                 .get_index_of(&ProcWidgetColumn::PidOrCount)
@@ -856,6 +1292,7 @@ impl ProcWidgetState {
                     let col = sort_col.inner_mut();
                     match col {
                         ProcColumn::Pid => {
+                            self.ungrouped_selected_pid = selected_pid;
                             *col = ProcColumn::Count;
                             sort_col.default_order = SortOrder::Descending;
 
@@ -874,6 +1311,8 @@ impl ProcWidgetState {
                         _ => unreachable!(),
                     }
 
+                    // Rows switch between PIDs and groups, which can't be compared.
+                    self.deltas.clear();
                     self.sort_table.set_data(self.column_text());
                     self.force_rerender_and_update();
                 }
@@ -898,6 +1337,32 @@ impl ProcWidgetState {
         self.proc_search.search_state.is_enabled
     }
 
+    /// Returns whether the search bar or the sort menu is open. While one is, it should get a
+    /// chance to handle keys before they are treated as global shortcuts.
+    pub fn is_overlay_open(&self) -> bool {
+        self.is_search_enabled() || self.is_sort_open
+    }
+
+    /// Closes the topmost open overlay, which is the search bar and then the sort menu, so each
+    /// Esc closes one of them. Returns `false` if neither was open, in which case the Esc is left
+    /// for whatever contains the widget, e.g. to leave expanded mode.
+    ///
+    /// The search query is cleared as the search bar closes, unless `keep_filter` is set.
+    pub fn close_top_overlay(&mut self, keep_filter: bool) -> bool {
+        if self.is_search_enabled() {
+            if !keep_filter {
+                self.clear_search();
+            }
+            self.proc_search.search_state.is_enabled = false;
+            true
+        } else if self.is_sort_open {
+            self.is_sort_open = false;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn current_search_query(&self) -> &str {
         &self.proc_search.search_state.current_search_query
     }
@@ -916,6 +1381,7 @@ impl ProcWidgetState {
             match parse_query(
                 &self.proc_search.search_state.current_search_query,
                 self.proc_search.is_searching_whole_word,
+                self.proc_search.is_matching_word_boundary,
                 self.proc_search.is_ignoring_case,
                 self.proc_search.is_searching_with_regex,
             ) {
@@ -932,8 +1398,20 @@ impl ProcWidgetState {
                 }
             }
         }
-        self.table.state.display_start_index = 0;
-        self.table.state.current_index = 0;
+        if self.restore_scroll_after_search {
+            // The table is still showing the rows from before this change to the query.
+            if self.get_query().is_some() {
+                if self.pre_search_position.is_none() {
+                    self.pre_search_position = Some(self.table.scroll_position());
+                }
+                self.is_restoring_scroll = false;
+            } else if self.proc_search.search_state.is_blank_search {
+                self.is_restoring_scroll = self.pre_search_position.is_some();
+            }
+        } else {
+            self.table.state.window.start = 0;
+            self.table.state.current_index = 0;
+        }
 
         // Update the internal sizes too.
         self.proc_search.search_state.update_sizes();
@@ -943,9 +1421,27 @@ impl ProcWidgetState {
 
     pub fn clear_search(&mut self) {
         self.proc_search.search_state.reset();
+        self.is_restoring_scroll = self.pre_search_position.is_some();
         self.force_data_update();
     }
 
+    /// Returns the search query if it is still filtering the table while the search widget is closed.
+    pub fn committed_filter(&self) -> Option<&str> {
+        if self.is_search_enabled() || self.get_query().is_none() {
+            None
+        } else {
+            Some(self.current_search_query())
+        }
+    }
+
+    /// Updates the table title, which shows any filter kept from a closed search.
+    pub fn update_title(&mut self) {
+        self.table.props.title = Some(match self.committed_filter() {
+            Some(filter) => format!(" Processes [filter: {filter}] ").into(),
+            None => " Processes ".into(),
+        });
+    }
+
     pub fn search_walk_forward(&mut self) {
         self.proc_search.search_state.walk_forward();
     }
@@ -960,6 +1456,22 @@ impl ProcWidgetState {
         self.table.columns.iter().filter(|c| !c.is_hidden).count()
     }
 
+    /// Returns the name and description of the column selected in the sort table, if it has a
+    /// description.
+    pub fn sort_table_column_description(&self) -> Option<(Cow<'static, str>, Cow<'static, str>)> {
+        let column = self
+            .table
+            .columns
+            .iter()
+            .filter(|c| !c.is_hidden)
+            .nth(self.sort_table.current_index())?;
+
+        Some((
+            column.inner().text(),
+            column.description()?.to_string().into(),
+        ))
+    }
+
     /// Sets the [`ProcWidget`]'s current sort index to whatever was in the sort table if possible, then closes the
     /// sort table.
     pub(crate) fn use_sort_table_value(&mut self) {
@@ -974,6 +1486,8 @@ impl ProcWidgetState {
         self.mode == other.mode
             && self.proc_search.is_ignoring_case == other.proc_search.is_ignoring_case
             && self.proc_search.is_searching_whole_word == other.proc_search.is_searching_whole_word
+            && self.proc_search.is_matching_word_boundary
+                == other.proc_search.is_matching_word_boundary
             && self.proc_search.is_searching_with_regex == other.proc_search.is_searching_with_regex
             && self
                 .table
@@ -1001,9 +1515,26 @@ fn sort_skip_pid_asc(column: &ProcColumn, data: &mut [ProcWidgetData], order: So
     }
 }
 
+/// Sorts `data` into the same order its rows had in `previous`, matching rows by PID. Rows that
+/// weren't in `previous` go at the end, ordered by PID.
+fn keep_previous_order(previous: &[ProcWidgetData], data: &mut [ProcWidgetData]) {
+    let positions: HashMap<Pid, usize> = previous
+        .iter()
+        .enumerate()
+        .map(|(index, row)| (row.pid, index))
+        .collect();
+
+    data.sort_by_key(|row| {
+        (
+            positions.get(&row.pid).copied().unwrap_or(usize::MAX),
+            row.pid,
+        )
+    });
+}
+
 #[cfg(test)]
 mod test {
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     use super::*;
     use crate::widgets::MemUsage;
@@ -1012,6 +1543,7 @@ mod test {
     fn test_proc_sort() {
         let a = ProcWidgetData {
             pid: 1,
+            row_id: 1,
             ppid: None,
             id: "A".into(),
             cpu_usage_percent: 0.0,
@@ -1028,7 +1560,12 @@ mod test {
             user: "N/A".to_string(),
             num_similar: 0,
             disabled: false,
+            is_totals: false,
             time: Duration::from_secs(0),
+            byte_standard: ByteStandard::default(),
+            number_format: NumberFormat::default(),
+            cpu_delta: None,
+            mem_delta: None,
         };
 
         let b = ProcWidgetData {
@@ -1135,6 +1672,23 @@ mod test {
         init_state(ProcTableConfig::default(), columns)
     }
 
+    #[test]
+    fn close_top_overlay() {
+        let mut state = init_default_state(&[ProcWidgetColumn::ProcNameOrCommand]);
+        state.proc_search.search_state.is_enabled = true;
+        state.is_sort_open = true;
+
+        assert!(state.close_top_overlay(false));
+        assert!(!state.is_search_enabled());
+        assert!(state.is_sort_open);
+
+        assert!(state.close_top_overlay(false));
+        assert!(!state.is_overlay_open());
+
+        // With nothing left to close, the Esc is passed on.
+        assert!(!state.close_top_overlay(false));
+    }
+
     #[test]
     fn custom_columns() {
         let init_columns = vec![
@@ -1219,6 +1773,51 @@ mod test {
         assert_eq!(get_columns(&state.table), original_columns);
     }
 
+    #[test]
+    fn config_hidden_columns() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::User,
+            ProcWidgetColumn::State,
+        ];
+
+        let mut state = init_default_state(&init_columns);
+        let names = ["user", "state", "Nice"].map(String::from);
+        assert_eq!(state.hide_columns_by_name(&names), ["Nice"]);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::Pid, ProcColumn::Name]
+        );
+        assert_eq!(state.column_text(), ["PID", "Name"]);
+
+        assert_eq!(state.hidden_column_names(), ["User", "State"]);
+
+        // Toggling back out of grouped mode shows the user and state again, which wins over the
+        // config.
+        state.toggle_tab();
+        state.toggle_tab();
+        assert_eq!(
+            get_columns(&state.table),
+            vec![
+                ProcColumn::Pid,
+                ProcColumn::Name,
+                ProcColumn::User,
+                ProcColumn::State
+            ]
+        );
+        assert!(state.hidden_column_names().is_empty());
+
+        // Names match the kind of column, however it is currently shown.
+        let names = ["count", "COMMAND"].map(String::from);
+        assert!(state.hide_columns_by_name(&names).is_empty());
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::User, ProcColumn::State]
+        );
+        assert_eq!(state.hidden_column_names(), ["PID", "Name"]);
+    }
+
     #[test]
     fn toggle_command() {
         let init_columns = [
@@ -1492,4 +2091,665 @@ mod test {
         state.toggle_command();
         assert_eq!(get_columns(&state.table), original_columns);
     }
+
+    #[test]
+    fn test_freeze_sort() {
+        use crate::app::data_harvester::processes::ProcessHarvest;
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ]);
+        state.table.set_sort_index(2);
+        state.table.set_order(SortOrder::Descending);
+
+        let mut data_collection = DataCollection::default();
+        let mut ingest = |state: &mut ProcWidgetState, usages: &[(Pid, f32)]| {
+            data_collection.process_data.process_harvest = usages
+                .iter()
+                .map(|&(pid, cpu_usage_percent)| {
+                    let process = ProcessHarvest {
+                        pid,
+                        cpu_usage_percent,
+                        name: format!("process {pid}"),
+                        ..Default::default()
+                    };
+                    (pid, process)
+                })
+                .collect();
+            state.ingest_data(&data_collection);
+
+            state
+                .processes()
+                .iter()
+                .map(|row| row.pid)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ingest(&mut state, &[(1, 10.0), (2, 20.0), (3, 30.0)]),
+            [3, 2, 1]
+        );
+
+        // While frozen, the old order is kept and new processes go at the end.
+        state.freeze_sort(true);
+        assert!(state.is_sort_frozen());
+        assert_eq!(
+            ingest(&mut state, &[(1, 50.0), (2, 0.0), (3, 30.0), (4, 90.0)]),
+            [3, 2, 1, 4]
+        );
+        assert_eq!(
+            ingest(&mut state, &[(1, 50.0), (3, 30.0), (4, 90.0)]),
+            [3, 1, 4]
+        );
+
+        state.freeze_sort(false);
+        assert_eq!(
+            ingest(&mut state, &[(1, 50.0), (3, 30.0), (4, 90.0)]),
+            [4, 1, 3]
+        );
+    }
+
+    #[test]
+    fn test_tree_collapsing() {
+        use crate::app::data_harvester::processes::ProcessHarvest;
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ]);
+        state.mode = ProcWidgetMode::Tree {
+            collapsed_pids: [1].into_iter().collect(),
+        };
+
+        // init ─┬─ shell ── vim
+        //       └─ top
+        let mut data_collection = DataCollection::default();
+        let process_data = &mut data_collection.process_data;
+        process_data.process_harvest = [
+            (1, None, "init"),
+            (2, Some(1), "shell"),
+            (3, Some(2), "vim"),
+            (4, Some(1), "top"),
+        ]
+        .into_iter()
+        .map(|(pid, parent_pid, name)| {
+            let process = ProcessHarvest {
+                pid,
+                parent_pid,
+                name: name.to_string(),
+                cpu_usage_percent: pid as f32,
+                ..Default::default()
+            };
+            (pid, process)
+        })
+        .collect();
+        process_data.process_parent_mapping = [(1, vec![2, 4]), (2, vec![3])].into_iter().collect();
+        process_data.orphan_pids = vec![1];
+
+        let rows = |state: &ProcWidgetState| {
+            state
+                .processes()
+                .iter()
+                .map(|row| (row.pid, row.id.to_prefixed_string()))
+                .collect::<Vec<_>>()
+        };
+
+        // Collapsed rows add up their descendants and say how many are hidden.
+        state.ingest_data(&data_collection);
+        assert_eq!(rows(&state), [(1, "+ init (+3)".to_string())]);
+        assert_eq!(state.processes()[0].cpu_usage_percent, 10.0);
+
+        // Searching expands the ancestors of matches, without forgetting they were collapsed.
+        state.proc_search.search_state.current_search_query = "vim".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(
+            rows(&state)
+                .into_iter()
+                .map(|(pid, _)| pid)
+                .collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+
+        state.proc_search.search_state.current_search_query.clear();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(rows(&state), [(1, "+ init (+3)".to_string())]);
+    }
+
+    #[test]
+    fn test_grouping() {
+        use crate::app::data_harvester::processes::ProcessHarvest;
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ]);
+        state.table.set_sort_index(2);
+        state.table.set_order(SortOrder::Descending);
+
+        let mut data_collection = DataCollection::default();
+        data_collection.process_data.process_harvest = [
+            (1, "a", 10.0),
+            (2, "b", 30.0),
+            (3, "a", 25.0),
+            (4, "c", 5.0),
+        ]
+        .into_iter()
+        .map(|(pid, name, cpu_usage_percent)| {
+            let process = ProcessHarvest {
+                pid,
+                name: name.to_string(),
+                cpu_usage_percent,
+                ..Default::default()
+            };
+            (pid, process)
+        })
+        .collect();
+
+        let rows = |state: &ProcWidgetState| {
+            state
+                .processes()
+                .iter()
+                .map(|row| (row.id.as_str().to_string(), row.num_similar))
+                .collect::<Vec<_>>()
+        };
+
+        state.ingest_data(&data_collection);
+        state.table.set_position(2);
+        assert_eq!(state.table.current_item().unwrap().pid, 1);
+
+        // Groups are sorted by their combined values.
+        state.toggle_tab();
+        state.ingest_data(&data_collection);
+        assert_eq!(
+            rows(&state),
+            [
+                ("a".to_string(), 2),
+                ("b".to_string(), 1),
+                ("c".to_string(), 1)
+            ]
+        );
+        assert_eq!(state.processes()[0].cpu_usage_percent, 35.0);
+        assert_eq!(state.id_pid_map["a"], [1, 3]);
+
+        // Searches are also checked against the combined values.
+        state.proc_search.search_state.current_search_query = "cpu > 32".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(rows(&state), [("a".to_string(), 2)]);
+        assert_eq!(state.id_pid_map.len(), 1);
+
+        // Values that aren't combined match if any process in the group matches, even if it
+        // isn't the first one.
+        state.proc_search.search_state.current_search_query = "pid = 3".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(rows(&state), [("a".to_string(), 2)]);
+        assert_eq!(state.id_pid_map["a"], [1, 3]);
+
+        state.proc_search.search_state.current_search_query = "pid = 3 and cpu > 32".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(rows(&state), [("a".to_string(), 2)]);
+
+        state.proc_search.search_state.current_search_query = "pid = 3 and cpu < 30".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert!(rows(&state).is_empty());
+
+        // Ungrouping goes back to the process that was selected before.
+        state.proc_search.search_state.current_search_query.clear();
+        state.update_query();
+        state.toggle_tab();
+        state.ingest_data(&data_collection);
+        assert_eq!(state.table.current_item().unwrap().pid, 1);
+    }
+
+    #[test]
+    fn test_totals() {
+        use crate::{
+            app::data_harvester::processes::ProcessHarvest, components::data_table::DataToCell,
+        };
+
+        let config = ProcTableConfig {
+            show_memory_as_values: true,
+            ..Default::default()
+        };
+        let mut state = init_state(
+            config,
+            &[
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::ProcNameOrCommand,
+                ProcWidgetColumn::Cpu,
+                ProcWidgetColumn::Mem,
+            ],
+        );
+
+        // init ─┬─ shell ── vim
+        //       └─ vim
+        let mut data_collection = DataCollection::default();
+        let process_data = &mut data_collection.process_data;
+        process_data.process_harvest = [
+            (1, None, "init"),
+            (2, Some(1), "shell"),
+            (3, Some(2), "vim"),
+            (4, Some(1), "vim"),
+        ]
+        .into_iter()
+        .map(|(pid, parent_pid, name)| {
+            let process = ProcessHarvest {
+                pid,
+                parent_pid,
+                name: name.to_string(),
+                cpu_usage_percent: pid as f32,
+                mem_usage_bytes: 1024,
+                ..Default::default()
+            };
+            (pid, process)
+        })
+        .collect();
+        process_data.process_parent_mapping = [(1, vec![2, 4]), (2, vec![3])].into_iter().collect();
+        process_data.orphan_pids = vec![1];
+
+        let totals = |state: &ProcWidgetState| {
+            let totals = state.totals().unwrap();
+            (
+                totals.id.as_str().to_string(),
+                totals.cpu_usage_percent,
+                totals.mem_usage.clone(),
+            )
+        };
+        let all = ("Total: 4".to_string(), 10.0, MemUsage::Bytes(4096));
+
+        state.ingest_data(&data_collection);
+        assert_eq!(totals(&state), all);
+        assert_eq!(state.processes().len(), 4);
+
+        // The totals stay below the processes, and can't be acted on like one.
+        state.table.set_sort_index(2);
+        state.table.set_order(SortOrder::Descending);
+        state.ingest_data(&data_collection);
+        assert!(state.table.data().last().unwrap().is_totals);
+        state.table.set_last();
+        assert_eq!(state.table.current_index(), state.processes().len() - 1);
+        state.table.increment_position(1);
+        assert_eq!(state.table.current_index(), state.processes().len() - 1);
+        assert!(state.selected_process().is_some());
+        assert_eq!(DataToCell::row_id(state.totals().unwrap()), None);
+        state.table.set_first();
+
+        // Only processes that match the search are counted.
+        state.proc_search.search_state.current_search_query = "vim".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(
+            totals(&state),
+            ("Matching: 2 of 4".to_string(), 7.0, MemUsage::Bytes(2048))
+        );
+
+        // Groups and collapsed branches don't count any process twice.
+        state.toggle_tab();
+        state.ingest_data(&data_collection);
+        assert_eq!(
+            totals(&state),
+            ("Matching: 2 of 4".to_string(), 7.0, MemUsage::Bytes(2048))
+        );
+        state.toggle_tab();
+
+        state.proc_search.search_state.current_search_query.clear();
+        state.update_query();
+        state.mode = ProcWidgetMode::Tree {
+            collapsed_pids: [2].into_iter().collect(),
+        };
+        state.ingest_data(&data_collection);
+        assert_eq!(state.processes().len(), 3);
+        assert_eq!(totals(&state), all);
+    }
+
+    #[test]
+    fn test_row_identity() {
+        use crate::app::data_harvester::processes::ProcessHarvest;
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ]);
+        state.table.set_sort_index(2);
+        state.table.set_order(SortOrder::Descending);
+
+        let set_processes = |state: &mut ProcWidgetState, processes: &[(Pid, &str, f32)]| {
+            let mut data_collection = DataCollection::default();
+            data_collection.process_data.process_harvest = processes
+                .iter()
+                .map(|&(pid, name, cpu_usage_percent)| {
+                    let process = ProcessHarvest {
+                        pid,
+                        name: name.to_string(),
+                        cpu_usage_percent,
+                        ..Default::default()
+                    };
+                    (pid, process)
+                })
+                .collect();
+            state.ingest_data(&data_collection);
+        };
+        let pids = |state: &ProcWidgetState| {
+            state
+                .processes()
+                .iter()
+                .map(|row| row.pid)
+                .collect::<Vec<_>>()
+        };
+
+        set_processes(
+            &mut state,
+            &[(1, "a", 30.0), (2, "b", 20.0), (3, "a", 10.0)],
+        );
+        assert_eq!(pids(&state), [1, 2, 3]);
+        state.table.set_position(1);
+        state.table.toggle_marked();
+        state.table.set_position(2);
+        state.table.toggle_marked();
+
+        // The selection and marks follow processes as they move around.
+        set_processes(
+            &mut state,
+            &[(1, "a", 10.0), (2, "b", 20.0), (3, "a", 30.0)],
+        );
+        assert_eq!(pids(&state), [3, 2, 1]);
+        assert_eq!(state.table.current_item().unwrap().pid, 3);
+        let marked = |state: &ProcWidgetState| {
+            state
+                .table
+                .marked_items()
+                .map(|row| row.pid)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(marked(&state), [3, 2]);
+
+        // Marks on processes that are gone are dropped, so they don't apply to a reused PID.
+        set_processes(&mut state, &[(1, "a", 10.0), (2, "b", 20.0)]);
+        assert_eq!(marked(&state), [2]);
+        set_processes(
+            &mut state,
+            &[(1, "a", 10.0), (2, "b", 20.0), (3, "c", 30.0)],
+        );
+        assert_eq!(marked(&state), [2]);
+
+        // Groups are known by their name, even if their processes change.
+        state.toggle_tab();
+        set_processes(
+            &mut state,
+            &[(1, "a", 10.0), (2, "b", 20.0), (3, "a", 30.0)],
+        );
+        state.table.set_position(0);
+        assert_eq!(state.table.current_item().unwrap().id.as_str(), "a");
+        set_processes(&mut state, &[(2, "b", 20.0), (3, "a", 5.0)]);
+        assert_eq!(state.table.current_item().unwrap().id.as_str(), "a");
+    }
+
+    #[test]
+    fn test_row_deltas() {
+        use crate::{
+            app::data_harvester::processes::ProcessHarvest, components::data_table::Delta,
+        };
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+            ProcWidgetColumn::CpuDelta,
+            ProcWidgetColumn::MemDelta,
+        ]);
+
+        let mut data_collection = DataCollection::default();
+        let mut set_processes =
+            |state: &mut ProcWidgetState, time, processes: &[(Pid, f32, f32)]| {
+                data_collection.current_instant = time;
+                data_collection.process_data.process_harvest = processes
+                    .iter()
+                    .map(|&(pid, cpu_usage_percent, mem_usage_percent)| {
+                        let process = ProcessHarvest {
+                            pid,
+                            cpu_usage_percent,
+                            mem_usage_percent,
+                            ..Default::default()
+                        };
+                        (pid, process)
+                    })
+                    .collect();
+                state.ingest_data(&data_collection);
+            };
+        let deltas = |state: &ProcWidgetState| {
+            let mut deltas = state
+                .processes()
+                .iter()
+                .map(|row| (row.pid, row.cpu_delta, row.mem_delta))
+                .collect::<Vec<_>>();
+            deltas.sort_by_key(|(pid, _, _)| *pid);
+            deltas
+        };
+
+        // There's nothing to compare against on the first update.
+        let start = Instant::now();
+        set_processes(&mut state, start, &[(1, 10.0, 1.0), (2, 20.0, 2.0)]);
+        assert_eq!(deltas(&state), [(1, None, None), (2, None, None)]);
+
+        let next = start + Duration::from_secs(1);
+        set_processes(&mut state, next, &[(1, 15.0, 0.5), (3, 5.0, 1.0)]);
+        let expected = [
+            (1, Some(Delta::Change(5.0)), Some(Delta::Change(-0.5))),
+            (3, Some(Delta::New), Some(Delta::New)),
+        ];
+        assert_eq!(deltas(&state), expected);
+
+        // Ingesting the same update again, like after re-sorting, keeps the deltas.
+        state.force_data_update();
+        set_processes(&mut state, next, &[(1, 15.0, 0.5), (3, 5.0, 1.0)]);
+        assert_eq!(deltas(&state), expected);
+
+        // Groups can't be compared to processes.
+        state.toggle_tab();
+        set_processes(
+            &mut state,
+            next + Duration::from_secs(1),
+            &[(1, 15.0, 0.5), (3, 5.0, 1.0)],
+        );
+        assert!(deltas(&state)
+            .iter()
+            .all(|(_, cpu, mem)| cpu.is_none() && mem.is_none()));
+    }
+
+    #[test]
+    fn test_row_deltas_with_search() {
+        use crate::{
+            app::data_harvester::processes::ProcessHarvest, components::data_table::Delta,
+        };
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::CpuDelta,
+        ]);
+
+        let mut data_collection = DataCollection::default();
+        let mut set_processes = |state: &mut ProcWidgetState, time, cpu_usage_percent: f32| {
+            data_collection.current_instant = time;
+            data_collection.process_data.process_harvest = [(1, "a"), (2, "b"), (3, "b")]
+                .into_iter()
+                .map(|(pid, name)| {
+                    let process = ProcessHarvest {
+                        pid,
+                        name: name.to_string(),
+                        cpu_usage_percent,
+                        ..Default::default()
+                    };
+                    (pid, process)
+                })
+                .collect();
+            state.ingest_data(&data_collection);
+        };
+        let search = |state: &mut ProcWidgetState, query: &str| {
+            state.proc_search.search_state.current_search_query = query.to_string();
+            state.update_query();
+        };
+        let deltas = |state: &ProcWidgetState| {
+            let mut deltas = state
+                .processes()
+                .iter()
+                .map(|row| (row.id.as_str().to_string(), row.cpu_delta))
+                .collect::<Vec<_>>();
+            deltas.sort_by(|a, b| a.0.cmp(&b.0));
+            deltas
+        };
+
+        // Processes hidden by the search in the previous update aren't new.
+        let start = Instant::now();
+        search(&mut state, "a");
+        set_processes(&mut state, start, 10.0);
+        search(&mut state, "b");
+        set_processes(&mut state, start + Duration::from_secs(1), 15.0);
+        assert_eq!(
+            deltas(&state),
+            [
+                ("b".to_string(), Some(Delta::Change(5.0))),
+                ("b".to_string(), Some(Delta::Change(5.0)))
+            ]
+        );
+
+        // The same goes for groups.
+        state.toggle_tab();
+        search(&mut state, "a");
+        set_processes(&mut state, start + Duration::from_secs(2), 15.0);
+        search(&mut state, "b");
+        set_processes(&mut state, start + Duration::from_secs(3), 20.0);
+        assert_eq!(
+            deltas(&state),
+            [("b".to_string(), Some(Delta::Change(10.0)))]
+        );
+    }
+
+    #[test]
+    fn test_restore_scroll_after_search() {
+        use crate::app::data_harvester::processes::ProcessHarvest;
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ]);
+        state.set_restore_scroll_after_search(true);
+        state.table.set_sort_index(0);
+        state.table.set_order(SortOrder::Ascending);
+
+        let mut data_collection = DataCollection::default();
+        data_collection.process_data.process_harvest = (1..=8)
+            .map(|pid| {
+                let process = ProcessHarvest {
+                    pid,
+                    name: if pid % 2 == 1 { "a" } else { "b" }.to_string(),
+                    ..Default::default()
+                };
+                (pid, process)
+            })
+            .collect();
+        let pid = |state: &ProcWidgetState| state.table.current_item().unwrap().pid;
+
+        state.ingest_data(&data_collection);
+        state.table.set_position(5);
+        state.table.state.window.start = 3;
+        assert_eq!(pid(&state), 6);
+
+        // The selected process doesn't match, so the selection stays within the results.
+        state.proc_search.search_state.current_search_query = "a".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(state.processes().len(), 4);
+        assert_eq!(state.table.current_index(), 3);
+        assert_eq!(pid(&state), 7);
+
+        // Scrolling only moves through the results.
+        state.table.set_position(0);
+        assert_eq!(state.table.increment_position(1), Some(1));
+        assert_eq!(pid(&state), 3);
+        assert_eq!(state.table.increment_position(5), Some(3));
+        assert_eq!(pid(&state), 7);
+
+        // Clearing the search goes back to where the table was before.
+        state.clear_search();
+        state.ingest_data(&data_collection);
+        assert_eq!(state.processes().len(), 8);
+        assert_eq!(pid(&state), 6);
+        assert_eq!(state.table.current_index(), 5);
+        assert_eq!(state.table.state.window.start, 3);
+
+        // Deleting the query restores the position too.
+        state.proc_search.search_state.current_search_query = "b".to_string();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(pid(&state), 6);
+        state.table.set_position(0);
+        state.proc_search.search_state.current_search_query.clear();
+        state.update_query();
+        state.ingest_data(&data_collection);
+        assert_eq!(pid(&state), 6);
+    }
+
+    #[test]
+    fn test_data_column_widths() {
+        use crate::{
+            app::data_harvester::processes::ProcessHarvest,
+            components::data_table::{ColumnWidthBounds, DataToCell},
+        };
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::User,
+        ]);
+        state.hide_column(ProcWidgetColumn::User);
+
+        let mut data_collection = DataCollection::default();
+        data_collection.process_data.process_harvest = [(1, "init"), (123456, "a_long_name")]
+            .into_iter()
+            .map(|(pid, name)| {
+                let process = ProcessHarvest {
+                    pid,
+                    name: name.to_string(),
+                    cpu_usage_percent: 12345.6,
+                    ..Default::default()
+                };
+                (pid, process)
+            })
+            .collect();
+        state.ingest_data(&data_collection);
+
+        // Only the visible columns are sized by their data.
+        assert_eq!(
+            ProcWidgetData::column_widths(state.table.data(), &state.table.columns),
+            [6, 11, 8, 0]
+        );
+
+        // Separators widen the CPU column past its header, but the PID is never grouped.
+        state.set_number_format(NumberFormat {
+            grouping: Some('\u{2019}'),
+            decimal: '.',
+        });
+        state.ingest_data(&data_collection);
+        assert_eq!(
+            ProcWidgetData::column_widths(state.table.data(), &state.table.columns),
+            [6, 11, 9, 0]
+        );
+        assert!(matches!(
+            state.table.columns[2].bounds(),
+            ColumnWidthBounds::Soft { desired: 9, .. }
+        ));
+    }
 }
@@ -2,9 +2,9 @@ use std::{borrow::Cow, cmp::Reverse};
 
 use serde::{de::Error, Deserialize, Serialize};
 
-use super::ProcWidgetData;
+use super::{ProcWidgetColumn, ProcWidgetData};
 use crate::{
-    components::data_table::{ColumnHeader, SortsRow},
+    components::data_table::{ColumnHeader, Delta, SortsRow},
     utils::gen_util::sort_partial_fn,
 };
 
@@ -24,6 +24,8 @@ pub enum ProcColumn {
     State,
     User,
     Time,
+    CpuDelta,
+    MemDelta,
 }
 
 impl<'de> Deserialize<'de> for ProcColumn {
@@ -47,6 +49,8 @@ impl<'de> Deserialize<'de> for ProcColumn {
             "state" => Ok(ProcColumn::State),
             "user" => Ok(ProcColumn::User),
             "time" => Ok(ProcColumn::Time),
+            "dcpu%" | "δcpu%" => Ok(ProcColumn::CpuDelta),
+            "dmem" | "δmem" => Ok(ProcColumn::MemDelta),
             _ => Err(D::Error::custom("doesn't match any column type")),
         }
     }
@@ -78,6 +82,8 @@ impl ColumnHeader for ProcColumn {
             ProcColumn::State => "State",
             ProcColumn::User => "User",
             ProcColumn::Time => "Time",
+            ProcColumn::CpuDelta => "ΔCPU%",
+            ProcColumn::MemDelta => "ΔMem",
         }
         .into()
     }
@@ -98,9 +104,40 @@ impl ColumnHeader for ProcColumn {
             ProcColumn::State => "State",
             ProcColumn::User => "User",
             ProcColumn::Time => "Time",
+            ProcColumn::CpuDelta => "ΔCPU%",
+            ProcColumn::MemDelta => "ΔMem",
         }
         .into()
     }
+
+    fn name(&self) -> Cow<'static, str> {
+        ProcWidgetColumn::from(self).name().into()
+    }
+
+    fn description(&self) -> Option<&str> {
+        let description = match self {
+            ProcColumn::CpuPercent => "CPU usage of the process since the last update",
+            ProcColumn::MemoryVal => "Memory used by the process",
+            ProcColumn::MemoryPercent => {
+                "Memory used by the process, as a percentage of all memory"
+            }
+            ProcColumn::Pid => "ID of the process",
+            ProcColumn::Count => "Number of processes grouped together under this name",
+            ProcColumn::Name => "Name of the process",
+            ProcColumn::Command => "Command the process was started with",
+            ProcColumn::ReadPerSecond => "Bytes read from disk per second",
+            ProcColumn::WritePerSecond => "Bytes written to disk per second",
+            ProcColumn::TotalRead => "Total bytes read from disk by the process",
+            ProcColumn::TotalWrite => "Total bytes written to disk by the process",
+            ProcColumn::State => "State of the process, like running or sleeping",
+            ProcColumn::User => "User that owns the process",
+            ProcColumn::Time => "Total CPU time used by the process",
+            ProcColumn::CpuDelta => "Change in CPU usage since the last update",
+            ProcColumn::MemDelta => "Change in memory used since the last update",
+        };
+
+        Some(description)
+    }
 }
 
 impl SortsRow for ProcColumn {
@@ -158,6 +195,31 @@ impl SortsRow for ProcColumn {
             ProcColumn::Time => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.time, b.time));
             }
+            ProcColumn::CpuDelta => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(
+                        delta_sort_key(a.cpu_delta),
+                        delta_sort_key(b.cpu_delta),
+                    )
+                });
+            }
+            ProcColumn::MemDelta => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(
+                        delta_sort_key(a.mem_delta),
+                        delta_sort_key(b.mem_delta),
+                    )
+                });
+            }
         }
     }
 }
+
+/// Sorts new rows above any change, and rows without a change below every other row.
+fn delta_sort_key(delta: Option<Delta<f64>>) -> f64 {
+    match delta {
+        Some(Delta::Change(change)) => change,
+        Some(Delta::New) => f64::INFINITY,
+        None => f64::NEG_INFINITY,
+    }
+}
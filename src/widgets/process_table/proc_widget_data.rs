@@ -1,32 +1,36 @@
-use std::{
-    cmp::{max, Ordering},
-    fmt::Display,
-    time::Duration,
-};
+use std::{cmp::Ordering, fmt::Display, time::Duration};
 
 use concat_string::concat_string;
-use tui::{text::Text, widgets::Row};
+use tui::{
+    style::{Modifier, Style},
+    text::Text,
+    widgets::Row,
+};
 
 use super::proc_widget_column::ProcColumn;
 use crate::{
     app::data_harvester::processes::ProcessHarvest,
+    canvas::canvas_styling::{Role, StyleResolver},
     canvas::Painter,
-    components::data_table::{DataTableColumn, DataToCell},
-    data_conversion::{binary_byte_string, dec_bytes_per_second_string, dec_bytes_string},
-    utils::gen_util::truncate_to_text,
+    components::data_table::{ColumnWidthBounds, DataTableColumn, DataToCell, Delta},
+    utils::{
+        gen_util::{hash_row_id, str_width, truncate_to_text},
+        units::{format_bytes, format_bytes_per_second, ByteStandard, NumberFormat},
+    },
     Pid,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 enum IdType {
     Name(String),
     Command(String),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Id {
     id_type: IdType,
     prefix: Option<String>,
+    suffix: Option<String>,
 }
 
 impl From<&'static str> for Id {
@@ -34,6 +38,7 @@ impl From<&'static str> for Id {
         Id {
             id_type: IdType::Name(s.to_string()),
             prefix: None,
+            suffix: None,
         }
     }
 }
@@ -57,22 +62,13 @@ impl Id {
         }
     }
 
-    /// Returns the ID as a [`String`] with prefix.
+    /// Returns the ID as a [`String`] with its prefix and suffix.
     pub fn to_prefixed_string(&self) -> String {
-        if let Some(prefix) = &self.prefix {
-            concat_string!(
-                prefix,
-                match &self.id_type {
-                    IdType::Name(name) => name,
-                    IdType::Command(cmd) => cmd,
-                }
-            )
-        } else {
-            match &self.id_type {
-                IdType::Name(name) => name.to_string(),
-                IdType::Command(cmd) => cmd.to_string(),
-            }
-        }
+        concat_string!(
+            self.prefix.as_deref().unwrap_or_default(),
+            self.as_str(),
+            self.suffix.as_deref().unwrap_or_default()
+        )
     }
 }
 
@@ -98,15 +94,82 @@ impl PartialOrd for MemUsage {
     }
 }
 
-impl Display for MemUsage {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl MemUsage {
+    /// Returns the memory usage of `process`, as a percentage if `is_mem_percent` is set.
+    pub fn of(process: &ProcessHarvest, is_mem_percent: bool) -> Self {
+        if is_mem_percent {
+            MemUsage::Percent(process.mem_usage_percent)
+        } else {
+            MemUsage::Bytes(process.mem_usage_bytes)
+        }
+    }
+
+    fn amount(&self) -> f64 {
         match self {
-            MemUsage::Percent(percent) => f.write_fmt(format_args!("{:.1}%", percent)),
-            MemUsage::Bytes(bytes) => f.write_str(&binary_byte_string(*bytes)),
+            MemUsage::Percent(percent) => f64::from(*percent),
+            MemUsage::Bytes(bytes) => *bytes as f64,
+        }
+    }
+
+    /// Formats the memory usage, showing any bytes with the prefixes of `byte_standard`.
+    fn format(&self, byte_standard: ByteStandard) -> String {
+        match self {
+            MemUsage::Percent(percent) => format!("{:.1}%", percent),
+            MemUsage::Bytes(bytes) => format_bytes(*bytes, byte_standard, 1),
+        }
+    }
+
+    /// Returns how much more memory this is than `previous`, or [`None`] if one is a percentage
+    /// and the other isn't.
+    pub fn change_since(&self, previous: &MemUsage) -> Option<f64> {
+        match (self, previous) {
+            (MemUsage::Percent(a), MemUsage::Percent(b)) => Some(f64::from(*a) - f64::from(*b)),
+            (MemUsage::Bytes(a), MemUsage::Bytes(b)) => Some(*a as f64 - *b as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Formats a signed change, with a leading `+` or `-` unless it rounds to zero. `format` writes
+/// out the size of the change.
+pub(crate) fn format_change(delta: Option<Delta<f64>>, format: impl Fn(f64) -> String) -> String {
+    match delta {
+        Some(Delta::Change(change)) => {
+            let text = format(change.abs());
+            if rounds_to_zero(change, &format) {
+                text
+            } else if change > 0.0 {
+                concat_string!("+", text)
+            } else {
+                concat_string!("-", text)
+            }
+        }
+        Some(Delta::New) => "new".to_string(),
+        None => "N/A".to_string(),
+    }
+}
+
+/// Returns the [`Role`] to colour a change with, if it isn't zero once written out by `format`.
+pub(crate) fn change_role(
+    delta: Option<Delta<f64>>, format: impl Fn(f64) -> String,
+) -> Option<Role> {
+    match delta {
+        Some(Delta::Change(change)) if !rounds_to_zero(change, &format) => {
+            if change > 0.0 {
+                Some(Role::Increase)
+            } else {
+                Some(Role::Decrease)
+            }
         }
+        _ => None,
     }
 }
 
+/// Whether `format` writes out `change` the same as no change at all.
+fn rounds_to_zero(change: f64, format: &impl Fn(f64) -> String) -> bool {
+    change == 0.0 || format(change.abs()) == format(0.0)
+}
+
 trait DurationExt {
     fn num_days(&self) -> u64;
     fn num_hours(&self) -> u64;
@@ -164,9 +227,10 @@ fn format_time(dur: Duration) -> String {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ProcWidgetData {
     pub pid: Pid,
+    pub row_id: u64,
     pub ppid: Option<Pid>,
     pub id: Id,
     pub cpu_usage_percent: f32,
@@ -180,7 +244,15 @@ pub struct ProcWidgetData {
     pub user: String,
     pub num_similar: u64,
     pub disabled: bool,
+    /// Whether this is the row of totals at the bottom of the table, rather than a process.
+    pub is_totals: bool,
     pub time: Duration,
+    /// How much CPU usage changed by since the last update.
+    pub cpu_delta: Option<Delta<f64>>,
+    /// How much memory usage changed by since the last update, in the same units as `mem_usage`.
+    pub mem_delta: Option<Delta<f64>>,
+    pub byte_standard: ByteStandard,
+    pub number_format: NumberFormat,
 }
 
 impl ProcWidgetData {
@@ -192,16 +264,14 @@ impl ProcWidgetData {
                 IdType::Name(process.name.clone())
             },
             prefix: None,
+            suffix: None,
         };
 
-        let mem_usage = if is_mem_percent {
-            MemUsage::Percent(process.mem_usage_percent)
-        } else {
-            MemUsage::Bytes(process.mem_usage_bytes)
-        };
+        let mem_usage = MemUsage::of(process, is_mem_percent);
 
         Self {
             pid: process.pid,
+            row_id: process.pid as u64,
             ppid: process.parent_pid,
             id,
             cpu_usage_percent: process.cpu_usage_percent,
@@ -215,15 +285,36 @@ impl ProcWidgetData {
             user: process.user.to_string(),
             num_similar: 1,
             disabled: false,
+            is_totals: false,
             time: process.time,
+            cpu_delta: None,
+            mem_delta: None,
+            byte_standard: ByteStandard::default(),
+            number_format: NumberFormat::default(),
         }
     }
 
+    /// Makes this the row of totals for `num_processes` processes, which were summed into the
+    /// process it was created from.
+    pub fn totals(mut self, num_processes: u64) -> Self {
+        self.is_totals = true;
+        self.row_id = u64::MAX;
+        self.num_similar = num_processes;
+        self
+    }
+
     pub fn num_similar(mut self, num_similar: u64) -> Self {
         self.num_similar = num_similar;
         self
     }
 
+    /// Identifies this row by its name or command rather than its PID, like for a group of
+    /// processes, where the PID is just whichever process came first.
+    pub fn keyed_by_id(mut self) -> Self {
+        self.row_id = hash_row_id(self.id.as_str());
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
@@ -234,7 +325,18 @@ impl ProcWidgetData {
         self
     }
 
+    pub fn suffix(mut self, suffix: Option<String>) -> Self {
+        self.id.suffix = suffix;
+        self
+    }
+
     pub fn add(&mut self, other: &Self) {
+        self.cpu_delta = add_delta(
+            self.cpu_delta,
+            other.cpu_delta,
+            f64::from(other.cpu_usage_percent),
+        );
+        self.mem_delta = add_delta(self.mem_delta, other.mem_delta, other.mem_usage.amount());
         self.cpu_usage_percent += other.cpu_usage_percent;
         self.mem_usage = match (&self.mem_usage, &other.mem_usage) {
             (MemUsage::Percent(a), MemUsage::Percent(b)) => MemUsage::Percent(a + b),
@@ -250,21 +352,73 @@ impl ProcWidgetData {
         self.total_write += other.total_write;
     }
 
+    fn format_rate(&self, bytes: u64) -> String {
+        format_bytes_per_second(bytes, self.byte_standard, 1)
+    }
+
+    fn format_cpu_change(change: f64) -> String {
+        format!("{change:.1}%")
+    }
+
+    fn format_mem_change(&self, change: f64) -> String {
+        match self.mem_usage {
+            MemUsage::Percent(_) => format!("{change:.1}%"),
+            MemUsage::Bytes(_) => format_bytes(change as u64, self.byte_standard, 1),
+        }
+    }
+
+    /// Returns the text of `column`. Numbers other than the PID are written in the number format;
+    /// PIDs are left as they are, as they are identifiers.
     fn to_string(&self, column: &ProcColumn) -> String {
+        let number = |formatted: String| self.number_format.apply(&formatted);
+
+        // Totals are only shown for columns that can be summed.
+        if self.is_totals {
+            match column {
+                ProcColumn::Pid
+                | ProcColumn::State
+                | ProcColumn::User
+                | ProcColumn::Time
+                | ProcColumn::CpuDelta
+                | ProcColumn::MemDelta => return String::new(),
+                _ => {}
+            }
+        }
+
         match column {
-            ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent),
-            ProcColumn::MemoryVal | ProcColumn::MemoryPercent => self.mem_usage.to_string(),
+            ProcColumn::CpuPercent => number(format!("{:.1}%", self.cpu_usage_percent)),
+            ProcColumn::MemoryVal | ProcColumn::MemoryPercent => {
+                number(self.mem_usage.format(self.byte_standard))
+            }
             ProcColumn::Pid => self.pid.to_string(),
-            ProcColumn::Count => self.num_similar.to_string(),
+            ProcColumn::Count => number(self.num_similar.to_string()),
             ProcColumn::Name | ProcColumn::Command => self.id.to_prefixed_string(),
-            ProcColumn::ReadPerSecond => dec_bytes_per_second_string(self.rps),
-            ProcColumn::WritePerSecond => dec_bytes_per_second_string(self.wps),
-            ProcColumn::TotalRead => dec_bytes_string(self.total_read),
-            ProcColumn::TotalWrite => dec_bytes_string(self.total_write),
+            ProcColumn::ReadPerSecond => number(self.format_rate(self.rps)),
+            ProcColumn::WritePerSecond => number(self.format_rate(self.wps)),
+            ProcColumn::TotalRead => number(format_bytes(self.total_read, self.byte_standard, 1)),
+            ProcColumn::TotalWrite => number(format_bytes(self.total_write, self.byte_standard, 1)),
             ProcColumn::State => self.process_char.to_string(),
             ProcColumn::User => self.user.clone(),
             ProcColumn::Time => format_time(self.time),
+            ProcColumn::CpuDelta => number(format_change(self.cpu_delta, Self::format_cpu_change)),
+            ProcColumn::MemDelta => number(format_change(self.mem_delta, |change| {
+                self.format_mem_change(change)
+            })),
+        }
+    }
+}
+
+/// Adds the change of a row with `other_value` to `delta`. A row that is new adds all of its
+/// value, unless the row it is added to is new itself.
+fn add_delta(
+    delta: Option<Delta<f64>>, other: Option<Delta<f64>>, other_value: f64,
+) -> Option<Delta<f64>> {
+    match (delta?, other?) {
+        (Delta::New, _) => Some(Delta::New),
+        (Delta::Change(change), Delta::Change(other_change)) => {
+            Some(Delta::Change(change + other_change))
         }
+        (Delta::Change(change), Delta::New) => Some(Delta::Change(change + other_value)),
     }
 }
 
@@ -275,56 +429,73 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
         }
 
         // TODO: Optimize the string allocations here...
-        // TODO: Also maybe just pull in the to_string call but add a variable for the differences.
         Some(truncate_to_text(
             &match column {
-                ProcColumn::CpuPercent => {
-                    format!("{:.1}%", self.cpu_usage_percent)
-                }
-                ProcColumn::MemoryVal | ProcColumn::MemoryPercent => self.mem_usage.to_string(),
-                ProcColumn::Pid => self.pid.to_string(),
-                ProcColumn::Count => self.num_similar.to_string(),
-                ProcColumn::Name | ProcColumn::Command => self.id.to_prefixed_string(),
-                ProcColumn::ReadPerSecond => dec_bytes_per_second_string(self.rps),
-                ProcColumn::WritePerSecond => dec_bytes_per_second_string(self.wps),
-                ProcColumn::TotalRead => dec_bytes_string(self.total_read),
-                ProcColumn::TotalWrite => dec_bytes_string(self.total_write),
-                ProcColumn::State => {
-                    if calculated_width < 8 {
-                        self.process_char.to_string()
-                    } else {
-                        self.process_state.clone()
-                    }
-                }
-                ProcColumn::User => self.user.clone(),
-                ProcColumn::Time => format_time(self.time),
+                ProcColumn::State if calculated_width >= 8 => self.process_state.clone(),
+                _ => self.to_string(column),
             },
             calculated_width,
         ))
     }
 
+    #[inline(always)]
+    fn style_cell<'a>(&self, column: &ProcColumn, cell: Text<'a>, painter: &Painter) -> Text<'a> {
+        let role = match column {
+            ProcColumn::CpuDelta => change_role(self.cpu_delta, Self::format_cpu_change),
+            ProcColumn::MemDelta => {
+                change_role(self.mem_delta, |change| self.format_mem_change(change))
+            }
+            _ => return cell,
+        };
+
+        match role {
+            Some(role) => {
+                let mut cell = cell;
+                cell.patch_style(painter.colours.resolve(role));
+                cell
+            }
+            None => cell,
+        }
+    }
+
+    fn row_id(&self) -> Option<u64> {
+        (!self.is_totals).then_some(self.row_id)
+    }
+
     #[inline(always)]
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
-        if self.disabled {
+        if self.is_totals {
+            row.style(Style::default().add_modifier(Modifier::BOLD))
+        } else if self.disabled {
             row.style(painter.colours.disabled_text_style)
         } else {
             row
         }
     }
 
+    fn same_cells(&self, other: &Self) -> bool {
+        self == other
+    }
+
     fn column_widths<C: DataTableColumn<ProcColumn>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
         Self: Sized,
     {
-        let mut widths = vec![0; columns.len()];
-
-        for d in data {
-            for (w, c) in widths.iter_mut().zip(columns) {
-                *w = max(*w, d.to_string(c.inner()).len() as u16);
-            }
-        }
+        // Only soft-bounded columns are sized by their data, so there's no need to format the
+        // rest of the cells of every row; those are formatted when drawing the visible rows.
+        columns
+            .iter()
+            .map(|c| {
+                if c.is_hidden() || !matches!(c.bounds(), ColumnWidthBounds::Soft { .. }) {
+                    return 0;
+                }
 
-        widths
+                data.iter()
+                    .map(|d| str_width(&d.to_string(c.inner())) as u16)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
     }
 }
 
@@ -332,7 +503,11 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
 mod test {
     use std::time::Duration;
 
-    use crate::widgets::proc_widget_data::format_time;
+    use crate::{
+        canvas::canvas_styling::Role,
+        components::data_table::Delta,
+        widgets::proc_widget_data::{add_delta, change_role, format_change, format_time},
+    };
 
     #[test]
     fn test_format_time() {
@@ -364,4 +539,60 @@ mod test {
             "364d 23h 59m"
         );
     }
+
+    #[test]
+    fn test_format_change() {
+        let percent = |change: f64| format!("{change:.1}%");
+
+        assert_eq!(format_change(Some(Delta::Change(5.0)), percent), "+5.0%");
+        assert_eq!(format_change(Some(Delta::Change(-2.25)), percent), "-2.2%");
+        assert_eq!(format_change(Some(Delta::Change(0.0)), percent), "0.0%");
+        assert_eq!(format_change(Some(Delta::Change(-0.01)), percent), "0.0%");
+        assert_eq!(format_change(Some(Delta::New), percent), "new");
+        assert_eq!(format_change(None, percent), "N/A");
+    }
+
+    #[test]
+    fn test_change_role() {
+        let percent = |change: f64| format!("{change:.1}%");
+
+        assert_eq!(
+            change_role(Some(Delta::Change(5.0)), percent),
+            Some(Role::Increase)
+        );
+        assert_eq!(
+            change_role(Some(Delta::Change(-2.25)), percent),
+            Some(Role::Decrease)
+        );
+        assert_eq!(change_role(Some(Delta::Change(0.0)), percent), None);
+        assert_eq!(change_role(Some(Delta::Change(-0.01)), percent), None);
+        assert_eq!(change_role(Some(Delta::New), percent), None);
+        assert_eq!(change_role(None, percent), None);
+
+        // The sign comes from the change, not its text, so formats that write a sign of their
+        // own don't matter.
+        let signed = |change: f64| format!("-{change:.1}%");
+        assert_eq!(
+            change_role(Some(Delta::Change(5.0)), signed),
+            Some(Role::Increase)
+        );
+    }
+
+    #[test]
+    fn test_add_delta() {
+        assert_eq!(
+            add_delta(Some(Delta::Change(2.0)), Some(Delta::Change(-3.0)), 1.0),
+            Some(Delta::Change(-1.0))
+        );
+        assert_eq!(
+            add_delta(Some(Delta::Change(2.0)), Some(Delta::New), 4.0),
+            Some(Delta::Change(6.0))
+        );
+        assert_eq!(
+            add_delta(Some(Delta::New), Some(Delta::Change(1.0)), 4.0),
+            Some(Delta::New)
+        );
+        assert_eq!(add_delta(None, Some(Delta::Change(1.0)), 4.0), None);
+        assert_eq!(add_delta(Some(Delta::Change(1.0)), None, 4.0), None);
+    }
 }
@@ -0,0 +1,441 @@
+use std::{
+    borrow::Cow,
+    cmp::max,
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use tui::text::Text;
+
+use crate::{
+    app::{
+        data_harvester::processes::ProcessHarvest, process_snapshots::ProcessSnapshot,
+        AppConfigFields,
+    },
+    canvas::{canvas_styling::StyleResolver, Painter},
+    components::data_table::{
+        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, Delta,
+        SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    },
+    utils::{
+        gen_util::{hash_row_id, sort_partial_fn, truncate_to_text},
+        units::{format_bytes, ByteStandard, NumberFormat, MAX_BYTES_WIDTH},
+    },
+    widgets::process_table::proc_widget_data::{change_role, format_change},
+    Pid,
+};
+
+/// How a process changed between a snapshot and now.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SnapshotStatus {
+    /// The process started after the snapshot was taken.
+    New,
+    /// The process was in the snapshot, but has since exited.
+    Exited,
+    /// The process was in the snapshot and is still running.
+    Running,
+}
+
+impl SnapshotStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotStatus::New => "new",
+            SnapshotStatus::Exited => "exited",
+            SnapshotStatus::Running => "running",
+        }
+    }
+}
+
+/// A process compared between a snapshot and now. Usage is zero on the side where the process
+/// isn't running.
+#[derive(Clone, Debug)]
+pub struct SnapshotWidgetData {
+    pub pid: Pid,
+    pub name: String,
+    pub status: SnapshotStatus,
+    pub cpu_before: f64,
+    pub cpu_now: f64,
+    pub mem_before: u64,
+    pub mem_now: u64,
+    pub byte_standard: ByteStandard,
+    pub number_format: NumberFormat,
+}
+
+impl SnapshotWidgetData {
+    fn new(
+        pid: Pid, name: &str, status: SnapshotStatus, before: Option<&ProcessHarvest>,
+        now: Option<&ProcessHarvest>,
+    ) -> Self {
+        Self {
+            pid,
+            name: name.to_string(),
+            status,
+            cpu_before: before.map_or(0.0, |p| p.cpu_usage_percent.into()),
+            cpu_now: now.map_or(0.0, |p| p.cpu_usage_percent.into()),
+            mem_before: before.map_or(0, |p| p.mem_usage_bytes),
+            mem_now: now.map_or(0, |p| p.mem_usage_bytes),
+            byte_standard: ByteStandard::default(),
+            number_format: NumberFormat::default(),
+        }
+    }
+
+    pub fn cpu_change(&self) -> f64 {
+        self.cpu_now - self.cpu_before
+    }
+
+    pub fn mem_change(&self) -> f64 {
+        self.mem_now as f64 - self.mem_before as f64
+    }
+
+    fn format_cpu_change(change: f64) -> String {
+        format!("{change:.1}%")
+    }
+
+    fn format_mem_change(&self, change: f64) -> String {
+        format_bytes(change as u64, self.byte_standard, 1)
+    }
+
+    fn to_string(&self, column: &SnapshotColumn) -> String {
+        let text = match column {
+            SnapshotColumn::Pid => return self.pid.to_string(),
+            SnapshotColumn::Name => return self.name.clone(),
+            SnapshotColumn::Status => return self.status.as_str().to_string(),
+            SnapshotColumn::Cpu => format!("{:.1}%", self.cpu_now),
+            SnapshotColumn::CpuChange => format_change(
+                Some(Delta::Change(self.cpu_change())),
+                Self::format_cpu_change,
+            ),
+            SnapshotColumn::MemChange => {
+                format_change(Some(Delta::Change(self.mem_change())), |change| {
+                    self.format_mem_change(change)
+                })
+            }
+        };
+
+        self.number_format.apply(&text)
+    }
+}
+
+/// Compares the processes in `before` against those in `now` by PID. A PID that now belongs to a
+/// process with a different name is counted as one process exiting and another starting.
+pub fn compare_processes(
+    before: &BTreeMap<Pid, ProcessHarvest>, now: &BTreeMap<Pid, ProcessHarvest>,
+) -> Vec<SnapshotWidgetData> {
+    let mut rows = Vec::with_capacity(max(before.len(), now.len()));
+
+    for (pid, old) in before {
+        match now.get(pid) {
+            Some(new) if new.name == old.name => rows.push(SnapshotWidgetData::new(
+                *pid,
+                &new.name,
+                SnapshotStatus::Running,
+                Some(old),
+                Some(new),
+            )),
+            _ => rows.push(SnapshotWidgetData::new(
+                *pid,
+                &old.name,
+                SnapshotStatus::Exited,
+                Some(old),
+                None,
+            )),
+        }
+    }
+
+    for (pid, new) in now {
+        let is_new = match before.get(pid) {
+            Some(old) => old.name != new.name,
+            None => true,
+        };
+
+        if is_new {
+            rows.push(SnapshotWidgetData::new(
+                *pid,
+                &new.name,
+                SnapshotStatus::New,
+                None,
+                Some(new),
+            ));
+        }
+    }
+
+    rows
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotColumn {
+    Pid,
+    Name,
+    Status,
+    Cpu,
+    CpuChange,
+    MemChange,
+}
+
+impl ColumnHeader for SnapshotColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            SnapshotColumn::Pid => "PID",
+            SnapshotColumn::Name => "Name",
+            SnapshotColumn::Status => "Status",
+            SnapshotColumn::Cpu => "CPU%",
+            SnapshotColumn::CpuChange => "ΔCPU%",
+            SnapshotColumn::MemChange => "ΔMem",
+        }
+        .into()
+    }
+}
+
+impl DataToCell<SnapshotColumn> for SnapshotWidgetData {
+    fn to_cell<'a>(&'a self, column: &SnapshotColumn, calculated_width: u16) -> Option<Text<'a>> {
+        if calculated_width == 0 {
+            return None;
+        }
+
+        Some(truncate_to_text(&self.to_string(column), calculated_width))
+    }
+
+    fn style_cell<'a>(
+        &self, column: &SnapshotColumn, cell: Text<'a>, painter: &Painter,
+    ) -> Text<'a> {
+        let role = match column {
+            SnapshotColumn::CpuChange => change_role(
+                Some(Delta::Change(self.cpu_change())),
+                Self::format_cpu_change,
+            ),
+            SnapshotColumn::MemChange => {
+                change_role(Some(Delta::Change(self.mem_change())), |change| {
+                    self.format_mem_change(change)
+                })
+            }
+            _ => return cell,
+        };
+
+        match role {
+            Some(role) => {
+                let mut cell = cell;
+                cell.patch_style(painter.colours.resolve(role));
+                cell
+            }
+            None => cell,
+        }
+    }
+
+    fn row_id(&self) -> Option<u64> {
+        Some(hash_row_id(&(self.pid, &self.name)))
+    }
+
+    fn column_widths<C: DataTableColumn<SnapshotColumn>>(data: &[Self], columns: &[C]) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        // Only the name is sized by its data, the rest have fixed widths.
+        columns
+            .iter()
+            .map(|column| match column.inner() {
+                SnapshotColumn::Name => data
+                    .iter()
+                    .map(|row| row.name.len() as u16)
+                    .max()
+                    .unwrap_or(0),
+                _ => 0,
+            })
+            .collect()
+    }
+}
+
+impl SortsRow for SnapshotColumn {
+    type DataType = SnapshotWidgetData;
+
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool) {
+        match self {
+            SnapshotColumn::Pid => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.pid, b.pid));
+            }
+            SnapshotColumn::Name => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.name.to_lowercase(), b.name.to_lowercase())
+                });
+            }
+            SnapshotColumn::Status => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.status, b.status));
+            }
+            SnapshotColumn::Cpu => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.cpu_now, b.cpu_now));
+            }
+            SnapshotColumn::CpuChange => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.cpu_change(), b.cpu_change()));
+            }
+            SnapshotColumn::MemChange => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.mem_change(), b.mem_change()));
+            }
+        }
+    }
+}
+
+/// Compares one of the process snapshots against the current processes.
+pub struct SnapshotComparison {
+    /// The index of the snapshot being compared against.
+    pub snapshot_index: usize,
+    pub table: SortDataTable<SnapshotWidgetData, SnapshotColumn>,
+    byte_standard: ByteStandard,
+    number_format: NumberFormat,
+}
+
+impl SnapshotComparison {
+    /// Creates an empty comparison against the snapshot at `snapshot_index`. The table is styled
+    /// when drawn, as the dialog may outlive a change of colour scheme.
+    pub fn new(config: &AppConfigFields, snapshot_index: usize) -> Self {
+        let columns = [
+            SortColumn::hard(SnapshotColumn::Pid, 8),
+            SortColumn::soft(SnapshotColumn::Name, Some(0.3)),
+            SortColumn::hard(SnapshotColumn::Status, 8),
+            SortColumn::hard(SnapshotColumn::Cpu, 8).default_descending(),
+            SortColumn::hard(SnapshotColumn::CpuChange, 8).default_descending(),
+            SortColumn::hard(SnapshotColumn::MemChange, MAX_BYTES_WIDTH as u16 + 1)
+                .default_descending(),
+        ];
+
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: None,
+                table_gap: config.table_gap,
+                left_to_right: true,
+                is_basic: false,
+                show_table_scroll_position: config.show_table_scroll_position,
+                show_current_entry_when_unfocused: false,
+                uppercase_headers: config.uppercase_headers,
+                dim_when_unfocused: config.dim_unfocused_tables,
+            },
+            sort_index: 4,
+            order: SortOrder::Descending,
+        };
+
+        Self {
+            snapshot_index,
+            table: SortDataTable::new_sortable(columns, props, DataTableStyling::default()),
+            byte_standard: config.byte_standard,
+            number_format: config.number_format,
+        }
+    }
+
+    /// Brings the table's settings in line with `config`, e.g. after reloading the config file.
+    pub fn set_table_settings(&mut self, config: &AppConfigFields) {
+        let props = &mut self.table.props;
+        props.table_gap = config.table_gap;
+        props.show_table_scroll_position = config.show_table_scroll_position;
+        props.uppercase_headers = config.uppercase_headers;
+        props.dim_when_unfocused = config.dim_unfocused_tables;
+    }
+
+    /// Compares `snapshot` against the processes in `now`, keeping the sort and selection.
+    pub fn update(
+        &mut self, snapshot: &ProcessSnapshot, now: &BTreeMap<Pid, ProcessHarvest>,
+        current_instant: Instant,
+    ) {
+        let age = current_instant.saturating_duration_since(snapshot.taken_at);
+        let age = humantime::format_duration(Duration::from_secs(age.as_secs()));
+        self.table.props.title = Some(
+            format!(
+                " {} ({age} ago) ─ ←/→ switch, s sort, I invert, x drop, Esc close ",
+                snapshot.name
+            )
+            .into(),
+        );
+
+        let mut data = compare_processes(&snapshot.processes, now);
+        for row in &mut data {
+            row.byte_standard = self.byte_standard;
+            row.number_format = self.number_format;
+        }
+        self.sort_and_set(data);
+    }
+
+    /// Sorts by the next column along, wrapping back to the first.
+    pub fn cycle_sort(&mut self) {
+        let index = (self.table.sort_index() + 1) % self.table.columns.len();
+        self.table.set_sort_index(index);
+        self.sort_and_set(self.table.data().to_vec());
+    }
+
+    pub fn invert_sort(&mut self) {
+        self.table.toggle_order();
+        self.sort_and_set(self.table.data().to_vec());
+    }
+
+    fn sort_and_set(&mut self, mut data: Vec<SnapshotWidgetData>) {
+        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+            column.sort_by(&mut data, self.table.order());
+        }
+        self.table.set_data(data);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(pid: Pid, name: &str, cpu: f32, mem: u64) -> (Pid, ProcessHarvest) {
+        (
+            pid,
+            ProcessHarvest {
+                pid,
+                name: name.to_string(),
+                cpu_usage_percent: cpu,
+                mem_usage_bytes: mem,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_compare_processes() {
+        let before = BTreeMap::from([
+            process(1, "init", 1.0, 100),
+            process(2, "gone", 5.0, 200),
+            process(3, "old", 0.0, 300),
+        ]);
+        let now = BTreeMap::from([
+            process(1, "init", 3.5, 50),
+            process(3, "reused", 2.0, 10),
+            process(4, "fresh", 1.0, 400),
+        ]);
+
+        let mut rows = compare_processes(&before, &now)
+            .into_iter()
+            .map(|row| {
+                (
+                    row.pid,
+                    row.name.clone(),
+                    row.status,
+                    row.cpu_change(),
+                    row.mem_change(),
+                )
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+        assert_eq!(
+            rows,
+            vec![
+                (1, "init".to_string(), SnapshotStatus::Running, 2.5, -50.0),
+                (2, "gone".to_string(), SnapshotStatus::Exited, -5.0, -200.0),
+                (3, "old".to_string(), SnapshotStatus::Exited, 0.0, -300.0),
+                (3, "reused".to_string(), SnapshotStatus::New, 2.0, 10.0),
+                (4, "fresh".to_string(), SnapshotStatus::New, 1.0, 400.0),
+            ]
+        );
+    }
+    #[test]
+    fn test_column_widths() {
+        let comparison = SnapshotComparison::new(&AppConfigFields::default(), 0);
+        let now = BTreeMap::from([process(1, "init", 0.0, 0), process(2, "longer", 0.0, 0)]);
+        let rows = compare_processes(&BTreeMap::new(), &now);
+
+        // There's a width for every column, even though only the name is sized by its data.
+        assert_eq!(
+            SnapshotWidgetData::column_widths(&rows, &comparison.table.columns),
+            vec![0, 6, 0, 0, 0, 0]
+        );
+    }
+}
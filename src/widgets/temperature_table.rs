@@ -11,39 +11,40 @@ use crate::{
         ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
         SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
-    utils::gen_util::{sort_partial_fn, truncate_to_text},
+    utils::{
+        gen_util::{hash_row_id, sort_partial_fn, truncate_to_text},
+        units::{format_temperature, temperature_suffix},
+    },
 };
 
 #[derive(Clone, Debug)]
 pub struct TempWidgetData {
     pub sensor: KString,
-    pub temperature_value: u64,
-    pub temperature_type: TemperatureType,
+    /// The temperature in degrees Celsius.
+    pub temperature: f32,
 }
 
 pub enum TempWidgetColumn {
     Sensor,
-    Temp,
+    /// The temperature, shown in the given unit.
+    Temp(TemperatureType),
 }
 
 impl ColumnHeader for TempWidgetColumn {
     fn text(&self) -> Cow<'static, str> {
         match self {
             TempWidgetColumn::Sensor => "Sensor(s)".into(),
-            TempWidgetColumn::Temp => "Temp(t)".into(),
+            TempWidgetColumn::Temp(unit) => {
+                concat_string!("Temp ", temperature_suffix(*unit), "(t)").into()
+            }
         }
     }
-}
 
-impl TempWidgetData {
-    pub fn temperature(&self) -> KString {
-        let temp_val = self.temperature_value.to_string();
-        let temp_type = match self.temperature_type {
-            TemperatureType::Celsius => "°C",
-            TemperatureType::Kelvin => "K",
-            TemperatureType::Fahrenheit => "°F",
-        };
-        concat_string!(temp_val, temp_type).into()
+    fn name(&self) -> Cow<'static, str> {
+        match self {
+            TempWidgetColumn::Sensor => "Sensor".into(),
+            TempWidgetColumn::Temp(_) => "Temp".into(),
+        }
     }
 }
 
@@ -55,21 +56,38 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
 
         Some(match column {
             TempWidgetColumn::Sensor => truncate_to_text(&self.sensor, calculated_width),
-            TempWidgetColumn::Temp => truncate_to_text(&self.temperature(), calculated_width),
+            TempWidgetColumn::Temp(unit) => truncate_to_text(
+                &format_temperature(self.temperature, *unit),
+                calculated_width,
+            ),
         })
     }
 
+    fn row_id(&self) -> Option<u64> {
+        Some(hash_row_id(&self.sensor))
+    }
+
     fn column_widths<C: DataTableColumn<TempWidgetColumn>>(
-        data: &[TempWidgetData], _columns: &[C],
+        data: &[TempWidgetData], columns: &[C],
     ) -> Vec<u16>
     where
         Self: Sized,
     {
         let mut widths = vec![0; 2];
+        let unit = columns
+            .iter()
+            .find_map(|column| match column.inner() {
+                TempWidgetColumn::Temp(unit) => Some(*unit),
+                TempWidgetColumn::Sensor => None,
+            })
+            .unwrap_or_default();
 
         data.iter().for_each(|row| {
             widths[0] = max(widths[0], row.sensor.len() as u16);
-            widths[1] = max(widths[1], row.temperature().len() as u16);
+            widths[1] = max(
+                widths[1],
+                format_temperature(row.temperature, unit).len() as u16,
+            );
         });
 
         widths
@@ -84,10 +102,8 @@ impl SortsRow for TempWidgetColumn {
             TempWidgetColumn::Sensor => {
                 data.sort_by(move |a, b| sort_partial_fn(descending)(&a.sensor, &b.sensor));
             }
-            TempWidgetColumn::Temp => {
-                data.sort_by(|a, b| {
-                    sort_partial_fn(descending)(a.temperature_value, b.temperature_value)
-                });
+            TempWidgetColumn::Temp(_) => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.temperature, b.temperature));
             }
         }
     }
@@ -102,7 +118,8 @@ impl TempWidgetState {
     pub fn new(config: &AppConfigFields, colours: &CanvasStyling) -> Self {
         let columns = [
             SortColumn::soft(TempWidgetColumn::Sensor, Some(0.8)),
-            SortColumn::soft(TempWidgetColumn::Temp, None).default_descending(),
+            SortColumn::soft(TempWidgetColumn::Temp(config.temperature_type), None)
+                .default_descending(),
         ];
 
         let props = SortDataTableProps {
@@ -113,6 +130,8 @@ impl TempWidgetState {
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
                 show_current_entry_when_unfocused: false,
+                uppercase_headers: config.uppercase_headers,
+                dim_when_unfocused: config.dim_unfocused_tables,
             },
             sort_index: 0,
             order: SortOrder::Ascending,
@@ -132,6 +151,39 @@ impl TempWidgetState {
         self.force_update_data = true;
     }
 
+    /// Shows temperatures in `unit`. The existing data is re-rendered on the next update, so nothing
+    /// needs to be collected again.
+    pub fn set_temperature_type(&mut self, unit: TemperatureType) {
+        for column in &mut self.table.columns {
+            if let TempWidgetColumn::Temp(current) = column.inner_mut() {
+                *current = unit;
+            }
+        }
+        self.force_data_update();
+    }
+
+    /// Restyles the table with `colours`, e.g. after switching colour schemes.
+    pub fn set_colours(&mut self, colours: &CanvasStyling) {
+        self.table.styling.set_colours(colours);
+    }
+
+    /// Brings the table's settings in line with `config`, e.g. after reloading the config file.
+    pub fn set_table_settings(&mut self, config: &AppConfigFields) {
+        let props = &mut self.table.props;
+        props.table_gap = config.table_gap;
+        props.show_table_scroll_position = config.show_table_scroll_position;
+        props.uppercase_headers = config.uppercase_headers;
+        props.dim_when_unfocused = config.dim_unfocused_tables;
+    }
+
+    /// Sorts by the column with the given [name](ColumnHeader::name), if the table has it.
+    pub fn sort_by_column(&mut self, name: &str) {
+        if let Some(index) = self.table.column_index_by_name(name) {
+            self.table.set_sort_index(index);
+            self.force_data_update();
+        }
+    }
+
     pub fn ingest_data(&mut self, data: &[TempWidgetData]) {
         let mut data = data.to_vec();
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
@@ -11,11 +11,11 @@ fn test_small_rate() {
         .arg("-C")
         .arg("./tests/empty_config.toml")
         .arg("-r")
-        .arg("249")
+        .arg("99")
         .assert()
         .failure()
         .stderr(predicate::str::contains(
-            "set your update rate to be at least 250 ms.",
+            "set your update rate to be at least 100 ms.",
         ));
 }
 